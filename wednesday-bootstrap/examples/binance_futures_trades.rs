@@ -0,0 +1,61 @@
+use tokio_stream::StreamExt;
+use tracing::info;
+use wednesday_connector::{
+    exchange::binance::futures::BinanceFuturesUsd,
+    stream::Streams,
+    subscriber::subscription::kind::{AggTrades, PublicTrades},
+};
+use wednesday_model::instruments::InstrumentKind;
+
+// Initialise an INFO `Subscriber` for `Tracing` Json logs and install it as the global default.
+fn init_logging() {
+    tracing_subscriber::fmt()
+        // Filter messages based on the INFO
+        .with_env_filter(
+            tracing_subscriber::filter::EnvFilter::builder()
+                .with_default_directive(tracing_subscriber::filter::LevelFilter::INFO.into())
+                .from_env_lossy(),
+        )
+        // Disable colours on release builds
+        .with_ansi(cfg!(debug_assertions))
+        // Enable Json formatting
+        .json()
+        // Install this Tracing subscriber as global default
+        .init()
+}
+
+#[rustfmt::skip]
+#[tokio::main]
+async fn main() {
+    init_logging();
+    // Install a default CryptoProvider
+    rustls::crypto::ring::default_provider().install_default().unwrap();
+
+    let trades = Streams::<PublicTrades>::builder()
+        .subscribe([
+            (BinanceFuturesUsd::default(), "btc", "usdt", InstrumentKind::CryptoPerpetual, PublicTrades),
+        ])
+        .init()
+        .await
+        .unwrap();
+
+    let agg_trades = Streams::<AggTrades>::builder()
+        .subscribe([
+            (BinanceFuturesUsd::default(), "btc", "usdt", InstrumentKind::CryptoPerpetual, AggTrades),
+        ])
+        .init()
+        .await
+        .unwrap();
+
+    tokio::spawn(async move {
+        let mut agg_trades = agg_trades.join_map().await;
+        while let Some((exchange, agg_trade)) = agg_trades.next().await {
+            info!(%exchange, ?agg_trade, "Received aggregated trade");
+        }
+    });
+
+    let mut trades = trades.join_map().await;
+    while let Some((exchange, trade)) = trades.next().await {
+        info!(%exchange, ?trade, "Received trade");
+    }
+}
@@ -96,7 +96,10 @@ async fn main() {
             .event_tx(event_tx.clone())
             .portfolio(Arc::clone(&portfolio))
             .data(historical::HistoricalMarketFeed::new(load_json_market_event_candles().into_iter()))
-            .strategy(RsiStrategy::new(StrategyConfig { rsi_period: 14 }))
+            .strategy(RsiStrategy::new(StrategyConfig {
+                rsi_period: 14,
+                ..Default::default()
+            }))
             .execution(SimulatedExecution::new(SimExecConfig {
                 simulated_fees_pct: Fees {
                     exchange: 0.1,
@@ -6,7 +6,13 @@ use parking_lot::Mutex;
 use tokio::sync::mpsc;
 use tracing::event;
 use uuid::Uuid;
-use wednesday_connector::{exchange::binance::spot::BinanceSpot, stream::Streams, subscriber::subscription::kind::PublicTrades};
+use wednesday_bootstrap::server::{ControlServer, EventPublisher, ServerConfig};
+use wednesday_connector::{
+    exchange::binance::spot::BinanceSpot,
+    protocol::exporter::Exporter,
+    stream::{reconnect::ReconnectPolicy, Streams},
+    subscriber::subscription::kind::PublicTrades,
+};
 use wednesday_core::statistic::summary::Initialiser;
 use wednesday_core::{
     data::{historical, live},
@@ -26,9 +32,13 @@ use wednesday_core::{
 };
 use wednesday_model::{
     bar::Bar,
+    channel::{channel, ChannelConfig, ChannelRx, OverflowPolicy},
     events::{DataKind, MarketEvent},
     identifiers::{Exchange, ExchangeId, Market},
     instruments::{Instrument, InstrumentKind},
+    latency::LatencyRecorder,
+    metric::MetricTx,
+    status::StatusRx,
 };
 
 // Initialise an INFO `Subscriber` for `Tracing` Json logs and install it as the global default.
@@ -52,11 +62,27 @@ fn init_logging() {
 async fn main() {
     init_logging();
     // Create channel to distribute Commands to the Engine & it's Traders (eg/ Command::Terminate)
-    let (_command_tx, command_rx) = mpsc::channel(10);
+    let (command_tx, command_rx) = mpsc::channel(10);
+
+    // Supervise the Engine remotely if a control token is configured, eg/
+    // ws://127.0.0.1:8700/events?token=<WEDNESDAY_CONTROL_TOKEN>
+    let publisher = std::env::var("WEDNESDAY_CONTROL_TOKEN").ok().map(|token| {
+        let server = ControlServer::new(ServerConfig::new(([127, 0, 0, 1], 8700).into(), token), command_tx.clone())
+            .expect("failed to initialise ControlServer");
+        let publisher = server.publisher();
+        tokio::spawn(server.run());
+        publisher
+    });
+
+    // Serve the connector & engine metrics as Prometheus text at http://127.0.0.1:9898/metrics
+    let metric_tx = Exporter::Prometheus(([127, 0, 0, 1], 9898).into())
+        .spawn()
+        .await
+        .expect("failed to bind metrics exporter");
 
     // Create Event channal to listen to all Engine Events in real-time
     let (event_tx, event_rx) = mpsc::unbounded_channel();
-    let event_tx = EventTx::new(event_tx);
+    let event_tx = EventTx::new(event_tx).with_metric_tx(metric_tx.clone());
 
     // Generate unique identifier to associate an Engine's components
     let engine_id = Uuid::new_v4();
@@ -88,7 +114,10 @@ async fn main() {
 
     let (trader_command_tx, trader_command_rx) = mpsc::channel(10);
 
-    let feed_rx = stream_market_event_trades().await;
+    // Record the latency from the exchange until the order is acknowledged
+    let latency = LatencyRecorder::new();
+    latency.spawn_snapshots(Duration::from_secs(10), metric_tx.clone());
+    let (feed_rx, status_rx) = stream_market_event_trades(latency.clone(), metric_tx).await;
 
     traders.push(
         Trader::builder()
@@ -97,7 +126,8 @@ async fn main() {
             .command_rx(trader_command_rx)
             .event_tx(event_tx.clone())
             .portfolio(Arc::clone(&portfolio))
-            .data(live::LiveMarketFeed::new(feed_rx))
+            .data(live::LiveMarketFeed::new(feed_rx).with_status(status_rx))
+            .latency(latency.clone())
             .strategy(TickReactStrategy::new(TickReactStrategyConfig { rsi_period: 14 }))
             .execution(SimulatedExecution::new(SimExecConfig {
                 simulated_fees_pct: Fees {
@@ -122,10 +152,11 @@ async fn main() {
             trading_days_per_year: 365,
             risk_free_return: 0.0,
         }))
+        .latency(latency)
         .build()
         .expect("failed to build TradingEngine");
 
-    tokio::spawn(listen_to_engine_events(event_rx));
+    tokio::spawn(listen_to_engine_events(event_rx, publisher));
 
     match tokio::time::timeout(Duration::from_secs(600), engine.run()).await {
         Ok(_) => println!("Engine run completed successfully."),
@@ -134,29 +165,37 @@ async fn main() {
     // engine.run().await;
 }
 
-async fn stream_market_event_trades() -> mpsc::UnboundedReceiver<MarketEvent<DataKind>> {
+async fn stream_market_event_trades(latency: LatencyRecorder, metric_tx: MetricTx) -> (ChannelRx<MarketEvent<DataKind>>, StatusRx) {
     rustls::crypto::ring::default_provider().install_default().unwrap();
     let mut streams = Streams::<PublicTrades>::builder()
+        .reconnect(ReconnectPolicy::default().with_stale_timeout(Duration::from_secs(60)))
+        .latency(latency)
         .subscribe([(BinanceSpot::default(), "btc", "usdt", InstrumentKind::CryptoSpot, PublicTrades)])
         .init()
         .await
         .unwrap();
 
+    streams.metrics().spawn(Duration::from_secs(10), metric_tx);
+
     // NOTE: 왜 여기서 두번 거쳐서 데이터를 전달하는거지 ?
     let mut trade_rx = streams.select(ExchangeId::BinanceSpot).unwrap();
-    let (tx, rx) = mpsc::unbounded_channel();
+    let (tx, rx) = channel(ChannelConfig::bounded(10_000, OverflowPolicy::DropOldest));
 
     tokio::spawn(async move {
         while let Some(trade) = trade_rx.recv().await {
-            tx.send(MarketEvent::from(trade));
+            tx.send(MarketEvent::from(trade)).await;
         }
     });
 
-    rx
+    (rx, streams.status().unwrap())
 }
 
-async fn listen_to_engine_events(mut event_rx: mpsc::UnboundedReceiver<Event>) {
+async fn listen_to_engine_events(mut event_rx: mpsc::UnboundedReceiver<Event>, publisher: Option<EventPublisher>) {
     while let Some(event) = event_rx.recv().await {
+        if let Some(publisher) = &publisher {
+            publisher.publish(&event);
+        }
+
         match event {
             Event::Market(market) => {
                 // Market Event occurred in Engine
@@ -0,0 +1,354 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{de::IntoDeserializer, Deserialize, Serialize};
+use wednesday_core::{
+    model::{fee::Fees, position::PositionSide},
+    oms::allocator::DefaultAllocator,
+};
+use wednesday_model::{
+    identifiers::{ExchangeId, MarketId},
+    instruments::InstrumentKind,
+};
+
+use crate::{
+    config::{AllocatorConfig, Config, ExecutionConfig, ExecutionMode, LogFormat, LoggingConfig, MarketConfig, PortfolioConfig, StrategyConfig},
+    error::BootstrapError,
+    runner::{self, Report},
+};
+
+/// File of the [`Report::summary`] written to the output directory.
+pub const SUMMARY_FILE: &str = "summary.json";
+
+/// File of the [`Report::trades`] written to the output directory.
+pub const TRADES_FILE: &str = "trades.csv";
+
+/// Backtest of a strategy over the historical data files of each market, with simulated fills,
+/// run by `wednesday backtest`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backtest {
+    /// Markets, each with the [`DataFormat`](crate::data::DataFormat) file replayed.
+    pub markets: Vec<MarketConfig>,
+    pub strategy: StrategyConfig,
+    pub fees: Fees,
+    pub starting_cash: f64,
+    pub order_value: f64,
+    /// Directory the [`SUMMARY_FILE`] & [`TRADES_FILE`] are written to.
+    pub output: PathBuf,
+}
+
+impl Backtest {
+    /// Simulated [`Config`] of the [`Backtest`].
+    pub fn config(&self) -> Config {
+        Config {
+            execution: ExecutionConfig {
+                mode: ExecutionMode::Simulated,
+                fees: self.fees,
+            },
+            markets: self.markets.clone(),
+            strategy: self.strategy,
+            portfolio: PortfolioConfig {
+                starting_cash: self.starting_cash,
+                allocator: AllocatorConfig::Default(DefaultAllocator {
+                    default_order_value: self.order_value,
+                }),
+                risk: Default::default(),
+                repository: Default::default(),
+                trading_days_per_year: 365,
+                risk_free_return: 0.0,
+            },
+            logging: LoggingConfig {
+                level: "warn".to_owned(),
+                format: LogFormat::Pretty,
+            },
+        }
+    }
+
+    /// Run the [`Backtest`] to completion & write its [`Report`] to the output directory.
+    pub async fn run(&self) -> Result<Report, BootstrapError> {
+        let report = runner::run(self.config()).await?;
+        write(&report, &self.output)?;
+        Ok(report)
+    }
+}
+
+/// Parse a market & its data file, eg/ "binance_spot:btc/usdt:crypto_spot=candles_1h.json".
+pub fn parse_market(spec: &str) -> Result<MarketConfig, String> {
+    let invalid = || format!("invalid market {spec:?}, expected <exchange>:<base>/<quote>:<instrument_kind>=<data file>");
+
+    let (market, data) = spec.split_once('=').ok_or_else(invalid)?;
+    let mut parts = market.split(':');
+    let (Some(exchange), Some(pair), Some(instrument_kind), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+        return Err(invalid());
+    };
+    let (base, quote) = pair
+        .split_once('/')
+        .filter(|(base, quote)| !base.is_empty() && !quote.is_empty())
+        .ok_or_else(invalid)?;
+    if data.is_empty() {
+        return Err(invalid());
+    }
+
+    Ok(MarketConfig {
+        exchange: parse_variant::<ExchangeId>(exchange)?,
+        base: base.to_lowercase(),
+        quote: quote.to_lowercase(),
+        instrument_kind: parse_variant::<InstrumentKind>(instrument_kind)?,
+        subscriptions: vec![],
+        data: Some(PathBuf::from(data)),
+    })
+}
+
+/// Parse a strategy parameter, eg/ "rsi_period=14".
+pub fn parse_param(param: &str) -> Result<(String, String), String> {
+    match param.split_once('=') {
+        Some((name, value)) if !name.is_empty() => Ok((name.to_owned(), value.to_owned())),
+        _ => Err(format!("invalid strategy parameter {param:?}, expected <name>=<value>")),
+    }
+}
+
+/// Parse the [`StrategyConfig`] of the strategy `name` & its parameters, where each value is
+/// parsed as Json if possible (eg/ 14), otherwise as a string.
+pub fn parse_strategy(name: &str, params: &[(String, String)]) -> Result<StrategyConfig, String> {
+    let mut config = serde_json::Map::new();
+    config.insert("name".to_owned(), serde_json::Value::from(name));
+    for (param, value) in params {
+        let value = serde_json::from_str(value).unwrap_or_else(|_| serde_json::Value::from(value.as_str()));
+        config.insert(param.clone(), value);
+    }
+
+    serde_json::from_value(serde_json::Value::Object(config)).map_err(|error| format!("invalid strategy {name:?}: {error}"))
+}
+
+fn parse_variant<'de, T: Deserialize<'de>>(value: &'de str) -> Result<T, String> {
+    T::deserialize(value.into_deserializer()).map_err(|error: serde::de::value::Error| error.to_string())
+}
+
+/// Row of the [`TRADES_FILE`], describing an exited [`Position`](wednesday_core::model::position::Position).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct Trade<'a> {
+    position_id: &'a str,
+    market: String,
+    side: PositionSide,
+    quantity: f64,
+    enter_time: DateTime<Utc>,
+    exit_time: DateTime<Utc>,
+    enter_avg_price_gross: f64,
+    exit_avg_price_gross: f64,
+    fees: f64,
+    realised_profit_loss: f64,
+}
+
+impl Trade<'_> {
+    /// Header of the [`TRADES_FILE`], written even if no positions were exited.
+    const HEADER: [&'static str; 10] = [
+        "position_id",
+        "market",
+        "side",
+        "quantity",
+        "enter_time",
+        "exit_time",
+        "enter_avg_price_gross",
+        "exit_avg_price_gross",
+        "fees",
+        "realised_profit_loss",
+    ];
+}
+
+/// Write the [`SUMMARY_FILE`] & [`TRADES_FILE`] of the [`Report`] to the `output` directory,
+/// creating it if required.
+pub fn write(report: &Report, output: &Path) -> Result<(), BootstrapError> {
+    let write_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |source| BootstrapError::WriteOutput { path, source }
+    };
+    std::fs::create_dir_all(output).map_err(write_error(output))?;
+
+    let summary_path = output.join(SUMMARY_FILE);
+    let summary = serde_json::to_string_pretty(&report.summary).expect("TradingSummary is always serialisable");
+    std::fs::write(&summary_path, summary).map_err(write_error(&summary_path))?;
+
+    let trades_path = output.join(TRADES_FILE);
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_path(&trades_path)
+        .map_err(|error| write_error(&trades_path)(error.into()))?;
+    writer.write_record(Trade::HEADER).map_err(|error| write_error(&trades_path)(error.into()))?;
+    for position in &report.trades {
+        let trade = Trade {
+            position_id: &position.position_id,
+            market: MarketId::new(&position.exchange, &position.instrument).0,
+            side: position.side,
+            quantity: position.quantity,
+            enter_time: position.meta.enter_timestamp,
+            // Market time of the exit fill, rather than when it was simulated
+            exit_time: position.meta.update_timestamp,
+            enter_avg_price_gross: position.enter_avg_price_gross,
+            exit_avg_price_gross: position.exit_avg_price_gross,
+            fees: position.enter_fees_total + position.exit_fees_total,
+            realised_profit_loss: position.realised_profit_loss,
+        };
+        writer.serialize(trade).map_err(|error| write_error(&trades_path)(error.into()))?;
+    }
+    writer.flush().map_err(write_error(&trades_path))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_market() {
+        struct TestCase {
+            input: &'static str,
+            expected: Result<MarketConfig, ()>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: valid market
+                input: "binance_spot:BTC/usdt:crypto_spot=data/candles_1h.json",
+                expected: Ok(MarketConfig {
+                    exchange: ExchangeId::BinanceSpot,
+                    base: "btc".to_owned(),
+                    quote: "usdt".to_owned(),
+                    instrument_kind: InstrumentKind::CryptoSpot,
+                    subscriptions: vec![],
+                    data: Some(PathBuf::from("data/candles_1h.json")),
+                }),
+            },
+            TestCase {
+                // TC1: missing data file
+                input: "binance_spot:btc/usdt:crypto_spot",
+                expected: Err(()),
+            },
+            TestCase {
+                // TC2: missing instrument kind
+                input: "binance_spot:btc/usdt=candles.json",
+                expected: Err(()),
+            },
+            TestCase {
+                // TC3: unknown exchange
+                input: "nasdaq:btc/usdt:crypto_spot=candles.json",
+                expected: Err(()),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            assert_eq!(parse_market(test.input).map_err(|_| ()), test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_parse_strategy() {
+        struct TestCase {
+            name: &'static str,
+            params: Vec<(String, String)>,
+            expected: Result<StrategyConfig, ()>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: valid strategy & parameters
+                name: "rsi",
+                params: vec![parse_param("rsi_period=14").unwrap()],
+                expected: Ok(StrategyConfig::Rsi { rsi_period: 14 }),
+            },
+            TestCase {
+                // TC1: missing parameter
+                name: "tick_react",
+                params: vec![],
+                expected: Err(()),
+            },
+            TestCase {
+                // TC2: unknown parameter
+                name: "rsi",
+                params: vec![parse_param("rsi_period=14").unwrap(), parse_param("window=3").unwrap()],
+                expected: Err(()),
+            },
+            TestCase {
+                // TC3: unknown strategy
+                name: "macd",
+                params: vec![],
+                expected: Err(()),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            assert_eq!(parse_strategy(test.name, &test.params).map_err(|_| ()), test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_backtest() {
+        let output = std::env::temp_dir().join(format!("wednesday-backtest-{}", std::process::id()));
+        let backtest = Backtest {
+            markets: vec![parse_market(&format!(
+                "binance_spot:btc/usdt:crypto_spot={}/examples/candles_1h.json",
+                env!("CARGO_MANIFEST_DIR")
+            ))
+            .unwrap()],
+            strategy: StrategyConfig::Rsi { rsi_period: 14 },
+            fees: Fees {
+                exchange: 0.001,
+                slippage: 0.0005,
+            },
+            starting_cash: 10_000.0,
+            order_value: 100.0,
+            output: output.clone(),
+        };
+
+        let report = backtest.run().await.unwrap();
+        assert!(report.summary.contains_key(Report::TOTAL));
+        assert!(report.summary.contains_key("binance_spot:btc/usdt-spot"));
+
+        let trades = std::fs::read_to_string(output.join(TRADES_FILE)).unwrap();
+        assert_eq!(trades.lines().next(), Some(Trade::HEADER.join(",").as_str()));
+        assert_eq!(trades.lines().count(), report.trades.len() + 1);
+        assert!(std::fs::read_to_string(output.join(SUMMARY_FILE)).unwrap().contains("\"total\""));
+    }
+
+    #[tokio::test]
+    async fn test_backtest_exits_open_positions() {
+        // RsiStrategy enters a Long on the drop to 100, which it never closes itself
+        let output = std::env::temp_dir().join(format!("wednesday-backtest-exit-{}", std::process::id()));
+        std::fs::create_dir_all(&output).unwrap();
+        let data = output.join("bars.csv");
+        std::fs::write(
+            &data,
+            "close_time,open,high,low,close,volume,trade_count\n\
+             2024-01-01T01:00:00Z,110.0,110.0,110.0,110.0,1.0,1\n\
+             2024-01-01T02:00:00Z,110.0,110.0,100.0,100.0,1.0,1\n\
+             2024-01-01T03:00:00Z,100.0,105.0,100.0,105.0,1.0,1\n\
+             2024-01-01T04:00:00Z,105.0,125.0,105.0,125.0,1.0,1\n",
+        )
+        .unwrap();
+
+        let backtest = Backtest {
+            markets: vec![parse_market(&format!("binance_spot:btc/usdt:crypto_spot={}", data.display())).unwrap()],
+            strategy: StrategyConfig::Rsi { rsi_period: 2 },
+            fees: Fees {
+                exchange: 0.001,
+                slippage: 0.0,
+            },
+            starting_cash: 10_000.0,
+            order_value: 100.0,
+            output: output.clone(),
+        };
+
+        let report = backtest.run().await.unwrap();
+        assert_eq!(report.trades.len(), 1);
+
+        // Open Long is exited at the close of the last bar, with the market time of each fill
+        let trades = std::fs::read_to_string(output.join(TRADES_FILE)).unwrap();
+        assert_eq!(
+            trades.lines().skip(1).collect::<Vec<_>>(),
+            vec![format!(
+                "\"{}\",binance_spot:btc/usdt-spot,Buy,1.0,2024-01-01T02:00:00Z,2024-01-01T04:00:00Z,100.0,125.0,0.225,24.775",
+                report.trades[0].position_id
+            )]
+        );
+    }
+}
@@ -0,0 +1,22 @@
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+/// All errors generated whilst loading & validating a [`Config`](super::Config).
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("failed to read config file {path:?}: {source}")]
+    Read { path: PathBuf, source: std::io::Error },
+
+    #[error("unsupported config file {0:?}, expected a .toml, .yaml or .yml extension")]
+    Format(PathBuf),
+
+    #[error("failed to parse TOML config: {0}")]
+    Toml(#[from] toml::de::Error),
+
+    #[error("failed to parse YAML config: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+
+    #[error("invalid config:{}", .0.iter().map(|problem| format!("\n  - {problem}")).collect::<String>())]
+    Invalid(Vec<String>),
+}
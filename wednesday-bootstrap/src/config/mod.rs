@@ -0,0 +1,437 @@
+pub mod error;
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use tracing_subscriber::EnvFilter;
+use wednesday_core::{model::fee::Fees, oms::allocator::DefaultAllocator};
+use wednesday_model::{
+    identifiers::{ExchangeId, Market},
+    instruments::{Instrument, InstrumentKind},
+};
+
+use self::error::ConfigError;
+use crate::data::DataFormat;
+
+/// Configuration of an engine run by the `wednesday` binary, loaded from a TOML or YAML file,
+/// eg/
+///
+/// ```toml
+/// [execution]
+/// mode = "paper"
+/// fees = { exchange = 0.001, slippage = 0.0005 }
+///
+/// [[markets]]
+/// exchange = "binance_spot"
+/// base = "btc"
+/// quote = "usdt"
+/// instrument_kind = "crypto_spot"
+/// subscriptions = ["public_trades"]
+///
+/// [strategy]
+/// name = "tick_react"
+/// rsi_period = 14
+///
+/// [portfolio]
+/// starting_cash = 10000.0
+/// allocator = { kind = "default", default_order_value = 100.0 }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub execution: ExecutionConfig,
+    pub markets: Vec<MarketConfig>,
+    pub strategy: StrategyConfig,
+    pub portfolio: PortfolioConfig,
+    #[serde(default)]
+    pub logging: LoggingConfig,
+}
+
+impl Config {
+    /// Load the [`Config`] from a `.toml`, `.yaml` or `.yml` file, & [`Config::validate`] it.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        let config = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => Self::from_toml(&contents)?,
+            Some("yaml" | "yml") => Self::from_yaml(&contents)?,
+            _ => return Err(ConfigError::Format(path.to_path_buf())),
+        };
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn from_toml(contents: &str) -> Result<Self, ConfigError> {
+        Ok(toml::from_str(contents)?)
+    }
+
+    pub fn from_yaml(contents: &str) -> Result<Self, ConfigError> {
+        Ok(serde_yaml::from_str(contents)?)
+    }
+
+    /// Check the [`Config`] can be run, reporting every problem found rather than the first.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut problems = Vec::new();
+
+        if self.execution.mode == ExecutionMode::Live {
+            problems.push("execution.mode: live trading is not supported yet, no ExecutionClient places orders on exchanges (use paper)".to_owned());
+        }
+        if self.execution.fees.exchange < 0.0 || self.execution.fees.slippage < 0.0 {
+            problems.push("execution.fees: fee percentages must not be negative".to_owned());
+        }
+
+        if self.markets.is_empty() {
+            problems.push("markets: at least one market is required".to_owned());
+        }
+
+        let mut markets = HashSet::with_capacity(self.markets.len());
+        for (index, market) in self.markets.iter().enumerate() {
+            let name = format!("markets[{index}] ({}:{}/{})", market.exchange, market.base, market.quote);
+
+            if !markets.insert(market.market()) {
+                problems.push(format!("{name}: duplicate market, each market is traded by a single trader"));
+            }
+            if !market.exchange.supports(market.instrument_kind) {
+                problems.push(format!("{name}: {} does not list {:?} instruments", market.exchange, market.instrument_kind));
+            }
+
+            match self.execution.mode {
+                ExecutionMode::Simulated => match &market.data {
+                    None => problems.push(format!("{name}: data file is required in simulated mode")),
+                    Some(data) if !data.is_file() => problems.push(format!("{name}: data file {data:?} does not exist")),
+                    Some(data) if DataFormat::from_path(data).is_none() => problems.push(format!(
+                        "{name}: data file {data:?} has an unsupported extension, expected one of {:?}",
+                        DataFormat::EXTENSIONS
+                    )),
+                    Some(_) => {},
+                },
+                ExecutionMode::Paper | ExecutionMode::Live => {
+                    if market.subscriptions.is_empty() {
+                        problems.push(format!("{name}: at least one subscription is required to stream live market data"));
+                    }
+                    for kind in market.subscriptions.iter().filter(|kind| !kind.supports(market.exchange)) {
+                        problems.push(format!("{name}: {} does not support {kind:?} subscriptions", market.exchange));
+                    }
+                },
+            }
+        }
+
+        match self.strategy {
+            StrategyConfig::Rsi { rsi_period } | StrategyConfig::TickReact { rsi_period } if rsi_period == 0 => {
+                problems.push("strategy.rsi_period: must be at least 1".to_owned());
+            },
+            _ => {},
+        }
+
+        if self.portfolio.starting_cash <= 0.0 {
+            problems.push("portfolio.starting_cash: must be positive".to_owned());
+        }
+        let AllocatorConfig::Default(allocator) = self.portfolio.allocator;
+        if allocator.default_order_value <= 0.0 || allocator.default_order_value > self.portfolio.starting_cash {
+            problems.push("portfolio.allocator.default_order_value: must be positive & at most the starting_cash".to_owned());
+        }
+        if self.portfolio.trading_days_per_year == 0 || self.portfolio.trading_days_per_year > 366 {
+            problems.push("portfolio.trading_days_per_year: must be within 1..=366".to_owned());
+        }
+
+        if let Err(error) = EnvFilter::try_new(&self.logging.level) {
+            problems.push(format!("logging.level: {error}"));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(problems))
+        }
+    }
+}
+
+/// How the orders generated by the engine are executed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionMode {
+    /// Replay the historical [`DataFormat`] file of each market & fill orders with the
+    /// [`SimulatedExecution`](wednesday_core::execution::simulated::SimulatedExecution).
+    Simulated,
+    /// Stream live market data & fill orders with the
+    /// [`SimulatedExecution`](wednesday_core::execution::simulated::SimulatedExecution).
+    Paper,
+    /// Stream live market data & place orders on the exchanges.
+    Live,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExecutionConfig {
+    pub mode: ExecutionMode,
+    /// Fee percentages in decimal form (eg/ 0.001 for 0.1%) applied to each simulated fill.
+    #[serde(default)]
+    pub fees: Fees,
+}
+
+/// Market traded by a [`Trader`](wednesday_core::engine::trader::Trader).
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MarketConfig {
+    pub exchange: ExchangeId,
+    pub base: String,
+    pub quote: String,
+    pub instrument_kind: InstrumentKind,
+    /// Live market data streamed in paper & live mode.
+    #[serde(default)]
+    pub subscriptions: Vec<SubKind>,
+    /// Historical market data file replayed in simulated mode, see [`DataFormat`].
+    #[serde(default)]
+    pub data: Option<PathBuf>,
+}
+
+impl MarketConfig {
+    pub fn instrument(&self) -> Instrument {
+        Instrument::from((self.base.as_str(), self.quote.as_str(), self.instrument_kind))
+    }
+
+    /// [`Market`] identified by the [`ExchangeId`], matching the exchange of the streamed
+    /// [`MarketEvent`](wednesday_model::events::MarketEvent)s.
+    pub fn market(&self) -> Market {
+        Market::new(self.exchange, self.instrument())
+    }
+}
+
+/// Kind of live market data a market subscribes to, each convertible into a
+/// [`DataKind`](wednesday_model::events::DataKind).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubKind {
+    PublicTrades,
+    AggTrades,
+    MarkPrices,
+    OpenInterests,
+}
+
+impl SubKind {
+    /// Whether a connector streams the [`SubKind`] from the exchange, see
+    /// [`subscribe`](crate::runner::subscribe).
+    pub fn supports(&self, exchange: ExchangeId) -> bool {
+        use ExchangeId::*;
+
+        match self {
+            SubKind::PublicTrades => matches!(
+                exchange,
+                BinanceSpot
+                    | BinanceFuturesUsd
+                    | Bitfinex
+                    | Bitmex
+                    | BybitSpot
+                    | BybitPerpetualsUsd
+                    | Coinbase
+                    | GateioSpot
+                    | GateioPerpetualsUsd
+                    | GateioPerpetualsBtc
+                    | Kraken
+                    | Okx
+            ),
+            SubKind::AggTrades => matches!(exchange, BinanceSpot | BinanceFuturesUsd),
+            SubKind::MarkPrices => matches!(exchange, BinanceFuturesUsd | BybitPerpetualsUsd),
+            SubKind::OpenInterests => matches!(exchange, BybitPerpetualsUsd),
+        }
+    }
+}
+
+/// Strategy generating the signals of every market, & its parameters.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "name", rename_all = "snake_case", deny_unknown_fields)]
+pub enum StrategyConfig {
+    /// [`RsiStrategy`](wednesday_core::strategy::sample::RsiStrategy) over bars.
+    Rsi { rsi_period: usize },
+    /// [`TickReactStrategy`](wednesday_core::strategy::tick_str1::TickReactStrategy) over trades.
+    TickReact { rsi_period: usize },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PortfolioConfig {
+    pub starting_cash: f64,
+    pub allocator: AllocatorConfig,
+    #[serde(default)]
+    pub risk: RiskConfig,
+    #[serde(default)]
+    pub repository: RepositoryConfig,
+    #[serde(default = "default_trading_days_per_year")]
+    pub trading_days_per_year: usize,
+    #[serde(default)]
+    pub risk_free_return: f64,
+}
+
+fn default_trading_days_per_year() -> usize {
+    365
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AllocatorConfig {
+    /// [`DefaultAllocator`] sizing every order by the `default_order_value`.
+    Default(DefaultAllocator),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RiskConfig {
+    /// [`DefaultRisk`](wednesday_core::oms::evaluator::DefaultRisk) approving every order.
+    #[default]
+    Default,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(tag = "backend", rename_all = "snake_case")]
+pub enum RepositoryConfig {
+    /// [`InMemoryRepository`](wednesday_core::portfolio::repository::in_memory::InMemoryRepository)
+    /// whose state is lost on shutdown.
+    #[default]
+    InMemory,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct LoggingConfig {
+    /// Default [`EnvFilter`] directives, eg/ "info,wednesday_connector=debug", overridden by
+    /// the `RUST_LOG` environment variable.
+    #[serde(default = "default_log_level")]
+    pub level: String,
+    #[serde(default)]
+    pub format: LogFormat,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: default_log_level(),
+            format: LogFormat::default(),
+        }
+    }
+}
+
+fn default_log_level() -> String {
+    "info".to_owned()
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    #[default]
+    Json,
+    Pretty,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAPER: &str = r#"
+        [execution]
+        mode = "paper"
+        fees = { exchange = 0.001, slippage = 0.0005 }
+
+        [[markets]]
+        exchange = "binance_spot"
+        base = "btc"
+        quote = "usdt"
+        instrument_kind = "crypto_spot"
+        subscriptions = ["public_trades", "agg_trades"]
+
+        [strategy]
+        name = "tick_react"
+        rsi_period = 14
+
+        [portfolio]
+        starting_cash = 10000.0
+        allocator = { kind = "default", default_order_value = 100.0 }
+    "#;
+
+    #[test]
+    fn test_de_config() {
+        let toml = Config::from_toml(PAPER).unwrap();
+        assert_eq!(toml.execution.mode, ExecutionMode::Paper);
+        assert_eq!(toml.markets[0].subscriptions, vec![SubKind::PublicTrades, SubKind::AggTrades]);
+        assert_eq!(toml.strategy, StrategyConfig::TickReact { rsi_period: 14 });
+        assert_eq!(toml.portfolio.repository, RepositoryConfig::InMemory);
+        assert_eq!(toml.logging, LoggingConfig::default());
+        assert!(toml.validate().is_ok());
+
+        let yaml = Config::from_yaml(
+            r#"
+            execution:
+              mode: paper
+              fees: { exchange: 0.001, slippage: 0.0005 }
+            markets:
+              - exchange: binance_spot
+                base: btc
+                quote: usdt
+                instrument_kind: crypto_spot
+                subscriptions: [public_trades, agg_trades]
+            strategy:
+              name: tick_react
+              rsi_period: 14
+            portfolio:
+              starting_cash: 10000.0
+              allocator: { kind: default, default_order_value: 100.0 }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(yaml, toml);
+
+        // Unknown fields are rejected rather than silently ignored
+        assert!(Config::from_toml(&PAPER.replace("rsi_period", "rsi_periods")).is_err());
+    }
+
+    #[test]
+    fn test_validate_config() {
+        struct TestCase {
+            input: String,
+            expected: Vec<&'static str>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: live mode & unsupported subscription
+                input: PAPER.replace(r#"mode = "paper""#, r#"mode = "live""#).replace("agg_trades", "open_interests"),
+                expected: vec![
+                    "execution.mode: live trading is not supported yet, no ExecutionClient places orders on exchanges (use paper)",
+                    "markets[0] (binance_spot:btc/usdt): binance_spot does not support OpenInterests subscriptions",
+                ],
+            },
+            TestCase {
+                // TC1: simulated mode without data file
+                input: PAPER.replace(r#"mode = "paper""#, r#"mode = "simulated""#),
+                expected: vec!["markets[0] (binance_spot:btc/usdt): data file is required in simulated mode"],
+            },
+            TestCase {
+                // TC2: instrument not listed by the exchange & invalid portfolio
+                input: PAPER
+                    .replace("crypto_spot", "crypto_perpetual")
+                    .replace("starting_cash = 10000.0", "starting_cash = 50.0")
+                    .replace("rsi_period = 14", "rsi_period = 0"),
+                expected: vec![
+                    "markets[0] (binance_spot:btc/usdt): binance_spot does not list CryptoPerpetual instruments",
+                    "strategy.rsi_period: must be at least 1",
+                    "portfolio.allocator.default_order_value: must be positive & at most the starting_cash",
+                ],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = match Config::from_toml(&test.input).unwrap().validate() {
+                Err(ConfigError::Invalid(problems)) => problems,
+                other => panic!("TC{} failed: {:?}", index, other),
+            };
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+}
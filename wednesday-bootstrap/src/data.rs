@@ -0,0 +1,211 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+use chrono::Utc;
+use tracing::debug;
+use wednesday_model::{
+    bar::Bar,
+    events::{DataKind, MarketEvent},
+    identifiers::Market,
+};
+
+use crate::error::BootstrapError;
+
+/// Format of a historical market data file replayed by a
+/// [`HistoricalMarketFeed`](wednesday_core::data::historical::HistoricalMarketFeed), determined
+/// by the file extension.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DataFormat {
+    /// `.json` array of [`Bar`]s of the market.
+    Json,
+    /// `.csv` of [`Bar`]s of the market, with a `close_time,open,high,low,close,volume,trade_count`
+    /// header.
+    Csv,
+    /// `.jsonl` or `.ndjson` recorded stream, one [`MarketEvent<DataKind>`] per line, of which the
+    /// events of other markets are skipped.
+    Recorded,
+}
+
+impl DataFormat {
+    pub const EXTENSIONS: [&'static str; 4] = ["json", "csv", "jsonl", "ndjson"];
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "json" => Some(DataFormat::Json),
+            "csv" => Some(DataFormat::Csv),
+            "jsonl" | "ndjson" => Some(DataFormat::Recorded),
+            _ => None,
+        }
+    }
+}
+
+/// Load the [`MarketEvent`]s of the `market` from the data file, in the order they are replayed.
+pub fn load(path: &Path, market: &Market) -> Result<Vec<MarketEvent<DataKind>>, BootstrapError> {
+    let read_error = |source| BootstrapError::ReadData {
+        path: path.to_path_buf(),
+        source,
+    };
+
+    let events = match DataFormat::from_path(path) {
+        Some(DataFormat::Json) => {
+            let bars = std::fs::read_to_string(path).map_err(read_error)?;
+            let bars = serde_json::from_str::<Vec<Bar>>(&bars).map_err(|source| BootstrapError::ParseData {
+                path: path.to_path_buf(),
+                line: None,
+                source,
+            })?;
+            bar_events(bars, market)
+        },
+        Some(DataFormat::Csv) => {
+            let bars = csv::Reader::from_path(path)
+                .and_then(|mut reader| reader.deserialize::<Bar>().collect::<Result<Vec<_>, _>>())
+                .map_err(|source| BootstrapError::ParseCsv {
+                    path: path.to_path_buf(),
+                    source,
+                })?;
+            bar_events(bars, market)
+        },
+        Some(DataFormat::Recorded) => {
+            let reader = BufReader::new(File::open(path).map_err(read_error)?);
+
+            let mut events = Vec::new();
+            for (index, line) in reader.lines().enumerate() {
+                let line = line.map_err(read_error)?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+
+                let event = serde_json::from_str::<MarketEvent<DataKind>>(&line).map_err(|source| BootstrapError::ParseData {
+                    path: path.to_path_buf(),
+                    line: Some(index + 1),
+                    source,
+                })?;
+                if event.exchange == market.exchange && event.instrument == market.instrument {
+                    events.push(event);
+                }
+            }
+            events
+        },
+        None => return Err(BootstrapError::DataFormat(path.to_path_buf())),
+    };
+
+    debug!(?path, ?market, events = events.len(), "loaded historical market data");
+    Ok(events)
+}
+
+fn bar_events(bars: Vec<Bar>, market: &Market) -> Vec<MarketEvent<DataKind>> {
+    bars.into_iter()
+        .map(|bar| MarketEvent {
+            exchange_ts: bar.close_time,
+            local_ts: Utc::now(),
+            exchange: market.exchange.clone(),
+            instrument: market.instrument.clone(),
+            kind: DataKind::Bar(bar),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use chrono::DateTime;
+    use wednesday_model::{enums::AggressorSide, instruments::InstrumentKind, trade::PublicTrade};
+
+    use super::*;
+
+    fn write(name: &str, contents: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("wednesday-data-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn market(base: &str) -> Market {
+        Market::new("binance_spot", (base, "usdt", InstrumentKind::CryptoSpot))
+    }
+
+    fn bar(close: f64) -> Bar {
+        Bar {
+            close_time: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+            open: 100.0,
+            high: 110.0,
+            low: 90.0,
+            close,
+            volume: 25.0,
+            trade_count: 42,
+        }
+    }
+
+    fn trade(market: &Market) -> MarketEvent<DataKind> {
+        MarketEvent {
+            exchange_ts: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+            local_ts: DateTime::from_timestamp(1_700_000_001, 0).unwrap(),
+            exchange: market.exchange.clone(),
+            instrument: market.instrument.clone(),
+            kind: DataKind::PublicTrade(PublicTrade {
+                id: "1".to_owned(),
+                price: 100.0,
+                quantity: 0.5,
+                aggressor_side: AggressorSide::Buy,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_load() {
+        let btc = market("btc");
+        let recorded = [trade(&btc), trade(&market("eth")), trade(&btc)]
+            .iter()
+            .map(|event| serde_json::to_string(event).unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        struct TestCase {
+            path: PathBuf,
+            expected: Result<Vec<DataKind>, ()>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: json bars
+                path: write("bars.json", &serde_json::to_string(&[bar(105.0), bar(95.0)]).unwrap()),
+                expected: Ok(vec![DataKind::Bar(bar(105.0)), DataKind::Bar(bar(95.0))]),
+            },
+            TestCase {
+                // TC1: csv bars
+                path: write(
+                    "bars.csv",
+                    "close_time,open,high,low,close,volume,trade_count\n2023-11-14T22:13:20Z,100.0,110.0,90.0,105.0,25.0,42\n",
+                ),
+                expected: Ok(vec![DataKind::Bar(bar(105.0))]),
+            },
+            TestCase {
+                // TC2: recorded stream with events of another market & a trailing newline
+                path: write("trades.jsonl", &format!("{recorded}\n")),
+                expected: Ok(vec![trade(&btc).kind, trade(&btc).kind]),
+            },
+            TestCase {
+                // TC3: malformed recorded stream
+                path: write("malformed.ndjson", "{}"),
+                expected: Err(()),
+            },
+            TestCase {
+                // TC4: unsupported format
+                path: write("bars.parquet", ""),
+                expected: Err(()),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = load(&test.path, &btc)
+                .map(|events| events.into_iter().map(|event| event.kind).collect())
+                .map_err(|_| ());
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+
+use thiserror::Error;
+use wednesday_core::model::{engine_error::EngineError, portfolio_error::PortfolioError, repository_error::RepositoryError};
+use wednesday_model::error::DataError;
+
+use crate::config::error::ConfigError;
+
+/// All errors generated whilst building & running an engine from a
+/// [`Config`](crate::config::Config).
+#[derive(Error, Debug)]
+pub enum BootstrapError {
+    #[error("{0}")]
+    Config(#[from] ConfigError),
+
+    #[error("failed to read market data file {path:?}: {source}")]
+    ReadData { path: PathBuf, source: std::io::Error },
+
+    #[error("failed to parse market data file {path:?}{}: {source}", line.map(|line| format!(" line {line}")).unwrap_or_default())]
+    ParseData {
+        path: PathBuf,
+        line: Option<usize>,
+        source: serde_json::Error,
+    },
+
+    #[error("failed to parse market data file {path:?}: {source}")]
+    ParseCsv { path: PathBuf, source: csv::Error },
+
+    #[error("unsupported market data file {0:?}, expected a .json, .csv, .jsonl or .ndjson extension")]
+    DataFormat(PathBuf),
+
+    #[error("failed to initialise market data streams: {0}")]
+    Streams(#[from] DataError),
+
+    #[error("failed to build portfolio: {0}")]
+    Portfolio(#[from] PortfolioError),
+
+    #[error("failed to build engine: {0}")]
+    Engine(#[from] EngineError),
+
+    #[error("failed to collect the engine results: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("failed to write backtest output {path:?}: {source}")]
+    WriteOutput { path: PathBuf, source: std::io::Error },
+}
@@ -0,0 +1,6 @@
+pub mod backtest;
+pub mod config;
+pub mod data;
+pub mod error;
+pub mod runner;
+pub mod server;
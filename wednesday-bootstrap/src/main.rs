@@ -1,45 +1,126 @@
-use tokio_stream::StreamExt;
-use tracing::{debug, info};
-use wednesday_connector::{exchange::bybit::linear::BybitPerpetualsUsd, stream::Streams, subscriber::subscription::kind::PublicTrades};
-use wednesday_model::instruments::InstrumentKind;
-
-// Initialise an INFO `Subscriber` for `Tracing` Json logs and install it as the global default.
-fn init_logging() {
-    tracing_subscriber::fmt()
-        // Filter messages based on the INFO
-        .with_env_filter(
-            tracing_subscriber::filter::EnvFilter::builder()
-                .with_default_directive(tracing_subscriber::filter::LevelFilter::DEBUG.into())
-                .from_env_lossy(),
-        )
+use std::{path::PathBuf, process::ExitCode};
+
+use clap::{Parser, Subcommand};
+use wednesday_bootstrap::{
+    backtest::{self, Backtest},
+    config::{Config, LogFormat, LoggingConfig, MarketConfig},
+    runner,
+};
+use wednesday_core::model::fee::Fees;
+
+#[derive(Debug, Parser)]
+#[command(name = "wednesday", version, about = "Run a wednesday trading engine")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Validate the TOML/YAML config file, then build & run the engine it describes.
+    Run { config: PathBuf },
+    /// Validate the TOML/YAML config file without running the engine.
+    Check { config: PathBuf },
+    /// Backtest a strategy over historical market data files with simulated fills, then write
+    /// the summary & trades to the output directory.
+    Backtest(BacktestArgs),
+}
+
+#[derive(Debug, clap::Args)]
+struct BacktestArgs {
+    /// Market & its JSON/CSV bars or recorded stream, eg/ "binance_spot:btc/usdt:crypto_spot=candles_1h.json".
+    #[arg(long = "market", required = true, value_parser = backtest::parse_market)]
+    markets: Vec<MarketConfig>,
+    /// Strategy to run, eg/ "rsi" or "tick_react".
+    #[arg(long)]
+    strategy: String,
+    /// Strategy parameter, eg/ "rsi_period=14".
+    #[arg(long = "param", value_parser = backtest::parse_param)]
+    params: Vec<(String, String)>,
+    /// Exchange fee as a fraction of the fill value.
+    #[arg(long, default_value_t = 0.0)]
+    exchange_fee: f64,
+    /// Slippage as a fraction of the fill value.
+    #[arg(long, default_value_t = 0.0)]
+    slippage: f64,
+    #[arg(long, default_value_t = 10_000.0)]
+    starting_cash: f64,
+    /// Value of each order the default allocator sizes.
+    #[arg(long, default_value_t = 100.0)]
+    order_value: f64,
+    /// Directory the summary & trades are written to.
+    #[arg(long, short)]
+    output: PathBuf,
+}
+
+impl BacktestArgs {
+    fn backtest(self) -> Result<Backtest, String> {
+        Ok(Backtest {
+            markets: self.markets,
+            strategy: backtest::parse_strategy(&self.strategy, &self.params)?,
+            fees: Fees {
+                exchange: self.exchange_fee,
+                slippage: self.slippage,
+            },
+            starting_cash: self.starting_cash,
+            order_value: self.order_value,
+            output: self.output,
+        })
+    }
+}
+
+// Initialise a `Subscriber` for `Tracing` logs as configured, overridden by `RUST_LOG`, and
+// install it as the global default.
+fn init_logging(config: &LoggingConfig) {
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| tracing_subscriber::EnvFilter::new(&config.level));
+    let subscriber = tracing_subscriber::fmt()
+        .with_env_filter(filter)
         // Disable colours on release builds
-        .with_ansi(cfg!(debug_assertions))
-        // Enable Json formatting
-        .json()
-        // Install this Tracing subscriber as global default
-        .init()
+        .with_ansi(cfg!(debug_assertions));
+
+    match config.format {
+        LogFormat::Json => subscriber.json().init(),
+        LogFormat::Pretty => subscriber.pretty().init(),
+    }
 }
 
-#[rustfmt::skip]
 #[tokio::main]
-async fn main() {
-    init_logging();
-    // Install a default CryptoProvider
-    rustls::crypto::ring::default_provider().install_default().unwrap();
-    let streams = Streams::<PublicTrades>::builder()
-        // .subscribe([
-        //     (BinanceSpot::default(), "pepe", "usdt", InstrumentKind::CryptoSpot, OrderBooksL2)])
-        .subscribe([
-            (BybitPerpetualsUsd::default(), "btc", "usdt", InstrumentKind::CryptoPerpetual, PublicTrades)
-        ])
-        .init()
-        .await
-        .unwrap();
-
-    let mut joined = streams.join_map().await;
-
-    while let Some((exchange, ob)) = joined.next().await {
-        // info!(%exchange, ?ob, "Received orderbook");
-    }
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
 
+    // Config errors are reported before any logging is initialised or the engine starts
+    let result = match cli.command {
+        Command::Check { config } => Config::load(&config).map(|_| println!("{config:?} is valid")).map_err(Into::into),
+        Command::Run { config } => match Config::load(&config) {
+            Ok(config) => {
+                init_logging(&config.logging);
+                runner::run(config).await.map(|_| ())
+            },
+            Err(error) => Err(error.into()),
+        },
+        Command::Backtest(args) => {
+            let backtest = match args.backtest() {
+                Ok(backtest) => backtest,
+                Err(error) => {
+                    eprintln!("error: {error}");
+                    return ExitCode::FAILURE;
+                },
+            };
+            match backtest.config().validate() {
+                Ok(()) => {
+                    init_logging(&backtest.config().logging);
+                    backtest.run().await.map(|_| println!("backtest results written to {:?}", backtest.output))
+                },
+                Err(error) => Err(error.into()),
+            }
+        },
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        },
+    }
 }
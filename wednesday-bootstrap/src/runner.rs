@@ -0,0 +1,377 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use parking_lot::Mutex;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+use wednesday_connector::{
+    exchange::{
+        binance::{futures::BinanceFuturesUsd, spot::BinanceSpot},
+        bitfinex::Bitfinex,
+        bitmex::Bitmex,
+        bybit::{linear::BybitPerpetualsUsd, spot::BybitSpot},
+        coinbase::Coinbase,
+        gateio::{
+            perpetual::{GateioPerpetualsBtc, GateioPerpetualsUsd},
+            spot::GateioSpot,
+        },
+        kraken::Kraken,
+        okx::Okx,
+    },
+    stream::{
+        builder::{multiple::MultiStreamBuilder, StreamBuilder},
+        Streams,
+    },
+    subscriber::subscription::kind::{AggTrades, MarkPrices, OpenInterests, PublicTrades},
+};
+use wednesday_core::{
+    data::{historical::HistoricalMarketFeed, live::LiveMarketFeed, FeedGenerator},
+    engine::{commond::EngineCommand, trader::Trader, TradingEngine},
+    execution::simulated::{SimExecConfig, SimulatedExecution},
+    model::{
+        event::{Event, EventTx},
+        position::Position,
+        signal::Signal,
+    },
+    oms::evaluator::DefaultRisk,
+    portfolio::{
+        repository::{in_memory::InMemoryRepository, PositionHandler, StatisticHandler},
+        MetaPortfolio,
+    },
+    statistic::summary::{
+        trading::{Config as StatisticConfig, TradingSummary},
+        Initialiser, PositionSummariser,
+    },
+    strategy::{
+        sample::{RsiStrategy, StrategyConfig as RsiStrategyConfig},
+        tick_str1::{TickReactStrategy, TickReactStrategyConfig},
+        SignalGenerator,
+    },
+};
+use wednesday_model::{
+    channel::{channel, ChannelConfig, OverflowPolicy},
+    events::{DataKind, MarketEvent},
+    identifiers::{ExchangeId, Market, MarketId},
+};
+
+use crate::{
+    config::{error::ConfigError, AllocatorConfig, Config, ExecutionMode, MarketConfig, RepositoryConfig, RiskConfig, StrategyConfig, SubKind},
+    data,
+    error::BootstrapError,
+};
+
+/// Market events buffered for each [`Trader`] before the oldest are dropped in paper mode.
+const LIVE_FEED_CAPACITY: usize = 10_000;
+
+/// Results of an engine run, collected from the portfolio once every [`Trader`] stopped.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub engine_id: Uuid,
+    /// [`TradingSummary`] of each market by [`MarketId`], & of every market under
+    /// [`Report::TOTAL`].
+    pub summary: BTreeMap<String, TradingSummary>,
+    /// Exited [`Position`]s of every market.
+    pub trades: Vec<Position>,
+}
+
+impl Report {
+    pub const TOTAL: &'static str = "total";
+}
+
+/// Build the engine described by the [`Config`] & run it until every [`Trader`] stops, or the
+/// process receives Ctrl-C.
+pub async fn run(config: Config) -> Result<Report, BootstrapError> {
+    config.validate()?;
+
+    match config.execution.mode {
+        ExecutionMode::Simulated => {
+            let feeds = config
+                .markets
+                .iter()
+                .map(|market| {
+                    let path = market.data.as_deref().expect("data file of every market is validated in simulated mode");
+                    Ok((market.market(), HistoricalMarketFeed::new(data::load(path, &market.market())?)))
+                })
+                .collect::<Result<Vec<_>, BootstrapError>>()?;
+
+            run_engine(&config, feeds).await
+        },
+        ExecutionMode::Paper => {
+            let feeds = stream_feeds(&config.markets).await?;
+            run_engine(&config, feeds).await
+        },
+        ExecutionMode::Live => unreachable!("live mode is rejected by Config::validate"),
+    }
+}
+
+async fn run_engine<Data>(config: &Config, feeds: Vec<(Market, Data)>) -> Result<Report, BootstrapError>
+where
+    Data: FeedGenerator<MarketEvent<DataKind>> + Send + 'static,
+{
+    // Create channel to distribute Commands to the Engine & it's Traders (eg/ Command::Terminate)
+    let (command_tx, command_rx) = mpsc::channel(10);
+    let (event_tx, event_rx) = mpsc::unbounded_channel();
+    let event_tx = EventTx::new(event_tx);
+    let engine_id = Uuid::new_v4();
+
+    let statistic_config = StatisticConfig {
+        starting_equity: config.portfolio.starting_cash,
+        trading_days_per_year: config.portfolio.trading_days_per_year,
+        risk_free_return: config.portfolio.risk_free_return,
+    };
+
+    let AllocatorConfig::Default(allocator) = config.portfolio.allocator;
+    let RiskConfig::Default = config.portfolio.risk;
+    let RepositoryConfig::InMemory = config.portfolio.repository;
+
+    let portfolio = Arc::new(Mutex::new(
+        MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(feeds.iter().map(|(market, _)| market.clone()).collect())
+            .starting_cash(config.portfolio.starting_cash)
+            .repository(InMemoryRepository::new())
+            .allocation_manager(allocator)
+            .risk_manager(DefaultRisk {})
+            .statistic_config(statistic_config)
+            .build_and_init()?,
+    ));
+
+    let mut traders = Vec::with_capacity(feeds.len());
+    let mut trader_command_txs = HashMap::with_capacity(feeds.len());
+    for (market, data) in feeds {
+        let (trader_command_tx, trader_command_rx) = mpsc::channel(10);
+        // Historical data ends, so the open position of a simulated market is exited at its last price
+        let exit_on_finish = match config.execution.mode {
+            ExecutionMode::Simulated => vec![market.clone()],
+            ExecutionMode::Paper | ExecutionMode::Live => vec![],
+        };
+        traders.push(
+            Trader::builder()
+                .engine_id(engine_id)
+                .market(market.clone())
+                .command_rx(trader_command_rx)
+                .event_tx(event_tx.clone())
+                .portfolio(Arc::clone(&portfolio))
+                .data(data)
+                .strategy(Strategy::from(config.strategy))
+                .execution(SimulatedExecution::new(SimExecConfig {
+                    simulated_fees_pct: config.execution.fees,
+                }))
+                .exit_on_finish(exit_on_finish)
+                .build()?,
+        );
+        trader_command_txs.insert(market, trader_command_tx);
+    }
+
+    let markets = trader_command_txs.keys().cloned().collect::<Vec<_>>();
+    let engine = TradingEngine::builder()
+        .engine_id(engine_id)
+        .command_rx(command_rx)
+        .portfolio(Arc::clone(&portfolio))
+        .traders(traders)
+        .trader_command_txs(trader_command_txs)
+        .statistics_summary(TradingSummary::init(statistic_config))
+        .build()?;
+
+    tokio::spawn(log_events(event_rx));
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            info!("received Ctrl-C, terminating engine");
+            let _ = command_tx.send(EngineCommand::Terminate("received Ctrl-C".to_owned())).await;
+        }
+    });
+
+    info!(%engine_id, mode = ?config.execution.mode, markets = config.markets.len(), "running engine");
+    engine.run().await;
+
+    let mut portfolio = portfolio.lock();
+    let trades = portfolio.get_exited_positions(engine_id)?;
+
+    let mut summary = BTreeMap::new();
+    for market in &markets {
+        let market_id = MarketId::from(market);
+        let statistic: TradingSummary = portfolio.get_statistics(&market_id)?;
+        summary.insert(market_id.0, statistic);
+    }
+    let mut total = TradingSummary::init(statistic_config);
+    total.generate_summary(&trades);
+    summary.insert(Report::TOTAL.to_owned(), total);
+
+    Ok(Report { engine_id, summary, trades })
+}
+
+/// Subscribe to the live market data of every market, & route the [`MarketEvent`]s of each
+/// market to the [`LiveMarketFeed`] of its [`Trader`].
+async fn stream_feeds(markets: &[MarketConfig]) -> Result<Vec<(Market, LiveMarketFeed<MarketEvent<DataKind>>)>, BootstrapError> {
+    // Install a default CryptoProvider, unless the process already installed one
+    let _ = rustls::crypto::ring::default_provider().install_default();
+
+    // Markets of each exchange & kind share their connections
+    let mut subscriptions = BTreeMap::<_, Vec<&MarketConfig>>::new();
+    for market in markets {
+        for kind in &market.subscriptions {
+            subscriptions.entry((market.exchange, *kind)).or_default().push(market);
+        }
+    }
+
+    let mut streams = Streams::builder_multi();
+    for ((exchange, kind), markets) in subscriptions {
+        streams = subscribe(streams, exchange, kind, &markets)
+            .ok_or_else(|| ConfigError::Invalid(vec![format!("markets: {exchange} does not support {kind:?} subscriptions")]))?;
+    }
+    let mut event_rx = streams.init().await?.join().await;
+
+    let (market_txs, feeds): (HashMap<_, _>, Vec<_>) = markets
+        .iter()
+        .map(|market| {
+            let (market_tx, market_rx) = channel(ChannelConfig::bounded(LIVE_FEED_CAPACITY, OverflowPolicy::DropOldest));
+            ((market.market(), market_tx), (market.market(), LiveMarketFeed::new(market_rx)))
+        })
+        .unzip();
+
+    tokio::spawn(async move {
+        while let Some(event) = event_rx.recv().await {
+            let market = Market::new(event.exchange.clone(), event.instrument.clone());
+            match market_txs.get(&market) {
+                Some(market_tx) => {
+                    if market_tx.send(event).await.is_err() {
+                        debug!(?market, why = "trader stopped", "dropping market event");
+                    }
+                },
+                None => warn!(?market, "received market event of an unconfigured market"),
+            }
+        }
+    });
+
+    Ok(feeds)
+}
+
+/// Add a [`StreamBuilder`] subscribing to the [`SubKind`] of every market on the exchange, or
+/// `None` if no connector streams it, see [`SubKind::supports`].
+pub fn subscribe(
+    streams: MultiStreamBuilder<MarketEvent<DataKind>>,
+    exchange: ExchangeId,
+    kind: SubKind,
+    markets: &[&MarketConfig],
+) -> Option<MultiStreamBuilder<MarketEvent<DataKind>>> {
+    macro_rules! add {
+        ($exchange:expr, $kind:expr) => {
+            streams.add(
+                StreamBuilder::new().subscribe(
+                    markets
+                        .iter()
+                        .map(|market| ($exchange, market.base.as_str(), market.quote.as_str(), market.instrument_kind, $kind)),
+                ),
+            )
+        };
+    }
+
+    let streams = match (exchange, kind) {
+        (ExchangeId::BinanceSpot, SubKind::PublicTrades) => add!(BinanceSpot::default(), PublicTrades),
+        (ExchangeId::BinanceSpot, SubKind::AggTrades) => add!(BinanceSpot::default(), AggTrades),
+        (ExchangeId::BinanceFuturesUsd, SubKind::PublicTrades) => add!(BinanceFuturesUsd::default(), PublicTrades),
+        (ExchangeId::BinanceFuturesUsd, SubKind::AggTrades) => add!(BinanceFuturesUsd::default(), AggTrades),
+        (ExchangeId::BinanceFuturesUsd, SubKind::MarkPrices) => add!(BinanceFuturesUsd::default(), MarkPrices),
+        (ExchangeId::Bitfinex, SubKind::PublicTrades) => add!(Bitfinex, PublicTrades),
+        (ExchangeId::Bitmex, SubKind::PublicTrades) => add!(Bitmex, PublicTrades),
+        (ExchangeId::BybitSpot, SubKind::PublicTrades) => add!(BybitSpot::default(), PublicTrades),
+        (ExchangeId::BybitPerpetualsUsd, SubKind::PublicTrades) => add!(BybitPerpetualsUsd::default(), PublicTrades),
+        (ExchangeId::BybitPerpetualsUsd, SubKind::MarkPrices) => add!(BybitPerpetualsUsd::default(), MarkPrices),
+        (ExchangeId::BybitPerpetualsUsd, SubKind::OpenInterests) => add!(BybitPerpetualsUsd::default(), OpenInterests),
+        (ExchangeId::Coinbase, SubKind::PublicTrades) => add!(Coinbase, PublicTrades),
+        (ExchangeId::GateioSpot, SubKind::PublicTrades) => add!(GateioSpot::default(), PublicTrades),
+        (ExchangeId::GateioPerpetualsUsd, SubKind::PublicTrades) => add!(GateioPerpetualsUsd::default(), PublicTrades),
+        (ExchangeId::GateioPerpetualsBtc, SubKind::PublicTrades) => add!(GateioPerpetualsBtc::default(), PublicTrades),
+        (ExchangeId::Kraken, SubKind::PublicTrades) => add!(Kraken, PublicTrades),
+        (ExchangeId::Okx, SubKind::PublicTrades) => add!(Okx, PublicTrades),
+        _ => return None,
+    };
+
+    Some(streams)
+}
+
+/// Strategy selected by the [`StrategyConfig`], shared by every [`Trader`] of the engine.
+pub enum Strategy {
+    Rsi(RsiStrategy),
+    TickReact(TickReactStrategy),
+}
+
+impl From<StrategyConfig> for Strategy {
+    fn from(config: StrategyConfig) -> Self {
+        match config {
+            StrategyConfig::Rsi { rsi_period } => Strategy::Rsi(RsiStrategy::new(RsiStrategyConfig {
+                rsi_period,
+                ..Default::default()
+            })),
+            StrategyConfig::TickReact { rsi_period } => Strategy::TickReact(TickReactStrategy::new(TickReactStrategyConfig { rsi_period })),
+        }
+    }
+}
+
+impl SignalGenerator for Strategy {
+    fn generate_signal(&mut self, market: &MarketEvent<DataKind>) -> Option<Signal> {
+        match self {
+            Strategy::Rsi(strategy) => strategy.generate_signal(market),
+            Strategy::TickReact(strategy) => strategy.generate_signal(market),
+        }
+    }
+}
+
+async fn log_events(mut event_rx: mpsc::UnboundedReceiver<Event>) {
+    while let Some(event) = event_rx.recv().await {
+        match event {
+            Event::Market(_) | Event::OrderUpdate => {},
+            Event::Signal(_) | Event::PositionUpdate(_) => debug!(?event, "engine event"),
+            _ => info!(?event, "engine event"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wednesday_model::instruments::InstrumentKind;
+
+    use super::*;
+
+    #[test]
+    fn test_subscribe_supported_kinds() {
+        let kinds = [SubKind::PublicTrades, SubKind::AggTrades, SubKind::MarkPrices, SubKind::OpenInterests];
+        let exchanges = [
+            ExchangeId::BinanceFuturesUsd,
+            ExchangeId::BinanceSpot,
+            ExchangeId::Bitfinex,
+            ExchangeId::Bitmex,
+            ExchangeId::BybitSpot,
+            ExchangeId::BybitPerpetualsUsd,
+            ExchangeId::Coinbase,
+            ExchangeId::GateioSpot,
+            ExchangeId::GateioFuturesUsd,
+            ExchangeId::GateioFuturesBtc,
+            ExchangeId::GateioPerpetualsBtc,
+            ExchangeId::GateioPerpetualsUsd,
+            ExchangeId::GateioOptions,
+            ExchangeId::Kraken,
+            ExchangeId::Krx,
+            ExchangeId::Okx,
+        ];
+
+        // SubKind::supports, used to validate configs, must agree with the connectors subscribed
+        for exchange in exchanges {
+            for kind in kinds {
+                let market = MarketConfig {
+                    exchange,
+                    base: "btc".to_owned(),
+                    quote: "usdt".to_owned(),
+                    instrument_kind: InstrumentKind::CryptoPerpetual,
+                    subscriptions: vec![kind],
+                    data: None,
+                };
+                let streams = subscribe(Streams::builder_multi(), exchange, kind, &[&market]);
+                assert_eq!(streams.is_some(), kind.supports(exchange), "{exchange} {kind:?} failed");
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use thiserror::Error;
+use wednesday_core::model::engine_error::EngineError;
+
+/// All errors generated by the [`ControlServer`](super::ControlServer).
+#[derive(Error, Debug)]
+pub enum ServerError {
+    #[error("ControlServer token must not be empty")]
+    EmptyToken,
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("WebSocket error: {0}")]
+    WebSocket(Box<tokio_tungstenite::tungstenite::Error>),
+
+    #[error("invalid command: {0}")]
+    InvalidCommand(#[from] serde_json::Error),
+
+    #[error("engine command receiver dropped")]
+    EngineUnavailable,
+
+    #[error("engine did not respond to {0} within the command timeout")]
+    Timeout(&'static str),
+
+    #[error("engine failed to action command: {0}")]
+    Engine(#[from] EngineError),
+}
+
+impl From<tokio_tungstenite::tungstenite::Error> for ServerError {
+    fn from(error: tokio_tungstenite::tungstenite::Error) -> Self {
+        ServerError::WebSocket(Box::new(error))
+    }
+}
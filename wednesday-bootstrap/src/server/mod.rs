@@ -0,0 +1,524 @@
+pub mod error;
+pub mod protocol;
+
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+use futures::{SinkExt, StreamExt};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::{broadcast, mpsc},
+};
+use tokio_tungstenite::{
+    accept_hdr_async,
+    tungstenite::{
+        handshake::server::{Callback, ErrorResponse, Request, Response},
+        http::StatusCode,
+        Message,
+    },
+};
+use tracing::{debug, info, warn};
+use wednesday_core::{
+    engine::commond::EngineCommand,
+    model::event::{Event, EventRecord},
+};
+
+use self::{
+    error::ServerError,
+    protocol::{ClientMessage, ServerMessage},
+};
+
+/// Path of the WebSocket endpoint that streams the [`Event`]s & accepts commands.
+pub const EVENTS_PATH: &str = "/events";
+
+/// Path of the Http endpoint that accepts a single command per `POST` request.
+pub const COMMANDS_PATH: &str = "/commands";
+
+/// Path of the unauthenticated Http liveness endpoint.
+pub const HEALTH_PATH: &str = "/health";
+
+pub const DEFAULT_EVENT_CAPACITY: usize = 1024;
+pub const DEFAULT_COMMAND_TIMEOUT_MS: u64 = 5000;
+
+/// Largest request head & body accepted by the [`ControlServer`].
+const MAX_REQUEST_BYTES: usize = 64 * 1024;
+
+/// Time allowed for a client to send its request head & body.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Configuration of a [`ControlServer`].
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    /// Address to serve on, eg/ "127.0.0.1:8700".
+    pub addr: SocketAddr,
+    /// Bearer token every client must present, either as an `Authorization: Bearer <token>`
+    /// header, or as a `token` query parameter since browsers can't set WebSocket headers. Must
+    /// not be empty or whitespace.
+    pub token: String,
+    /// Events buffered for each WebSocket client before it is told it lagged.
+    pub event_capacity: usize,
+    /// Time the engine is given to reply to a command.
+    pub command_timeout: Duration,
+}
+
+impl ServerConfig {
+    pub fn new<Token: Into<String>>(addr: SocketAddr, token: Token) -> Self {
+        Self {
+            addr,
+            token: token.into(),
+            event_capacity: DEFAULT_EVENT_CAPACITY,
+            command_timeout: Duration::from_millis(DEFAULT_COMMAND_TIMEOUT_MS),
+        }
+    }
+
+    pub fn with_event_capacity(self, event_capacity: usize) -> Self {
+        Self {
+            event_capacity: event_capacity.max(1),
+            ..self
+        }
+    }
+
+    pub fn with_command_timeout(self, command_timeout: Duration) -> Self {
+        Self { command_timeout, ..self }
+    }
+}
+
+/// Publishes the serialised [`Event`]s of an engine to every WebSocket client of a
+/// [`ControlServer`], cheap to clone into the listener of the engine [`Event`]s.
+#[derive(Debug, Clone)]
+pub struct EventPublisher(broadcast::Sender<Arc<str>>);
+
+impl EventPublisher {
+    /// Serialise & publish the [`Event`], which is dropped if no client is connected.
+    pub fn publish(&self, event: &Event) {
+        if self.0.receiver_count() > 0 {
+            let message = ServerMessage::Event {
+                event: EventRecord::new(event),
+            };
+            let _ = self.0.send(Arc::from(message.to_json()));
+        }
+    }
+
+    /// Spawn a task that publishes every [`Event`] of the `event_rx`.
+    pub fn forward(&self, mut event_rx: mpsc::UnboundedReceiver<Event>) {
+        let publisher = self.clone();
+        tokio::spawn(async move {
+            while let Some(event) = event_rx.recv().await {
+                publisher.publish(&event);
+            }
+        });
+    }
+}
+
+/// Optional Http & WebSocket server to supervise a running engine remotely.
+///
+/// - `GET` [`EVENTS_PATH`] upgrades to a WebSocket that streams every published [`Event`] as a
+///   [`ServerMessage`], & accepts a [`ClientMessage`] command per text frame.
+/// - `POST` [`COMMANDS_PATH`] accepts a single [`ClientMessage`] command as the request body.
+/// - `GET` [`HEALTH_PATH`] responds without authentication, eg/ for a load balancer.
+///
+/// Every other endpoint requires the [`ServerConfig::token`].
+#[derive(Debug, Clone)]
+pub struct ControlServer {
+    config: Arc<ServerConfig>,
+    command_tx: mpsc::Sender<EngineCommand>,
+    events: EventPublisher,
+}
+
+impl ControlServer {
+    /// Construct a [`ControlServer`], rejecting an empty or whitespace [`ServerConfig::token`]
+    /// since a blank bearer token would authorise any client that sends one.
+    pub fn new(config: ServerConfig, command_tx: mpsc::Sender<EngineCommand>) -> Result<Self, ServerError> {
+        if config.token.trim().is_empty() {
+            return Err(ServerError::EmptyToken);
+        }
+
+        let (events, _) = broadcast::channel(config.event_capacity);
+        Ok(Self {
+            config: Arc::new(config),
+            command_tx,
+            events: EventPublisher(events),
+        })
+    }
+
+    /// [`EventPublisher`] of the [`Event`]s streamed to the WebSocket clients.
+    pub fn publisher(&self) -> EventPublisher {
+        self.events.clone()
+    }
+
+    /// Bind the [`ServerConfig::addr`] & serve until the listener fails.
+    pub async fn run(self) -> Result<(), ServerError> {
+        let listener = TcpListener::bind(self.config.addr).await?;
+        self.serve(listener).await
+    }
+
+    /// Serve the connections of the bound `listener`.
+    pub async fn serve(self, listener: TcpListener) -> Result<(), ServerError> {
+        info!(addr = %listener.local_addr()?, "ControlServer listening");
+
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            let server = self.clone();
+            tokio::spawn(async move {
+                if let Err(error) = server.connection(stream).await {
+                    debug!(%peer, %error, "ControlServer connection closed with error");
+                }
+            });
+        }
+    }
+
+    async fn connection(self, mut stream: TcpStream) -> Result<(), ServerError> {
+        // The request head & body share a deadline, so a client can't hold the connection open
+        // by sending them slowly
+        let deadline = tokio::time::Instant::now() + REQUEST_TIMEOUT;
+
+        let Some(head) = tokio::time::timeout_at(deadline, peek_head(&stream)).await.ok().flatten() else {
+            return respond(&mut stream, StatusCode::BAD_REQUEST, String::new()).await;
+        };
+
+        if head.len + head.content_length > MAX_REQUEST_BYTES {
+            return respond(&mut stream, StatusCode::PAYLOAD_TOO_LARGE, String::new()).await;
+        }
+
+        if head.upgrade && head.path == EVENTS_PATH {
+            return self.websocket(stream).await;
+        }
+
+        // Consume the peeked request head & the body
+        let mut request = vec![0u8; head.len + head.content_length];
+        match tokio::time::timeout_at(deadline, stream.read_exact(&mut request)).await {
+            Ok(read) => read?,
+            Err(_) => return respond(&mut stream, StatusCode::REQUEST_TIMEOUT, String::new()).await,
+        };
+        let body = &request[head.len..];
+
+        match (head.method.as_str(), head.path.as_str()) {
+            ("GET", HEALTH_PATH) => respond(&mut stream, StatusCode::OK, r#"{"status":"ok"}"#.to_owned()).await,
+            (_, COMMANDS_PATH | EVENTS_PATH) if !self.authorised(head.authorization.as_deref(), head.query.as_deref()) => {
+                respond(&mut stream, StatusCode::UNAUTHORIZED, String::new()).await
+            },
+            ("POST", COMMANDS_PATH) => {
+                let reply = self.command(body).await;
+                respond(&mut stream, StatusCode::OK, reply).await
+            },
+            (_, COMMANDS_PATH | EVENTS_PATH) => respond(&mut stream, StatusCode::METHOD_NOT_ALLOWED, String::new()).await,
+            _ => respond(&mut stream, StatusCode::NOT_FOUND, String::new()).await,
+        }
+    }
+
+    /// Stream the published [`Event`]s to an authenticated WebSocket client, & action its
+    /// commands.
+    async fn websocket(self, stream: TcpStream) -> Result<(), ServerError> {
+        let websocket = accept_hdr_async(stream, Authorise(&self.config.token)).await?;
+
+        info!("ControlServer WebSocket client connected");
+        let (mut sink, mut messages) = websocket.split();
+        let mut events = self.events.0.subscribe();
+
+        loop {
+            tokio::select! {
+                event = events.recv() => match event {
+                    Ok(event) => sink.send(Message::Text(event.to_string())).await?,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!(skipped, "ControlServer WebSocket client lagged");
+                        sink.send(Message::Text(ServerMessage::Lagged { skipped }.to_json())).await?;
+                    },
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                message = messages.next() => match message {
+                    Some(Ok(Message::Text(text))) => {
+                        let reply = self.command(text.as_bytes()).await;
+                        sink.send(Message::Text(reply)).await?;
+                    },
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Ok(_)) => continue,
+                    Some(Err(error)) => return Err(error.into()),
+                },
+            }
+        }
+
+        info!("ControlServer WebSocket client disconnected");
+        Ok(())
+    }
+
+    /// Parse & execute a [`ClientMessage`], returning the serialised [`ServerMessage`] reply.
+    async fn command(&self, message: &[u8]) -> String {
+        let message = match serde_json::from_slice::<ClientMessage>(message) {
+            Ok(message) => message,
+            Err(error) => return ServerMessage::reply(None, Err(error.into())).to_json(),
+        };
+
+        info!(id = ?message.id, command = message.command.name(), "ControlServer actioning command");
+        let outcome = message.command.execute(&self.command_tx, self.config.command_timeout).await;
+        ServerMessage::reply(message.id, outcome).to_json()
+    }
+
+    fn authorised(&self, authorization: Option<&str>, query: Option<&str>) -> bool {
+        authorised(&self.config.token, authorization, query)
+    }
+}
+
+/// Parsed head of a Http request that is yet to be read from the connection.
+#[derive(Debug)]
+struct RequestHead {
+    len: usize,
+    method: String,
+    path: String,
+    query: Option<String>,
+    authorization: Option<String>,
+    content_length: usize,
+    upgrade: bool,
+}
+
+/// Peek the Http request head without consuming it, so that a WebSocket upgrade request can be
+/// handed to the WebSocket handshake as is.
+async fn peek_head(stream: &TcpStream) -> Option<RequestHead> {
+    let mut buffer = vec![0u8; MAX_REQUEST_BYTES];
+
+    loop {
+        let peeked = stream.peek(&mut buffer).await.ok().filter(|peeked| *peeked > 0)?;
+
+        let mut headers = [httparse::EMPTY_HEADER; 32];
+        let mut request = httparse::Request::new(&mut headers);
+        match request.parse(&buffer[..peeked]).ok()? {
+            httparse::Status::Complete(len) => {
+                let header = |name: &str| {
+                    request
+                        .headers
+                        .iter()
+                        .find(|header| header.name.eq_ignore_ascii_case(name))
+                        .and_then(|header| std::str::from_utf8(header.value).ok())
+                };
+
+                let (path, query) = match request.path?.split_once('?') {
+                    Some((path, query)) => (path, Some(query.to_owned())),
+                    None => (request.path?, None),
+                };
+                let content_length = header("content-length").and_then(|value| value.trim().parse().ok()).unwrap_or(0);
+
+                return Some(RequestHead {
+                    len,
+                    method: request.method.unwrap_or_default().to_owned(),
+                    path: path.to_owned(),
+                    query,
+                    authorization: header("authorization").map(str::to_owned),
+                    content_length,
+                    upgrade: header("upgrade").is_some_and(|value| value.eq_ignore_ascii_case("websocket")),
+                });
+            },
+            // Wait for the remainder of the request head
+            httparse::Status::Partial if peeked < buffer.len() => tokio::time::sleep(Duration::from_millis(5)).await,
+            httparse::Status::Partial => return None,
+        }
+    }
+}
+
+/// WebSocket handshake [`Callback`] rejecting a client that is not [`authorised`] by the token.
+struct Authorise<'a>(&'a str);
+
+impl Callback for Authorise<'_> {
+    fn on_request(self, request: &Request, response: Response) -> Result<Response, ErrorResponse> {
+        let authorization = request.headers().get("authorization").and_then(|value| value.to_str().ok());
+        match authorised(self.0, authorization, request.uri().query()) {
+            true => Ok(response),
+            false => {
+                let mut rejection = ErrorResponse::new(None);
+                *rejection.status_mut() = StatusCode::UNAUTHORIZED;
+                Err(rejection)
+            },
+        }
+    }
+}
+
+/// Whether the `Authorization: Bearer <token>` header or the `token` query parameter matches the
+/// expected `token`.
+fn authorised(token: &str, authorization: Option<&str>, query: Option<&str>) -> bool {
+    let bearer = authorization.and_then(|value| value.strip_prefix("Bearer ")).map(str::trim);
+    let query = query.and_then(|query| {
+        url::form_urlencoded::parse(query.as_bytes())
+            .find(|(key, _)| key == "token")
+            .map(|(_, value)| value.into_owned())
+    });
+
+    bearer.is_some_and(|bearer| constant_time_eq(bearer, token)) || query.is_some_and(|query| constant_time_eq(&query, token))
+}
+
+/// Compare the secrets in constant time with respect to their contents.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+async fn respond(stream: &mut TcpStream, status: StatusCode, body: String) -> Result<(), ServerError> {
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio_tungstenite::connect_async;
+
+    use super::*;
+
+    const TOKEN: &str = "secret";
+
+    async fn server() -> (SocketAddr, EventPublisher, mpsc::Receiver<EngineCommand>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (command_tx, command_rx) = mpsc::channel(10);
+
+        let server = ControlServer::new(ServerConfig::new(addr, TOKEN), command_tx).unwrap();
+        let publisher = server.publisher();
+        tokio::spawn(server.serve(listener));
+
+        (addr, publisher, command_rx)
+    }
+
+    async fn http(addr: SocketAddr, request: String) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[test]
+    fn test_authorised() {
+        struct TestCase {
+            authorization: Option<&'static str>,
+            query: Option<&'static str>,
+            expected: bool,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: bearer token
+                authorization: Some("Bearer secret"),
+                query: None,
+                expected: true,
+            },
+            TestCase {
+                // TC1: query token amongst other parameters
+                authorization: None,
+                query: Some("client=dashboard&token=secret"),
+                expected: true,
+            },
+            TestCase {
+                // TC2: wrong token
+                authorization: Some("Bearer secreT"),
+                query: Some("token=secrets"),
+                expected: false,
+            },
+            TestCase {
+                // TC3: no token
+                authorization: None,
+                query: None,
+                expected: false,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            assert_eq!(authorised(TOKEN, test.authorization, test.query), test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_control_server_rejects_blank_token() {
+        struct TestCase {
+            token: &'static str,
+            expected: bool,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: empty token
+                token: "",
+                expected: false,
+            },
+            TestCase {
+                // TC1: whitespace token
+                token: " \t\n",
+                expected: false,
+            },
+            TestCase {
+                // TC2: valid token
+                token: TOKEN,
+                expected: true,
+            },
+        ];
+
+        let addr = SocketAddr::from(([127, 0, 0, 1], 0));
+        let (command_tx, _command_rx) = mpsc::channel(1);
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = ControlServer::new(ServerConfig::new(addr, test.token), command_tx.clone());
+            match (actual, test.expected) {
+                (Ok(_), true) | (Err(ServerError::EmptyToken), false) => {},
+                (actual, _) => panic!("TC{} failed: {:?}", index, actual.map(|_| ())),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_http_endpoints() {
+        let (addr, _publisher, mut command_rx) = server().await;
+
+        let health = http(addr, "GET /health HTTP/1.1\r\n\r\n".to_owned()).await;
+        assert!(health.starts_with("HTTP/1.1 200 OK\r\n"));
+
+        let command = r#"{"id": 1, "command": "exit_all_positions"}"#;
+        let unauthorised = http(addr, format!("POST /commands HTTP/1.1\r\nContent-Length: {}\r\n\r\n{command}", command.len())).await;
+        assert!(unauthorised.starts_with("HTTP/1.1 401 Unauthorized\r\n"));
+
+        let authorised = http(
+            addr,
+            format!(
+                "POST /commands HTTP/1.1\r\nAuthorization: Bearer {TOKEN}\r\nContent-Length: {}\r\n\r\n{command}",
+                command.len()
+            ),
+        )
+        .await;
+        assert!(authorised.ends_with(r#"{"type":"ack","id":1,"command":"exit_all_positions"}"#));
+        assert!(matches!(command_rx.recv().await, Some(EngineCommand::ExitAllPositions)));
+
+        let unknown = http(addr, "GET /unknown HTTP/1.1\r\n\r\n".to_owned()).await;
+        assert!(unknown.starts_with("HTTP/1.1 404 Not Found\r\n"));
+
+        let too_large = http(addr, format!("POST /commands HTTP/1.1\r\nContent-Length: {MAX_REQUEST_BYTES}\r\n\r\n")).await;
+        assert!(too_large.starts_with("HTTP/1.1 413 Payload Too Large\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_websocket() {
+        let (addr, publisher, mut command_rx) = server().await;
+
+        // Unauthenticated clients are rejected during the handshake
+        assert!(connect_async(format!("ws://{addr}{EVENTS_PATH}")).await.is_err());
+
+        let (mut websocket, _) = connect_async(format!("ws://{addr}{EVENTS_PATH}?token={TOKEN}")).await.unwrap();
+
+        websocket.send(Message::Text(r#"{"id": 2, "command": "pause"}"#.to_owned())).await.unwrap();
+        assert!(matches!(command_rx.recv().await, Some(EngineCommand::Pause)));
+        assert_eq!(
+            websocket.next().await.unwrap().unwrap(),
+            Message::Text(r#"{"type":"ack","id":2,"command":"pause"}"#.to_owned())
+        );
+
+        websocket.send(Message::Text(r#"{"command": "liquidate"}"#.to_owned())).await.unwrap();
+        let Message::Text(error) = websocket.next().await.unwrap().unwrap() else {
+            panic!("expected text frame");
+        };
+        assert!(error.starts_with(r#"{"type":"error","id":null,"message":"invalid command"#));
+
+        publisher.publish(&Event::OrderUpdate);
+        assert_eq!(
+            websocket.next().await.unwrap().unwrap(),
+            Message::Text(r#"{"type":"event","event":{"version":1,"type":"order_update"}}"#.to_owned())
+        );
+    }
+}
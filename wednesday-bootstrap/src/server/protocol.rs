@@ -0,0 +1,194 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, oneshot};
+use wednesday_core::{
+    engine::commond::EngineCommand,
+    model::{
+        event::{Event, EventRecord},
+        position::Position,
+    },
+};
+use wednesday_model::identifiers::Market;
+
+use super::error::ServerError;
+
+/// Command sent by a client as a JSON text frame over the WebSocket, or as the body of a
+/// `POST /commands` request, eg/ `{"id": 1, "command": "exit_position", "market": {..}}`.
+///
+/// The optional `id` is echoed in the [`ServerMessage`] replying to the command.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ClientMessage {
+    #[serde(default)]
+    pub id: Option<u64>,
+    #[serde(flatten)]
+    pub command: ClientCommand,
+}
+
+/// Remote command mapped to an [`EngineCommand`].
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum ClientCommand {
+    FetchOpenPositions,
+    ExitPosition { market: Market },
+    ExitAllPositions,
+    Terminate { reason: Option<String> },
+    Pause,
+    Resume,
+}
+
+impl ClientCommand {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ClientCommand::FetchOpenPositions => "fetch_open_positions",
+            ClientCommand::ExitPosition { .. } => "exit_position",
+            ClientCommand::ExitAllPositions => "exit_all_positions",
+            ClientCommand::Terminate { .. } => "terminate",
+            ClientCommand::Pause => "pause",
+            ClientCommand::Resume => "resume",
+        }
+    }
+
+    /// Send the [`EngineCommand`] of the [`ClientCommand`] to the engine, waiting at most the
+    /// `timeout` for the engine to reply to a [`ClientCommand::FetchOpenPositions`].
+    pub async fn execute(self, command_tx: &mpsc::Sender<EngineCommand>, timeout: Duration) -> Result<Reply, ServerError> {
+        let name = self.name();
+        let (command, positions_rx) = match self {
+            ClientCommand::FetchOpenPositions => {
+                let (positions_tx, positions_rx) = oneshot::channel();
+                (EngineCommand::FetchOpenPositions(positions_tx), Some(positions_rx))
+            },
+            ClientCommand::ExitPosition { market } => (EngineCommand::ExitPosition(market), None),
+            ClientCommand::ExitAllPositions => (EngineCommand::ExitAllPositions, None),
+            ClientCommand::Terminate { reason } => (EngineCommand::Terminate(reason.unwrap_or_else(|| "remote terminate command".to_owned())), None),
+            ClientCommand::Pause => (EngineCommand::Pause, None),
+            ClientCommand::Resume => (EngineCommand::Resume, None),
+        };
+
+        command_tx.send(command).await.map_err(|_| ServerError::EngineUnavailable)?;
+
+        match positions_rx {
+            Some(positions_rx) => match tokio::time::timeout(timeout, positions_rx).await {
+                Ok(Ok(positions)) => Ok(Reply::Positions(positions?)),
+                Ok(Err(_)) => Err(ServerError::EngineUnavailable),
+                Err(_) => Err(ServerError::Timeout(name)),
+            },
+            None => Ok(Reply::Ack(name)),
+        }
+    }
+}
+
+/// Successful outcome of a [`ClientCommand`].
+#[derive(Debug)]
+pub enum Reply {
+    Ack(&'static str),
+    Positions(Vec<Position>),
+}
+
+/// Message sent by the server as a JSON text frame over the WebSocket, or as the body of a
+/// `POST /commands` response.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage<'a> {
+    /// [`Event`] of the engine, tagged with its schema version.
+    Event { event: EventRecord<&'a Event> },
+    /// [`ClientCommand`] was sent to the engine.
+    Ack { id: Option<u64>, command: &'static str },
+    /// Open positions replying to a [`ClientCommand::FetchOpenPositions`].
+    Positions { id: Option<u64>, positions: Vec<Position> },
+    /// [`ClientCommand`] could not be parsed or actioned.
+    Error { id: Option<u64>, message: String },
+    /// Client fell behind & missed the `skipped` most recent events.
+    Lagged { skipped: u64 },
+}
+
+impl ServerMessage<'_> {
+    /// [`ServerMessage`] replying to the outcome of the [`ClientCommand`] with the `id`.
+    pub fn reply(id: Option<u64>, outcome: Result<Reply, ServerError>) -> Self {
+        match outcome {
+            Ok(Reply::Ack(command)) => ServerMessage::Ack { id, command },
+            Ok(Reply::Positions(positions)) => ServerMessage::Positions { id, positions },
+            Err(error) => ServerMessage::Error {
+                id,
+                message: error.to_string(),
+            },
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("ServerMessage is always serialisable")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wednesday_model::instruments::InstrumentKind;
+
+    use super::*;
+
+    #[test]
+    fn test_de_client_message() {
+        struct TestCase {
+            input: &'static str,
+            expected: Result<ClientMessage, ()>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: command without id
+                input: r#"{"command": "pause"}"#,
+                expected: Ok(ClientMessage {
+                    id: None,
+                    command: ClientCommand::Pause,
+                }),
+            },
+            TestCase {
+                // TC1: command with market
+                input: r#"{"id": 7, "command": "exit_position", "market": {"exchange": "binance", "base_currency": "btc", "quote_currency": "usdt", "instrument_kind": "crypto_spot"}}"#,
+                expected: Ok(ClientMessage {
+                    id: Some(7),
+                    command: ClientCommand::ExitPosition {
+                        market: Market::new("binance", ("btc", "usdt", InstrumentKind::CryptoSpot)),
+                    },
+                }),
+            },
+            TestCase {
+                // TC2: terminate with reason
+                input: r#"{"id": 8, "command": "terminate", "reason": "end of day"}"#,
+                expected: Ok(ClientMessage {
+                    id: Some(8),
+                    command: ClientCommand::Terminate {
+                        reason: Some("end of day".to_owned()),
+                    },
+                }),
+            },
+            TestCase {
+                // TC3: unknown command
+                input: r#"{"id": 9, "command": "liquidate"}"#,
+                expected: Err(()),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = serde_json::from_str::<ClientMessage>(test.input).map_err(|_| ());
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_fetch_open_positions() {
+        let (command_tx, mut command_rx) = mpsc::channel(1);
+        tokio::spawn(async move {
+            if let Some(EngineCommand::FetchOpenPositions(positions_tx)) = command_rx.recv().await {
+                let _ = positions_tx.send(Ok(vec![]));
+            }
+        });
+
+        let reply = ClientCommand::FetchOpenPositions.execute(&command_tx, Duration::from_secs(1)).await;
+        assert!(matches!(reply, Ok(Reply::Positions(positions)) if positions.is_empty()));
+
+        // Engine no longer replies
+        let reply = ClientCommand::FetchOpenPositions.execute(&command_tx, Duration::from_millis(10)).await;
+        assert!(matches!(reply, Err(ServerError::EngineUnavailable)));
+    }
+}
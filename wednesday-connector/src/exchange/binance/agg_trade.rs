@@ -0,0 +1,198 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use wednesday_model::{
+    deserialization,
+    enums::AggressorSide,
+    events::MarketEvent,
+    identifiers::{Exchange, ExchangeId, Identifier, SubscriptionId},
+    instruments::Instrument,
+    trade::AggregatedTrade,
+};
+
+use crate::{
+    exchange::binance::{channel::BinanceChannel, spot::trade::de_side_from_buyer_is_maker},
+    subscriber::subscription::ExchangeSubscription,
+    transformer::iterator::MarketIter,
+};
+
+/// Binance real-time aggregated trade message, shared by [`BinanceSpot`](super::spot::BinanceSpot)
+/// & [`BinanceFuturesUsd`](super::futures::BinanceFuturesUsd).
+///
+/// ### Raw Payload Examples
+/// See docs: <https://binance-docs.github.io/apidocs/spot/en/#aggregate-trade-streams>
+/// #### Spot Side::Sell AggTrade
+/// ```json
+/// {
+///     "e": "aggTrade",
+///     "E": 1672515782136,
+///     "s": "BNBBTC",
+///     "a": 12345,
+///     "p": "0.001",
+///     "q": "100",
+///     "f": 100,
+///     "l": 105,
+///     "T": 1672515782136,
+///     "m": true,
+///     "M": true
+/// }
+/// ```
+///
+/// See docs: <https://binance-docs.github.io/apidocs/futures/en/#aggregate-trade-streams>
+/// #### FuturePerpetual Side::Buy AggTrade
+/// ```json
+/// {
+///     "e": "aggTrade",
+///     "E": 123456789,
+///     "s": "BTCUSDT",
+///     "a": 5933014,
+///     "p": "0.001",
+///     "q": "100",
+///     "f": 100,
+///     "l": 105,
+///     "T": 123456785,
+///     "m": false
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BinanceAggTrade {
+    #[serde(alias = "s", deserialize_with = "de_agg_trade_subscription_id")]
+    pub subscription_id: SubscriptionId,
+    #[serde(alias = "T", deserialize_with = "deserialization::de_u64_epoch_ms_as_datetime_utc")]
+    pub time: DateTime<Utc>,
+    #[serde(alias = "a")]
+    pub id: u64,
+    #[serde(alias = "p", deserialize_with = "deserialization::de_str")]
+    pub price: f64,
+    #[serde(alias = "q", deserialize_with = "deserialization::de_str")]
+    pub amount: f64,
+    #[serde(alias = "f")]
+    pub first_trade_id: u64,
+    #[serde(alias = "l")]
+    pub last_trade_id: u64,
+    #[serde(alias = "m", deserialize_with = "de_side_from_buyer_is_maker")]
+    pub side: AggressorSide,
+}
+
+impl Identifier<Option<SubscriptionId>> for BinanceAggTrade {
+    fn id(&self) -> Option<SubscriptionId> {
+        Some(self.subscription_id.clone())
+    }
+}
+
+impl From<(ExchangeId, Instrument, BinanceAggTrade)> for MarketIter<AggregatedTrade> {
+    fn from((exchange_id, instrument, trade): (ExchangeId, Instrument, BinanceAggTrade)) -> Self {
+        Self(vec![Ok(MarketEvent {
+            exchange_ts: trade.time,
+            local_ts: Utc::now(),
+            exchange: Exchange::from(exchange_id),
+            instrument,
+            kind: AggregatedTrade {
+                id: trade.id.to_string(),
+                price: trade.price,
+                quantity: trade.amount,
+                first_trade_id: trade.first_trade_id.to_string(),
+                last_trade_id: trade.last_trade_id.to_string(),
+                aggressor_side: trade.side,
+            },
+        })])
+    }
+}
+
+/// Deserialize a [`BinanceAggTrade`] "s" (eg/ "BTCUSDT") as the associated [`SubscriptionId`]
+/// (eg/ "@aggTrade|BTCUSDT").
+pub fn de_agg_trade_subscription_id<'de, D>(deserializer: D) -> Result<SubscriptionId, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    <&str as Deserialize>::deserialize(deserializer).map(|market| ExchangeSubscription::from((BinanceChannel::AGG_TRADES, market)).id())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use super::*;
+        use deserialization::datetime_utc_from_epoch_duration;
+        use std::time::Duration;
+        use wednesday_model::error::SocketError;
+
+        #[test]
+        fn test_binance_agg_trade() {
+            struct TestCase {
+                input: &'static str,
+                expected: Result<BinanceAggTrade, SocketError>,
+            }
+
+            let tests = vec![
+                TestCase {
+                    // TC0: Spot aggTrade valid
+                    input: r#"
+                    {
+                        "e":"aggTrade","E":1672515782136,"s":"BNBBTC","a":12345,"p":"0.001",
+                        "q":"100","f":100,"l":105,"T":1672515782136,"m":true,"M":true
+                    }
+                    "#,
+                    expected: Ok(BinanceAggTrade {
+                        subscription_id: SubscriptionId::from("@aggTrade|BNBBTC"),
+                        time: datetime_utc_from_epoch_duration(Duration::from_millis(1672515782136)),
+                        id: 12345,
+                        price: 0.001,
+                        amount: 100.0,
+                        first_trade_id: 100,
+                        last_trade_id: 105,
+                        side: AggressorSide::Sell,
+                    }),
+                },
+                TestCase {
+                    // TC1: FuturePerpetual aggTrade valid
+                    input: r#"
+                    {
+                        "e":"aggTrade","E":123456789,"s":"BTCUSDT","a":5933014,"p":"0.001",
+                        "q":"100","f":100,"l":105,"T":123456785,"m":false
+                    }
+                    "#,
+                    expected: Ok(BinanceAggTrade {
+                        subscription_id: SubscriptionId::from("@aggTrade|BTCUSDT"),
+                        time: datetime_utc_from_epoch_duration(Duration::from_millis(123456785)),
+                        id: 5933014,
+                        price: 0.001,
+                        amount: 100.0,
+                        first_trade_id: 100,
+                        last_trade_id: 105,
+                        side: AggressorSide::Buy,
+                    }),
+                },
+                TestCase {
+                    // TC2: aggTrade malformed w/ missing last trade id
+                    input: r#"
+                    {
+                        "e":"aggTrade","E":123456789,"s":"BTCUSDT","a":5933014,"p":"0.001",
+                        "q":"100","f":100,"T":123456785,"m":false
+                    }
+                    "#,
+                    expected: Err(SocketError::Unsupported {
+                        entity: "",
+                        item: "".to_string(),
+                    }),
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = serde_json::from_str::<BinanceAggTrade>(test.input);
+                match (actual, test.expected) {
+                    (Ok(actual), Ok(expected)) => {
+                        assert_eq!(actual, expected, "TC{} failed", index)
+                    },
+                    (Err(_), Err(_)) => {
+                        // Test passed
+                    },
+                    (actual, expected) => {
+                        // Test failed
+                        panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                    },
+                }
+            }
+        }
+    }
+}
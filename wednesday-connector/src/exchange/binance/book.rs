@@ -37,6 +37,7 @@ impl From<BinanceOrderBookL2Snapshot> for OrderBook {
     fn from(snapshot: BinanceOrderBookL2Snapshot) -> Self {
         Self {
             last_update_ts: Utc::now(),
+            update_id: Some(snapshot.last_update_id),
             bids: OrderBookSide::new(BookSide::Bid, snapshot.bids),
             asks: OrderBookSide::new(BookSide::Ask, snapshot.asks),
         }
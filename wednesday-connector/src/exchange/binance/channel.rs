@@ -1,18 +1,20 @@
 use wednesday_model::identifiers::Identifier;
 
 use crate::subscriber::subscription::{
-    kind::{OrderBooksL2, PublicTrades},
+    kind::{AggTrades, MarkPrices, OrderBooksL2, PublicTrades},
     Subscription,
 };
 
-use super::Binance;
+use super::{futures::BinanceFuturesUsd, Binance};
 
 pub struct BinanceChannel(pub &'static str);
 
 impl BinanceChannel {
     pub const TRADES: Self = Self("@trade");
+    pub const AGG_TRADES: Self = Self("@aggTrade");
     pub const ORDER_BOOK_L2: Self = Self("@depth@100ms");
     pub const LIQUIDATIONS: Self = Self("@forceOrder");
+    pub const MARK_PRICE: Self = Self("@markPrice@1s");
 }
 
 impl<Server> Identifier<BinanceChannel> for Subscription<Binance<Server>, PublicTrades> {
@@ -21,12 +23,24 @@ impl<Server> Identifier<BinanceChannel> for Subscription<Binance<Server>, Public
     }
 }
 
+impl<Server> Identifier<BinanceChannel> for Subscription<Binance<Server>, AggTrades> {
+    fn id(&self) -> BinanceChannel {
+        BinanceChannel::AGG_TRADES
+    }
+}
+
 impl<Server> Identifier<BinanceChannel> for Subscription<Binance<Server>, OrderBooksL2> {
     fn id(&self) -> BinanceChannel {
         BinanceChannel::ORDER_BOOK_L2
     }
 }
 
+impl Identifier<BinanceChannel> for Subscription<BinanceFuturesUsd, MarkPrices> {
+    fn id(&self) -> BinanceChannel {
+        BinanceChannel::MARK_PRICE
+    }
+}
+
 // impl<Server> Identifier<BinanceChannel> for Subscription<Binance<Server>, Liquidations> {
 //     fn id(&self) -> BinanceChannel {
 //         BinanceChannel::LIQUIDATIONS
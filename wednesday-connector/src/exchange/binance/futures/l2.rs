@@ -172,6 +172,7 @@ impl OrderBookUpdater for BinanceFuturesBookUpdater {
         // 7. The data in each event is the absolute quantity for a price level.
         // 8. If the quantity is 0, remove the price level.
         book.last_update_ts = Utc::now();
+        book.update_id = Some(update.last_update_id);
         book.bids.upsert(update.bids);
         book.asks.upsert(update.asks);
 
@@ -457,6 +458,7 @@ mod tests {
                     },
                     book: OrderBook {
                         last_update_ts: time,
+                        update_id: None,
                         bids: OrderBookSide::new(BookSide::Bid, vec![Level::new(50, 1)]),
                         asks: OrderBookSide::new(BookSide::Ask, vec![Level::new(100, 1)]),
                     },
@@ -478,6 +480,7 @@ mod tests {
                     },
                     book: OrderBook {
                         last_update_ts: time,
+                        update_id: None,
                         bids: OrderBookSide::new(BookSide::Bid, vec![Level::new(80, 1), Level::new(100, 1), Level::new(90, 1)]),
                         asks: OrderBookSide::new(BookSide::Ask, vec![Level::new(150, 1), Level::new(110, 1), Level::new(120, 1)]),
                     },
@@ -501,6 +504,7 @@ mod tests {
                     },
                     expected: Ok(Some(OrderBook {
                         last_update_ts: time,
+                        update_id: Some(110),
                         bids: OrderBookSide::new(BookSide::Bid, vec![Level::new(100, 1), Level::new(90, 10)]),
                         asks: OrderBookSide::new(
                             BookSide::Ask,
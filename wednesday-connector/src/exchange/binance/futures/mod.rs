@@ -3,15 +3,16 @@ use wednesday_model::identifiers::ExchangeId;
 use crate::{
     exchange::connector::ExchangeServer,
     stream::{protocol::ws_stream::ExchangeWsStream, selector::StreamSelector},
-    subscriber::subscription::kind::OrderBooksL2,
-    transformer::stateful::MultiBookTransformer,
+    subscriber::subscription::kind::{MarkPrices, OrderBooksL2, PublicTrades},
+    transformer::{stateful::MultiBookTransformer, stateless::StatelessTransformer},
 };
 
-use self::l2::BinanceFuturesBookUpdater;
+use self::{l2::BinanceFuturesBookUpdater, mark_price::BinanceMarkPrice, trade::BinanceFuturesTrade};
 
 use super::Binance;
 
 pub mod l2;
+pub mod mark_price;
 pub mod trade;
 
 pub const WEBSOCKET_BASE_URL_BINANCE_FUTURES_USD: &str = "wss://fstream.binance.com/ws";
@@ -29,6 +30,14 @@ impl ExchangeServer for BinanceServerFuturesUsd {
     }
 }
 
+impl StreamSelector<PublicTrades> for BinanceFuturesUsd {
+    type Stream = ExchangeWsStream<StatelessTransformer<Self, PublicTrades, BinanceFuturesTrade>>;
+}
+
 impl StreamSelector<OrderBooksL2> for BinanceFuturesUsd {
     type Stream = ExchangeWsStream<MultiBookTransformer<Self, OrderBooksL2, BinanceFuturesBookUpdater>>;
 }
+
+impl StreamSelector<MarkPrices> for BinanceFuturesUsd {
+    type Stream = ExchangeWsStream<StatelessTransformer<Self, MarkPrices, BinanceMarkPrice>>;
+}
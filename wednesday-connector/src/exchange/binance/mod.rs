@@ -3,9 +3,10 @@ use std::marker::PhantomData;
 
 use crate::protocol::http::websocket::{PingInterval, WsMessage};
 use crate::stream::protocol::ws_stream::ExchangeWsStream;
+use crate::stream::rate_limit::RateLimit;
 use crate::stream::selector::StreamSelector;
 use crate::subscriber::protocol::websocket::WsSubscriber;
-use crate::subscriber::subscription::kind::PublicTrades;
+use crate::subscriber::subscription::kind::AggTrades;
 use crate::subscriber::subscription::{ExchangeSubscription, Map};
 use crate::subscriber::validator::WsSubscriptionValidator;
 use crate::transformer::stateless::StatelessTransformer;
@@ -15,17 +16,40 @@ use wednesday_model::identifiers::ExchangeId;
 use wednesday_model::instruments::Instrument;
 
 use self::market::BinanceMarket;
-use self::{channel::BinanceChannel, spot::trade::BinanceSpotTrade, subscription::BinanceSubscriptionResponse};
+use self::{agg_trade::BinanceAggTrade, channel::BinanceChannel, subscription::BinanceSubscriptionResponse};
 
 use super::connector::{Connector, ExchangeServer};
 
+pub mod agg_trade;
 pub mod book;
 pub mod channel;
-mod futures;
+pub mod futures;
 pub mod market;
 pub mod spot;
 pub mod subscription;
 
+/// Maximum number of streams of a [`Binance`] spot WebSocket connection.
+///
+/// See docs: <https://developers.binance.com/docs/binance-spot-api-docs/web-socket-streams#websocket-limits>
+pub const MAX_STREAMS_PER_CONNECTION_BINANCE_SPOT: usize = 1024;
+
+/// Maximum number of streams of a [`Binance`] USD-M futures WebSocket connection.
+///
+/// See docs: <https://developers.binance.com/docs/derivatives/usds-margined-futures/websocket-market-streams>
+pub const MAX_STREAMS_PER_CONNECTION_BINANCE_FUTURES: usize = 200;
+
+/// Maximum number of messages per second a client may send over a [`Binance`] spot WebSocket
+/// connection, including pings & pongs.
+///
+/// See docs: <https://developers.binance.com/docs/binance-spot-api-docs/web-socket-streams#websocket-limits>
+pub const MAX_MESSAGES_PER_SECOND_BINANCE_SPOT: usize = 5;
+
+/// Maximum number of messages per second a client may send over a [`Binance`] USD-M futures
+/// WebSocket connection.
+///
+/// See docs: <https://developers.binance.com/docs/derivatives/usds-margined-futures/websocket-market-streams>
+pub const MAX_MESSAGES_PER_SECOND_BINANCE_FUTURES: usize = 10;
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
 pub struct Binance<Server> {
     server: PhantomData<Server>,
@@ -46,20 +70,23 @@ where
         Url::parse(Server::ws_url()).map_err(|e| SocketError::UrlParse(e))
     }
 
+    fn leg_url(leg: usize) -> Result<Url, SocketError> {
+        match (Server::ID, leg % 2) {
+            (ExchangeId::BinanceSpot, 1) => Url::parse(spot::WEBSOCKET_DATA_URL_BINANCE_SPOT).map_err(SocketError::UrlParse),
+            _ => Self::url(),
+        }
+    }
+
     fn requests(exchange_subscriptions: Vec<ExchangeSubscription<Self::Channel, Self::Market>>) -> Vec<WsMessage> {
-        let stream_names = exchange_subscriptions
-            .into_iter()
-            .map(|sub| format!("{}{}", sub.market.as_ref().to_lowercase(), sub.channel.as_ref()))
-            .collect::<Vec<String>>();
-
-        vec![WsMessage::Text(
-            serde_json::json!({
-                "method": "SUBSCRIBE",
-                "params": stream_names,
-                "id": 1
-            })
-            .to_string(),
-        )]
+        vec![binance_request("SUBSCRIBE", exchange_subscriptions)]
+    }
+
+    fn dynamic_subscriptions() -> bool {
+        true
+    }
+
+    fn unsubscribe_requests(exchange_subscriptions: Vec<ExchangeSubscription<Self::Channel, Self::Market>>) -> Vec<WsMessage> {
+        vec![binance_request("UNSUBSCRIBE", exchange_subscriptions)]
     }
 
     fn ping_interval() -> Option<PingInterval> {
@@ -70,16 +97,49 @@ where
         1
     }
 
+    fn max_subscriptions_per_connection() -> Option<usize> {
+        match Server::ID {
+            ExchangeId::BinanceFuturesUsd => Some(MAX_STREAMS_PER_CONNECTION_BINANCE_FUTURES),
+            _ => Some(MAX_STREAMS_PER_CONNECTION_BINANCE_SPOT),
+        }
+    }
+
     fn subscription_timeout() -> std::time::Duration {
         crate::exchange::connector::DEFAULT_SUBSCRIPTION_TIMEOUT
     }
+
+    fn message_rate_limit() -> Option<RateLimit> {
+        // Leave room for the pongs sent over the connection
+        let messages = match Server::ID {
+            ExchangeId::BinanceFuturesUsd => MAX_MESSAGES_PER_SECOND_BINANCE_FUTURES,
+            _ => MAX_MESSAGES_PER_SECOND_BINANCE_SPOT,
+        };
+        Some(RateLimit::new(messages - 1, std::time::Duration::from_secs(1)))
+    }
+}
+
+/// Single request with the provided "SUBSCRIBE" or "UNSUBSCRIBE" method for every stream name.
+fn binance_request(method: &str, exchange_subscriptions: Vec<ExchangeSubscription<BinanceChannel, BinanceMarket>>) -> WsMessage {
+    let stream_names = exchange_subscriptions
+        .into_iter()
+        .map(|sub| format!("{}{}", sub.market.as_ref().to_lowercase(), sub.channel.as_ref()))
+        .collect::<Vec<String>>();
+
+    WsMessage::Text(
+        serde_json::json!({
+            "method": method,
+            "params": stream_names,
+            "id": 1
+        })
+        .to_string(),
+    )
 }
 
-impl<Server> StreamSelector<PublicTrades> for Binance<Server>
+impl<Server> StreamSelector<AggTrades> for Binance<Server>
 where
     Server: ExchangeServer + Debug + Send + Sync,
 {
-    type Stream = ExchangeWsStream<StatelessTransformer<Self, PublicTrades, BinanceSpotTrade>>;
+    type Stream = ExchangeWsStream<StatelessTransformer<Self, AggTrades, BinanceAggTrade>>;
 }
 
 impl<'de, Server> serde::Deserialize<'de> for Binance<Server>
@@ -96,10 +156,7 @@ where
         if input.as_str() == Self::ID.as_str() {
             Ok(Self::default())
         } else {
-            Err(serde::de::Error::invalid_value(
-                serde::de::Unexpected::Str(input.as_str()),
-                &expected,
-            ))
+            Err(serde::de::Error::invalid_value(serde::de::Unexpected::Str(input.as_str()), &expected))
         }
     }
 }
@@ -165,6 +165,7 @@ impl OrderBookUpdater for BinanceSpotBookUpdater {
         }
 
         book.last_update_ts = chrono::Utc::now();
+        book.update_id = Some(update.last_update_id);
         book.bids.upsert(update.bids);
         book.asks.upsert(update.asks);
 
@@ -3,11 +3,11 @@ use wednesday_model::identifiers::ExchangeId;
 use crate::{
     exchange::connector::ExchangeServer,
     stream::{protocol::ws_stream::ExchangeWsStream, selector::StreamSelector},
-    subscriber::subscription::kind::OrderBooksL2,
-    transformer::stateful::MultiBookTransformer,
+    subscriber::subscription::kind::{OrderBooksL2, PublicTrades},
+    transformer::{stateful::MultiBookTransformer, stateless::StatelessTransformer},
 };
 
-use self::l2::BinanceSpotBookUpdater;
+use self::{l2::BinanceSpotBookUpdater, trade::BinanceSpotTrade};
 
 use super::Binance;
 
@@ -16,6 +16,12 @@ pub mod trade;
 
 pub const WEBSOCKET_BASE_URL_BINANCE_SPOT: &str = "wss://stream.binance.com:9443/ws";
 
+/// [`Binance`] spot market data only WebSocket endpoint, used by every other
+/// redundant connection.
+///
+/// See docs: <https://developers.binance.com/docs/binance-spot-api-docs/web-socket-streams#general-wss-information>
+pub const WEBSOCKET_DATA_URL_BINANCE_SPOT: &str = "wss://data-stream.binance.vision/ws";
+
 pub type BinanceSpot = Binance<BinanceServerSpot>;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
@@ -30,6 +36,10 @@ impl ExchangeServer for BinanceServerSpot {
     }
 }
 
+impl StreamSelector<PublicTrades> for BinanceSpot {
+    type Stream = ExchangeWsStream<StatelessTransformer<Self, PublicTrades, BinanceSpotTrade>>;
+}
+
 impl StreamSelector<OrderBooksL2> for BinanceSpot {
     type Stream = ExchangeWsStream<MultiBookTransformer<Self, OrderBooksL2, BinanceSpotBookUpdater>>;
 }
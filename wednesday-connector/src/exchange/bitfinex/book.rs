@@ -0,0 +1,225 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{Deserialize, Deserializer, Serialize};
+use tokio::sync::mpsc::UnboundedSender;
+use wednesday_model::{
+    enums::BookSide,
+    error::DataError,
+    instruments::Instrument,
+    orderbook::{Level, OrderBook, OrderBookSide},
+};
+
+use crate::{
+    protocol::http::websocket::WsMessage,
+    transformer::updater::{InstrumentOrderBook, OrderBookUpdater},
+};
+
+use super::message::BitfinexMessage;
+
+/// Number of price levels per side requested for a [`Bitfinex`](super::Bitfinex) L2 book.
+pub const BITFINEX_BOOK_DEPTH: usize = 25;
+
+pub type BitfinexOrderBookL2 = BitfinexMessage<BitfinexLevel>;
+
+/// [`Bitfinex`](super::Bitfinex) aggregated price level.
+///
+/// A positive amount is a bid, a negative amount is an ask, and a zero count removes the level.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.bitfinex.com/reference/ws-public-books>
+/// ```json
+/// [PRICE, COUNT, AMOUNT]
+/// [7254.7, 3, -3.3]
+/// ```
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Serialize)]
+pub struct BitfinexLevel {
+    pub price: f64,
+    pub count: u64,
+    pub amount: f64,
+}
+
+impl<'de> Deserialize<'de> for BitfinexLevel {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (price, count, amount) = <(f64, u64, f64)>::deserialize(deserializer)?;
+        Ok(Self { price, count, amount })
+    }
+}
+
+impl BitfinexLevel {
+    /// [`BookSide`] of the level, determined by the sign of the amount.
+    pub fn side(&self) -> BookSide {
+        if self.amount > 0.0 {
+            BookSide::Bid
+        } else {
+            BookSide::Ask
+        }
+    }
+}
+
+impl From<BitfinexLevel> for Level {
+    fn from(level: BitfinexLevel) -> Self {
+        Self::new(level.price, level.amount.abs())
+    }
+}
+
+/// [`Bitfinex`](super::Bitfinex) L2 [`OrderBookUpdater`].
+///
+/// The book is replaced by each snapshot (sent after every (re)subscription), and each update
+/// upserts a level, or removes it if the count is zero.
+///
+/// See docs: <https://docs.bitfinex.com/reference/ws-public-books>
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct BitfinexBookUpdater {
+    pub updates_processed: u64,
+}
+
+impl BitfinexBookUpdater {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl OrderBookUpdater for BitfinexBookUpdater {
+    type OrderBook = OrderBook;
+    type Update = BitfinexOrderBookL2;
+
+    async fn init<Exchange, Kind>(_: UnboundedSender<WsMessage>, instrument: Instrument) -> Result<InstrumentOrderBook<Self>, DataError>
+    where
+        Exchange: Send,
+        Kind: Send,
+    {
+        // Bitfinex pushes a snapshot as the first channel message after subscribing, so no
+        // REST snapshot is required to initialise the book
+        Ok(InstrumentOrderBook {
+            instrument,
+            updater: Self::new(),
+            book: OrderBook::default(),
+        })
+    }
+
+    fn update(&mut self, book: &mut Self::OrderBook, update: Self::Update) -> Result<Option<Self::OrderBook>, DataError> {
+        match update {
+            BitfinexMessage::Snapshot { data, .. } => {
+                let (bids, asks): (Vec<_>, Vec<_>) = data.into_iter().partition(|level| level.side() == BookSide::Bid);
+                book.bids = OrderBookSide::new(BookSide::Bid, bids);
+                book.asks = OrderBookSide::new(BookSide::Ask, asks);
+            },
+            BitfinexMessage::Update { data: level, .. } => {
+                let side = level.side();
+                let level = match level.count {
+                    0 => Level::new(level.price, 0.0),
+                    _ => Level::from(level),
+                };
+
+                match side {
+                    BookSide::Bid => book.bids.upsert_single(level),
+                    BookSide::Ask => book.asks.upsert_single(level),
+                }
+            },
+            BitfinexMessage::Event => return Ok(None),
+        }
+
+        self.updates_processed += 1;
+        book.last_update_ts = Utc::now();
+
+        Ok(Some(book.snapshot()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wednesday_model::identifiers::SubscriptionId;
+
+    use super::*;
+
+    mod de {
+        use super::*;
+
+        #[test]
+        fn test_bitfinex_order_book_l2() {
+            let input = r#"[17470, [[7254.7, 3, 3.3], [7255.1, 1, -2.0]]]"#;
+
+            assert_eq!(
+                serde_json::from_str::<BitfinexOrderBookL2>(input).unwrap(),
+                BitfinexMessage::Snapshot {
+                    subscription_id: SubscriptionId::from("17470"),
+                    data: vec![
+                        BitfinexLevel {
+                            price: 7254.7,
+                            count: 3,
+                            amount: 3.3
+                        },
+                        BitfinexLevel {
+                            price: 7255.1,
+                            count: 1,
+                            amount: -2.0
+                        },
+                    ],
+                }
+            );
+        }
+    }
+
+    fn update(price: f64, count: u64, amount: f64) -> BitfinexOrderBookL2 {
+        BitfinexMessage::Update {
+            subscription_id: SubscriptionId::from("17470"),
+            data: BitfinexLevel { price, count, amount },
+        }
+    }
+
+    #[test]
+    fn test_update_bitfinex_order_book_l2() {
+        let mut updater = BitfinexBookUpdater::new();
+        let mut book = OrderBook::default();
+
+        // Heartbeats & events are ignored
+        assert_eq!(updater.update(&mut book, BitfinexMessage::Event).unwrap(), None);
+
+        // Snapshot initialises the book
+        let output = updater
+            .update(
+                &mut book,
+                BitfinexMessage::Snapshot {
+                    subscription_id: SubscriptionId::from("17470"),
+                    data: vec![
+                        BitfinexLevel {
+                            price: 100.0,
+                            count: 1,
+                            amount: 1.0,
+                        },
+                        BitfinexLevel {
+                            price: 99.0,
+                            count: 2,
+                            amount: 2.0,
+                        },
+                        BitfinexLevel {
+                            price: 101.0,
+                            count: 1,
+                            amount: -3.0,
+                        },
+                    ],
+                },
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(output.bids.levels(), vec![Level::new(100.0, 1.0), Level::new(99.0, 2.0)]);
+        assert_eq!(output.asks.levels(), vec![Level::new(101.0, 3.0)]);
+
+        // Update upserts the ask level
+        let output = updater.update(&mut book, update(101.0, 2, -5.0)).unwrap().unwrap();
+        assert_eq!(output.asks.levels(), vec![Level::new(101.0, 5.0)]);
+
+        // Zero count removes the bid level
+        let output = updater.update(&mut book, update(100.0, 0, 1.0)).unwrap().unwrap();
+        assert_eq!(output.bids.levels(), vec![Level::new(99.0, 2.0)]);
+
+        // Zero count removes the ask level
+        let output = updater.update(&mut book, update(101.0, 0, -1.0)).unwrap().unwrap();
+        assert!(output.asks.levels().is_empty());
+        assert_eq!(updater.updates_processed, 4);
+    }
+}
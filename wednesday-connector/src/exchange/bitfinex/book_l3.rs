@@ -0,0 +1,288 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use tokio::sync::mpsc::UnboundedSender;
+use wednesday_model::{
+    enums::BookSide,
+    error::DataError,
+    instruments::Instrument,
+    orderbook_l3::{L3Order, OrderBookL3, OrderBookL3Event},
+};
+
+use crate::{
+    protocol::http::websocket::WsMessage,
+    transformer::updater::{InstrumentOrderBook, OrderBookUpdater},
+};
+
+use super::message::BitfinexMessage;
+
+/// Number of price points per side requested for a [`Bitfinex`](super::Bitfinex) raw book.
+pub const BITFINEX_RAW_BOOK_DEPTH: usize = 250;
+
+/// Precision of the [`Bitfinex`](super::Bitfinex) "book" channel that sends raw books of
+/// individual orders, rather than aggregated price levels.
+pub const BITFINEX_RAW_BOOK_PRECISION: &str = "R0";
+
+pub type BitfinexOrderBookL3 = BitfinexMessage<BitfinexRawOrder>;
+
+/// [`Bitfinex`](super::Bitfinex) raw book order.
+///
+/// A positive amount is a bid, a negative amount is an ask, and a zero price removes the order.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.bitfinex.com/reference/ws-public-raw-books>
+/// ```json
+/// [ORDER_ID, PRICE, AMOUNT]
+/// [34930047290, 7254.7, -0.5]
+/// ```
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Serialize)]
+pub struct BitfinexRawOrder {
+    pub id: u64,
+    pub price: f64,
+    pub amount: f64,
+}
+
+impl<'de> Deserialize<'de> for BitfinexRawOrder {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (id, price, amount) = <(u64, f64, f64)>::deserialize(deserializer)?;
+        Ok(Self { id, price, amount })
+    }
+}
+
+impl BitfinexRawOrder {
+    /// [`BookSide`] of the order, determined by the sign of the amount.
+    pub fn side(&self) -> BookSide {
+        if self.amount > 0.0 {
+            BookSide::Bid
+        } else {
+            BookSide::Ask
+        }
+    }
+
+    pub fn l3_order(&self, time: DateTime<Utc>) -> L3Order {
+        L3Order {
+            id: self.id.to_string(),
+            side: self.side(),
+            price: self.price,
+            amount: self.amount.abs(),
+            time,
+        }
+    }
+
+    /// [`OrderBookL3Event`] of an update, which adds the order if it is not yet in the book.
+    pub fn l3_event(&self, book: &OrderBookL3, time: DateTime<Utc>) -> OrderBookL3Event {
+        let id = self.id.to_string();
+
+        if self.price == 0.0 {
+            OrderBookL3Event::Delete { id, time }
+        } else if book.orders.contains_key(&id) {
+            OrderBookL3Event::Modify {
+                id,
+                price: self.price,
+                amount: self.amount.abs(),
+                time,
+            }
+        } else {
+            OrderBookL3Event::Add(self.l3_order(time))
+        }
+    }
+}
+
+/// [`Bitfinex`](super::Bitfinex) L3 [`OrderBookUpdater`] of raw books.
+///
+/// The book is replaced by each snapshot (sent after every (re)subscription), and each update
+/// adds or modifies an order, or removes it if the price is zero. Raw books have no order
+/// timestamps, so the time an update is received is used for queue priority.
+///
+/// See docs: <https://docs.bitfinex.com/reference/ws-public-raw-books>
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct BitfinexBookL3Updater {
+    pub updates_processed: u64,
+}
+
+impl BitfinexBookL3Updater {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl OrderBookUpdater for BitfinexBookL3Updater {
+    type OrderBook = OrderBookL3;
+    type Update = BitfinexOrderBookL3;
+
+    async fn init<Exchange, Kind>(_: UnboundedSender<WsMessage>, instrument: Instrument) -> Result<InstrumentOrderBook<Self, OrderBookL3>, DataError>
+    where
+        Exchange: Send,
+        Kind: Send,
+    {
+        // Bitfinex pushes a snapshot as the first channel message after subscribing, so no
+        // REST snapshot is required to initialise the book
+        Ok(InstrumentOrderBook {
+            instrument,
+            updater: Self::new(),
+            book: OrderBookL3::default(),
+        })
+    }
+
+    fn update(&mut self, book: &mut Self::OrderBook, update: Self::Update) -> Result<Option<Self::OrderBook>, DataError> {
+        let time = Utc::now();
+
+        match update {
+            BitfinexMessage::Snapshot { data, .. } => {
+                *book = OrderBookL3::new(time, data.iter().map(|order| order.l3_order(time)));
+            },
+            BitfinexMessage::Update { data: order, .. } => {
+                let event = order.l3_event(book, time);
+                book.apply(event);
+            },
+            BitfinexMessage::Event => return Ok(None),
+        }
+
+        self.updates_processed += 1;
+
+        Ok(Some(book.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wednesday_model::{
+        identifiers::SubscriptionId,
+        orderbook::{Level, OrderBook, OrderBookSide},
+    };
+
+    use super::*;
+
+    mod de {
+        use super::*;
+
+        #[test]
+        fn test_bitfinex_order_book_l3() {
+            struct TestCase {
+                input: &'static str,
+                expected: Option<BitfinexOrderBookL3>,
+            }
+
+            let tests = vec![
+                // TC0: snapshot of raw orders
+                TestCase {
+                    input: r#"[224, [[34930047290, 7254.7, 0.5], [34930047291, 7255.1, -2.0]]]"#,
+                    expected: Some(BitfinexMessage::Snapshot {
+                        subscription_id: SubscriptionId::from("224"),
+                        data: vec![
+                            BitfinexRawOrder {
+                                id: 34930047290,
+                                price: 7254.7,
+                                amount: 0.5,
+                            },
+                            BitfinexRawOrder {
+                                id: 34930047291,
+                                price: 7255.1,
+                                amount: -2.0,
+                            },
+                        ],
+                    }),
+                },
+                // TC1: raw order removal
+                TestCase {
+                    input: r#"[224, [34930047290, 0, 1]]"#,
+                    expected: Some(BitfinexMessage::Update {
+                        subscription_id: SubscriptionId::from("224"),
+                        data: BitfinexRawOrder {
+                            id: 34930047290,
+                            price: 0.0,
+                            amount: 1.0,
+                        },
+                    }),
+                },
+                // TC2: aggregated price level is not a raw order
+                TestCase {
+                    input: r#"[224, [7254.7, 3, 3.3]]"#,
+                    expected: None,
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = serde_json::from_str::<BitfinexOrderBookL3>(test.input).ok();
+                assert_eq!(actual, test.expected, "TC{} failed", index);
+            }
+        }
+    }
+
+    fn update(id: u64, price: f64, amount: f64) -> BitfinexOrderBookL3 {
+        BitfinexMessage::Update {
+            subscription_id: SubscriptionId::from("224"),
+            data: BitfinexRawOrder { id, price, amount },
+        }
+    }
+
+    #[test]
+    fn test_update_bitfinex_order_book_l3() {
+        let mut updater = BitfinexBookL3Updater::new();
+        let mut book = OrderBookL3::default();
+
+        // Heartbeats & events are ignored
+        assert_eq!(updater.update(&mut book, BitfinexMessage::Event).unwrap(), None);
+
+        // Snapshot initialises the book
+        let output = updater
+            .update(
+                &mut book,
+                BitfinexMessage::Snapshot {
+                    subscription_id: SubscriptionId::from("224"),
+                    data: vec![
+                        BitfinexRawOrder {
+                            id: 1,
+                            price: 100.0,
+                            amount: 1.0,
+                        },
+                        BitfinexRawOrder {
+                            id: 2,
+                            price: 100.0,
+                            amount: 2.0,
+                        },
+                        BitfinexRawOrder {
+                            id: 3,
+                            price: 101.0,
+                            amount: -3.0,
+                        },
+                    ],
+                },
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(output.orders.len(), 3);
+        assert_eq!(output.levels(BookSide::Bid), vec![Level::new(100.0, 3.0)]);
+        assert_eq!(output.levels(BookSide::Ask), vec![Level::new(101.0, 3.0)]);
+
+        // Update of an unknown order adds it
+        let output = updater.update(&mut book, update(4, 102.0, -1.0)).unwrap().unwrap();
+        assert_eq!(output.orders["4"].side, BookSide::Ask);
+        assert_eq!(output.levels(BookSide::Ask), vec![Level::new(101.0, 3.0), Level::new(102.0, 1.0)]);
+
+        // Update of a known order modifies it
+        let output = updater.update(&mut book, update(2, 100.0, 0.5)).unwrap().unwrap();
+        assert_eq!(output.orders["2"].amount, 0.5);
+        assert_eq!(output.levels(BookSide::Bid), vec![Level::new(100.0, 1.5)]);
+
+        // Zero price removes the order
+        let output = updater.update(&mut book, update(1, 0.0, 1.0)).unwrap().unwrap();
+        assert!(!output.orders.contains_key("1"));
+        assert_eq!(updater.updates_processed, 4);
+
+        // L3 book aggregates into the L2 book
+        assert_eq!(
+            OrderBook::from(&output),
+            OrderBook {
+                last_update_ts: output.last_update_ts,
+                update_id: None,
+                bids: OrderBookSide::new(BookSide::Bid, vec![Level::new(100.0, 0.5)]),
+                asks: OrderBookSide::new(BookSide::Ask, vec![Level::new(101.0, 3.0), Level::new(102.0, 1.0)]),
+            }
+        );
+    }
+}
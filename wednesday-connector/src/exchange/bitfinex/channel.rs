@@ -0,0 +1,49 @@
+use wednesday_model::identifiers::Identifier;
+
+use crate::subscriber::subscription::{
+    kind::{OrderBooksL2, OrderBooksL3, PublicTrades},
+    Subscription,
+};
+
+use super::Bitfinex;
+
+/// Type that defines how to translate a [`Subscription`] into a [`Bitfinex`] channel to be
+/// subscribed to.
+///
+/// See docs: <https://docs.bitfinex.com/docs/ws-public>
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct BitfinexChannel(pub &'static str);
+
+impl BitfinexChannel {
+    /// [`Bitfinex`] real-time trades channel.
+    pub const TRADES: Self = Self("trades");
+    /// [`Bitfinex`] aggregated price level L2 channel, a snapshot followed by level updates.
+    pub const ORDER_BOOK_L2: Self = Self("book");
+    /// [`Bitfinex`] raw book L3 channel, the "book" channel with "R0" precision, which is a
+    /// snapshot followed by updates of individual orders.
+    pub const ORDER_BOOK_L3: Self = Self("book.R0");
+}
+
+impl Identifier<BitfinexChannel> for Subscription<Bitfinex, PublicTrades> {
+    fn id(&self) -> BitfinexChannel {
+        BitfinexChannel::TRADES
+    }
+}
+
+impl Identifier<BitfinexChannel> for Subscription<Bitfinex, OrderBooksL2> {
+    fn id(&self) -> BitfinexChannel {
+        BitfinexChannel::ORDER_BOOK_L2
+    }
+}
+
+impl Identifier<BitfinexChannel> for Subscription<Bitfinex, OrderBooksL3> {
+    fn id(&self) -> BitfinexChannel {
+        BitfinexChannel::ORDER_BOOK_L3
+    }
+}
+
+impl AsRef<str> for BitfinexChannel {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use wednesday_model::{identifiers::Identifier, instruments::Symbol};
+
+use crate::subscriber::subscription::Subscription;
+
+use super::Bitfinex;
+
+/// Type that defines how to translate a [`Subscription`] into a [`Bitfinex`] trading pair
+/// symbol that can be subscribed to.
+///
+/// Trading pair symbols are prefixed with "t", and currencies longer than three characters
+/// are separated by a ":", eg/ "tBTCUSD" & "tTESTBTC:TESTUSD".
+///
+/// See docs: <https://docs.bitfinex.com/docs/ws-general#supported-pairs>
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct BitfinexMarket(pub String);
+
+impl<Kind> Identifier<BitfinexMarket> for Subscription<Bitfinex, Kind> {
+    fn id(&self) -> BitfinexMarket {
+        BitfinexMarket::new(&self.instrument.base_currency, &self.instrument.quote_currency)
+    }
+}
+
+impl BitfinexMarket {
+    pub fn new(base_currency: &Symbol, quote_currency: &Symbol) -> Self {
+        let base = base_currency.as_ref().to_uppercase();
+        let quote = quote_currency.as_ref().to_uppercase();
+
+        if base.len() > 3 || quote.len() > 3 {
+            Self(format!("t{base}:{quote}"))
+        } else {
+            Self(format!("t{base}{quote}"))
+        }
+    }
+}
+
+impl AsRef<str> for BitfinexMarket {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitfinex_market() {
+        struct TestCase {
+            input: (&'static str, &'static str),
+            expected: BitfinexMarket,
+        }
+
+        let tests = vec![
+            // TC0: three character currencies
+            TestCase {
+                input: ("btc", "usd"),
+                expected: BitfinexMarket("tBTCUSD".to_string()),
+            },
+            // TC1: longer currencies are separated
+            TestCase {
+                input: ("doge", "usd"),
+                expected: BitfinexMarket("tDOGE:USD".to_string()),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = BitfinexMarket::new(&Symbol::from(test.input.0), &Symbol::from(test.input.1));
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use serde_json::value::RawValue;
+use wednesday_model::identifiers::{Identifier, SubscriptionId};
+
+use super::subscription::BitfinexChannelId;
+
+/// [`Bitfinex`](super::Bitfinex) WebSocket channel message.
+///
+/// Market data messages are arrays identified by the "chanId" of the subscription. Every
+/// message received on the connection must be deserialisable, so info events, heartbeats &
+/// "tu" trade executions (duplicates of the "te" trade executions) are consumed as
+/// [`BitfinexMessage::Event`].
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.bitfinex.com/docs/ws-general>
+/// #### Snapshot
+/// ```json
+/// [17470, [[7254.7, 3, 3.3], [7254.6, 2, 1.5], [7255.1, 1, -2.0]]]
+/// ```
+///
+/// #### Update
+/// ```json
+/// [17470, [7254.7, 0, 1]]
+/// ```
+///
+/// #### Trade Execution
+/// ```json
+/// [19111, "te", [1372226347, 1676712479661, -0.0043, 24564]]
+/// ```
+///
+/// #### Heartbeat
+/// ```json
+/// [19111, "hb"]
+/// ```
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub enum BitfinexMessage<T> {
+    Snapshot { subscription_id: SubscriptionId, data: Vec<T> },
+    Update { subscription_id: SubscriptionId, data: T },
+    Event,
+}
+
+impl<T> Identifier<Option<SubscriptionId>> for BitfinexMessage<T> {
+    fn id(&self) -> Option<SubscriptionId> {
+        match self {
+            BitfinexMessage::Snapshot { subscription_id, .. } | BitfinexMessage::Update { subscription_id, .. } => Some(subscription_id.clone()),
+            BitfinexMessage::Event => None,
+        }
+    }
+}
+
+impl<'de, T> Deserialize<'de> for BitfinexMessage<T>
+where
+    T: DeserializeOwned,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = Box::<RawValue>::deserialize(deserializer)?;
+
+        // Info, subscription & error events are JSON objects
+        if !raw.get().trim_start().starts_with('[') {
+            return Ok(Self::Event);
+        }
+
+        let elements = serde_json::from_str::<Vec<&RawValue>>(raw.get()).map_err(serde::de::Error::custom)?;
+        let (channel_id, body) = match elements.as_slice() {
+            [channel_id, body, ..] => (
+                serde_json::from_str::<BitfinexChannelId>(channel_id.get()).map_err(serde::de::Error::custom)?,
+                body.get().trim_start(),
+            ),
+            _ => return Err(serde::de::Error::invalid_length(elements.len(), &"[CHANNEL_ID, BODY, ..]")),
+        };
+        let subscription_id = SubscriptionId::from(channel_id);
+
+        // Message type abbreviations, eg/ "hb" heartbeat, "te" & "tu" trade executions
+        if body.starts_with('"') {
+            let kind = serde_json::from_str::<&str>(body).map_err(serde::de::Error::custom)?;
+            return match (kind, elements.get(2)) {
+                ("te", Some(data)) => Ok(Self::Update {
+                    subscription_id,
+                    data: serde_json::from_str(data.get()).map_err(serde::de::Error::custom)?,
+                }),
+                _ => Ok(Self::Event),
+            };
+        }
+
+        // Snapshots are arrays of entries, whereas an update is a single entry
+        let is_snapshot = body.strip_prefix('[').map(str::trim_start).is_some_and(|rest| rest.starts_with(['[', ']']));
+
+        if is_snapshot {
+            Ok(Self::Snapshot {
+                subscription_id,
+                data: serde_json::from_str(body).map_err(serde::de::Error::custom)?,
+            })
+        } else {
+            Ok(Self::Update {
+                subscription_id,
+                data: serde_json::from_str(body).map_err(serde::de::Error::custom)?,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use super::*;
+
+        #[derive(Debug, PartialEq, Deserialize, Serialize)]
+        struct Data(f64, u64, f64);
+
+        #[test]
+        fn test_bitfinex_message() {
+            struct TestCase {
+                input: &'static str,
+                expected: Option<BitfinexMessage<Data>>,
+            }
+
+            let tests = vec![
+                // TC0: info event is an Event
+                TestCase {
+                    input: r#"{"event": "info", "version": 2, "serverId": "e293377e", "platform": {"status": 1}}"#,
+                    expected: Some(BitfinexMessage::Event),
+                },
+                // TC1: heartbeat is an Event
+                TestCase {
+                    input: r#"[17470, "hb"]"#,
+                    expected: Some(BitfinexMessage::Event),
+                },
+                // TC2: snapshot of entries
+                TestCase {
+                    input: r#"[17470, [[7254.7, 3, 3.3], [7255.1, 1, -2.0]]]"#,
+                    expected: Some(BitfinexMessage::Snapshot {
+                        subscription_id: SubscriptionId::from("17470"),
+                        data: vec![Data(7254.7, 3, 3.3), Data(7255.1, 1, -2.0)],
+                    }),
+                },
+                // TC3: empty snapshot
+                TestCase {
+                    input: r#"[17470, [ ]]"#,
+                    expected: Some(BitfinexMessage::Snapshot {
+                        subscription_id: SubscriptionId::from("17470"),
+                        data: vec![],
+                    }),
+                },
+                // TC4: single entry update
+                TestCase {
+                    input: r#"[17470, [7254.7, 0, 1]]"#,
+                    expected: Some(BitfinexMessage::Update {
+                        subscription_id: SubscriptionId::from("17470"),
+                        data: Data(7254.7, 0, 1.0),
+                    }),
+                },
+                // TC5: "te" trade execution is an Update
+                TestCase {
+                    input: r#"[19111, "te", [7254.7, 1676712479661, -0.0043]]"#,
+                    expected: Some(BitfinexMessage::Update {
+                        subscription_id: SubscriptionId::from("19111"),
+                        data: Data(7254.7, 1676712479661, -0.0043),
+                    }),
+                },
+                // TC6: "tu" trade execution is an Event
+                TestCase {
+                    input: r#"[19111, "tu", [7254.7, 1676712479661, -0.0043]]"#,
+                    expected: Some(BitfinexMessage::Event),
+                },
+                // TC7: missing body is invalid
+                TestCase {
+                    input: r#"[19111]"#,
+                    expected: None,
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = serde_json::from_str::<BitfinexMessage<Data>>(test.input).ok();
+                assert_eq!(actual, test.expected, "TC{} failed", index);
+            }
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use url::Url;
+use wednesday_macro::{DeExchange, SerExchange};
+use wednesday_model::{error::SocketError, identifiers::ExchangeId};
+
+use crate::{
+    protocol::http::websocket::WsMessage,
+    stream::{protocol::ws_stream::ExchangeWsStream, selector::StreamSelector},
+    subscriber::{
+        protocol::websocket::WsSubscriber,
+        subscription::{
+            kind::{OrderBooksL2, OrderBooksL3, PublicTrades},
+            ExchangeSubscription,
+        },
+    },
+    transformer::{stateful::MultiBookTransformer, stateless::StatelessTransformer},
+};
+
+use self::{
+    book::{BitfinexBookUpdater, BITFINEX_BOOK_DEPTH},
+    book_l3::{BitfinexBookL3Updater, BITFINEX_RAW_BOOK_DEPTH, BITFINEX_RAW_BOOK_PRECISION},
+    channel::BitfinexChannel,
+    market::BitfinexMarket,
+    subscription::BitfinexSubscriptionResponse,
+    trade::BitfinexTrades,
+    validator::BitfinexWebSocketSubscriptionValidator,
+};
+
+use super::connector::Connector;
+
+pub mod book;
+pub mod book_l3;
+pub mod channel;
+pub mod market;
+pub mod message;
+pub mod subscription;
+pub mod trade;
+pub mod validator;
+
+pub const WEBSOCKET_BASE_URL_BITFINEX: &str = "wss://api-pub.bitfinex.com/ws/2";
+
+/// [`Bitfinex`] spot public market data connector.
+///
+/// Market data messages are identified by the numeric "chanId" assigned in each subscription
+/// response, so [`BitfinexWebSocketSubscriptionValidator`] re-keys the instrument map by
+/// channel id. Bitfinex sends a heartbeat on every channel each 15s, so no ping is required.
+///
+/// See docs: <https://docs.bitfinex.com/docs/ws-general>
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default, DeExchange, SerExchange)]
+pub struct Bitfinex;
+
+impl Connector for Bitfinex {
+    const ID: ExchangeId = ExchangeId::Bitfinex;
+    type Channel = BitfinexChannel;
+    type Market = BitfinexMarket;
+    type Subscriber = WsSubscriber;
+    type SubscriptionValidator = BitfinexWebSocketSubscriptionValidator;
+    type SubscriptionResponse = BitfinexSubscriptionResponse;
+
+    fn url() -> Result<Url, SocketError> {
+        Url::parse(WEBSOCKET_BASE_URL_BITFINEX).map_err(SocketError::UrlParse)
+    }
+
+    fn requests(exchange_subscriptions: Vec<ExchangeSubscription<Self::Channel, Self::Market>>) -> Vec<WsMessage> {
+        exchange_subscriptions
+            .into_iter()
+            .map(|sub| {
+                let request = match sub.channel {
+                    BitfinexChannel::ORDER_BOOK_L2 => serde_json::json!({
+                        "event": "subscribe",
+                        "channel": sub.channel.as_ref(),
+                        "symbol": sub.market.as_ref(),
+                        "prec": "P0",
+                        "freq": "F0",
+                        "len": BITFINEX_BOOK_DEPTH.to_string(),
+                    }),
+                    BitfinexChannel::ORDER_BOOK_L3 => serde_json::json!({
+                        "event": "subscribe",
+                        "channel": BitfinexChannel::ORDER_BOOK_L2.as_ref(),
+                        "symbol": sub.market.as_ref(),
+                        "prec": BITFINEX_RAW_BOOK_PRECISION,
+                        "len": BITFINEX_RAW_BOOK_DEPTH.to_string(),
+                    }),
+                    _ => serde_json::json!({
+                        "event": "subscribe",
+                        "channel": sub.channel.as_ref(),
+                        "symbol": sub.market.as_ref(),
+                    }),
+                };
+
+                WsMessage::Text(request.to_string())
+            })
+            .collect()
+    }
+}
+
+impl StreamSelector<PublicTrades> for Bitfinex {
+    type Stream = ExchangeWsStream<StatelessTransformer<Self, PublicTrades, BitfinexTrades>>;
+}
+
+impl StreamSelector<OrderBooksL2> for Bitfinex {
+    type Stream = ExchangeWsStream<MultiBookTransformer<Self, OrderBooksL2, BitfinexBookUpdater>>;
+}
+
+impl StreamSelector<OrderBooksL3> for Bitfinex {
+    type Stream = ExchangeWsStream<MultiBookTransformer<Self, OrderBooksL3, BitfinexBookL3Updater>>;
+}
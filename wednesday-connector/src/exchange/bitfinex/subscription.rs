@@ -0,0 +1,207 @@
+use serde::{Deserialize, Serialize};
+use wednesday_model::{
+    error::SocketError,
+    identifiers::{Identifier, SubscriptionId},
+};
+
+use crate::subscriber::{subscription::ExchangeSubscription, validator::Validator};
+
+use super::{book_l3::BITFINEX_RAW_BOOK_PRECISION, channel::BitfinexChannel};
+
+/// [`Bitfinex`](super::Bitfinex) numeric channel identifier, assigned by the exchange in each
+/// subscription response and used to route every following market data message.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct BitfinexChannelId(pub u64);
+
+impl From<BitfinexChannelId> for SubscriptionId {
+    fn from(channel_id: BitfinexChannelId) -> Self {
+        SubscriptionId::from(channel_id.0.to_string())
+    }
+}
+
+/// [`Bitfinex`](super::Bitfinex) WebSocket subscription response, one is received per
+/// subscribe request.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.bitfinex.com/docs/ws-general#subscribe-to-channels>
+/// #### Subscription Success
+/// ```json
+/// {
+///     "event": "subscribed",
+///     "channel": "trades",
+///     "chanId": 19111,
+///     "symbol": "tBTCUSD",
+///     "pair": "BTCUSD"
+/// }
+/// ```
+///
+/// #### Raw Book Subscription Success
+/// ```json
+/// {
+///     "event": "subscribed",
+///     "channel": "book",
+///     "chanId": 224,
+///     "symbol": "tBTCUSD",
+///     "prec": "R0",
+///     "len": "250",
+///     "pair": "BTCUSD"
+/// }
+/// ```
+///
+/// #### Subscription Failure
+/// ```json
+/// {
+///     "event": "error",
+///     "msg": "symbol: invalid",
+///     "code": 10300
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(tag = "event", rename_all = "lowercase")]
+pub enum BitfinexSubscriptionResponse {
+    Subscribed {
+        channel: String,
+        #[serde(rename = "chanId")]
+        channel_id: BitfinexChannelId,
+        symbol: String,
+        /// Precision of a "book" channel, eg/ "P0" for aggregated price levels & "R0" for raw
+        /// books.
+        #[serde(rename = "prec", default)]
+        precision: Option<String>,
+    },
+    Error {
+        #[serde(rename = "msg")]
+        message: String,
+        code: u32,
+    },
+}
+
+impl BitfinexSubscriptionResponse {
+    /// [`SubscriptionId`] of the subscription request this response belongs to, eg/
+    /// "trades|tBTCUSD". Raw books are subscribed to via the "book" channel, so they are
+    /// identified by their precision, eg/ "book.R0|tBTCUSD".
+    pub fn subscription_id(&self) -> Option<SubscriptionId> {
+        match self {
+            BitfinexSubscriptionResponse::Subscribed {
+                channel, symbol, precision, ..
+            } => {
+                let channel = match precision.as_deref() {
+                    Some(BITFINEX_RAW_BOOK_PRECISION) => BitfinexChannel::ORDER_BOOK_L3.as_ref(),
+                    _ => channel.as_str(),
+                };
+                Some(ExchangeSubscription::from((channel, symbol.as_str())).id())
+            },
+            BitfinexSubscriptionResponse::Error { .. } => None,
+        }
+    }
+}
+
+impl Validator for BitfinexSubscriptionResponse {
+    fn validate(self) -> Result<Self, SocketError>
+    where
+        Self: Sized,
+    {
+        match self {
+            BitfinexSubscriptionResponse::Subscribed { .. } => Ok(self),
+            BitfinexSubscriptionResponse::Error { message, code } => Err(SocketError::Subscribe(format!(
+                "received failure subscription response code: {code} with message: {message}",
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use super::*;
+
+        #[test]
+        fn test_bitfinex_sub_response() {
+            struct TestCase {
+                input: &'static str,
+                expected: Result<BitfinexSubscriptionResponse, SocketError>,
+            }
+
+            let cases = vec![
+                TestCase {
+                    // TC0: input response is trades Subscribed
+                    input: r#"{"event": "subscribed", "channel": "trades", "chanId": 19111, "symbol": "tBTCUSD", "pair": "BTCUSD"}"#,
+                    expected: Ok(BitfinexSubscriptionResponse::Subscribed {
+                        channel: "trades".to_string(),
+                        channel_id: BitfinexChannelId(19111),
+                        symbol: "tBTCUSD".to_string(),
+                        precision: None,
+                    }),
+                },
+                TestCase {
+                    // TC1: input response is book Subscribed
+                    input: r#"
+                    {
+                        "event": "subscribed", "channel": "book", "chanId": 224, "symbol": "tETHUSD",
+                        "prec": "P0", "freq": "F0", "len": "25", "pair": "ETHUSD"
+                    }
+                    "#,
+                    expected: Ok(BitfinexSubscriptionResponse::Subscribed {
+                        channel: "book".to_string(),
+                        channel_id: BitfinexChannelId(224),
+                        symbol: "tETHUSD".to_string(),
+                        precision: Some("P0".to_string()),
+                    }),
+                },
+                TestCase {
+                    // TC2: input response is failed subscription
+                    input: r#"{"event": "error", "msg": "symbol: invalid", "code": 10300}"#,
+                    expected: Ok(BitfinexSubscriptionResponse::Error {
+                        message: "symbol: invalid".to_string(),
+                        code: 10300,
+                    }),
+                },
+                TestCase {
+                    // TC3: input is the info event
+                    input: r#"{"event": "info", "version": 2, "serverId": "e293377e", "platform": {"status": 1}}"#,
+                    expected: Err(SocketError::Subscribe("".to_string())),
+                },
+            ];
+
+            for (index, test) in cases.into_iter().enumerate() {
+                let actual = serde_json::from_str::<BitfinexSubscriptionResponse>(test.input);
+                match (actual, test.expected) {
+                    (Ok(actual), Ok(expected)) => {
+                        assert_eq!(actual, expected, "TC{} failed", index)
+                    },
+                    (Err(_), Err(_)) => {
+                        // Test passed
+                    },
+                    (actual, expected) => {
+                        // Test failed
+                        panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                    },
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bitfinex_sub_response_subscription_id() {
+        let response = BitfinexSubscriptionResponse::Subscribed {
+            channel: "book".to_string(),
+            channel_id: BitfinexChannelId(224),
+            symbol: "tETHUSD".to_string(),
+            precision: Some("P0".to_string()),
+        };
+
+        assert_eq!(response.subscription_id(), Some(SubscriptionId::from("book|tETHUSD")));
+        assert!(response.validate().is_ok());
+
+        let response = BitfinexSubscriptionResponse::Subscribed {
+            channel: "book".to_string(),
+            channel_id: BitfinexChannelId(225),
+            symbol: "tETHUSD".to_string(),
+            precision: Some("R0".to_string()),
+        };
+
+        assert_eq!(response.subscription_id(), Some(SubscriptionId::from("book.R0|tETHUSD")));
+    }
+}
@@ -0,0 +1,159 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use wednesday_model::{
+    deserialization::datetime_utc_from_epoch_duration,
+    enums::AggressorSide,
+    events::MarketEvent,
+    identifiers::{Exchange, ExchangeId},
+    instruments::Instrument,
+    trade::PublicTrade,
+};
+
+use crate::transformer::iterator::MarketIter;
+
+use super::message::BitfinexMessage;
+
+pub type BitfinexTrades = BitfinexMessage<BitfinexTrade>;
+
+/// [`Bitfinex`](super::Bitfinex) real-time trade execution.
+///
+/// A negative amount indicates the aggressor was the seller.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.bitfinex.com/reference/ws-public-trades>
+/// ```json
+/// [ID, MTS, AMOUNT, PRICE]
+/// [1372226347, 1676712479661, -0.0043, 24564]
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Serialize)]
+pub struct BitfinexTrade {
+    pub id: u64,
+    pub time: DateTime<Utc>,
+    pub side: AggressorSide,
+    pub amount: f64,
+    pub price: f64,
+}
+
+impl<'de> Deserialize<'de> for BitfinexTrade {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (id, time, amount, price) = <(u64, u64, f64, f64)>::deserialize(deserializer)?;
+
+        Ok(Self {
+            id,
+            time: datetime_utc_from_epoch_duration(std::time::Duration::from_millis(time)),
+            side: if amount < 0.0 { AggressorSide::Sell } else { AggressorSide::Buy },
+            amount: amount.abs(),
+            price,
+        })
+    }
+}
+
+impl From<(ExchangeId, Instrument, BitfinexTrades)> for MarketIter<PublicTrade> {
+    fn from((exchange_id, instrument, trades): (ExchangeId, Instrument, BitfinexTrades)) -> Self {
+        match trades {
+            // The initial snapshot contains historic trades, which are not republished
+            BitfinexMessage::Update { data: trade, .. } => Self(vec![Ok(MarketEvent {
+                exchange_ts: trade.time,
+                local_ts: Utc::now(),
+                exchange: Exchange::from(exchange_id),
+                instrument,
+                kind: PublicTrade {
+                    id: trade.id.to_string(),
+                    price: trade.price,
+                    quantity: trade.amount,
+                    aggressor_side: trade.side,
+                },
+            })]),
+            BitfinexMessage::Snapshot { .. } | BitfinexMessage::Event => Self(vec![]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wednesday_model::identifiers::SubscriptionId;
+
+    use super::*;
+
+    mod de {
+        use super::*;
+
+        #[test]
+        fn test_bitfinex_trades() {
+            struct TestCase {
+                input: &'static str,
+                expected: Option<BitfinexTrades>,
+            }
+
+            let tests = vec![
+                // TC0: sell trade execution
+                TestCase {
+                    input: r#"[19111, "te", [1372226347, 1676712479661, -0.0043, 24564]]"#,
+                    expected: Some(BitfinexMessage::Update {
+                        subscription_id: SubscriptionId::from("19111"),
+                        data: BitfinexTrade {
+                            id: 1372226347,
+                            time: datetime_utc_from_epoch_duration(std::time::Duration::from_millis(1676712479661)),
+                            side: AggressorSide::Sell,
+                            amount: 0.0043,
+                            price: 24564.0,
+                        },
+                    }),
+                },
+                // TC1: buy trade snapshot
+                TestCase {
+                    input: r#"[19111, [[1372226346, 1676712479600, 0.5, 24563.5]]]"#,
+                    expected: Some(BitfinexMessage::Snapshot {
+                        subscription_id: SubscriptionId::from("19111"),
+                        data: vec![BitfinexTrade {
+                            id: 1372226346,
+                            time: datetime_utc_from_epoch_duration(std::time::Duration::from_millis(1676712479600)),
+                            side: AggressorSide::Buy,
+                            amount: 0.5,
+                            price: 24563.5,
+                        }],
+                    }),
+                },
+                // TC2: trade with missing fields is invalid
+                TestCase {
+                    input: r#"[19111, "te", [1372226347, 1676712479661, -0.0043]]"#,
+                    expected: None,
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = serde_json::from_str::<BitfinexTrades>(test.input).ok();
+                assert_eq!(actual, test.expected, "TC{} failed", index);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bitfinex_trades_market_iter() {
+        let trade = BitfinexTrade {
+            id: 1,
+            time: Utc::now(),
+            side: AggressorSide::Buy,
+            amount: 1.0,
+            price: 100.0,
+        };
+        let instrument = Instrument::from(("btc", "usd", wednesday_model::instruments::InstrumentKind::CryptoSpot));
+
+        let snapshot = BitfinexMessage::Snapshot {
+            subscription_id: SubscriptionId::from("19111"),
+            data: vec![trade.clone()],
+        };
+        assert!(MarketIter::<PublicTrade>::from((ExchangeId::Bitfinex, instrument.clone(), snapshot))
+            .0
+            .is_empty());
+
+        let update = BitfinexMessage::Update {
+            subscription_id: SubscriptionId::from("19111"),
+            data: trade,
+        };
+        assert_eq!(MarketIter::<PublicTrade>::from((ExchangeId::Bitfinex, instrument, update)).0.len(), 1);
+    }
+}
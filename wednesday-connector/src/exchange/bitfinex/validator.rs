@@ -0,0 +1,178 @@
+use std::{collections::HashMap, rc::Rc};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio_stream::StreamExt;
+use tracing::{debug, error, info};
+use wednesday_model::{error::SocketError, identifiers::SubscriptionId, instruments::Instrument};
+
+use crate::{
+    exchange::connector::Connector,
+    protocol::http::websocket::{WsClient, WsParser},
+    stream::parser::StreamParser,
+    subscriber::{
+        subscription::{Map, SubscriptionKind},
+        validator::{SubscriptionValidator, Validator},
+    },
+};
+
+use super::subscription::BitfinexSubscriptionResponse;
+
+/// [`SubscriptionValidator`] for [`Bitfinex`](super::Bitfinex) that validates every
+/// subscription response, and re-keys the instrument [`Map`] from the "channel|symbol"
+/// [`SubscriptionId`] to the "chanId" assigned by the exchange.
+///
+/// Bitfinex market data messages only contain the "chanId", eg/ `[19111, "te", [...]]`, so
+/// the returned [`Map`] is what allows the transformer to identify them.
+///
+/// See docs: <https://docs.bitfinex.com/docs/ws-general#subscribe-to-channels>
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct BitfinexWebSocketSubscriptionValidator;
+
+#[async_trait]
+impl SubscriptionValidator for BitfinexWebSocketSubscriptionValidator {
+    type Parser = WsParser;
+
+    async fn validate<Exchange, Kind>(mut instrument_map: Map<Instrument>, ws_client: &mut WsClient) -> Result<Map<Instrument>, SocketError>
+    where
+        Exchange: Connector + Send,
+        Kind: SubscriptionKind + Send,
+    {
+        // Establish exchange specific subscription validation parameters
+        let timeout = Exchange::subscription_timeout();
+        let expected_responses = Exchange::expected_responses(&instrument_map);
+
+        // Instrument Map keyed by the chanId of each successful Subscription
+        let mut channel_map = Map(HashMap::with_capacity(expected_responses));
+
+        loop {
+            // Break if all Subscriptions were a success
+            if channel_map.0.len() == expected_responses {
+                info!(exchange = %Exchange::ID, "validated exchange WebSocket subscriptions");
+                break Ok(channel_map);
+            }
+
+            tokio::select! {
+                // If timeout reached, return SubscribeError
+                _ = tokio::time::sleep(timeout) => {
+                    break Err(SocketError::Subscribe(
+                        format!("subscription validation timeout reached: {:?}", timeout)
+                    ))
+                },
+                // Parse incoming messages and determine subscription outcomes
+                message = ws_client.next() => {
+                    let response = Rc::new(match message {
+                        Some(response) => response,
+                        None => break Err(SocketError::Subscribe("WebSocket stream terminated unexpectedly".to_string()))
+                    });
+
+                    match Self::Parser::parse::<BitfinexSubscriptionResponse>(response) {
+                        Some(Ok(response)) => {
+                            if let Err(err) = map_channel_id(&mut instrument_map, &mut channel_map, response) {
+                                error!(exchange = %Exchange::ID, %err, "received invalid subscription response");
+                                break Err(err)
+                            }
+
+                            debug!(
+                                exchange = %Exchange::ID,
+                                success_responses = %channel_map.0.len(),
+                                %expected_responses,
+                                "received valid Ok subscription response",
+                            );
+                        }
+                        Some(Err(SocketError::DeserializingJson { error, payload })) => {
+                            // Info events & early market data are not subscription responses
+                            debug!(
+                                exchange = %Exchange::ID,
+                                ?error,
+                                %payload,
+                                "failed to deserialize non SubResponse payload"
+                            );
+                            continue;
+                        },
+                        Some(Err(SocketError::Terminated(close_frame))) => {
+                            break Err(SocketError::Subscribe(
+                                format!("received WebSocket CloseFrame: {close_frame}")
+                            ))
+                        }
+                        _ => {
+                            // Pings, Pongs, Frames, etc.
+                            continue
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Validate a [`BitfinexSubscriptionResponse`] and move the associated [`Instrument`] from the
+/// "channel|symbol" keyed `instrument_map` to the "chanId" keyed `channel_map`.
+pub fn map_channel_id(
+    instrument_map: &mut Map<Instrument>,
+    channel_map: &mut Map<Instrument>,
+    response: BitfinexSubscriptionResponse,
+) -> Result<(), SocketError> {
+    let response = response.validate()?;
+
+    let (subscription_id, channel_id) = match (response.subscription_id(), response) {
+        (Some(subscription_id), BitfinexSubscriptionResponse::Subscribed { channel_id, .. }) => (subscription_id, channel_id),
+        (_, response) => return Err(SocketError::Subscribe(format!("received unexpected subscription response: {response:?}"))),
+    };
+
+    let instrument = instrument_map
+        .0
+        .remove(&subscription_id)
+        .ok_or_else(|| SocketError::Unidentifiable(subscription_id))?;
+
+    channel_map.0.insert(SubscriptionId::from(channel_id), instrument);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use wednesday_model::instruments::InstrumentKind;
+
+    use super::*;
+    use crate::exchange::bitfinex::subscription::BitfinexChannelId;
+
+    #[test]
+    fn test_map_channel_id() {
+        let instrument = Instrument::from(("btc", "usd", InstrumentKind::CryptoSpot));
+        let mut instrument_map = Map::from_iter([(SubscriptionId::from("trades|tBTCUSD"), instrument.clone())]);
+        let mut channel_map = Map(HashMap::new());
+
+        // Subscription for an unknown symbol is unidentifiable
+        let unknown = BitfinexSubscriptionResponse::Subscribed {
+            channel: "trades".to_string(),
+            channel_id: BitfinexChannelId(1),
+            symbol: "tETHUSD".to_string(),
+            precision: None,
+        };
+        assert!(matches!(
+            map_channel_id(&mut instrument_map, &mut channel_map, unknown),
+            Err(SocketError::Unidentifiable(_))
+        ));
+
+        // Failed subscription is an error
+        let failure = BitfinexSubscriptionResponse::Error {
+            message: "symbol: invalid".to_string(),
+            code: 10300,
+        };
+        assert!(matches!(
+            map_channel_id(&mut instrument_map, &mut channel_map, failure),
+            Err(SocketError::Subscribe(_))
+        ));
+
+        // Successful subscription is re-keyed by chanId
+        let success = BitfinexSubscriptionResponse::Subscribed {
+            channel: "trades".to_string(),
+            channel_id: BitfinexChannelId(19111),
+            symbol: "tBTCUSD".to_string(),
+            precision: None,
+        };
+        map_channel_id(&mut instrument_map, &mut channel_map, success).unwrap();
+        assert!(instrument_map.0.is_empty());
+        assert_eq!(channel_map.find(&SubscriptionId::from("19111")).unwrap(), instrument);
+    }
+}
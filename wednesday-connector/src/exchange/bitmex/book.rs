@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::debug;
+use wednesday_model::{
+    enums::BookSide,
+    error::DataError,
+    instruments::Instrument,
+    orderbook::{Level, OrderBook, OrderBookSide},
+};
+
+use crate::{
+    protocol::http::websocket::WsMessage,
+    transformer::updater::{InstrumentOrderBook, OrderBookUpdater},
+};
+
+use super::message::{BitmexAction, BitmexMessage};
+
+pub type BitmexOrderBookL2 = BitmexMessage<BitmexLevel>;
+
+/// [`Bitmex`](super::Bitmex) "orderBookL2_25" row.
+///
+/// Levels are keyed by "id" (which is unique per symbol & price), and "update" & "delete" rows
+/// may omit the price & size, so they are resolved from the level previously stored for the id.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.bitmex.com/app/wsAPI#OrderBookL2>
+/// #### Partial & Insert
+/// ```json
+/// {"symbol": "XBTUSD", "id": 8799755950, "side": "Sell", "size": 100, "price": 24405.0, "timestamp": "2023-02-18T09:27:59.701Z"}
+/// ```
+///
+/// #### Update
+/// ```json
+/// {"symbol": "XBTUSD", "id": 8799755950, "side": "Sell", "size": 300, "timestamp": "2023-02-18T09:28:00.102Z"}
+/// ```
+///
+/// #### Delete
+/// ```json
+/// {"symbol": "XBTUSD", "id": 8799755950, "side": "Sell", "timestamp": "2023-02-18T09:28:01.003Z"}
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BitmexLevel {
+    pub symbol: String,
+    pub id: u64,
+    pub side: BookSide,
+    #[serde(default, rename = "size")]
+    pub amount: Option<f64>,
+    #[serde(default)]
+    pub price: Option<f64>,
+    #[serde(default)]
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+/// [`Bitmex`](super::Bitmex) L2 [`OrderBookUpdater`].
+///
+/// Maintains the id -> (side, level) mapping required to apply the id based "insert",
+/// "update" & "delete" actions, which are ignored until the "partial" image is received.
+///
+/// See docs: <https://www.bitmex.com/app/wsAPI#OrderBookL2>
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct BitmexBookUpdater {
+    pub updates_processed: u64,
+    pub is_initialised: bool,
+    pub levels: HashMap<u64, (BookSide, Level)>,
+}
+
+impl BitmexBookUpdater {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply a [`Level`] to the associated [`OrderBook`] side, a zero amount removes it.
+    fn upsert(book: &mut OrderBook, side: BookSide, level: Level) {
+        match side {
+            BookSide::Bid => book.bids.upsert_single(level),
+            BookSide::Ask => book.asks.upsert_single(level),
+        }
+    }
+}
+
+#[async_trait]
+impl OrderBookUpdater for BitmexBookUpdater {
+    type OrderBook = OrderBook;
+    type Update = BitmexOrderBookL2;
+
+    async fn init<Exchange, Kind>(_: UnboundedSender<WsMessage>, instrument: Instrument) -> Result<InstrumentOrderBook<Self>, DataError>
+    where
+        Exchange: Send,
+        Kind: Send,
+    {
+        // Bitmex pushes a "partial" image as the first table message after subscribing, so no
+        // REST snapshot is required to initialise the book
+        Ok(InstrumentOrderBook {
+            instrument,
+            updater: Self::new(),
+            book: OrderBook::default(),
+        })
+    }
+
+    fn update(&mut self, book: &mut Self::OrderBook, update: Self::Update) -> Result<Option<Self::OrderBook>, DataError> {
+        let payload = match update {
+            BitmexMessage::Data(payload) => payload,
+            BitmexMessage::Event => return Ok(None),
+        };
+
+        match payload.action {
+            BitmexAction::Partial => {
+                self.levels = payload
+                    .data
+                    .iter()
+                    .filter_map(|row| Some((row.id, (row.side, Level::new(row.price?, row.amount?)))))
+                    .collect();
+                self.is_initialised = true;
+
+                let (bids, asks): (Vec<_>, Vec<_>) = self.levels.values().partition(|(side, _)| *side == BookSide::Bid);
+                book.bids = OrderBookSide::new(BookSide::Bid, bids.into_iter().map(|(_, level)| level));
+                book.asks = OrderBookSide::new(BookSide::Ask, asks.into_iter().map(|(_, level)| level));
+            },
+            // Actions received before the "partial" image must be ignored
+            _ if !self.is_initialised => return Ok(None),
+            BitmexAction::Insert | BitmexAction::Update => {
+                for row in &payload.data {
+                    let stored = self.levels.get(&row.id).map(|(_, level)| *level);
+                    let (price, amount) = match (row.price.or(stored.map(|level| level.price)), row.amount) {
+                        (Some(price), Some(amount)) => (price, amount),
+                        _ => {
+                            debug!(?row, "Bitmex level to update not found");
+                            continue;
+                        },
+                    };
+
+                    let level = Level::new(price, amount);
+                    self.levels.insert(row.id, (row.side, level));
+                    Self::upsert(book, row.side, level);
+                }
+            },
+            BitmexAction::Delete => {
+                for row in &payload.data {
+                    match self.levels.remove(&row.id) {
+                        Some((side, level)) => Self::upsert(book, side, Level::new(level.price, 0.0)),
+                        None => debug!(?row, "Bitmex level to delete not found"),
+                    }
+                }
+            },
+        }
+
+        self.updates_processed += 1;
+        book.last_update_ts = payload.data.iter().filter_map(|row| row.timestamp).max().unwrap_or_else(Utc::now);
+
+        Ok(Some(book.snapshot()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wednesday_model::identifiers::SubscriptionId;
+
+    use super::*;
+    use crate::exchange::bitmex::message::BitmexPayload;
+
+    mod de {
+        use super::*;
+
+        #[test]
+        fn test_bitmex_order_book_l2() {
+            let input = r#"
+            {
+                "table": "orderBookL2_25",
+                "action": "update",
+                "data": [
+                    {"symbol": "XBTUSD", "id": 8799755950, "side": "Sell", "size": 300, "timestamp": "2023-02-18T09:28:00.102Z"}
+                ]
+            }
+            "#;
+
+            assert_eq!(
+                serde_json::from_str::<BitmexOrderBookL2>(input).unwrap(),
+                BitmexMessage::Data(BitmexPayload {
+                    subscription_id: SubscriptionId::from("orderBookL2_25|XBTUSD"),
+                    action: BitmexAction::Update,
+                    data: vec![BitmexLevel {
+                        symbol: "XBTUSD".to_string(),
+                        id: 8799755950,
+                        side: BookSide::Ask,
+                        amount: Some(300.0),
+                        price: None,
+                        timestamp: Some("2023-02-18T09:28:00.102Z".parse().unwrap()),
+                    }],
+                })
+            );
+        }
+    }
+
+    fn message(action: BitmexAction, rows: Vec<(u64, BookSide, Option<f64>, Option<f64>)>) -> BitmexOrderBookL2 {
+        BitmexMessage::Data(BitmexPayload {
+            subscription_id: SubscriptionId::from("orderBookL2_25|XBTUSD"),
+            action,
+            data: rows
+                .into_iter()
+                .map(|(id, side, price, amount)| BitmexLevel {
+                    symbol: "XBTUSD".to_string(),
+                    id,
+                    side,
+                    amount,
+                    price,
+                    timestamp: None,
+                })
+                .collect(),
+        })
+    }
+
+    #[test]
+    fn test_update_bitmex_order_book_l2() {
+        let mut updater = BitmexBookUpdater::new();
+        let mut book = OrderBook::default();
+
+        // Actions before the partial image are ignored
+        let output = updater
+            .update(&mut book, message(BitmexAction::Insert, vec![(1, BookSide::Bid, Some(100.0), Some(1.0))]))
+            .unwrap();
+        assert_eq!(output, None);
+
+        // Partial initialises the book
+        let output = updater
+            .update(
+                &mut book,
+                message(
+                    BitmexAction::Partial,
+                    vec![
+                        (1, BookSide::Bid, Some(100.0), Some(1.0)),
+                        (2, BookSide::Bid, Some(99.0), Some(2.0)),
+                        (3, BookSide::Ask, Some(101.0), Some(3.0)),
+                    ],
+                ),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(output.bids.levels(), vec![Level::new(100.0, 1.0), Level::new(99.0, 2.0)]);
+        assert_eq!(output.asks.levels(), vec![Level::new(101.0, 3.0)]);
+
+        // Update w/o price resolves the price from the level id
+        let output = updater
+            .update(&mut book, message(BitmexAction::Update, vec![(3, BookSide::Ask, None, Some(5.0))]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(output.asks.levels(), vec![Level::new(101.0, 5.0)]);
+
+        // Delete w/o price removes the level associated with the id
+        let output = updater
+            .update(&mut book, message(BitmexAction::Delete, vec![(1, BookSide::Bid, None, None)]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(output.bids.levels(), vec![Level::new(99.0, 2.0)]);
+
+        // Insert adds a new level
+        let output = updater
+            .update(&mut book, message(BitmexAction::Insert, vec![(4, BookSide::Ask, Some(102.0), Some(1.0))]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(output.asks.levels(), vec![Level::new(101.0, 5.0), Level::new(102.0, 1.0)]);
+        assert_eq!(updater.levels.len(), 3);
+    }
+}
@@ -0,0 +1,41 @@
+use wednesday_model::identifiers::Identifier;
+
+use crate::subscriber::subscription::{
+    kind::{OrderBooksL2, PublicTrades},
+    Subscription,
+};
+
+use super::Bitmex;
+
+/// Type that defines how to translate a [`Subscription`] into a [`Bitmex`] table to be
+/// subscribed to.
+///
+/// See docs: <https://www.bitmex.com/app/wsAPI#Subscriptions>
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct BitmexChannel(pub &'static str);
+
+impl BitmexChannel {
+    /// [`Bitmex`] real-time trades table.
+    pub const TRADES: Self = Self("trade");
+    /// [`Bitmex`] top 25 levels L2 table, a "partial" snapshot followed by id based "insert",
+    /// "update" & "delete" actions.
+    pub const ORDER_BOOK_L2: Self = Self("orderBookL2_25");
+}
+
+impl Identifier<BitmexChannel> for Subscription<Bitmex, PublicTrades> {
+    fn id(&self) -> BitmexChannel {
+        BitmexChannel::TRADES
+    }
+}
+
+impl Identifier<BitmexChannel> for Subscription<Bitmex, OrderBooksL2> {
+    fn id(&self) -> BitmexChannel {
+        BitmexChannel::ORDER_BOOK_L2
+    }
+}
+
+impl AsRef<str> for BitmexChannel {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use wednesday_model::{identifiers::Identifier, instruments::Symbol};
+
+use crate::subscriber::subscription::Subscription;
+
+use super::Bitmex;
+
+/// Type that defines how to translate a [`Subscription`] into a [`Bitmex`] market (symbol)
+/// that can be subscribed to.
+///
+/// Note:
+/// Bitmex uses "XBT" for bitcoin, eg/ btc_usd perpetual -> "XBTUSD".
+///
+/// See docs: <https://www.bitmex.com/app/contract/XBTUSD>
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct BitmexMarket(pub String);
+
+impl<Kind> Identifier<BitmexMarket> for Subscription<Bitmex, Kind> {
+    fn id(&self) -> BitmexMarket {
+        BitmexMarket::new(&self.instrument.base_currency, &self.instrument.quote_currency)
+    }
+}
+
+impl BitmexMarket {
+    pub fn new(base_currency: &Symbol, quote_currency: &Symbol) -> Self {
+        let currency = |symbol: &Symbol| match symbol.as_ref().to_uppercase().as_str() {
+            "BTC" => "XBT".to_string(),
+            other => other.to_string(),
+        };
+
+        Self(format!("{}{}", currency(base_currency), currency(quote_currency)))
+    }
+}
+
+impl AsRef<str> for BitmexMarket {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitmex_market() {
+        struct TestCase {
+            input: (&'static str, &'static str),
+            expected: BitmexMarket,
+        }
+
+        let tests = vec![
+            // TC0: btc is translated to XBT
+            TestCase {
+                input: ("btc", "usd"),
+                expected: BitmexMarket("XBTUSD".to_string()),
+            },
+            // TC1: usdt quoted
+            TestCase {
+                input: ("eth", "usdt"),
+                expected: BitmexMarket("ETHUSDT".to_string()),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = BitmexMarket::new(&Symbol::from(test.input.0), &Symbol::from(test.input.1));
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+}
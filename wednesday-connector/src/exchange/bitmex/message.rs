@@ -0,0 +1,178 @@
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use serde_json::value::RawValue;
+use wednesday_model::identifiers::{Identifier, SubscriptionId};
+
+use crate::subscriber::subscription::ExchangeSubscription;
+
+use super::channel::BitmexChannel;
+
+/// [`Bitmex`](super::Bitmex) WebSocket table message.
+///
+/// Every message received on the connection must be deserialisable, so the welcome message &
+/// late subscription responses are consumed as [`BitmexMessage::Event`].
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.bitmex.com/app/wsAPI#Response-Format>
+/// ```json
+/// {
+///     "table": "orderBookL2_25",
+///     "action": "partial",
+///     "keys": ["symbol", "id", "side"],
+///     "filter": {"symbol": "XBTUSD"},
+///     "data": [...]
+/// }
+/// ```
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub enum BitmexMessage<T> {
+    Data(BitmexPayload<T>),
+    Event,
+}
+
+/// [`Bitmex`](super::Bitmex) market data message of a subscribed table.
+///
+/// Note:
+/// The [`SubscriptionId`] is derived from the table & the "filter" symbol, or the symbol of
+/// the first data row if no filter is provided.
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct BitmexPayload<T> {
+    pub subscription_id: SubscriptionId,
+    pub action: BitmexAction,
+    pub data: Vec<T>,
+}
+
+/// [`Bitmex`](super::Bitmex) table action.
+///
+/// See docs: <https://www.bitmex.com/app/wsAPI#Response-Format>
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BitmexAction {
+    /// Full table image, received once after subscribing.
+    Partial,
+    Insert,
+    Update,
+    Delete,
+}
+
+impl<T> Identifier<Option<SubscriptionId>> for BitmexMessage<T> {
+    fn id(&self) -> Option<SubscriptionId> {
+        match self {
+            BitmexMessage::Data(payload) => Some(payload.subscription_id.clone()),
+            BitmexMessage::Event => None,
+        }
+    }
+}
+
+impl<'de, T> Deserialize<'de> for BitmexMessage<T>
+where
+    T: DeserializeOwned,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Envelope {
+            table: Option<String>,
+            action: Option<BitmexAction>,
+            data: Option<Box<RawValue>>,
+            filter: Option<Symbol>,
+        }
+
+        #[derive(Deserialize)]
+        struct Symbol {
+            symbol: Option<String>,
+        }
+
+        let Envelope { table, action, data, filter } = Envelope::deserialize(deserializer)?;
+
+        let (table, action, data) = match (table, action, data) {
+            (Some(table), Some(action), Some(data)) if [BitmexChannel::TRADES.0, BitmexChannel::ORDER_BOOK_L2.0].contains(&table.as_str()) => {
+                (table, action, data)
+            },
+            _ => return Ok(Self::Event),
+        };
+
+        let symbol = match filter.and_then(|filter| filter.symbol) {
+            Some(symbol) => Some(symbol),
+            None => serde_json::from_str::<Vec<Symbol>>(data.get())
+                .map_err(serde::de::Error::custom)?
+                .into_iter()
+                .next()
+                .and_then(|row| row.symbol),
+        };
+
+        // Nothing can be routed, or applied, from an unfiltered empty table message
+        let Some(symbol) = symbol else {
+            return Ok(Self::Event);
+        };
+
+        Ok(Self::Data(BitmexPayload {
+            subscription_id: ExchangeSubscription::from((table.as_str(), symbol.as_str())).id(),
+            action,
+            data: serde_json::from_str(data.get()).map_err(serde::de::Error::custom)?,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use super::*;
+
+        #[derive(Debug, PartialEq, Deserialize, Serialize)]
+        struct Data {
+            symbol: String,
+        }
+
+        #[test]
+        fn test_bitmex_message() {
+            struct TestCase {
+                input: &'static str,
+                expected: Option<BitmexMessage<Data>>,
+            }
+
+            let tests = vec![
+                // TC0: welcome message is an Event
+                TestCase {
+                    input: r#"{"info": "Welcome to the BitMEX Realtime API.", "version": "2.0.0", "limit": {"remaining": 39}}"#,
+                    expected: Some(BitmexMessage::Event),
+                },
+                // TC1: subscription response is an Event
+                TestCase {
+                    input: r#"{"success": true, "subscribe": "trade:XBTUSD", "request": {"op": "subscribe", "args": ["trade:XBTUSD"]}}"#,
+                    expected: Some(BitmexMessage::Event),
+                },
+                // TC2: empty partial is identified by the filter symbol
+                TestCase {
+                    input: r#"{"table": "trade", "action": "partial", "keys": [], "filter": {"symbol": "XBTUSD"}, "data": []}"#,
+                    expected: Some(BitmexMessage::Data(BitmexPayload {
+                        subscription_id: SubscriptionId::from("trade|XBTUSD"),
+                        action: BitmexAction::Partial,
+                        data: vec![],
+                    })),
+                },
+                // TC3: insert is identified by the data symbol
+                TestCase {
+                    input: r#"{"table": "trade", "action": "insert", "data": [{"symbol": "XBTUSD"}]}"#,
+                    expected: Some(BitmexMessage::Data(BitmexPayload {
+                        subscription_id: SubscriptionId::from("trade|XBTUSD"),
+                        action: BitmexAction::Insert,
+                        data: vec![Data { symbol: "XBTUSD".to_string() }],
+                    })),
+                },
+                // TC4: unknown action is invalid
+                TestCase {
+                    input: r#"{"table": "trade", "action": "unknown", "data": [{"symbol": "XBTUSD"}]}"#,
+                    expected: None,
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = serde_json::from_str::<BitmexMessage<Data>>(test.input).ok();
+                assert_eq!(actual, test.expected, "TC{} failed", index);
+            }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use std::time::Duration;
+
+use tokio::time;
+use url::Url;
+use wednesday_macro::{DeExchange, SerExchange};
+use wednesday_model::{error::SocketError, identifiers::ExchangeId};
+
+use crate::{
+    protocol::http::websocket::{PingInterval, WsMessage},
+    stream::{protocol::ws_stream::ExchangeWsStream, selector::StreamSelector},
+    subscriber::{
+        protocol::websocket::WsSubscriber,
+        subscription::{
+            kind::{OrderBooksL2, PublicTrades},
+            ExchangeSubscription,
+        },
+        validator::WsSubscriptionValidator,
+    },
+    transformer::{stateful::MultiBookTransformer, stateless::StatelessTransformer},
+};
+
+use self::{book::BitmexBookUpdater, channel::BitmexChannel, market::BitmexMarket, subscription::BitmexSubscriptionResponse, trade::BitmexTrades};
+
+use super::connector::Connector;
+
+pub mod book;
+pub mod channel;
+pub mod market;
+pub mod message;
+pub mod subscription;
+pub mod trade;
+
+pub const WEBSOCKET_BASE_URL_BITMEX: &str = "wss://ws.bitmex.com/realtime";
+
+/// [`Bitmex`] recommends sending a "ping" text message if no data is received for 5s, the
+/// plain text "pong" response is skipped by the parser.
+///
+/// See docs: <https://www.bitmex.com/app/wsAPI#Heartbeats>
+pub const PING_INTERVAL_BITMEX: Duration = Duration::from_secs(5);
+
+/// [`Bitmex`] perpetual swap public market data connector.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default, DeExchange, SerExchange)]
+pub struct Bitmex;
+
+impl Connector for Bitmex {
+    const ID: ExchangeId = ExchangeId::Bitmex;
+    type Channel = BitmexChannel;
+    type Market = BitmexMarket;
+    type Subscriber = WsSubscriber;
+    type SubscriptionValidator = WsSubscriptionValidator;
+    type SubscriptionResponse = BitmexSubscriptionResponse;
+
+    fn url() -> Result<Url, SocketError> {
+        Url::parse(WEBSOCKET_BASE_URL_BITMEX).map_err(SocketError::UrlParse)
+    }
+
+    fn requests(exchange_subscriptions: Vec<ExchangeSubscription<Self::Channel, Self::Market>>) -> Vec<WsMessage> {
+        // Bitmex responds once per "table:symbol" argument
+        vec![bitmex_request("subscribe", exchange_subscriptions)]
+    }
+
+    fn dynamic_subscriptions() -> bool {
+        true
+    }
+
+    fn unsubscribe_requests(exchange_subscriptions: Vec<ExchangeSubscription<Self::Channel, Self::Market>>) -> Vec<WsMessage> {
+        vec![bitmex_request("unsubscribe", exchange_subscriptions)]
+    }
+
+    fn ping_interval() -> Option<PingInterval> {
+        Some(PingInterval {
+            interval: time::interval(PING_INTERVAL_BITMEX),
+            ping: || WsMessage::Text("ping".to_string()),
+        })
+    }
+}
+
+/// Single request with the provided "subscribe" or "unsubscribe" op for every "table:symbol".
+fn bitmex_request(op: &str, exchange_subscriptions: Vec<ExchangeSubscription<BitmexChannel, BitmexMarket>>) -> WsMessage {
+    let args = exchange_subscriptions
+        .into_iter()
+        .map(|sub| format!("{}:{}", sub.channel.as_ref(), sub.market.as_ref()))
+        .collect::<Vec<String>>();
+
+    WsMessage::Text(
+        serde_json::json!({
+            "op": op,
+            "args": args
+        })
+        .to_string(),
+    )
+}
+
+impl StreamSelector<PublicTrades> for Bitmex {
+    type Stream = ExchangeWsStream<StatelessTransformer<Self, PublicTrades, BitmexTrades>>;
+}
+
+impl StreamSelector<OrderBooksL2> for Bitmex {
+    type Stream = ExchangeWsStream<MultiBookTransformer<Self, OrderBooksL2, BitmexBookUpdater>>;
+}
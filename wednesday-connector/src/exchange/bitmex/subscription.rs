@@ -0,0 +1,179 @@
+use serde::{Deserialize, Serialize};
+use wednesday_model::error::SocketError;
+
+use crate::subscriber::validator::Validator;
+
+/// [`Bitmex`](super::Bitmex) WebSocket subscription response, one is received per subscribed
+/// "table:symbol" argument.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.bitmex.com/app/wsAPI#Subscriptions>
+/// #### Subscription Success
+/// ```json
+/// {
+///     "success": true,
+///     "subscribe": "trade:XBTUSD",
+///     "request": {"op": "subscribe", "args": ["trade:XBTUSD"]}
+/// }
+/// ```
+///
+/// #### Subscription Failure
+/// ```json
+/// {
+///     "status": 400,
+///     "error": "Unknown or expired table: trade:XBTUSDD",
+///     "meta": {},
+///     "request": {"op": "subscribe", "args": ["trade:XBTUSDD"]}
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum BitmexSubscriptionResponse {
+    Subscribed { success: bool, subscribe: String },
+    Unsubscribed { success: bool, unsubscribe: String },
+    Error { status: u16, error: String },
+}
+
+impl Validator for BitmexSubscriptionResponse {
+    fn validate(self) -> Result<Self, SocketError>
+    where
+        Self: Sized,
+    {
+        match &self {
+            BitmexSubscriptionResponse::Subscribed { success: true, .. } | BitmexSubscriptionResponse::Unsubscribed { success: true, .. } => Ok(self),
+            BitmexSubscriptionResponse::Subscribed { subscribe, .. } => {
+                Err(SocketError::Subscribe(format!("received failure subscription response for: {subscribe}",)))
+            },
+            BitmexSubscriptionResponse::Unsubscribed { unsubscribe, .. } => {
+                Err(SocketError::Subscribe(format!("received failure unsubscription response for: {unsubscribe}",)))
+            },
+            BitmexSubscriptionResponse::Error { status, error } => Err(SocketError::Subscribe(format!(
+                "received failure subscription response status: {status} with error: {error}",
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use super::*;
+
+        #[test]
+        fn test_bitmex_sub_response() {
+            struct TestCase {
+                input: &'static str,
+                expected: Result<BitmexSubscriptionResponse, SocketError>,
+            }
+
+            let cases = vec![
+                TestCase {
+                    // TC0: input response is Subscribed
+                    input: r#"
+                    {
+                        "success": true,
+                        "subscribe": "trade:XBTUSD",
+                        "request": {"op": "subscribe", "args": ["trade:XBTUSD"]}
+                    }
+                    "#,
+                    expected: Ok(BitmexSubscriptionResponse::Subscribed {
+                        success: true,
+                        subscribe: "trade:XBTUSD".to_string(),
+                    }),
+                },
+                TestCase {
+                    // TC1: input response is failed subscription
+                    input: r#"
+                    {
+                        "status": 400,
+                        "error": "Unknown or expired table: trade:XBTUSDD",
+                        "meta": {},
+                        "request": {"op": "subscribe", "args": ["trade:XBTUSDD"]}
+                    }
+                    "#,
+                    expected: Ok(BitmexSubscriptionResponse::Error {
+                        status: 400,
+                        error: "Unknown or expired table: trade:XBTUSDD".to_string(),
+                    }),
+                },
+                TestCase {
+                    // TC2: input is the welcome message
+                    input: r#"
+                    {
+                        "info": "Welcome to the BitMEX Realtime API.",
+                        "version": "2.0.0",
+                        "timestamp": "2023-02-18T09:27:59.701Z",
+                        "docs": "https://www.bitmex.com/app/wsAPI",
+                        "limit": {"remaining": 39}
+                    }
+                    "#,
+                    expected: Err(SocketError::Subscribe("".to_string())),
+                },
+                TestCase {
+                    // TC3: input response is Unsubscribed from a running stream
+                    input: r#"
+                    {
+                        "success": true,
+                        "unsubscribe": "trade:XBTUSD",
+                        "request": {"op": "unsubscribe", "args": ["trade:XBTUSD"]}
+                    }
+                    "#,
+                    expected: Ok(BitmexSubscriptionResponse::Unsubscribed {
+                        success: true,
+                        unsubscribe: "trade:XBTUSD".to_string(),
+                    }),
+                },
+            ];
+
+            for (index, test) in cases.into_iter().enumerate() {
+                let actual = serde_json::from_str::<BitmexSubscriptionResponse>(test.input);
+                match (actual, test.expected) {
+                    (Ok(actual), Ok(expected)) => {
+                        assert_eq!(actual, expected, "TC{} failed", index)
+                    },
+                    (Err(_), Err(_)) => {
+                        // Test passed
+                    },
+                    (actual, expected) => {
+                        // Test failed
+                        panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                    },
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_bitmex_sub_response() {
+        struct TestCase {
+            input_response: BitmexSubscriptionResponse,
+            is_valid: bool,
+        }
+
+        let cases = vec![
+            TestCase {
+                // TC0: input response is successful subscription
+                input_response: BitmexSubscriptionResponse::Subscribed {
+                    success: true,
+                    subscribe: "trade:XBTUSD".to_string(),
+                },
+                is_valid: true,
+            },
+            TestCase {
+                // TC1: input response is failed subscription
+                input_response: BitmexSubscriptionResponse::Error {
+                    status: 400,
+                    error: "Unknown or expired table".to_string(),
+                },
+                is_valid: false,
+            },
+        ];
+
+        for (index, test) in cases.into_iter().enumerate() {
+            let actual = test.input_response.validate().is_ok();
+            assert_eq!(actual, test.is_valid, "TestCase {} failed", index);
+        }
+    }
+}
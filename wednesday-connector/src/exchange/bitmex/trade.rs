@@ -0,0 +1,163 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use wednesday_model::{
+    enums::AggressorSide,
+    events::MarketEvent,
+    identifiers::{Exchange, ExchangeId},
+    instruments::Instrument,
+    trade::PublicTrade,
+};
+
+use crate::transformer::iterator::MarketIter;
+
+use super::message::{BitmexAction, BitmexMessage};
+
+pub type BitmexTrades = BitmexMessage<BitmexTrade>;
+
+/// [`Bitmex`](super::Bitmex) real-time trade, a row of the "trade" table.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.bitmex.com/app/wsAPI#Subscriptions>
+/// ```json
+/// {
+///     "timestamp": "2023-02-18T09:27:59.701Z",
+///     "symbol": "XBTUSD",
+///     "side": "Sell",
+///     "size": 200,
+///     "price": 24564.5,
+///     "tickDirection": "MinusTick",
+///     "trdMatchID": "31e50cb7-e005-a44e-f354-86e88dff52eb",
+///     "grossValue": 814184,
+///     "homeNotional": 0.00814184,
+///     "foreignNotional": 200,
+///     "trdType": "Regular"
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BitmexTrade {
+    pub timestamp: DateTime<Utc>,
+    pub symbol: String,
+    pub side: AggressorSide,
+    #[serde(rename = "size")]
+    pub amount: f64,
+    pub price: f64,
+    #[serde(rename = "trdMatchID")]
+    pub id: String,
+}
+
+impl From<(ExchangeId, Instrument, BitmexTrades)> for MarketIter<PublicTrade> {
+    fn from((exchange_id, instrument, trades): (ExchangeId, Instrument, BitmexTrades)) -> Self {
+        let payload = match trades {
+            // The "partial" image contains historic trades, which are not republished
+            BitmexMessage::Data(payload) if payload.action == BitmexAction::Insert => payload,
+            _ => return Self(vec![]),
+        };
+
+        payload
+            .data
+            .into_iter()
+            .map(|trade| MarketEvent {
+                exchange_ts: trade.timestamp,
+                local_ts: Utc::now(),
+                exchange: Exchange::from(exchange_id),
+                instrument: instrument.clone(),
+                kind: PublicTrade {
+                    id: trade.id,
+                    price: trade.price,
+                    quantity: trade.amount,
+                    aggressor_side: trade.side,
+                },
+            })
+            .map(Ok)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use wednesday_model::{error::SocketError, identifiers::SubscriptionId};
+
+        use super::*;
+        use crate::exchange::bitmex::message::BitmexPayload;
+
+        #[test]
+        fn test_bitmex_trades() {
+            struct TestCase {
+                input: &'static str,
+                expected: Result<BitmexTrades, SocketError>,
+            }
+
+            let tests = vec![
+                // TC0: input BitmexTrades is deserialised
+                TestCase {
+                    input: r#"
+                    {
+                        "table": "trade",
+                        "action": "insert",
+                        "data": [
+                            {
+                                "timestamp": "2023-02-18T09:27:59.701Z",
+                                "symbol": "XBTUSD",
+                                "side": "Sell",
+                                "size": 200,
+                                "price": 24564.5,
+                                "tickDirection": "MinusTick",
+                                "trdMatchID": "31e50cb7-e005-a44e-f354-86e88dff52eb",
+                                "grossValue": 814184,
+                                "homeNotional": 0.00814184,
+                                "foreignNotional": 200,
+                                "trdType": "Regular"
+                            }
+                        ]
+                    }
+                    "#,
+                    expected: Ok(BitmexMessage::Data(BitmexPayload {
+                        subscription_id: SubscriptionId::from("trade|XBTUSD"),
+                        action: BitmexAction::Insert,
+                        data: vec![BitmexTrade {
+                            timestamp: "2023-02-18T09:27:59.701Z".parse().unwrap(),
+                            symbol: "XBTUSD".to_string(),
+                            side: AggressorSide::Sell,
+                            amount: 200.0,
+                            price: 24564.5,
+                            id: "31e50cb7-e005-a44e-f354-86e88dff52eb".to_string(),
+                        }],
+                    })),
+                },
+                // TC1: input BitmexTrades is invalid w/ missing trdMatchID
+                TestCase {
+                    input: r#"
+                    {
+                        "table": "trade",
+                        "action": "insert",
+                        "data": [{"timestamp": "2023-02-18T09:27:59.701Z", "symbol": "XBTUSD", "side": "Buy", "size": 200, "price": 24564.5}]
+                    }
+                    "#,
+                    expected: Err(SocketError::Unsupported {
+                        entity: "",
+                        item: "".to_string(),
+                    }),
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = serde_json::from_str::<BitmexTrades>(test.input);
+                match (actual, test.expected) {
+                    (Ok(actual), Ok(expected)) => {
+                        assert_eq!(actual, expected, "TC{} failed", index)
+                    },
+                    (Err(_), Err(_)) => {
+                        // Test passed
+                    },
+                    (actual, expected) => {
+                        // Test failed
+                        panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                    },
+                }
+            }
+        }
+    }
+}
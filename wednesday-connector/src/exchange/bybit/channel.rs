@@ -1,11 +1,11 @@
 use wednesday_model::identifiers::Identifier;
 
 use crate::subscriber::subscription::{
-    kind::{OrderBooksL2, PublicTrades},
+    kind::{MarkPrices, OpenInterests, OrderBooksL2, PublicTrades},
     Subscription,
 };
 
-use super::Bybit;
+use super::{linear::BybitPerpetualsUsd, Bybit, BybitServer};
 
 pub struct BybitChannel(pub &'static str);
 
@@ -16,7 +16,36 @@ pub struct BybitChannel(pub &'static str);
 /// `orderbook.1.BTCUSDT`
 impl BybitChannel {
     pub const TRADES: Self = Self("publicTrade");
+    pub const ORDER_BOOK_L2_1: Self = Self("orderbook.1");
     pub const ORDER_BOOK_L2: Self = Self("orderbook.50");
+    pub const ORDER_BOOK_L2_200: Self = Self("orderbook.200");
+    pub const ORDER_BOOK_L2_500: Self = Self("orderbook.500");
+    pub const TICKERS: Self = Self("tickers");
+}
+
+/// Depth of the [`Bybit`] `orderbook.{depth}` topic, see [`BybitServer::BOOK_DEPTH`].
+///
+/// Spot supports depths 1, 50 & 200, while perpetuals additionally support a depth of 500.
+///
+/// See docs: <https://bybit-exchange.github.io/docs/v5/websocket/public/orderbook>
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum BybitBookDepth {
+    Depth1,
+    #[default]
+    Depth50,
+    Depth200,
+    Depth500,
+}
+
+impl BybitBookDepth {
+    pub const fn channel(self) -> BybitChannel {
+        match self {
+            BybitBookDepth::Depth1 => BybitChannel::ORDER_BOOK_L2_1,
+            BybitBookDepth::Depth50 => BybitChannel::ORDER_BOOK_L2,
+            BybitBookDepth::Depth200 => BybitChannel::ORDER_BOOK_L2_200,
+            BybitBookDepth::Depth500 => BybitChannel::ORDER_BOOK_L2_500,
+        }
+    }
 }
 
 impl<Server> Identifier<BybitChannel> for Subscription<Bybit<Server>, PublicTrades> {
@@ -25,9 +54,24 @@ impl<Server> Identifier<BybitChannel> for Subscription<Bybit<Server>, PublicTrad
     }
 }
 
-impl<Server> Identifier<BybitChannel> for Subscription<Bybit<Server>, OrderBooksL2> {
+impl<Server> Identifier<BybitChannel> for Subscription<Bybit<Server>, OrderBooksL2>
+where
+    Server: BybitServer,
+{
+    fn id(&self) -> BybitChannel {
+        Server::BOOK_DEPTH.channel()
+    }
+}
+
+impl Identifier<BybitChannel> for Subscription<BybitPerpetualsUsd, MarkPrices> {
+    fn id(&self) -> BybitChannel {
+        BybitChannel::TICKERS
+    }
+}
+
+impl Identifier<BybitChannel> for Subscription<BybitPerpetualsUsd, OpenInterests> {
     fn id(&self) -> BybitChannel {
-        BybitChannel::ORDER_BOOK_L2
+        BybitChannel::TICKERS
     }
 }
 
@@ -36,3 +80,40 @@ impl AsRef<str> for BybitChannel {
         self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use wednesday_model::{
+        identifiers::ExchangeId,
+        instruments::{Instrument, InstrumentKind},
+    };
+
+    use super::*;
+    use crate::exchange::{bybit::linear::WS_BASE_URL_BYBIT_PERPETUALS_USD, connector::ExchangeServer};
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+    struct BybitServerDeepBook;
+
+    impl ExchangeServer for BybitServerDeepBook {
+        const ID: ExchangeId = ExchangeId::BybitPerpetualsUsd;
+
+        fn ws_url() -> &'static str {
+            WS_BASE_URL_BYBIT_PERPETUALS_USD
+        }
+    }
+
+    impl BybitServer for BybitServerDeepBook {
+        const BOOK_DEPTH: BybitBookDepth = BybitBookDepth::Depth500;
+    }
+
+    #[test]
+    fn test_bybit_order_book_l2_channel_depth() {
+        let instrument = Instrument::from(("btc", "usdt", InstrumentKind::CryptoPerpetual));
+
+        let subscription = Subscription::new(BybitPerpetualsUsd::default(), instrument.clone(), OrderBooksL2);
+        assert_eq!(Identifier::<BybitChannel>::id(&subscription).as_ref(), "orderbook.50");
+
+        let subscription = Subscription::new(Bybit::<BybitServerDeepBook>::default(), instrument, OrderBooksL2);
+        assert_eq!(Identifier::<BybitChannel>::id(&subscription).as_ref(), "orderbook.500");
+    }
+}
@@ -1,6 +1,11 @@
 use wednesday_model::identifiers::ExchangeId;
 
-use super::{Bybit, ExchangeServer};
+use crate::{
+    stream::{protocol::ws_stream::ExchangeWsStream, selector::StreamSelector},
+    subscriber::subscription::kind::{MarkPrices, OpenInterests},
+};
+
+use super::{model::ticker::BybitTickerTransformer, Bybit, BybitServer, ExchangeServer};
 
 /// See docs: <https://bybit-exchange.github.io/docs/v5/ws/connect>
 pub const WS_BASE_URL_BYBIT_PERPETUALS_USD: &str = "wss://stream.bybit.com/v5/public/linear";
@@ -17,3 +22,13 @@ impl ExchangeServer for BybitServerPerpetualsUsd {
         WS_BASE_URL_BYBIT_PERPETUALS_USD
     }
 }
+
+impl BybitServer for BybitServerPerpetualsUsd {}
+
+impl StreamSelector<MarkPrices> for BybitPerpetualsUsd {
+    type Stream = ExchangeWsStream<BybitTickerTransformer<Self, MarkPrices>>;
+}
+
+impl StreamSelector<OpenInterests> for BybitPerpetualsUsd {
+    type Stream = ExchangeWsStream<BybitTickerTransformer<Self, OpenInterests>>;
+}
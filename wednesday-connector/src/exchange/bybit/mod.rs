@@ -22,7 +22,7 @@ use crate::{
 
 use self::model::message::BybitMessage;
 use self::{
-    channel::BybitChannel,
+    channel::{BybitBookDepth, BybitChannel},
     market::BybitMarket,
     model::{l2::BybitBookUpdater, trade::BybitTrade},
     subscription::BybitSubscriptionResponse,
@@ -37,6 +37,17 @@ pub mod model;
 pub mod spot;
 pub mod subscription;
 
+/// Maximum number of topics ("args") of a single [`Bybit`] subscription request.
+///
+/// See docs: <https://bybit-exchange.github.io/docs/v5/ws/connect#how-to-subscribe-to-topics>
+pub const MAX_ARGS_PER_REQUEST_BYBIT: usize = 10;
+
+/// [`ExchangeServer`] of a [`Bybit`] market, which also configures the depth of the order book
+/// topic subscribed to for [`OrderBooksL2`].
+pub trait BybitServer: ExchangeServer {
+    const BOOK_DEPTH: BybitBookDepth = BybitBookDepth::Depth50;
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
 pub struct Bybit<Server> {
     server: PhantomData<Server>,
@@ -58,18 +69,15 @@ where
     }
 
     fn requests(exchange_subscriptions: Vec<ExchangeSubscription<Self::Channel, Self::Market>>) -> Vec<WsMessage> {
-        let stream_names = exchange_subscriptions
-            .into_iter()
-            .map(|sub| format!("{}.{}", sub.channel.as_ref(), sub.market.as_ref(),))
-            .collect::<Vec<String>>();
-
-        vec![WsMessage::Text(
-            serde_json::json!({
-                "op": "subscribe",
-                "args": stream_names
-            })
-            .to_string(),
-        )]
+        vec![bybit_request("subscribe", exchange_subscriptions)]
+    }
+
+    fn dynamic_subscriptions() -> bool {
+        true
+    }
+
+    fn unsubscribe_requests(exchange_subscriptions: Vec<ExchangeSubscription<Self::Channel, Self::Market>>) -> Vec<WsMessage> {
+        vec![bybit_request("unsubscribe", exchange_subscriptions)]
     }
 
     fn ping_interval() -> Option<PingInterval> {
@@ -86,8 +94,13 @@ where
         })
     }
 
-    fn expected_responses(_map: &Map<Instrument>) -> usize {
-        1
+    fn expected_responses(map: &Map<Instrument>) -> usize {
+        // Bybit responds once per subscription request
+        map.0.len().div_ceil(MAX_ARGS_PER_REQUEST_BYBIT)
+    }
+
+    fn max_subscriptions_per_request() -> Option<usize> {
+        Some(MAX_ARGS_PER_REQUEST_BYBIT)
     }
 
     fn subscription_timeout() -> std::time::Duration {
@@ -95,6 +108,22 @@ where
     }
 }
 
+/// Single request with the provided "subscribe" or "unsubscribe" op for every "channel.market" topic.
+fn bybit_request(op: &str, exchange_subscriptions: Vec<ExchangeSubscription<BybitChannel, BybitMarket>>) -> WsMessage {
+    let stream_names = exchange_subscriptions
+        .into_iter()
+        .map(|sub| format!("{}.{}", sub.channel.as_ref(), sub.market.as_ref(),))
+        .collect::<Vec<String>>();
+
+    WsMessage::Text(
+        serde_json::json!({
+            "op": op,
+            "args": stream_names
+        })
+        .to_string(),
+    )
+}
+
 // impl<Server> StreamSelector<PublicTrades> for Bybit<Server>
 // where
 //     Server: ExchangeServer + Debug + Send + Sync,
@@ -111,7 +140,7 @@ where
 
 impl<Server> StreamSelector<OrderBooksL2> for Bybit<Server>
 where
-    Server: ExchangeServer + Debug + Send + Sync,
+    Server: BybitServer + Debug + Send + Sync,
 {
     type Stream = ExchangeWsStream<MultiBookTransformer<Self, OrderBooksL2, BybitBookUpdater>>;
 }
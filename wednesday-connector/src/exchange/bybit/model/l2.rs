@@ -2,16 +2,18 @@ use async_trait::async_trait;
 use chrono::Utc;
 use serde::{Deserialize, Deserializer, Serialize};
 use tokio::sync::mpsc::UnboundedSender;
+use tracing::warn;
 use wednesday_model::{
     deserialization,
+    enums::BookSide,
     error::DataError,
     identifiers::{Identifier, SubscriptionId},
     instruments::Instrument,
-    orderbook::{Level, OrderBook},
+    orderbook::{Level, OrderBook, OrderBookSide},
 };
 
 use crate::{
-    exchange::bybit::channel::BybitChannel,
+    exchange::bybit::{channel::BybitChannel, subscription::BybitSubscriptionResponse},
     protocol::http::websocket::WsMessage,
     subscriber::subscription::ExchangeSubscription,
     transformer::updater::{InstrumentOrderBook, OrderBookUpdater},
@@ -21,6 +23,30 @@ use super::message::BybitPayload;
 
 pub type BybitOrderBookL2 = BybitPayload<BybitOrderBookL2Delta>;
 
+/// [`BybitOrderBookL2`] `type` of a message that replaces the whole order book.
+pub const BYBIT_ORDER_BOOK_SNAPSHOT: &str = "snapshot";
+
+/// Message received on a [`Bybit`](crate::exchange::bybit::Bybit) order book connection.
+///
+/// Pongs & (un)subscription responses are sent over the same connection as the order book
+/// topics, so they are consumed as [`BybitOrderBookMessage::Response`] & ignored by the
+/// [`BybitBookUpdater`].
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum BybitOrderBookMessage {
+    OrderBook(BybitOrderBookL2),
+    Response(BybitSubscriptionResponse),
+}
+
+impl Identifier<Option<SubscriptionId>> for BybitOrderBookMessage {
+    fn id(&self) -> Option<SubscriptionId> {
+        match self {
+            BybitOrderBookMessage::OrderBook(order_book) => order_book.id(),
+            BybitOrderBookMessage::Response(_) => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct BybitLevel {
     #[serde(deserialize_with = "deserialization::de_str")]
@@ -52,11 +78,20 @@ pub struct BybitOrderBookL2Delta {
     pub bids: Vec<BybitLevel>,
 }
 
-// impl Identifier<Option<SubscriptionId>> for BybitOrderBookL2Delta {
-//     fn id(&self) -> Option<SubscriptionId> {
-//         Some(self.subscription_id.clone())
-//     }
-// }
+impl BybitOrderBookL2 {
+    /// `true` if the message replaces the whole order book. Besides "snapshot" messages, Bybit
+    /// resets the update id to 1 when the service restarts, & that message must also overwrite
+    /// the local order book.
+    pub fn is_snapshot(&self) -> bool {
+        self.r#type == BYBIT_ORDER_BOOK_SNAPSHOT || self.data.last_update_id == 1
+    }
+
+    /// Bybit topic of the message, eg/ "orderbook.50.BTCUSDT", re-constructed from the
+    /// [`SubscriptionId`] "orderbook.50|BTCUSDT".
+    pub fn topic(&self) -> String {
+        self.subscription_id.as_ref().replacen('|', ".", 1)
+    }
+}
 
 // NOTE: This deserialization implementation has to be refactored.
 pub fn de_ob_l2_subscription_id<'de, D>(deserializer: D) -> Result<SubscriptionId, D::Error>
@@ -71,58 +106,150 @@ where
     D: Deserializer<'de>,
 {
     let raw_levels: Vec<[String; 2]> = Deserialize::deserialize(deserializer)?;
-    let levels = raw_levels
+    raw_levels
         .into_iter()
-        .map(|[price, amount]| BybitLevel {
-            price: price.parse().unwrap(),
-            amount: amount.parse().unwrap(),
+        .map(|[price, amount]| {
+            Ok(BybitLevel {
+                price: price
+                    .parse()
+                    .map_err(|error| serde::de::Error::custom(format!("invalid level price {price}: {error}")))?,
+                amount: amount
+                    .parse()
+                    .map_err(|error| serde::de::Error::custom(format!("invalid level amount {amount}: {error}")))?,
+            })
         })
-        .collect();
-    Ok(levels)
+        .collect()
 }
 
-// See docs: https://bybit-exchange.github.io/docs/v5/websocket/public/orderbook
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+/// Bybit: How To Manage A Local OrderBook Correctly
+///
+/// 1. Subscribe to the `orderbook.{depth}.{symbol}` topic.
+/// 2. The first message is a "snapshot" of the order book, which replaces the local book.
+/// 3. Drop any "delta" received before the first snapshot.
+/// 4. Each "delta" u should be equal to the previous message's u + 1, otherwise the local book
+///    is out of sync & the topic is re-subscribed to receive a new snapshot (back to step 2).
+/// 5. A new "snapshot", or any message with u == 1 (service restart), replaces the local book.
+/// 6. The data in each "delta" is the absolute quantity for a price level.
+/// 7. If the quantity is 0, remove the price level.
+///
+/// Notes:
+///  - Lowercase u => last_update_id
+///  - seq is a cross sequence shared with other topics, so it is not continuous per topic.
+///  - Bybit has no REST snapshot endpoint with a matching update id, hence the re-subscription.
+///
+/// See docs: <https://bybit-exchange.github.io/docs/v5/websocket/public/orderbook>
+#[derive(Clone, Debug)]
 pub struct BybitBookUpdater {
     pub updates_processed: u64,
     pub last_update_id: u64,
+    /// `true` once a snapshot has been applied to the local book.
+    pub is_synchronised: bool,
+    /// Sink of the WebSocket connection, used to re-subscribe to a topic when a gap is detected.
+    pub ws_sink_tx: UnboundedSender<WsMessage>,
 }
 
 impl BybitBookUpdater {
-    pub fn new(last_update_id: u64) -> Self {
+    /// Construct a new Bybit [`OrderBookUpdater`] that waits for the first snapshot.
+    pub fn new(ws_sink_tx: UnboundedSender<WsMessage>) -> Self {
         Self {
             updates_processed: 0,
-            last_update_id,
+            last_update_id: 0,
+            is_synchronised: false,
+            ws_sink_tx,
         }
     }
+
+    /// Bybit: How To Manage A Local OrderBook Correctly: Step 4:
+    /// "Each "delta" u should be equal to the previous message's u + 1"
+    pub fn validate_next_update(&self, update: &BybitOrderBookL2) -> Result<(), DataError> {
+        if update.data.last_update_id == self.last_update_id + 1 {
+            Ok(())
+        } else {
+            Err(DataError::InvalidSequence {
+                prev_last_update_id: self.last_update_id,
+                first_update_id: update.data.last_update_id,
+            })
+        }
+    }
+
+    /// Bybit: How To Manage A Local OrderBook Correctly: Step 4:
+    /// Re-subscribe to the topic of the out of sync book, so Bybit sends a new snapshot. Deltas
+    /// are dropped until it arrives.
+    ///
+    /// If the connection is already closed the [`DataError::InvalidSequence`] is returned, which
+    /// is terminal & re-initialises the whole stream.
+    fn resubscribe(&mut self, update: &BybitOrderBookL2, error: DataError) -> Result<Option<OrderBook>, DataError> {
+        let topic = update.topic();
+        warn!(%topic, %error, action = "re-subscribing to receive a new snapshot", "Bybit order book is out of sync");
+
+        self.is_synchronised = false;
+
+        ["unsubscribe", "subscribe"]
+            .into_iter()
+            .map(|op| WsMessage::Text(serde_json::json!({ "op": op, "args": [topic] }).to_string()))
+            .try_for_each(|request| self.ws_sink_tx.send(request))
+            .map(|_| None)
+            .map_err(|_| error)
+    }
 }
 
 #[async_trait]
 impl OrderBookUpdater for BybitBookUpdater {
     type OrderBook = OrderBook;
-    type Update = BybitOrderBookL2;
+    type Update = BybitOrderBookMessage;
 
-    async fn init<Exchange, Kind>(_: UnboundedSender<WsMessage>, instrument: Instrument) -> Result<InstrumentOrderBook<Self>, DataError>
+    async fn init<Exchange, Kind>(ws_sink_tx: UnboundedSender<WsMessage>, instrument: Instrument) -> Result<InstrumentOrderBook<Self>, DataError>
     where
         Exchange: Send,
         Kind: Send,
     {
-        // Bybit does not provide a snapshot for the order book (does not provide a snapshot via REST API)
-        // Therefore, a Dummy InstrumentOrderBook is returned for now
+        // Bybit sends a snapshot as the first message of every order book topic, so no REST
+        // snapshot is required to initialise the book
         Ok(InstrumentOrderBook {
             instrument,
-            updater: Self::new(0),
+            updater: Self::new(ws_sink_tx),
             book: OrderBook::default(),
         })
     }
 
     fn update(&mut self, book: &mut Self::OrderBook, update: Self::Update) -> Result<Option<Self::OrderBook>, DataError> {
+        let update = match update {
+            BybitOrderBookMessage::OrderBook(update) => update,
+            BybitOrderBookMessage::Response(_) => return Ok(None),
+        };
+
+        // Step 2 & 5: snapshot replaces the local book
+        if update.is_snapshot() {
+            self.is_synchronised = true;
+            self.updates_processed += 1;
+            self.last_update_id = update.data.last_update_id;
+            book.last_update_ts = Utc::now();
+            book.update_id = Some(update.data.last_update_id);
+            book.bids = OrderBookSide::new(BookSide::Bid, update.data.bids);
+            book.asks = OrderBookSide::new(BookSide::Ask, update.data.asks);
+            return Ok(Some(book.snapshot()));
+        }
+
+        // Step 3: drop deltas received before the (re-subscribed) snapshot
+        if !self.is_synchronised {
+            return Ok(None);
+        }
+
+        // Drop any stale delta that was already applied
         if update.data.last_update_id <= self.last_update_id {
             return Ok(None);
         }
+
+        // Step 4: u continuity
+        if let Err(error) = self.validate_next_update(&update) {
+            return self.resubscribe(&update, error);
+        }
+
+        // Step 6 & 7: apply the absolute quantities of the delta
         self.updates_processed += 1;
         self.last_update_id = update.data.last_update_id;
         book.last_update_ts = Utc::now();
+        book.update_id = Some(update.data.last_update_id);
         book.bids.upsert(update.data.bids);
         book.asks.upsert(update.data.asks);
         Ok(Some(book.snapshot()))
@@ -135,6 +262,7 @@ mod tests {
     use wednesday_model::deserialization::datetime_utc_from_epoch_duration;
 
     use super::*;
+    use crate::exchange::bybit::subscription::BybitReturnMessage;
 
     #[test]
     fn test_bybit_futures_order_book_l2_deltas() {
@@ -195,16 +323,10 @@ mod tests {
                             price: 30240.30,
                             amount: 1.305
                         },
-                        BybitLevel {
-                            price: 30240.00,
-                            amount: 0.0
-                        },
+                        BybitLevel { price: 30240.00, amount: 0.0 },
                     ],
                     asks: vec![
-                        BybitLevel {
-                            price: 30248.70,
-                            amount: 0.0
-                        },
+                        BybitLevel { price: 30248.70, amount: 0.0 },
                         BybitLevel {
                             price: 30249.30,
                             amount: 0.892
@@ -213,10 +335,7 @@ mod tests {
                             price: 30249.50,
                             amount: 1.778
                         },
-                        BybitLevel {
-                            price: 30249.60,
-                            amount: 0.0
-                        },
+                        BybitLevel { price: 30249.60, amount: 0.0 },
                         BybitLevel {
                             price: 30251.90,
                             amount: 2.947
@@ -234,17 +353,87 @@ mod tests {
             }
         );
     }
+    #[test]
+    fn test_bybit_order_book_message() {
+        struct TestCase {
+            input: &'static str,
+            expected: Option<BybitOrderBookMessage>,
+        }
+
+        let tests = vec![
+            // TC0: snapshot
+            TestCase {
+                input: r#"{"topic":"orderbook.200.BTCUSDT","type":"snapshot","ts":1687940967466,"data":{"s":"BTCUSDT","b":[["30247.20","30.028"]],"a":[],"u":1,"seq":66544703342}}"#,
+                expected: Some(BybitOrderBookMessage::OrderBook(BybitOrderBookL2 {
+                    subscription_id: SubscriptionId::from("orderbook.200|BTCUSDT"),
+                    r#type: "snapshot".to_string(),
+                    exchange_ts: datetime_utc_from_epoch_duration(Duration::from_millis(1687940967466)),
+                    data: BybitOrderBookL2Delta {
+                        symbol: "BTCUSDT".to_string(),
+                        last_update_id: 1,
+                        sequence: 66544703342,
+                        bids: vec![BybitLevel {
+                            price: 30247.20,
+                            amount: 30.028,
+                        }],
+                        asks: vec![],
+                    },
+                })),
+            },
+            // TC1: unsubscribe response
+            TestCase {
+                input: r#"{"success":true,"ret_msg":"","conn_id":"0970e817","op":"unsubscribe"}"#,
+                expected: Some(BybitOrderBookMessage::Response(BybitSubscriptionResponse {
+                    success: true,
+                    ret_msg: BybitReturnMessage::Empty,
+                    conn_id: "0970e817".to_string(),
+                    req_id: "".to_string(),
+                    op: "unsubscribe".to_string(),
+                })),
+            },
+            // TC2: invalid level price is an error rather than a panic
+            TestCase {
+                input: r#"{"topic":"orderbook.50.BTCUSDT","type":"delta","ts":1687940967466,"data":{"s":"BTCUSDT","b":[["invalid","30.028"]],"a":[],"u":2,"seq":66544703342}}"#,
+                expected: None,
+            },
+            // TC3: invalid level amount is an error rather than a panic
+            TestCase {
+                input: r#"{"topic":"orderbook.50.BTCUSDT","type":"delta","ts":1687940967466,"data":{"s":"BTCUSDT","b":[],"a":[["30248.70",""]],"u":2,"seq":66544703342}}"#,
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = serde_json::from_str::<BybitOrderBookMessage>(test.input).ok();
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_bybit_order_book_l2_topic() {
+        let message: BybitOrderBookL2 = serde_json::from_str(
+            r#"{"topic":"orderbook.500.BTCUSDT","type":"snapshot","ts":1687940967466,"data":{"s":"BTCUSDT","b":[],"a":[],"u":5,"seq":1}}"#,
+        )
+        .unwrap();
+        assert!(message.is_snapshot());
+        assert_eq!(message.topic(), "orderbook.500.BTCUSDT");
+    }
+
     mod bybit_futures_book_updater {
         use chrono::Utc;
+        use tokio::sync::mpsc;
         use wednesday_model::{
             enums::BookSide,
             identifiers::SubscriptionId,
             orderbook::{Level, OrderBook, OrderBookSide},
         };
 
-        use crate::exchange::bybit::model::l2::{
-            tests::{BybitPayload, DataError, OrderBookUpdater},
-            BybitBookUpdater, BybitLevel, BybitOrderBookL2Delta,
+        use crate::{
+            exchange::bybit::model::l2::{
+                tests::{BybitPayload, DataError, OrderBookUpdater},
+                BybitBookUpdater, BybitLevel, BybitOrderBookL2Delta, BybitOrderBookMessage,
+            },
+            protocol::http::websocket::WsMessage,
         };
 
         #[test]
@@ -257,15 +446,19 @@ mod tests {
             }
 
             let time = Utc::now();
+            let (ws_sink_tx, _ws_sink_rx) = mpsc::unbounded_channel();
 
             let tests = vec![
                 TestCase {
                     updater: BybitBookUpdater {
                         updates_processed: 0,
-                        last_update_id: 0,
+                        last_update_id: 177400506,
+                        is_synchronised: true,
+                        ws_sink_tx: ws_sink_tx.clone(),
                     },
                     book: OrderBook {
                         last_update_ts: time,
+                        update_id: None,
                         bids: OrderBookSide::new(
                             BookSide::Bid,
                             vec![Level {
@@ -273,13 +466,7 @@ mod tests {
                                 amount: 30.028,
                             }],
                         ),
-                        asks: OrderBookSide::new(
-                            BookSide::Ask,
-                            vec![Level {
-                                price: 30248.70,
-                                amount: 0.0,
-                            }],
-                        ),
+                        asks: OrderBookSide::new(BookSide::Ask, vec![Level { price: 30248.70, amount: 0.0 }]),
                     },
                     input_update: BybitPayload {
                         subscription_id: SubscriptionId::from("orderbook.50|BTCUSDT"),
@@ -306,16 +493,10 @@ mod tests {
                                     price: 30240.30,
                                     amount: 1.305,
                                 },
-                                BybitLevel {
-                                    price: 30240.00,
-                                    amount: 0.0,
-                                },
+                                BybitLevel { price: 30240.00, amount: 0.0 },
                             ],
                             asks: vec![
-                                BybitLevel {
-                                    price: 30248.70,
-                                    amount: 0.0,
-                                },
+                                BybitLevel { price: 30248.70, amount: 0.0 },
                                 BybitLevel {
                                     price: 30249.30,
                                     amount: 0.892,
@@ -324,10 +505,7 @@ mod tests {
                                     price: 30249.50,
                                     amount: 1.778,
                                 },
-                                BybitLevel {
-                                    price: 30249.60,
-                                    amount: 0.0,
-                                },
+                                BybitLevel { price: 30249.60, amount: 0.0 },
                                 BybitLevel {
                                     price: 30251.90,
                                     amount: 2.947,
@@ -345,6 +523,7 @@ mod tests {
                     },
                     expected: Ok(Some(OrderBook {
                         last_update_ts: time,
+                        update_id: Some(177400507),
                         bids: OrderBookSide::new(
                             BookSide::Bid,
                             vec![
@@ -396,10 +575,13 @@ mod tests {
                 TestCase {
                     updater: BybitBookUpdater {
                         updates_processed: 0,
-                        last_update_id: 0,
+                        last_update_id: 1,
+                        is_synchronised: true,
+                        ws_sink_tx: ws_sink_tx.clone(),
                     },
                     book: OrderBook {
                         last_update_ts: time,
+                        update_id: None,
                         bids: OrderBookSide::new(BookSide::Bid, vec![Level::new(80, 1), Level::new(10, 1), Level::new(90, 1)]),
                         asks: OrderBookSide::new(BookSide::Ask, vec![Level::new(150, 1), Level::new(110, 1), Level::new(120, 1)]),
                     },
@@ -409,14 +591,15 @@ mod tests {
                         exchange_ts: time,
                         data: BybitOrderBookL2Delta {
                             symbol: "BTCUSDT".to_string(),
-                            last_update_id: 1,
-                            sequence: 1,
+                            last_update_id: 2,
+                            sequence: 2,
                             bids: vec![BybitLevel { price: 80.0, amount: 0.0 }, BybitLevel { price: 90.0, amount: 10.0 }],
                             asks: vec![BybitLevel { price: 200.0, amount: 1.0 }, BybitLevel { price: 500.0, amount: 0.0 }],
                         },
                     },
                     expected: Ok(Some(OrderBook {
                         last_update_ts: time,
+                        update_id: Some(2),
                         bids: OrderBookSide::new(BookSide::Bid, vec![Level::new(90, 10), Level::new(10, 1)]),
                         asks: OrderBookSide::new(
                             BookSide::Ask,
@@ -427,7 +610,7 @@ mod tests {
             ];
 
             for (index, mut test) in tests.into_iter().enumerate() {
-                let actual = test.updater.update(&mut test.book, test.input_update);
+                let actual = test.updater.update(&mut test.book, BybitOrderBookMessage::OrderBook(test.input_update));
 
                 match (actual, test.expected) {
                     (Ok(Some(actual)), Ok(Some(expected))) => {
@@ -451,5 +634,141 @@ mod tests {
                 }
             }
         }
+
+        #[test]
+        fn sequence() {
+            fn message(r#type: &str, last_update_id: u64, bids: Vec<BybitLevel>) -> BybitOrderBookMessage {
+                BybitOrderBookMessage::OrderBook(BybitPayload {
+                    subscription_id: SubscriptionId::from("orderbook.50|BTCUSDT"),
+                    r#type: r#type.to_string(),
+                    exchange_ts: Utc::now(),
+                    data: BybitOrderBookL2Delta {
+                        symbol: "BTCUSDT".to_string(),
+                        last_update_id,
+                        sequence: last_update_id,
+                        bids,
+                        asks: vec![],
+                    },
+                })
+            }
+
+            struct TestCase {
+                input: BybitOrderBookMessage,
+                expected_bids: Option<Vec<Level>>,
+                expected_requests: Vec<&'static str>,
+            }
+
+            let tests = vec![
+                // TC0: delta before the first snapshot is dropped
+                TestCase {
+                    input: message("delta", 9, vec![BybitLevel { price: 90.0, amount: 1.0 }]),
+                    expected_bids: None,
+                    expected_requests: vec![],
+                },
+                // TC1: snapshot replaces the book
+                TestCase {
+                    input: message("snapshot", 10, vec![BybitLevel { price: 100.0, amount: 1.0 }]),
+                    expected_bids: Some(vec![Level::new(100, 1)]),
+                    expected_requests: vec![],
+                },
+                // TC2: next delta is applied
+                TestCase {
+                    input: message("delta", 11, vec![BybitLevel { price: 101.0, amount: 2.0 }]),
+                    expected_bids: Some(vec![Level::new(101, 2), Level::new(100, 1)]),
+                    expected_requests: vec![],
+                },
+                // TC3: stale delta is dropped
+                TestCase {
+                    input: message("delta", 11, vec![BybitLevel { price: 102.0, amount: 1.0 }]),
+                    expected_bids: None,
+                    expected_requests: vec![],
+                },
+                // TC4: gap in u triggers a re-subscription of the topic
+                TestCase {
+                    input: message("delta", 13, vec![BybitLevel { price: 103.0, amount: 1.0 }]),
+                    expected_bids: None,
+                    expected_requests: vec![
+                        r#"{"args":["orderbook.50.BTCUSDT"],"op":"unsubscribe"}"#,
+                        r#"{"args":["orderbook.50.BTCUSDT"],"op":"subscribe"}"#,
+                    ],
+                },
+                // TC5: deltas are dropped until the re-subscribed snapshot arrives
+                TestCase {
+                    input: message("delta", 14, vec![BybitLevel { price: 104.0, amount: 1.0 }]),
+                    expected_bids: None,
+                    expected_requests: vec![],
+                },
+                // TC6: re-subscribed snapshot replaces the book
+                TestCase {
+                    input: message("snapshot", 20, vec![BybitLevel { price: 99.0, amount: 3.0 }]),
+                    expected_bids: Some(vec![Level::new(99, 3)]),
+                    expected_requests: vec![],
+                },
+                // TC7: delta w/ u == 1 (service restart) replaces the book
+                TestCase {
+                    input: message("delta", 1, vec![BybitLevel { price: 98.0, amount: 1.0 }]),
+                    expected_bids: Some(vec![Level::new(98, 1)]),
+                    expected_requests: vec![],
+                },
+                // TC8: pong is ignored
+                TestCase {
+                    input: serde_json::from_str(r#"{"success":true,"ret_msg":"pong","conn_id":"0970e817","op":"ping"}"#).unwrap(),
+                    expected_bids: None,
+                    expected_requests: vec![],
+                },
+            ];
+
+            let (ws_sink_tx, mut ws_sink_rx) = mpsc::unbounded_channel();
+            let mut updater = BybitBookUpdater::new(ws_sink_tx);
+            let mut book = OrderBook::default();
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = updater.update(&mut book, test.input).unwrap();
+                assert_eq!(actual.map(|book| book.bids.levels()), test.expected_bids, "TC{} failed", index);
+
+                let requests = std::iter::from_fn(|| ws_sink_rx.try_recv().ok()).collect::<Vec<_>>();
+                let expected_requests = test
+                    .expected_requests
+                    .into_iter()
+                    .map(|request| WsMessage::Text(request.to_string()))
+                    .collect::<Vec<_>>();
+                assert_eq!(requests, expected_requests, "TC{} failed", index);
+            }
+        }
+
+        #[test]
+        fn gap_with_closed_connection_is_terminal() {
+            let (ws_sink_tx, ws_sink_rx) = mpsc::unbounded_channel();
+            drop(ws_sink_rx);
+
+            let mut updater = BybitBookUpdater {
+                updates_processed: 1,
+                last_update_id: 10,
+                is_synchronised: true,
+                ws_sink_tx,
+            };
+            let update = BybitOrderBookMessage::OrderBook(BybitPayload {
+                subscription_id: SubscriptionId::from("orderbook.50|BTCUSDT"),
+                r#type: "delta".to_string(),
+                exchange_ts: Utc::now(),
+                data: BybitOrderBookL2Delta {
+                    symbol: "BTCUSDT".to_string(),
+                    last_update_id: 12,
+                    sequence: 12,
+                    bids: vec![],
+                    asks: vec![],
+                },
+            });
+
+            let error = updater.update(&mut OrderBook::default(), update).unwrap_err();
+            assert!(matches!(
+                error,
+                DataError::InvalidSequence {
+                    prev_last_update_id: 10,
+                    first_update_id: 12
+                }
+            ));
+            assert!(error.is_terminal());
+        }
     }
 }
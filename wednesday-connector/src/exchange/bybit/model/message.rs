@@ -10,14 +10,15 @@ use wednesday_model::{
 
 use crate::{exchange::bybit::subscription::BybitSubscriptionResponse, transformer::iterator::MarketIter};
 
-use super::{l2::BybitOrderBookL2, trade::BybitTrade};
+use super::{l2::BybitOrderBookL2, ticker::BybitTicker, trade::BybitTrade};
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum BybitMessage {
     Response(BybitSubscriptionResponse),
     Trade(BybitTrade),
-    OrderBook(BybitOrderBookL2)
+    OrderBook(BybitOrderBookL2),
+    Ticker(BybitTicker),
 }
 
 impl Identifier<Option<SubscriptionId>> for BybitMessage {
@@ -25,6 +26,7 @@ impl Identifier<Option<SubscriptionId>> for BybitMessage {
         match self {
             BybitMessage::Trade(trade) => trade.id(),
             BybitMessage::OrderBook(order_book) => order_book.id(),
+            BybitMessage::Ticker(ticker) => ticker.id(),
             BybitMessage::Response(pong_response) => pong_response.id(),
         }
     }
@@ -60,6 +62,7 @@ impl From<(ExchangeId, Instrument, BybitMessage)> for MarketIter<PublicTrade> {
             )
             },
             BybitMessage::OrderBook(order_book) => { Self(vec![]) },
+            BybitMessage::Ticker(_) => Self(vec![]),
         }
 
 
@@ -85,6 +88,7 @@ where
     // parsing Example
     // - publicTrade.BTCUSDT
     // - orderbook.50.BTCUSDT
+    // - tickers.BTCUSDT
     let input = <&str as Deserialize>::deserialize(deserializer)?;
     let tokens: Vec<&str> = input.split(".").collect();
 
@@ -92,7 +96,7 @@ where
     let mut level: Option<&str> = None;
     let mut market: Option<&str> = None;
 
-    if topic_type == Some(&"publicTrade") {
+    if topic_type == Some(&"publicTrade") || topic_type == Some(&"tickers") {
         market = Some(tokens[1]);
     } else if topic_type == Some(&"orderbook") {
         level = Some(tokens[1]);
@@ -100,7 +104,7 @@ where
     }
 
     match (topic_type, level, market) {
-        (Some(&"publicTrade"), None, market) => {
+        (Some(&"publicTrade") | Some(&"tickers"), None, market) => {
             if tokens.len() > 2 {
                 return Err(Error::invalid_value(
                     Unexpected::Str(input),
@@ -132,6 +136,12 @@ impl Identifier<Option<SubscriptionId>> for BybitOrderBookL2 {
     }
 }
 
+impl Identifier<Option<SubscriptionId>> for BybitTicker {
+    fn id(&self) -> Option<SubscriptionId> {
+        Some(self.subscription_id.clone())
+    }
+}
+
 impl Identifier<Option<SubscriptionId>> for BybitTrade {
     fn id(&self) -> Option<SubscriptionId> {
         Some(self.subscription_id.clone())
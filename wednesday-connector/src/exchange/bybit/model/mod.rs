@@ -1,3 +1,4 @@
 pub mod l2;
 pub mod message;
+pub mod ticker;
 pub mod trade;
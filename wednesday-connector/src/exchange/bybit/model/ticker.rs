@@ -0,0 +1,379 @@
+use std::{fmt::Debug, marker::PhantomData, time::Duration};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use wednesday_model::{
+    deserialization::{self, datetime_utc_from_epoch_duration},
+    error::DataError,
+    events::MarketEvent,
+    identifiers::{Exchange, ExchangeId, SubscriptionId},
+    instruments::Instrument,
+    perpetual::{MarkPrice, OpenInterest},
+};
+
+use crate::{
+    exchange::connector::Connector,
+    protocol::http::websocket::WsMessage,
+    subscriber::subscription::{Map, SubscriptionKind},
+    transformer::{iterator::MarketIter, ExchangeTransformer, Transformer},
+};
+
+use super::message::{BybitMessage, BybitPayload};
+
+pub type BybitTicker = BybitPayload<BybitTickerInner>;
+
+/// Bybit linear ticker, carrying the mark price, index price, funding rate & open interest.
+///
+/// Note:
+/// The first message after subscribing is a "snapshot", every following message is a "delta"
+/// that only contains the fields that changed.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://bybit-exchange.github.io/docs/v5/websocket/public/ticker>
+/// ```json
+/// {
+///     "symbol": "BTCUSDT",
+///     "tickDirection": "PlusTick",
+///     "lastPrice": "17216.00",
+///     "markPrice": "17217.33",
+///     "indexPrice": "17227.36",
+///     "openInterest": "68744.761",
+///     "openInterestValue": "1183601235.91",
+///     "nextFundingTime": "1673280000000",
+///     "fundingRate": "-0.000212"
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BybitTickerInner {
+    pub symbol: String,
+    #[serde(default, deserialize_with = "deserialization::de_option_str")]
+    pub mark_price: Option<f64>,
+    #[serde(default, deserialize_with = "deserialization::de_option_str")]
+    pub index_price: Option<f64>,
+    #[serde(default, deserialize_with = "deserialization::de_option_str")]
+    pub funding_rate: Option<f64>,
+    #[serde(default, deserialize_with = "deserialization::de_option_str")]
+    pub next_funding_time: Option<u64>,
+    #[serde(default, deserialize_with = "deserialization::de_option_str")]
+    pub open_interest: Option<f64>,
+    #[serde(default, deserialize_with = "deserialization::de_option_str")]
+    pub open_interest_value: Option<f64>,
+}
+
+impl BybitTickerInner {
+    /// Apply a "delta" [`BybitTickerInner`], overwriting every field that is present.
+    pub fn merge(&mut self, delta: BybitTickerInner) {
+        self.mark_price = delta.mark_price.or(self.mark_price);
+        self.index_price = delta.index_price.or(self.index_price);
+        self.funding_rate = delta.funding_rate.or(self.funding_rate);
+        self.next_funding_time = delta.next_funding_time.or(self.next_funding_time);
+        self.open_interest = delta.open_interest.or(self.open_interest);
+        self.open_interest_value = delta.open_interest_value.or(self.open_interest_value);
+    }
+}
+
+impl From<(ExchangeId, Instrument, BybitTicker)> for MarketIter<MarkPrice> {
+    fn from((exchange_id, instrument, ticker): (ExchangeId, Instrument, BybitTicker)) -> Self {
+        let BybitTickerInner {
+            mark_price: Some(mark_price),
+            index_price: Some(index_price),
+            funding_rate: Some(funding_rate),
+            next_funding_time: Some(next_funding_time),
+            ..
+        } = ticker.data
+        else {
+            return Self(vec![]);
+        };
+
+        Self(vec![Ok(MarketEvent {
+            exchange_ts: ticker.exchange_ts,
+            local_ts: Utc::now(),
+            exchange: Exchange::from(exchange_id),
+            instrument,
+            kind: MarkPrice {
+                mark_price,
+                index_price,
+                funding_rate,
+                next_funding_time: datetime_utc_from_epoch_duration(Duration::from_millis(next_funding_time)),
+            },
+        })])
+    }
+}
+
+impl From<(ExchangeId, Instrument, BybitTicker)> for MarketIter<OpenInterest> {
+    fn from((exchange_id, instrument, ticker): (ExchangeId, Instrument, BybitTicker)) -> Self {
+        let Some(contracts) = ticker.data.open_interest else {
+            return Self(vec![]);
+        };
+
+        Self(vec![Ok(MarketEvent {
+            exchange_ts: ticker.exchange_ts,
+            local_ts: Utc::now(),
+            exchange: Exchange::from(exchange_id),
+            instrument,
+            kind: OpenInterest {
+                contracts,
+                notional: ticker.data.open_interest_value,
+            },
+        })])
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct InstrumentTicker {
+    pub instrument: Instrument,
+    pub ticker: BybitTickerInner,
+}
+
+/// Stateful [`Transformer`] that merges Bybit ticker "delta" messages into the last known
+/// ticker of each instrument before generating the [`MarketEvent`]s of the `Kind`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct BybitTickerTransformer<Exchange, Kind> {
+    pub ticker_map: Map<InstrumentTicker>,
+    phantom: PhantomData<(Exchange, Kind)>,
+}
+
+impl<Exchange, Kind> Transformer for BybitTickerTransformer<Exchange, Kind>
+where
+    Exchange: Connector,
+    Kind: SubscriptionKind,
+    MarketIter<Kind::Event>: From<(ExchangeId, Instrument, BybitTicker)>,
+{
+    type Error = DataError;
+    type Input = BybitMessage;
+    type Output = MarketEvent<Kind::Event>;
+    type OutputIter = Vec<Result<Self::Output, Self::Error>>;
+    type Pong = ();
+
+    fn transform(&mut self, input: Self::Input) -> Self::OutputIter {
+        let ticker = match input {
+            BybitMessage::Ticker(ticker) => ticker,
+            _ => return vec![],
+        };
+
+        let InstrumentTicker { instrument, ticker: state } = match self.ticker_map.find_mut(&ticker.subscription_id) {
+            Ok(instrument_ticker) => instrument_ticker,
+            Err(unidentifiable) => return vec![Err(DataError::Socket(unidentifiable))],
+        };
+
+        let BybitPayload {
+            subscription_id,
+            r#type,
+            exchange_ts,
+            data,
+        } = ticker;
+
+        if r#type == "snapshot" {
+            *state = data;
+        } else {
+            state.merge(data);
+        }
+
+        let merged = BybitTicker {
+            subscription_id,
+            r#type,
+            exchange_ts,
+            data: state.clone(),
+        };
+
+        MarketIter::<Kind::Event>::from((Exchange::ID, instrument.clone(), merged)).0
+    }
+}
+
+#[async_trait]
+impl<Exchange, Kind> ExchangeTransformer<Exchange, Kind> for BybitTickerTransformer<Exchange, Kind>
+where
+    Exchange: Connector + Send,
+    Kind: SubscriptionKind + Send,
+    MarketIter<Kind::Event>: From<(ExchangeId, Instrument, BybitTicker)>,
+{
+    async fn new(_: mpsc::UnboundedSender<WsMessage>, instrument_map: Map<Instrument>) -> Result<Self, DataError> {
+        let ticker_map = instrument_map
+            .0
+            .into_iter()
+            .map(|(subscription_id, instrument)| {
+                (
+                    subscription_id,
+                    InstrumentTicker {
+                        instrument,
+                        ticker: BybitTickerInner::default(),
+                    },
+                )
+            })
+            .collect();
+
+        Ok(Self {
+            ticker_map,
+            phantom: PhantomData,
+        })
+    }
+    async fn subscribe(&mut self, _: mpsc::UnboundedSender<WsMessage>, instrument_map: Map<Instrument>) -> Result<(), DataError> {
+        self.ticker_map.extend(instrument_map.0.into_iter().map(|(subscription_id, instrument)| {
+            (
+                subscription_id,
+                InstrumentTicker {
+                    instrument,
+                    ticker: BybitTickerInner::default(),
+                },
+            )
+        }));
+        Ok(())
+    }
+
+    fn unsubscribe(&mut self, subscription_ids: &[SubscriptionId]) {
+        self.ticker_map.remove(subscription_ids);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use wednesday_model::{error::SocketError, identifiers::SubscriptionId};
+
+        use super::*;
+
+        #[test]
+        fn test_bybit_ticker() {
+            struct TestCase {
+                input: &'static str,
+                expected: Result<BybitTicker, SocketError>,
+            }
+
+            let tests = vec![
+                // TC0: input BybitTicker snapshot is deserialised
+                TestCase {
+                    input: r#"
+                        {
+                            "topic": "tickers.BTCUSDT",
+                            "type": "snapshot",
+                            "data": {
+                                "symbol": "BTCUSDT",
+                                "tickDirection": "PlusTick",
+                                "lastPrice": "17216.00",
+                                "markPrice": "17217.33",
+                                "indexPrice": "17227.36",
+                                "openInterest": "68744.761",
+                                "openInterestValue": "1183601235.91",
+                                "nextFundingTime": "1673280000000",
+                                "fundingRate": "-0.000212"
+                            },
+                            "cs": 24987956059,
+                            "ts": 1673272861686
+                        }
+                    "#,
+                    expected: Ok(BybitTicker {
+                        subscription_id: SubscriptionId::from("tickers|BTCUSDT"),
+                        r#type: "snapshot".to_string(),
+                        exchange_ts: datetime_utc_from_epoch_duration(Duration::from_millis(1673272861686)),
+                        data: BybitTickerInner {
+                            symbol: "BTCUSDT".to_string(),
+                            mark_price: Some(17217.33),
+                            index_price: Some(17227.36),
+                            funding_rate: Some(-0.000212),
+                            next_funding_time: Some(1673280000000),
+                            open_interest: Some(68744.761),
+                            open_interest_value: Some(1183601235.91),
+                        },
+                    }),
+                },
+                // TC1: input BybitTicker delta w/ missing fields is deserialised
+                TestCase {
+                    input: r#"
+                        {
+                            "topic": "tickers.BTCUSDT",
+                            "type": "delta",
+                            "data": {
+                                "symbol": "BTCUSDT",
+                                "markPrice": "17218.00"
+                            },
+                            "cs": 24987956060,
+                            "ts": 1673272861786
+                        }
+                    "#,
+                    expected: Ok(BybitTicker {
+                        subscription_id: SubscriptionId::from("tickers|BTCUSDT"),
+                        r#type: "delta".to_string(),
+                        exchange_ts: datetime_utc_from_epoch_duration(Duration::from_millis(1673272861786)),
+                        data: BybitTickerInner {
+                            symbol: "BTCUSDT".to_string(),
+                            mark_price: Some(17218.00),
+                            ..Default::default()
+                        },
+                    }),
+                },
+                // TC2: input BybitTicker is invalid w/ non-numeric mark price
+                TestCase {
+                    input: r#"
+                        {
+                            "topic": "tickers.BTCUSDT",
+                            "type": "delta",
+                            "data": {
+                                "symbol": "BTCUSDT",
+                                "markPrice": "unknown"
+                            },
+                            "ts": 1673272861786
+                        }
+                    "#,
+                    expected: Err(SocketError::Unsupported {
+                        entity: "",
+                        item: "".to_string(),
+                    }),
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = serde_json::from_str::<BybitTicker>(test.input);
+                match (actual, test.expected) {
+                    (Ok(actual), Ok(expected)) => {
+                        assert_eq!(actual, expected, "TC{} failed", index)
+                    },
+                    (Err(_), Err(_)) => {
+                        // Test passed
+                    },
+                    (actual, expected) => {
+                        // Test failed
+                        panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                    },
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bybit_ticker_merge() {
+        let mut ticker = BybitTickerInner {
+            symbol: "BTCUSDT".to_string(),
+            mark_price: Some(17217.33),
+            index_price: Some(17227.36),
+            funding_rate: Some(-0.000212),
+            next_funding_time: Some(1673280000000),
+            open_interest: Some(68744.761),
+            open_interest_value: Some(1183601235.91),
+        };
+
+        ticker.merge(BybitTickerInner {
+            symbol: "BTCUSDT".to_string(),
+            mark_price: Some(17218.00),
+            open_interest: Some(68745.0),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            ticker,
+            BybitTickerInner {
+                symbol: "BTCUSDT".to_string(),
+                mark_price: Some(17218.00),
+                index_price: Some(17227.36),
+                funding_rate: Some(-0.000212),
+                next_funding_time: Some(1673280000000),
+                open_interest: Some(68745.0),
+                open_interest_value: Some(1183601235.91),
+            }
+        );
+    }
+}
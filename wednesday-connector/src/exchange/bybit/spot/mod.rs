@@ -2,13 +2,13 @@ use wednesday_model::identifiers::ExchangeId;
 
 use crate::exchange::connector::ExchangeServer;
 
-use super::Bybit;
+use super::{Bybit, BybitServer};
 
 pub const WS_BASE_URL_BYBIT_SPOT: &str = "wss://stream.bybit.com/v5/public/spot";
 
 pub type BybitSpot = Bybit<BybitServerSpot>;
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Hash)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
 pub struct BybitServerSpot;
 
 impl ExchangeServer for BybitServerSpot {
@@ -17,3 +17,5 @@ impl ExchangeServer for BybitServerSpot {
         WS_BASE_URL_BYBIT_SPOT
     }
 }
+
+impl BybitServer for BybitServerSpot {}
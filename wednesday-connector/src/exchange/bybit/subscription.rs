@@ -49,7 +49,7 @@ impl Validator for BybitSubscriptionResponse {
             BybitReturnMessage::Pong => Ok(self),
             BybitReturnMessage::Empty => {
                 debug!("Received a response from the exchange: {:?}", self);
-                if self.op == "subscribe" && self.success {
+                if matches!(self.op.as_str(), "subscribe" | "unsubscribe") && self.success {
                     Ok(self)
                 } else {
                     Err(SocketError::Subscribe("received failure subsciption response".to_owned()))
@@ -179,6 +179,17 @@ mod tests {
                 },
                 is_valid: false,
             },
+            TestCase {
+                // TC3: input response is successful unsubscription
+                input_response: BybitSubscriptionResponse {
+                    success: true,
+                    ret_msg: BybitReturnMessage::Empty,
+                    conn_id: String::new(),
+                    req_id: String::new(),
+                    op: "unsubscribe".to_string(),
+                },
+                is_valid: true,
+            },
         ];
 
         for (index, test) in cases.into_iter().enumerate() {
@@ -1,19 +1,29 @@
-use tokio::sync::mpsc;
+use wednesday_model::channel::{channel, ChannelConfig, ChannelKey, ChannelRx, ChannelTx};
 
 #[derive(Debug)]
 pub struct ExchangeChannel<T> {
-    pub tx: mpsc::UnboundedSender<T>,
-    pub rx: mpsc::UnboundedReceiver<T>,
+    pub tx: ChannelTx<T>,
+    pub rx: ChannelRx<T>,
 }
 
-impl<T> ExchangeChannel<T> {
+impl<T> ExchangeChannel<T>
+where
+    T: ChannelKey,
+{
     pub fn new() -> Self {
-        let (tx, rx) = mpsc::unbounded_channel();
+        Self::with_config(ChannelConfig::default())
+    }
+
+    pub fn with_config(config: ChannelConfig) -> Self {
+        let (tx, rx) = channel(config);
         Self { tx, rx }
     }
 }
 
-impl<T> Default for ExchangeChannel<T> {
+impl<T> Default for ExchangeChannel<T>
+where
+    T: ChannelKey,
+{
     fn default() -> Self {
         Self::new()
     }
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use wednesday_model::orderbook::Level;
+
+/// Decimal places an exchange formats the price & amount of its order book levels with, learnt
+/// from the raw numbers it sends, so that an exchange checksum can be calculated from the `f64`
+/// levels of an [`OrderBook`](wednesday_model::orderbook::OrderBook).
+///
+/// A field the exchange pads to a fixed number of decimals (eg/ "4410.00000000") is formatted
+/// with those decimals once a raw number with trailing zeros has been seen. Any other number is
+/// reproduced by the shortest representation of the `f64`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Deserialize, Serialize)]
+pub struct LevelPrecision {
+    price: Option<usize>,
+    amount: Option<usize>,
+}
+
+impl LevelPrecision {
+    /// Learn the decimals of each field from the raw numbers of a level sent by the exchange.
+    pub fn observe(&mut self, raw_price: &str, raw_amount: &str) {
+        observe(&mut self.price, raw_price);
+        observe(&mut self.amount, raw_amount);
+    }
+
+    /// Format the price & amount of the [`Level`] as the exchange sends them.
+    pub fn format(&self, level: &Level) -> [String; 2] {
+        [format(level.price, self.price), format(level.amount, self.amount)]
+    }
+}
+
+fn observe(decimals: &mut Option<usize>, raw: &str) {
+    if let Some((_, fraction)) = raw.split_once('.').filter(|(_, fraction)| fraction.ends_with('0')) {
+        *decimals = Some(decimals.map_or(fraction.len(), |decimals| decimals.max(fraction.len())));
+    }
+}
+
+fn format(value: f64, decimals: Option<usize>) -> String {
+    match decimals {
+        Some(decimals) => format!("{value:.decimals$}"),
+        None => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_precision_format() {
+        struct TestCase {
+            observed: Vec<(&'static str, &'static str)>,
+            input: Level,
+            expected: [&'static str; 2],
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: unpadded numbers are formatted with the shortest representation
+                observed: vec![("3366.1", "7"), ("3366", "6")],
+                input: Level::new(3366.1, 7.0),
+                expected: ["3366.1", "7"],
+            },
+            TestCase {
+                // TC1: padded amounts are formatted with the decimals of the exchange
+                observed: vec![("0.5668", "4410.00000000")],
+                input: Level::new(0.5657, 1.5),
+                expected: ["0.5657", "1.50000000"],
+            },
+            TestCase {
+                // TC2: padded prices & amounts
+                observed: vec![("0.05000", "0.00000500")],
+                input: Level::new(0.0501, 2.0),
+                expected: ["0.05010", "2.00000000"],
+            },
+            TestCase {
+                // TC3: nothing observed
+                observed: vec![],
+                input: Level::new(0.000005, 100.0),
+                expected: ["0.000005", "100"],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let mut precision = LevelPrecision::default();
+            test.observed.iter().for_each(|(price, amount)| precision.observe(price, amount));
+            assert_eq!(precision.format(&test.input), test.expected.map(str::to_owned), "TC{} failed", index);
+        }
+    }
+}
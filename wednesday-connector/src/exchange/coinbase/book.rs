@@ -0,0 +1,326 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use wednesday_model::{
+    deserialization,
+    enums::BookSide,
+    error::DataError,
+    events::MarketEvent,
+    identifiers::Identifier,
+    instruments::Instrument,
+    orderbook::{Level, OrderBook, OrderBookSide},
+};
+
+use crate::{
+    exchange::connector::Connector,
+    protocol::http::websocket::WsMessage,
+    subscriber::subscription::{kind::OrderBooksL2, ExchangeSubscription, Map},
+    transformer::{iterator::MarketIter, ExchangeTransformer, Transformer},
+};
+
+use super::{
+    channel::CoinbaseChannel,
+    message::{CoinbaseMessage, CoinbaseSequencer},
+    Coinbase,
+};
+
+/// [`Coinbase`] "l2_data" event.
+///
+/// Note:
+/// The first event after subscribing is a "snapshot" of the entire book, every following event
+/// is an "update" containing the new quantity at each changed price level (zero removes it).
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/docs/ws-channels#level2-channel>
+/// ```json
+/// {
+///     "type": "snapshot",
+///     "product_id": "BTC-USD",
+///     "updates": [
+///         {
+///             "side": "bid",
+///             "event_time": "1970-01-01T00:00:00Z",
+///             "price_level": "21921.73",
+///             "new_quantity": "0.06317902"
+///         },
+///         {
+///             "side": "offer",
+///             "event_time": "1970-01-01T00:00:00Z",
+///             "price_level": "21921.74",
+///             "new_quantity": "0.1"
+///         }
+///     ]
+/// }
+/// ```
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct CoinbaseL2Event {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub product_id: String,
+    pub updates: Vec<CoinbaseL2Update>,
+}
+
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct CoinbaseL2Update {
+    pub side: BookSide,
+    pub event_time: DateTime<Utc>,
+    #[serde(rename = "price_level", deserialize_with = "deserialization::de_str")]
+    pub price: f64,
+    #[serde(rename = "new_quantity", deserialize_with = "deserialization::de_str")]
+    pub amount: f64,
+}
+
+impl From<&CoinbaseL2Update> for Level {
+    fn from(update: &CoinbaseL2Update) -> Self {
+        Level::new(update.price, update.amount)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct InstrumentBook {
+    pub instrument: Instrument,
+    pub book: OrderBook,
+}
+
+/// Stateful [`Transformer`] that validates the connection "sequence_num" of every
+/// [`CoinbaseMessage`] before applying "l2_data" snapshots & updates to the associated
+/// instrument [`OrderBook`].
+///
+/// The "sequence_num" is shared by every product on the connection, so it cannot be validated
+/// by a per instrument [`OrderBookUpdater`](crate::transformer::updater::OrderBookUpdater).
+#[derive(Clone, PartialEq, Debug)]
+pub struct CoinbaseBookTransformer {
+    pub book_map: Map<InstrumentBook>,
+    pub sequencer: CoinbaseSequencer,
+}
+
+impl Transformer for CoinbaseBookTransformer {
+    type Error = DataError;
+    type Input = CoinbaseMessage;
+    type Output = MarketEvent<OrderBook>;
+    type OutputIter = Vec<Result<Self::Output, Self::Error>>;
+    type Pong = ();
+
+    fn transform(&mut self, input: Self::Input) -> Self::OutputIter {
+        if let Err(error) = self.sequencer.validate(input.sequence_num()) {
+            return vec![Err(error)];
+        }
+
+        let payload = match input {
+            CoinbaseMessage::L2Data(payload) => payload,
+            _ => return vec![],
+        };
+
+        let mut output = Vec::with_capacity(payload.events.len());
+        for event in payload.events {
+            let subscription_id = ExchangeSubscription::from((CoinbaseChannel::ORDER_BOOK_L2, event.product_id.as_str())).id();
+            let InstrumentBook { instrument, book } = match self.book_map.find_mut(&subscription_id) {
+                Ok(instrument_book) => instrument_book,
+                Err(unidentifiable) => {
+                    output.push(Err(DataError::Socket(unidentifiable)));
+                    continue;
+                },
+            };
+
+            let (bids, asks): (Vec<_>, Vec<_>) = event.updates.iter().partition(|update| update.side == BookSide::Bid);
+
+            if event.kind == "snapshot" {
+                book.bids = OrderBookSide::new(BookSide::Bid, bids);
+                book.asks = OrderBookSide::new(BookSide::Ask, asks);
+            } else {
+                book.bids.upsert(bids);
+                book.asks.upsert(asks);
+            }
+            book.last_update_ts = payload.timestamp;
+
+            output.extend(MarketIter::<OrderBook>::from((Coinbase::ID, instrument.clone(), book.snapshot())).0);
+        }
+
+        output
+    }
+}
+
+#[async_trait]
+impl ExchangeTransformer<Coinbase, OrderBooksL2> for CoinbaseBookTransformer {
+    async fn new(_: mpsc::UnboundedSender<WsMessage>, instrument_map: Map<Instrument>) -> Result<Self, DataError> {
+        // Coinbase pushes a full snapshot as the first "l2_data" event after subscribing, so no
+        // REST snapshot is required to initialise the books
+        let book_map = instrument_map
+            .0
+            .into_iter()
+            .map(|(subscription_id, instrument)| {
+                (
+                    subscription_id,
+                    InstrumentBook {
+                        instrument,
+                        book: OrderBook::default(),
+                    },
+                )
+            })
+            .collect();
+
+        Ok(Self {
+            book_map,
+            sequencer: CoinbaseSequencer::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wednesday_model::instruments::InstrumentKind;
+
+    use super::*;
+
+    mod de {
+        use wednesday_model::error::SocketError;
+
+        use super::*;
+
+        #[test]
+        fn test_coinbase_message_l2_data() {
+            struct TestCase {
+                input: &'static str,
+                expected: Result<Vec<CoinbaseL2Event>, SocketError>,
+            }
+
+            let tests = vec![
+                // TC0: input l2_data snapshot is deserialised
+                TestCase {
+                    input: r#"
+                    {
+                        "channel": "l2_data",
+                        "client_id": "",
+                        "timestamp": "2023-02-09T20:32:50.714964855Z",
+                        "sequence_num": 0,
+                        "events": [
+                            {
+                                "type": "snapshot",
+                                "product_id": "BTC-USD",
+                                "updates": [
+                                    {"side": "bid", "event_time": "1970-01-01T00:00:00Z", "price_level": "21921.73", "new_quantity": "0.06317902"},
+                                    {"side": "offer", "event_time": "1970-01-01T00:00:00Z", "price_level": "21921.74", "new_quantity": "0.1"}
+                                ]
+                            }
+                        ]
+                    }
+                    "#,
+                    expected: Ok(vec![CoinbaseL2Event {
+                        kind: "snapshot".to_string(),
+                        product_id: "BTC-USD".to_string(),
+                        updates: vec![
+                            CoinbaseL2Update {
+                                side: BookSide::Bid,
+                                event_time: "1970-01-01T00:00:00Z".parse().unwrap(),
+                                price: 21921.73,
+                                amount: 0.06317902,
+                            },
+                            CoinbaseL2Update {
+                                side: BookSide::Ask,
+                                event_time: "1970-01-01T00:00:00Z".parse().unwrap(),
+                                price: 21921.74,
+                                amount: 0.1,
+                            },
+                        ],
+                    }]),
+                },
+                // TC1: input l2_data w/ unknown side is invalid
+                TestCase {
+                    input: r#"
+                    {
+                        "channel": "l2_data",
+                        "client_id": "",
+                        "timestamp": "2023-02-09T20:32:50.714964855Z",
+                        "sequence_num": 0,
+                        "events": [
+                            {
+                                "type": "update",
+                                "product_id": "BTC-USD",
+                                "updates": [
+                                    {"side": "unknown", "event_time": "2023-02-09T20:32:50.714964855Z", "price_level": "21921.73", "new_quantity": "0"}
+                                ]
+                            }
+                        ]
+                    }
+                    "#,
+                    expected: Err(SocketError::Unsupported {
+                        entity: "",
+                        item: "".to_string(),
+                    }),
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = serde_json::from_str::<CoinbaseMessage>(test.input);
+                match (actual, test.expected) {
+                    (Ok(CoinbaseMessage::L2Data(actual)), Ok(expected)) => {
+                        assert_eq!(actual.events, expected, "TC{} failed", index)
+                    },
+                    (Err(_), Err(_)) => {
+                        // Test passed
+                    },
+                    (actual, expected) => {
+                        // Test failed
+                        panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                    },
+                }
+            }
+        }
+    }
+
+    fn l2_message(sequence_num: u64, kind: &str, updates: &[(&str, &str, &str)]) -> CoinbaseMessage {
+        let updates = updates
+            .iter()
+            .map(|(side, price, amount)| {
+                format!(r#"{{"side":"{side}","event_time":"2023-02-09T20:32:50Z","price_level":"{price}","new_quantity":"{amount}"}}"#)
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        serde_json::from_str(&format!(
+            r#"{{"channel":"l2_data","client_id":"","timestamp":"2023-02-09T20:32:50Z","sequence_num":{sequence_num},"events":[{{"type":"{kind}","product_id":"BTC-USD","updates":[{updates}]}}]}}"#
+        ))
+        .unwrap()
+    }
+
+    fn transformer() -> CoinbaseBookTransformer {
+        CoinbaseBookTransformer {
+            book_map: Map::from_iter([(
+                ExchangeSubscription::from((CoinbaseChannel::ORDER_BOOK_L2, "BTC-USD")).id(),
+                InstrumentBook {
+                    instrument: Instrument::from(("btc", "usd", InstrumentKind::CryptoSpot)),
+                    book: OrderBook::default(),
+                },
+            )]),
+            sequencer: CoinbaseSequencer::default(),
+        }
+    }
+
+    #[test]
+    fn test_coinbase_book_transformer() {
+        let mut transformer = transformer();
+
+        // Snapshot initialises the book
+        let output = transformer.transform(l2_message(3, "snapshot", &[("bid", "100", "1"), ("bid", "99", "2"), ("offer", "101", "3")]));
+        let book = output.into_iter().next().unwrap().unwrap().kind;
+        assert_eq!(book.bids.levels(), vec![Level::new(100.0, 1.0), Level::new(99.0, 2.0)]);
+        assert_eq!(book.asks.levels(), vec![Level::new(101.0, 3.0)]);
+
+        // Update removes, replaces & inserts levels
+        let output = transformer.transform(l2_message(4, "update", &[("bid", "100", "0"), ("bid", "99", "5"), ("offer", "102", "1")]));
+        let book = output.into_iter().next().unwrap().unwrap().kind;
+        assert_eq!(book.bids.levels(), vec![Level::new(99.0, 5.0)]);
+        assert_eq!(book.asks.levels(), vec![Level::new(101.0, 3.0), Level::new(102.0, 1.0)]);
+
+        // Sequence gap is terminal
+        let output = transformer.transform(l2_message(6, "update", &[("bid", "99", "1")]));
+        assert!(matches!(
+            output.as_slice(),
+            [Err(DataError::InvalidSequence {
+                prev_last_update_id: 4,
+                first_update_id: 6
+            })]
+        ));
+    }
+}
@@ -0,0 +1,44 @@
+use wednesday_model::identifiers::Identifier;
+
+use crate::subscriber::subscription::{
+    kind::{OrderBooksL2, PublicTrades},
+    Subscription,
+};
+
+use super::Coinbase;
+
+/// Type that defines how to translate a [`Subscription`] into a [`Coinbase`] channel to be
+/// subscribed to.
+///
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/docs/ws-channels>
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct CoinbaseChannel(pub &'static str);
+
+impl CoinbaseChannel {
+    /// [`Coinbase`] real-time trades channel.
+    pub const MARKET_TRADES: Self = Self("market_trades");
+    /// [`Coinbase`] L2 channel, initial snapshot followed by incremental updates. Messages are
+    /// received on the "l2_data" channel.
+    pub const ORDER_BOOK_L2: Self = Self("level2");
+    /// [`Coinbase`] heartbeats channel, keeps the connection open when subscribed markets are
+    /// illiquid.
+    pub const HEARTBEATS: Self = Self("heartbeats");
+}
+
+impl Identifier<CoinbaseChannel> for Subscription<Coinbase, PublicTrades> {
+    fn id(&self) -> CoinbaseChannel {
+        CoinbaseChannel::MARKET_TRADES
+    }
+}
+
+impl Identifier<CoinbaseChannel> for Subscription<Coinbase, OrderBooksL2> {
+    fn id(&self) -> CoinbaseChannel {
+        CoinbaseChannel::ORDER_BOOK_L2
+    }
+}
+
+impl AsRef<str> for CoinbaseChannel {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use wednesday_model::{identifiers::Identifier, instruments::Symbol};
+
+use crate::subscriber::subscription::Subscription;
+
+use super::Coinbase;
+
+/// Type that defines how to translate a [`Subscription`] into a [`Coinbase`] product id that can
+/// be subscribed to.
+///
+/// eg/ "BTC-USD"
+///
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/docs/ws-overview>
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct CoinbaseMarket(pub String);
+
+impl<Kind> Identifier<CoinbaseMarket> for Subscription<Coinbase, Kind> {
+    fn id(&self) -> CoinbaseMarket {
+        CoinbaseMarket::new(&self.instrument.base_currency, &self.instrument.quote_currency)
+    }
+}
+
+impl CoinbaseMarket {
+    pub fn new(base_currency: &Symbol, quote_currency: &Symbol) -> Self {
+        Self(format!("{}-{}", base_currency, quote_currency).to_uppercase())
+    }
+}
+
+impl AsRef<str> for CoinbaseMarket {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
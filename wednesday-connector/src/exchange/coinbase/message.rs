@@ -0,0 +1,146 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use wednesday_model::error::DataError;
+
+use super::{book::CoinbaseL2Event, subscription::CoinbaseSubscriptions, trade::CoinbaseTradesEvent};
+
+/// [`Coinbase`](super::Coinbase) WebSocket message, tagged by the "channel" it was received on.
+///
+/// Every message received on a connection carries a "sequence_num" that increments by one,
+/// regardless of channel, so all of them must be consumed to detect gaps.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/docs/ws-channels>
+/// #### Heartbeats
+/// ```json
+/// {
+///     "channel": "heartbeats",
+///     "client_id": "",
+///     "timestamp": "2023-06-23T20:31:26.122969572Z",
+///     "sequence_num": 0,
+///     "events": [{"current_time": "2023-06-23 20:31:56.121961769 +0000 UTC m=+91717.525857105", "heartbeat_counter": 3049}]
+/// }
+/// ```
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(tag = "channel", rename_all = "snake_case")]
+pub enum CoinbaseMessage {
+    MarketTrades(CoinbasePayload<CoinbaseTradesEvent>),
+    L2Data(CoinbasePayload<CoinbaseL2Event>),
+    Heartbeats(CoinbasePayload<CoinbaseHeartbeat>),
+    Subscriptions(CoinbasePayload<CoinbaseSubscriptions>),
+}
+
+impl CoinbaseMessage {
+    pub fn sequence_num(&self) -> u64 {
+        match self {
+            CoinbaseMessage::MarketTrades(payload) => payload.sequence_num,
+            CoinbaseMessage::L2Data(payload) => payload.sequence_num,
+            CoinbaseMessage::Heartbeats(payload) => payload.sequence_num,
+            CoinbaseMessage::Subscriptions(payload) => payload.sequence_num,
+        }
+    }
+}
+
+/// Generic [`Coinbase`](super::Coinbase) message envelope.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct CoinbasePayload<T> {
+    pub timestamp: DateTime<Utc>,
+    pub sequence_num: u64,
+    pub events: Vec<T>,
+}
+
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct CoinbaseHeartbeat {
+    pub current_time: String,
+    pub heartbeat_counter: u64,
+}
+
+/// Validates that the "sequence_num" of each [`CoinbaseMessage`] follows on from the last one
+/// received on the connection.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct CoinbaseSequencer {
+    pub last_sequence_num: Option<u64>,
+}
+
+impl CoinbaseSequencer {
+    pub fn validate(&mut self, sequence_num: u64) -> Result<(), DataError> {
+        match self.last_sequence_num {
+            Some(last_sequence_num) if sequence_num != last_sequence_num + 1 => Err(DataError::InvalidSequence {
+                prev_last_update_id: last_sequence_num,
+                first_update_id: sequence_num,
+            }),
+            _ => {
+                self.last_sequence_num = Some(sequence_num);
+                Ok(())
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use wednesday_model::error::SocketError;
+
+        use super::*;
+
+        #[test]
+        fn test_coinbase_message_heartbeats() {
+            let input = r#"
+            {
+                "channel": "heartbeats",
+                "client_id": "",
+                "timestamp": "2023-06-23T20:31:26.122969572Z",
+                "sequence_num": 7,
+                "events": [{"current_time": "2023-06-23 20:31:56.121961769 +0000 UTC m=+91717.525857105", "heartbeat_counter": 3049}]
+            }
+            "#;
+
+            let actual = serde_json::from_str::<CoinbaseMessage>(input).map_err(|error| SocketError::DeserializingJson {
+                error: error.to_string(),
+                payload: input.to_string(),
+            });
+
+            match actual {
+                Ok(CoinbaseMessage::Heartbeats(payload)) => {
+                    assert_eq!(payload.sequence_num, 7);
+                    assert_eq!(payload.events[0].heartbeat_counter, 3049);
+                },
+                other => panic!("unexpected CoinbaseMessage: {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_coinbase_sequencer_validate() {
+        struct TestCase {
+            input: u64,
+            expected_ok: bool,
+        }
+
+        let mut sequencer = CoinbaseSequencer::default();
+
+        let tests = vec![
+            // TC0: first sequence_num is always valid
+            TestCase { input: 5, expected_ok: true },
+            // TC1: sequence_num follows on from the last
+            TestCase { input: 6, expected_ok: true },
+            // TC2: sequence_num gap
+            TestCase { input: 8, expected_ok: false },
+            // TC3: sequence_num duplicate of the last valid
+            TestCase { input: 6, expected_ok: false },
+            // TC4: sequence_num follows on from the last valid
+            TestCase { input: 7, expected_ok: true },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = sequencer.validate(test.input);
+            assert_eq!(actual.is_ok(), test.expected_ok, "TC{} failed", index);
+            if let Err(error) = actual {
+                assert!(error.is_terminal(), "TC{} failed", index);
+            }
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use tracing::error;
+use url::Url;
+use wednesday_macro::{DeExchange, SerExchange};
+use wednesday_model::{error::SocketError, identifiers::ExchangeId, instruments::Instrument};
+
+use crate::{
+    protocol::http::websocket::{PingInterval, WsMessage},
+    stream::{protocol::ws_stream::ExchangeWsStream, selector::StreamSelector},
+    subscriber::{
+        protocol::websocket::WsSubscriber,
+        subscription::{
+            kind::{OrderBooksL2, PublicTrades},
+            ExchangeSubscription, Map,
+        },
+        validator::WsSubscriptionValidator,
+    },
+};
+
+use self::{
+    book::CoinbaseBookTransformer,
+    channel::CoinbaseChannel,
+    market::CoinbaseMarket,
+    signer::{CoinbaseCredentials, CoinbaseSigner, CoinbaseSubscriptionAuth},
+    subscription::CoinbaseSubscriptionResponse,
+    trade::CoinbaseTradesTransformer,
+};
+
+use super::connector::Connector;
+
+pub mod book;
+pub mod channel;
+pub mod market;
+pub mod message;
+pub mod signer;
+pub mod subscription;
+pub mod trade;
+
+pub const WEBSOCKET_BASE_URL_COINBASE: &str = "wss://advanced-trade-ws.coinbase.com";
+
+/// [`Coinbase`] Advanced Trade market data connector.
+///
+/// Subscriptions are signed when [`CoinbaseCredentials`] are found in the environment, and every
+/// connection is also subscribed to the "heartbeats" channel so it is not closed when markets
+/// are illiquid.
+///
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/docs/ws-overview>
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default, DeExchange, SerExchange)]
+pub struct Coinbase;
+
+/// [`Coinbase`] subscribe request for a single channel.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
+pub struct CoinbaseSubscriptionRequest<'a> {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub product_ids: Vec<&'a str>,
+    pub channel: &'a str,
+    #[serde(flatten)]
+    pub auth: Option<CoinbaseSubscriptionAuth>,
+}
+
+impl<'a> CoinbaseSubscriptionRequest<'a> {
+    pub fn subscribe(signer: Option<&CoinbaseSigner>, channel: &'a str, product_ids: Vec<&'a str>) -> Self {
+        let auth = signer.and_then(|signer| match signer.sign_subscription(channel, &product_ids) {
+            Ok(auth) => Some(auth),
+            Err(error) => {
+                error!(exchange = %Coinbase::ID, %channel, %error, "failed to sign subscription, subscribing without authentication");
+                None
+            },
+        });
+
+        Self {
+            kind: "subscribe",
+            product_ids,
+            channel,
+            auth,
+        }
+    }
+}
+
+impl Connector for Coinbase {
+    const ID: ExchangeId = ExchangeId::Coinbase;
+    type Channel = CoinbaseChannel;
+    type Market = CoinbaseMarket;
+    type Subscriber = WsSubscriber;
+    type SubscriptionValidator = WsSubscriptionValidator;
+    type SubscriptionResponse = CoinbaseSubscriptionResponse;
+
+    fn url() -> Result<Url, SocketError> {
+        Url::parse(WEBSOCKET_BASE_URL_COINBASE).map_err(SocketError::UrlParse)
+    }
+
+    fn requests(exchange_subscriptions: Vec<ExchangeSubscription<Self::Channel, Self::Market>>) -> Vec<WsMessage> {
+        let signer = CoinbaseCredentials::from_env().map(CoinbaseSigner::new);
+
+        let mut channels = BTreeMap::<&str, Vec<&str>>::new();
+        for sub in exchange_subscriptions.iter() {
+            channels.entry(sub.channel.as_ref()).or_default().push(sub.market.as_ref());
+        }
+
+        // Heartbeats are requested last so the data channel subscription response is received
+        // first by the SubscriptionValidator
+        let heartbeats = CoinbaseSubscriptionRequest::subscribe(signer.as_ref(), CoinbaseChannel::HEARTBEATS.as_ref(), vec![]);
+
+        channels
+            .into_iter()
+            .map(|(channel, product_ids)| CoinbaseSubscriptionRequest::subscribe(signer.as_ref(), channel, product_ids))
+            .chain(std::iter::once(heartbeats))
+            .map(|request| WsMessage::Text(serde_json::to_string(&request).unwrap_or_default()))
+            .collect()
+    }
+
+    fn ping_interval() -> Option<PingInterval> {
+        None
+    }
+
+    fn expected_responses(_: &Map<Instrument>) -> usize {
+        // The heartbeats subscription response is consumed, & ignored, by the transformer
+        1
+    }
+}
+
+impl StreamSelector<PublicTrades> for Coinbase {
+    type Stream = ExchangeWsStream<CoinbaseTradesTransformer>;
+}
+
+impl StreamSelector<OrderBooksL2> for Coinbase {
+    type Stream = ExchangeWsStream<CoinbaseBookTransformer>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coinbase_requests() {
+        let requests = Coinbase::requests(vec![
+            ExchangeSubscription {
+                channel: CoinbaseChannel::ORDER_BOOK_L2,
+                market: CoinbaseMarket("BTC-USD".to_string()),
+            },
+            ExchangeSubscription {
+                channel: CoinbaseChannel::ORDER_BOOK_L2,
+                market: CoinbaseMarket("ETH-USD".to_string()),
+            },
+        ]);
+
+        let requests = requests
+            .into_iter()
+            .map(|request| match request {
+                WsMessage::Text(text) => serde_json::from_str::<serde_json::Value>(&text).unwrap(),
+                other => panic!("unexpected WsMessage: {other:?}"),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0]["type"], "subscribe");
+        assert_eq!(requests[0]["channel"], "level2");
+        assert_eq!(requests[0]["product_ids"], serde_json::json!(["BTC-USD", "ETH-USD"]));
+        assert_eq!(requests[1]["channel"], "heartbeats");
+    }
+}
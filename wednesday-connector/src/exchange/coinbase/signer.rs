@@ -0,0 +1,275 @@
+use base64::Engine;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use p256::{
+    ecdsa::{signature::Signer as _, Signature, SigningKey},
+    pkcs8::DecodePrivateKey,
+    SecretKey,
+};
+use reqwest::RequestBuilder;
+use serde::Serialize;
+use sha2::Sha256;
+use wednesday_model::error::SocketError;
+
+use crate::protocol::http::{
+    private::{
+        encoder::{Encoder, HexEncoder},
+        Signer,
+    },
+    rest::request::RestRequest,
+};
+
+/// [`Coinbase`](super::Coinbase) Advanced Trade REST API host, included in the REST JWT "uri" claim.
+pub const HTTP_HOST_COINBASE: &str = "api.coinbase.com";
+
+/// Lifetime of a [`Coinbase`](super::Coinbase) JWT in seconds.
+pub const JWT_EXPIRY_SECS_COINBASE: i64 = 120;
+
+/// [`Coinbase`](super::Coinbase) API credentials.
+///
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/docs/ws-auth>
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum CoinbaseCredentials {
+    /// Legacy API key & secret, requests are signed with HMAC-SHA256.
+    Hmac { api_key: String, secret: String },
+    /// Coinbase Developer Platform API key name & EC private key PEM, requests are authenticated
+    /// with an ES256 JWT.
+    Jwt { key_name: String, private_key: String },
+}
+
+impl CoinbaseCredentials {
+    /// Load [`CoinbaseCredentials`] from the environment, preferring the JWT credentials
+    /// (COINBASE_API_KEY_NAME & COINBASE_API_PRIVATE_KEY) over the HMAC credentials
+    /// (COINBASE_API_KEY & COINBASE_API_SECRET).
+    pub fn from_env() -> Option<Self> {
+        let var = |key: &str| std::env::var(key).ok().filter(|value| !value.is_empty());
+
+        match (var("COINBASE_API_KEY_NAME"), var("COINBASE_API_PRIVATE_KEY")) {
+            (Some(key_name), Some(private_key)) => Some(Self::Jwt {
+                key_name,
+                // PEMs stored in a single line environment variable usually have escaped newlines
+                private_key: private_key.replace("\\n", "\n"),
+            }),
+            _ => match (var("COINBASE_API_KEY"), var("COINBASE_API_SECRET")) {
+                (Some(api_key), Some(secret)) => Some(Self::Hmac { api_key, secret }),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// [`Signer`] for [`Coinbase`](super::Coinbase) REST requests & WebSocket subscriptions.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct CoinbaseSigner {
+    pub credentials: CoinbaseCredentials,
+}
+
+/// Configuration required to sign a [`Coinbase`](super::Coinbase) REST request or WebSocket
+/// subscription.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct CoinbaseSignConfig<'a> {
+    pub credentials: &'a CoinbaseCredentials,
+    pub timestamp: String,
+    /// Bytes signed with HMAC-SHA256, eg/ timestamp + method + path + body for REST requests.
+    pub prehash: String,
+    /// JWT, only generated for [`CoinbaseCredentials::Jwt`].
+    pub jwt: Option<String>,
+}
+
+/// Authentication fields added to a [`Coinbase`](super::Coinbase) subscribe request.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
+#[serde(untagged)]
+pub enum CoinbaseSubscriptionAuth {
+    Hmac { api_key: String, timestamp: String, signature: String },
+    Jwt { jwt: String },
+}
+
+impl Signer for CoinbaseSigner {
+    type Config<'a>
+        = CoinbaseSignConfig<'a>
+    where
+        Self: 'a;
+
+    fn config<'a, Request>(&'a self, request: Request, _: &RequestBuilder) -> Result<Self::Config<'a>, SocketError>
+    where
+        Request: RestRequest,
+    {
+        let timestamp = Utc::now().timestamp().to_string();
+        let method = Request::method();
+        let path = request.path();
+        let body = match request.body() {
+            Some(body) => serde_json::to_string(body).map_err(|error| SocketError::SerializingJson {
+                error: error.to_string(),
+                payload: String::new(),
+            })?,
+            None => String::new(),
+        };
+
+        let jwt = match &self.credentials {
+            CoinbaseCredentials::Jwt { .. } => Some(self.jwt(Some(format!("{method} {HTTP_HOST_COINBASE}{path}")))?),
+            CoinbaseCredentials::Hmac { .. } => None,
+        };
+
+        Ok(CoinbaseSignConfig {
+            credentials: &self.credentials,
+            prehash: format!("{timestamp}{method}{path}{body}"),
+            timestamp,
+            jwt,
+        })
+    }
+
+    fn add_bytes_to_sign<M>(mac: &mut M, config: &Self::Config<'_>)
+    where
+        M: Mac,
+    {
+        mac.update(config.prehash.as_bytes());
+    }
+
+    fn build_signed_request(config: Self::Config<'_>, builder: RequestBuilder, signature: String) -> Result<reqwest::Request, SocketError> {
+        let builder = match (config.credentials, config.jwt) {
+            (_, Some(jwt)) => builder.bearer_auth(jwt),
+            (CoinbaseCredentials::Hmac { api_key, .. }, None) => builder
+                .header("CB-ACCESS-KEY", api_key)
+                .header("CB-ACCESS-SIGN", signature)
+                .header("CB-ACCESS-TIMESTAMP", config.timestamp),
+            (CoinbaseCredentials::Jwt { .. }, None) => return Err(SocketError::Sign("missing Coinbase JWT".to_string())),
+        };
+
+        builder.build().map_err(SocketError::from)
+    }
+}
+
+impl CoinbaseSigner {
+    pub fn new(credentials: CoinbaseCredentials) -> Self {
+        Self { credentials }
+    }
+
+    /// Generate the [`CoinbaseSubscriptionAuth`] for a subscribe request to the provided channel
+    /// & product ids.
+    ///
+    /// HMAC signatures are generated from timestamp + channel + comma separated product ids.
+    pub fn sign_subscription(&self, channel: &str, product_ids: &[&str]) -> Result<CoinbaseSubscriptionAuth, SocketError> {
+        match &self.credentials {
+            CoinbaseCredentials::Hmac { api_key, secret } => {
+                let timestamp = Utc::now().timestamp().to_string();
+                let config = CoinbaseSignConfig {
+                    credentials: &self.credentials,
+                    prehash: format!("{timestamp}{channel}{}", product_ids.join(",")),
+                    timestamp,
+                    jwt: None,
+                };
+
+                let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).map_err(|error| SocketError::Sign(error.to_string()))?;
+                Self::add_bytes_to_sign(&mut mac, &config);
+                let signature = HexEncoder.encode(mac.finalize().into_bytes());
+
+                Ok(CoinbaseSubscriptionAuth::Hmac {
+                    api_key: api_key.clone(),
+                    timestamp: config.timestamp,
+                    signature,
+                })
+            },
+            CoinbaseCredentials::Jwt { .. } => Ok(CoinbaseSubscriptionAuth::Jwt { jwt: self.jwt(None)? }),
+        }
+    }
+
+    /// Generate an ES256 JWT for [`CoinbaseCredentials::Jwt`], including the "uri" claim (eg/
+    /// "GET api.coinbase.com/api/v3/brokerage/accounts") for REST requests.
+    pub fn jwt(&self, uri: Option<String>) -> Result<String, SocketError> {
+        let CoinbaseCredentials::Jwt { key_name, private_key } = &self.credentials else {
+            return Err(SocketError::Sign("JWT requires Coinbase Developer Platform credentials".to_string()));
+        };
+
+        let signing_key = SecretKey::from_sec1_pem(private_key)
+            .or_else(|_| SecretKey::from_pkcs8_pem(private_key))
+            .map(SigningKey::from)
+            .map_err(|error| SocketError::Sign(format!("invalid Coinbase private key: {error}")))?;
+
+        let now = Utc::now();
+        let header = serde_json::json!({
+            "alg": "ES256",
+            "typ": "JWT",
+            "kid": key_name,
+            "nonce": format!("{:x}", now.timestamp_nanos_opt().unwrap_or_default()),
+        });
+        let mut claims = serde_json::json!({
+            "iss": "cdp",
+            "sub": key_name,
+            "nbf": now.timestamp(),
+            "exp": now.timestamp() + JWT_EXPIRY_SECS_COINBASE,
+        });
+        if let Some(uri) = uri {
+            claims["uri"] = serde_json::Value::String(uri);
+        }
+
+        let engine = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+        let message = format!("{}.{}", engine.encode(header.to_string()), engine.encode(claims.to_string()));
+        let signature: Signature = signing_key.sign(message.as_bytes());
+
+        Ok(format!("{message}.{}", engine.encode(signature.to_bytes())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use p256::{ecdsa::signature::Verifier, pkcs8::LineEnding};
+
+    use super::*;
+
+    #[test]
+    fn test_coinbase_hmac_subscription_signature() {
+        let credentials = CoinbaseCredentials::Hmac {
+            api_key: "key".to_string(),
+            secret: "secret".to_string(),
+        };
+        let config = CoinbaseSignConfig {
+            credentials: &credentials,
+            timestamp: "1700000000".to_string(),
+            prehash: "1700000000level2BTC-USD,ETH-USD".to_string(),
+            jwt: None,
+        };
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        CoinbaseSigner::add_bytes_to_sign(&mut mac, &config);
+
+        assert_eq!(
+            HexEncoder.encode(mac.finalize().into_bytes()),
+            "7cb830b36e56312d0e21fc231d165d0054f01308e20a784e783a08ff4e4c520c"
+        );
+    }
+
+    #[test]
+    fn test_coinbase_jwt() {
+        let secret = SecretKey::from_slice(&[7u8; 32]).unwrap();
+        let signer = CoinbaseSigner::new(CoinbaseCredentials::Jwt {
+            key_name: "organizations/org/apiKeys/key".to_string(),
+            private_key: secret.to_sec1_pem(LineEnding::LF).unwrap().to_string(),
+        });
+
+        let jwt = signer.jwt(Some("GET api.coinbase.com/api/v3/brokerage/accounts".to_string())).unwrap();
+        let parts = jwt.split('.').collect::<Vec<_>>();
+        assert_eq!(parts.len(), 3);
+
+        let engine = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+        let header: serde_json::Value = serde_json::from_slice(&engine.decode(parts[0]).unwrap()).unwrap();
+        let claims: serde_json::Value = serde_json::from_slice(&engine.decode(parts[1]).unwrap()).unwrap();
+        assert_eq!(header["alg"], "ES256");
+        assert_eq!(header["kid"], "organizations/org/apiKeys/key");
+        assert_eq!(claims["iss"], "cdp");
+        assert_eq!(claims["uri"], "GET api.coinbase.com/api/v3/brokerage/accounts");
+
+        let signature = Signature::from_slice(&engine.decode(parts[2]).unwrap()).unwrap();
+        let verifying_key = SigningKey::from(secret).verifying_key().to_owned();
+        assert!(verifying_key.verify(format!("{}.{}", parts[0], parts[1]).as_bytes(), &signature).is_ok());
+    }
+
+    #[test]
+    fn test_coinbase_jwt_invalid_private_key() {
+        let signer = CoinbaseSigner::new(CoinbaseCredentials::Jwt {
+            key_name: "key".to_string(),
+            private_key: "not a pem".to_string(),
+        });
+
+        assert!(matches!(signer.sign_subscription("level2", &["BTC-USD"]), Err(SocketError::Sign(_))));
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use wednesday_model::error::SocketError;
+
+use crate::subscriber::validator::Validator;
+
+/// [`Coinbase`](super::Coinbase) WebSocket subscription response, one "subscriptions" message is
+/// received per subscribe request, listing every channel & product currently subscribed to.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/docs/ws-overview>
+/// #### Subscription Success
+/// ```json
+/// {
+///     "channel": "subscriptions",
+///     "client_id": "",
+///     "timestamp": "2023-02-09T20:32:50.714964855Z",
+///     "sequence_num": 1,
+///     "events": [{"subscriptions": {"level2": ["BTC-USD"]}}]
+/// }
+/// ```
+///
+/// #### Subscription Failure
+/// ```json
+/// {
+///     "type": "error",
+///     "message": "authentication failure"
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum CoinbaseSubscriptionResponse {
+    Subscribed {
+        channel: CoinbaseSubscriptionsChannel,
+        events: Vec<CoinbaseSubscriptions>,
+    },
+    Error {
+        #[serde(rename = "type")]
+        kind: CoinbaseErrorType,
+        message: String,
+    },
+}
+
+/// Marker for the "subscriptions" channel of a [`CoinbaseSubscriptionResponse`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CoinbaseSubscriptionsChannel {
+    Subscriptions,
+}
+
+/// Marker for the "error" type of a [`CoinbaseSubscriptionResponse`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CoinbaseErrorType {
+    Error,
+}
+
+/// Channels & products currently subscribed to, eg/ {"level2": ["BTC-USD"]}.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct CoinbaseSubscriptions {
+    pub subscriptions: HashMap<String, Vec<String>>,
+}
+
+impl Validator for CoinbaseSubscriptionResponse {
+    fn validate(self) -> Result<Self, SocketError>
+    where
+        Self: Sized,
+    {
+        match self {
+            CoinbaseSubscriptionResponse::Subscribed { .. } => Ok(self),
+            CoinbaseSubscriptionResponse::Error { message, .. } => Err(SocketError::Subscribe(format!("received failure subscription response: {message}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use super::*;
+
+        #[test]
+        fn test_coinbase_sub_response() {
+            struct TestCase {
+                input: &'static str,
+                expected: Result<CoinbaseSubscriptionResponse, SocketError>,
+            }
+
+            let cases = vec![
+                TestCase {
+                    // TC0: input response is Subscribed
+                    input: r#"
+                    {
+                        "channel": "subscriptions",
+                        "client_id": "",
+                        "timestamp": "2023-02-09T20:32:50.714964855Z",
+                        "sequence_num": 1,
+                        "events": [{"subscriptions": {"level2": ["BTC-USD"]}}]
+                    }
+                    "#,
+                    expected: Ok(CoinbaseSubscriptionResponse::Subscribed {
+                        channel: CoinbaseSubscriptionsChannel::Subscriptions,
+                        events: vec![CoinbaseSubscriptions {
+                            subscriptions: HashMap::from([("level2".to_string(), vec!["BTC-USD".to_string()])]),
+                        }],
+                    }),
+                },
+                TestCase {
+                    // TC1: input response is failed subscription
+                    input: r#"{"type": "error", "message": "authentication failure"}"#,
+                    expected: Ok(CoinbaseSubscriptionResponse::Error {
+                        kind: CoinbaseErrorType::Error,
+                        message: "authentication failure".to_string(),
+                    }),
+                },
+                TestCase {
+                    // TC2: input is a market data message
+                    input: r#"
+                    {
+                        "channel": "market_trades",
+                        "client_id": "",
+                        "timestamp": "2023-02-09T20:19:35.39625135Z",
+                        "sequence_num": 0,
+                        "events": [{"type": "snapshot", "trades": []}]
+                    }
+                    "#,
+                    expected: Err(SocketError::Subscribe("".to_string())),
+                },
+            ];
+
+            for (index, test) in cases.into_iter().enumerate() {
+                let actual = serde_json::from_str::<CoinbaseSubscriptionResponse>(test.input);
+                match (actual, test.expected) {
+                    (Ok(actual), Ok(expected)) => {
+                        assert_eq!(actual, expected, "TC{} failed", index)
+                    },
+                    (Err(_), Err(_)) => {
+                        // Test passed
+                    },
+                    (actual, expected) => {
+                        // Test failed
+                        panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                    },
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_coinbase_sub_response() {
+        struct TestCase {
+            input_response: CoinbaseSubscriptionResponse,
+            is_valid: bool,
+        }
+
+        let cases = vec![
+            TestCase {
+                // TC0: input response is successful subscription
+                input_response: CoinbaseSubscriptionResponse::Subscribed {
+                    channel: CoinbaseSubscriptionsChannel::Subscriptions,
+                    events: vec![],
+                },
+                is_valid: true,
+            },
+            TestCase {
+                // TC1: input response is failed subscription
+                input_response: CoinbaseSubscriptionResponse::Error {
+                    kind: CoinbaseErrorType::Error,
+                    message: "authentication failure".to_string(),
+                },
+                is_valid: false,
+            },
+        ];
+
+        for (index, test) in cases.into_iter().enumerate() {
+            let actual = test.input_response.validate().is_ok();
+            assert_eq!(actual, test.is_valid, "TestCase {} failed", index);
+        }
+    }
+}
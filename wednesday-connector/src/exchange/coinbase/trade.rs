@@ -0,0 +1,239 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use wednesday_model::{
+    deserialization,
+    enums::AggressorSide,
+    error::DataError,
+    events::MarketEvent,
+    identifiers::{Exchange, Identifier},
+    instruments::Instrument,
+    trade::PublicTrade,
+};
+
+use crate::{
+    exchange::connector::Connector,
+    protocol::http::websocket::WsMessage,
+    subscriber::subscription::{kind::PublicTrades, ExchangeSubscription, Map},
+    transformer::{ExchangeTransformer, Transformer},
+};
+
+use super::{
+    channel::CoinbaseChannel,
+    message::{CoinbaseMessage, CoinbaseSequencer},
+    Coinbase,
+};
+
+/// [`Coinbase`] "market_trades" event.
+///
+/// Note:
+/// The first event after subscribing is a "snapshot" of recent historic trades, every following
+/// event is an "update" containing new trades.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/docs/ws-channels#market-trades-channel>
+/// ```json
+/// {
+///     "type": "update",
+///     "trades": [
+///         {
+///             "trade_id": "000000000",
+///             "product_id": "ETH-USD",
+///             "price": "1260.01",
+///             "size": "0.3",
+///             "side": "BUY",
+///             "time": "2019-08-14T20:42:27.265Z"
+///         }
+///     ]
+/// }
+/// ```
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct CoinbaseTradesEvent {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub trades: Vec<CoinbaseTrade>,
+}
+
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct CoinbaseTrade {
+    #[serde(rename = "trade_id")]
+    pub id: String,
+    pub product_id: String,
+    #[serde(deserialize_with = "deserialization::de_str")]
+    pub price: f64,
+    #[serde(rename = "size", deserialize_with = "deserialization::de_str")]
+    pub amount: f64,
+    pub side: AggressorSide,
+    pub time: DateTime<Utc>,
+}
+
+impl CoinbaseTrade {
+    fn to_market_event(&self, instrument: Instrument) -> MarketEvent<PublicTrade> {
+        MarketEvent {
+            exchange_ts: self.time,
+            local_ts: Utc::now(),
+            exchange: Exchange::from(Coinbase::ID),
+            instrument,
+            kind: PublicTrade {
+                id: self.id.clone(),
+                price: self.price,
+                quantity: self.amount,
+                aggressor_side: self.side,
+            },
+        }
+    }
+}
+
+/// Stateful [`Transformer`] that validates the connection "sequence_num" of every
+/// [`CoinbaseMessage`] before generating [`PublicTrade`]s from "market_trades" updates.
+#[derive(Clone, PartialEq, Debug)]
+pub struct CoinbaseTradesTransformer {
+    pub instrument_map: Map<Instrument>,
+    pub sequencer: CoinbaseSequencer,
+}
+
+impl Transformer for CoinbaseTradesTransformer {
+    type Error = DataError;
+    type Input = CoinbaseMessage;
+    type Output = MarketEvent<PublicTrade>;
+    type OutputIter = Vec<Result<Self::Output, Self::Error>>;
+    type Pong = ();
+
+    fn transform(&mut self, input: Self::Input) -> Self::OutputIter {
+        if let Err(error) = self.sequencer.validate(input.sequence_num()) {
+            return vec![Err(error)];
+        }
+
+        let payload = match input {
+            CoinbaseMessage::MarketTrades(payload) => payload,
+            _ => return vec![],
+        };
+
+        payload
+            .events
+            .iter()
+            // Snapshot contains recent historic trades that were not received live
+            .filter(|event| event.kind == "update")
+            .flat_map(|event| event.trades.iter())
+            .map(|trade| {
+                let subscription_id = ExchangeSubscription::from((CoinbaseChannel::MARKET_TRADES, trade.product_id.as_str())).id();
+                self.instrument_map
+                    .find(&subscription_id)
+                    .map(|instrument| trade.to_market_event(instrument))
+                    .map_err(DataError::Socket)
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl ExchangeTransformer<Coinbase, PublicTrades> for CoinbaseTradesTransformer {
+    async fn new(_: mpsc::UnboundedSender<WsMessage>, instrument_map: Map<Instrument>) -> Result<Self, DataError> {
+        Ok(Self {
+            instrument_map,
+            sequencer: CoinbaseSequencer::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use wednesday_model::error::SocketError;
+
+        use super::*;
+
+        #[test]
+        fn test_coinbase_message_market_trades() {
+            struct TestCase {
+                input: &'static str,
+                expected: Result<Vec<CoinbaseTradesEvent>, SocketError>,
+            }
+
+            let tests = vec![
+                // TC0: input market_trades update is deserialised
+                TestCase {
+                    input: r#"
+                    {
+                        "channel": "market_trades",
+                        "client_id": "",
+                        "timestamp": "2023-02-09T20:19:35.39625135Z",
+                        "sequence_num": 0,
+                        "events": [
+                            {
+                                "type": "update",
+                                "trades": [
+                                    {
+                                        "trade_id": "000000000",
+                                        "product_id": "ETH-USD",
+                                        "price": "1260.01",
+                                        "size": "0.3",
+                                        "side": "BUY",
+                                        "time": "2019-08-14T20:42:27.265Z"
+                                    }
+                                ]
+                            }
+                        ]
+                    }
+                    "#,
+                    expected: Ok(vec![CoinbaseTradesEvent {
+                        kind: "update".to_string(),
+                        trades: vec![CoinbaseTrade {
+                            id: "000000000".to_string(),
+                            product_id: "ETH-USD".to_string(),
+                            price: 1260.01,
+                            amount: 0.3,
+                            side: AggressorSide::Buy,
+                            time: "2019-08-14T20:42:27.265Z".parse().unwrap(),
+                        }],
+                    }]),
+                },
+                // TC1: input market_trades w/ malformed size is invalid
+                TestCase {
+                    input: r#"
+                    {
+                        "channel": "market_trades",
+                        "client_id": "",
+                        "timestamp": "2023-02-09T20:19:35.39625135Z",
+                        "sequence_num": 0,
+                        "events": [
+                            {
+                                "type": "update",
+                                "trades": [
+                                    {
+                                        "trade_id": "1", "product_id": "ETH-USD", "price": "1260.01",
+                                        "size": "unknown", "side": "SELL", "time": "2019-08-14T20:42:27.265Z"
+                                    }
+                                ]
+                            }
+                        ]
+                    }
+                    "#,
+                    expected: Err(SocketError::Unsupported {
+                        entity: "",
+                        item: "".to_string(),
+                    }),
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = serde_json::from_str::<CoinbaseMessage>(test.input);
+                match (actual, test.expected) {
+                    (Ok(CoinbaseMessage::MarketTrades(actual)), Ok(expected)) => {
+                        assert_eq!(actual.events, expected, "TC{} failed", index)
+                    },
+                    (Err(_), Err(_)) => {
+                        // Test passed
+                    },
+                    (actual, expected) => {
+                        // Test failed
+                        panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                    },
+                }
+            }
+        }
+    }
+}
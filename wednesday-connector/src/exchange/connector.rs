@@ -5,6 +5,7 @@ use wednesday_model::{error::SocketError, identifiers::ExchangeId, instruments::
 
 use crate::{
     protocol::http::websocket::{PingInterval, WsMessage},
+    stream::rate_limit::RateLimit,
     subscriber::{
         subscription::{ExchangeSubscription, Map},
         validator::{SubscriptionValidator, Validator},
@@ -27,6 +28,13 @@ where
 
     fn url() -> Result<Url, SocketError>;
 
+    /// Url of the connection `leg` of redundant connections to the exchange (see
+    /// [`Redundancy`](crate::stream::redundancy::Redundancy)), which allows the legs to connect
+    /// to different endpoints. Every leg connects to [`Connector::url`] by default.
+    fn leg_url(_leg: usize) -> Result<Url, SocketError> {
+        Self::url()
+    }
+
     fn ping_interval() -> Option<PingInterval> {
         None
     }
@@ -39,7 +47,39 @@ where
         DEFAULT_SUBSCRIPTION_TIMEOUT
     }
 
+    /// Maximum number of subscriptions the exchange serves over a single WebSocket connection,
+    /// beyond which the [`StreamBuilder`](crate::stream::builder::StreamBuilder) shards the
+    /// subscriptions across multiple connections.
+    fn max_subscriptions_per_connection() -> Option<usize> {
+        None
+    }
+
+    /// Maximum number of subscriptions the exchange accepts in a single subscription request,
+    /// beyond which the subscriptions are split across multiple [`Connector::requests`].
+    fn max_subscriptions_per_request() -> Option<usize> {
+        None
+    }
+
     fn requests(exchange_subscriptions: Vec<ExchangeSubscription<Self::Channel, Self::Market>>) -> Vec<WsMessage>;
+
+    /// Whether subscriptions can be added to, & removed from, a running stream over its existing
+    /// WebSocket connection. Exchanges whose [`SubscriptionValidator`] re-keys the subscriptions
+    /// (eg/ with exchange assigned channel ids) cannot support this.
+    fn dynamic_subscriptions() -> bool {
+        false
+    }
+
+    /// Payloads to unsubscribe from the provided exchange subscriptions of a running stream, only
+    /// used if [`Connector::dynamic_subscriptions`] is supported.
+    fn unsubscribe_requests(_exchange_subscriptions: Vec<ExchangeSubscription<Self::Channel, Self::Market>>) -> Vec<WsMessage> {
+        vec![]
+    }
+
+    /// [`RateLimit`] of the messages the exchange accepts over a single connection, which the
+    /// (un)subscription requests sent to a running stream are delayed to respect.
+    fn message_rate_limit() -> Option<RateLimit> {
+        None
+    }
 }
 
 pub trait ExchangeServer: Default + Debug + Clone + Send {
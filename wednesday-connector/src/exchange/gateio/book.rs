@@ -0,0 +1,168 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use wednesday_model::{
+    enums::BookSide,
+    error::DataError,
+    orderbook::{Level, OrderBook, OrderBookSide},
+};
+
+/// [`Gateio`](super::Gateio) REST L2 order book snapshot, requested with "with_id=true" so the
+/// "order_book_update" events can be sequenced against it.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.gate.io/docs/developers/apiv4/en/#retrieve-order-book>
+/// #### Spot
+/// ```json
+/// {
+///     "id": 123456,
+///     "current": 1623898993123,
+///     "update": 1623898993121,
+///     "asks": [["1.52", "1.151"]],
+///     "bids": [["1.17", "201.863"]]
+/// }
+/// ```
+///
+/// #### Futures
+/// ```json
+/// {
+///     "id": 123456,
+///     "current": 1623898993.123,
+///     "update": 1623898993.121,
+///     "asks": [{"p": "1.52", "s": 100}],
+///     "bids": [{"p": "1.17", "s": 150}]
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct GateioOrderBookL2Snapshot<GateioLevel> {
+    #[serde(rename = "id")]
+    pub last_update_id: u64,
+    pub bids: Vec<GateioLevel>,
+    pub asks: Vec<GateioLevel>,
+}
+
+impl<GateioLevel> From<GateioOrderBookL2Snapshot<GateioLevel>> for OrderBook
+where
+    GateioLevel: Into<Level>,
+{
+    fn from(snapshot: GateioOrderBookL2Snapshot<GateioLevel>) -> Self {
+        Self {
+            last_update_ts: Utc::now(),
+            update_id: None,
+            bids: OrderBookSide::new(BookSide::Bid, snapshot.bids),
+            asks: OrderBookSide::new(BookSide::Ask, snapshot.asks),
+        }
+    }
+}
+
+/// Gateio: How To Maintain A Local OrderBook
+///
+/// 1. Subscribe to the "order_book_update" channel & buffer the events received.
+/// 2. Get a REST snapshot with "with_id=true", referred to as the baseID.
+/// 3. Drop any event where u < baseID+1.
+/// 4. The first processed event should have U <= baseID+1 AND u >= baseID+1.
+/// 5. Each following event's U should be equal to the previous event's u+1, otherwise
+///    initialise the process from step 2.
+/// 6. The data in each event is the absolute amount for a price level, zero removes it.
+///
+/// Spot & futures books are maintained the same way.
+///
+/// See docs: <https://www.gate.io/docs/developers/apiv4/ws/en/#changed-order-book-levels>
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Deserialize, Serialize)]
+pub struct GateioBookSequencer {
+    pub updates_processed: u64,
+    pub last_update_id: u64,
+}
+
+impl GateioBookSequencer {
+    /// Construct a new [`GateioBookSequencer`] using the provided baseID of a REST snapshot.
+    pub fn new(last_update_id: u64) -> Self {
+        Self {
+            updates_processed: 0,
+            last_update_id,
+        }
+    }
+
+    /// Validate the first (U) & last (u) update ids of the next "order_book_update" event.
+    ///
+    /// Returns `Ok(false)` if the event precedes the snapshot and should be dropped.
+    pub fn validate(&mut self, first_update_id: u64, last_update_id: u64) -> Result<bool, DataError> {
+        // 3. Drop any event where u < baseID+1
+        if last_update_id <= self.last_update_id {
+            return Ok(false);
+        }
+
+        let expected_next_id = self.last_update_id + 1;
+        let is_valid = if self.updates_processed == 0 {
+            first_update_id <= expected_next_id
+        } else {
+            first_update_id == expected_next_id
+        };
+
+        if !is_valid {
+            return Err(DataError::InvalidSequence {
+                prev_last_update_id: self.last_update_id,
+                first_update_id,
+            });
+        }
+
+        self.updates_processed += 1;
+        self.last_update_id = last_update_id;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gateio_book_sequencer() {
+        struct TestCase {
+            input: (u64, u64),
+            expected: Result<bool, DataError>,
+        }
+
+        let mut sequencer = GateioBookSequencer::new(100);
+
+        let tests = vec![
+            // TC0: event preceding the snapshot is dropped
+            TestCase {
+                input: (90, 100),
+                expected: Ok(false),
+            },
+            // TC1: first event must overlap the snapshot id
+            TestCase {
+                input: (95, 105),
+                expected: Ok(true),
+            },
+            // TC2: next event follows the previous event
+            TestCase {
+                input: (106, 110),
+                expected: Ok(true),
+            },
+            // TC3: gap between events is invalid
+            TestCase {
+                input: (112, 115),
+                expected: Err(DataError::InvalidSequence {
+                    prev_last_update_id: 110,
+                    first_update_id: 112,
+                }),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = sequencer.validate(test.input.0, test.input.1);
+            match (actual, test.expected) {
+                (Ok(actual), Ok(expected)) => assert_eq!(actual, expected, "TC{} failed", index),
+                (Err(DataError::InvalidSequence { .. }), Err(DataError::InvalidSequence { .. })) => {},
+                (actual, expected) => {
+                    panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                },
+            }
+        }
+
+        // First event not overlapping the snapshot is invalid
+        let mut sequencer = GateioBookSequencer::new(100);
+        assert!(matches!(sequencer.validate(102, 105), Err(DataError::InvalidSequence { .. })));
+    }
+}
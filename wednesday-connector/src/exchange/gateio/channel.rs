@@ -0,0 +1,72 @@
+use wednesday_model::identifiers::{ExchangeId, Identifier};
+
+use crate::{
+    exchange::connector::ExchangeServer,
+    subscriber::subscription::{
+        kind::{OrderBooksL2, PublicTrades},
+        Subscription,
+    },
+};
+
+use super::Gateio;
+
+/// Type that defines how to translate a [`Subscription`] into a [`Gateio`] channel to be
+/// subscribed to.
+///
+/// See docs: <https://www.gate.io/docs/developers/apiv4/ws/en/#public-trades-channel>
+/// See docs: <https://www.gate.io/docs/developers/futures/ws/en/#trades-api>
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct GateioChannel(pub &'static str);
+
+impl GateioChannel {
+    /// [`Gateio`] spot real-time trades channel.
+    pub const SPOT_TRADES: Self = Self("spot.trades");
+    /// [`Gateio`] spot incremental L2 order book channel.
+    pub const SPOT_ORDER_BOOK_L2: Self = Self("spot.order_book_update");
+    /// [`Gateio`] spot application level ping channel.
+    pub const SPOT_PING: Self = Self("spot.ping");
+    /// [`Gateio`] futures real-time trades channel.
+    pub const FUTURES_TRADES: Self = Self("futures.trades");
+    /// [`Gateio`] futures incremental L2 order book channel.
+    pub const FUTURES_ORDER_BOOK_L2: Self = Self("futures.order_book_update");
+    /// [`Gateio`] futures application level ping channel.
+    pub const FUTURES_PING: Self = Self("futures.ping");
+
+    /// Application level ping channel of the provided [`Gateio`] [`ExchangeId`].
+    pub fn ping(exchange: ExchangeId) -> Self {
+        match exchange {
+            ExchangeId::GateioSpot => Self::SPOT_PING,
+            _ => Self::FUTURES_PING,
+        }
+    }
+}
+
+impl<Server> Identifier<GateioChannel> for Subscription<Gateio<Server>, PublicTrades>
+where
+    Server: ExchangeServer,
+{
+    fn id(&self) -> GateioChannel {
+        match Server::ID {
+            ExchangeId::GateioSpot => GateioChannel::SPOT_TRADES,
+            _ => GateioChannel::FUTURES_TRADES,
+        }
+    }
+}
+
+impl<Server> Identifier<GateioChannel> for Subscription<Gateio<Server>, OrderBooksL2>
+where
+    Server: ExchangeServer,
+{
+    fn id(&self) -> GateioChannel {
+        match Server::ID {
+            ExchangeId::GateioSpot => GateioChannel::SPOT_ORDER_BOOK_L2,
+            _ => GateioChannel::FUTURES_ORDER_BOOK_L2,
+        }
+    }
+}
+
+impl AsRef<str> for GateioChannel {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use wednesday_model::identifiers::Identifier;
+
+use crate::subscriber::subscription::Subscription;
+
+use super::Gateio;
+
+/// Type that defines how to translate a [`Subscription`] into a [`Gateio`] market that can be
+/// subscribed to.
+///
+/// Spot currency pairs & perpetual contracts share the same format, eg/ "BTC_USDT".
+///
+/// See docs: <https://www.gate.io/docs/developers/apiv4/en/#list-all-currency-pairs-supported>
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct GateioMarket(pub String);
+
+impl<Server, Kind> Identifier<GateioMarket> for Subscription<Gateio<Server>, Kind> {
+    fn id(&self) -> GateioMarket {
+        GateioMarket(format!("{}_{}", self.instrument.base_currency, self.instrument.quote_currency).to_uppercase())
+    }
+}
+
+impl AsRef<str> for GateioMarket {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
@@ -0,0 +1,119 @@
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use serde_json::value::RawValue;
+use wednesday_model::identifiers::{Identifier, SubscriptionId};
+
+/// [`Gateio`](super::Gateio) WebSocket v4 message.
+///
+/// Every message received on the connection must be deserialisable, so pongs & late
+/// subscription responses are consumed as [`GateioMessage::Event`], and only "update" events
+/// are deserialised as [`GateioMessage::Data`].
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.gate.io/docs/developers/apiv4/ws/en/#server-notification>
+/// #### Update
+/// ```json
+/// {
+///     "time": 1606292218,
+///     "time_ms": 1606292218231,
+///     "channel": "spot.trades",
+///     "event": "update",
+///     "result": {...}
+/// }
+/// ```
+///
+/// #### Pong
+/// ```json
+/// {"time": 1545404023, "time_ms": 1545404023123, "channel": "spot.pong", "event": "", "result": null}
+/// ```
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub enum GateioMessage<T> {
+    Data(T),
+    Event,
+}
+
+impl<T> Identifier<Option<SubscriptionId>> for GateioMessage<T>
+where
+    T: Identifier<Option<SubscriptionId>>,
+{
+    fn id(&self) -> Option<SubscriptionId> {
+        match self {
+            GateioMessage::Data(data) => data.id(),
+            GateioMessage::Event => None,
+        }
+    }
+}
+
+impl<'de, T> Deserialize<'de> for GateioMessage<T>
+where
+    T: DeserializeOwned,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Envelope {
+            #[serde(default)]
+            event: String,
+            #[serde(default)]
+            result: Option<Box<RawValue>>,
+        }
+
+        match Envelope::deserialize(deserializer)? {
+            Envelope { event, result: Some(result) } if event == "update" => {
+                serde_json::from_str(result.get()).map(Self::Data).map_err(serde::de::Error::custom)
+            },
+            _ => Ok(Self::Event),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use super::*;
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Data {
+            s: String,
+        }
+
+        #[test]
+        fn test_gateio_message() {
+            struct TestCase {
+                input: &'static str,
+                expected: Option<GateioMessage<Data>>,
+            }
+
+            let tests = vec![
+                // TC0: pong is an Event
+                TestCase {
+                    input: r#"{"time": 1545404023, "time_ms": 1545404023123, "channel": "spot.pong", "event": "", "result": null}"#,
+                    expected: Some(GateioMessage::Event),
+                },
+                // TC1: subscription response is an Event
+                TestCase {
+                    input: r#"{"time": 1606292218, "channel": "spot.trades", "event": "subscribe", "result": {"status": "success"}}"#,
+                    expected: Some(GateioMessage::Event),
+                },
+                // TC2: update is Data
+                TestCase {
+                    input: r#"{"time": 1606292218, "channel": "spot.order_book_update", "event": "update", "result": {"s": "BTC_USDT"}}"#,
+                    expected: Some(GateioMessage::Data(Data { s: "BTC_USDT".to_string() })),
+                },
+                // TC3: invalid update result
+                TestCase {
+                    input: r#"{"time": 1606292218, "channel": "spot.order_book_update", "event": "update", "result": {}}"#,
+                    expected: None,
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = serde_json::from_str::<GateioMessage<Data>>(test.input).ok();
+                assert_eq!(actual, test.expected, "TC{} failed", index);
+            }
+        }
+    }
+}
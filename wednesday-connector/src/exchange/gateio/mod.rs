@@ -0,0 +1,210 @@
+use std::marker::PhantomData;
+
+use chrono::Utc;
+use tokio::time;
+use url::Url;
+use wednesday_model::{error::SocketError, identifiers::ExchangeId};
+
+use crate::{
+    protocol::http::websocket::{PingInterval, WsMessage},
+    subscriber::{protocol::websocket::WsSubscriber, subscription::ExchangeSubscription, validator::WsSubscriptionValidator},
+};
+
+use self::{channel::GateioChannel, market::GateioMarket, subscription::GateioSubscriptionResponse};
+
+use super::connector::{Connector, ExchangeServer};
+
+pub mod book;
+pub mod channel;
+pub mod market;
+pub mod message;
+pub mod perpetual;
+pub mod spot;
+pub mod subscription;
+
+/// Application level ping interval of the [`Gateio`] WebSocket v4 API.
+///
+/// See docs: <https://www.gate.io/docs/developers/apiv4/ws/en/#application-ping-pong>
+pub const PING_INTERVAL_GATEIO: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Update frequency of the [`Gateio`] "order_book_update" channels.
+pub const ORDER_BOOK_L2_INTERVAL_GATEIO: &str = "100ms";
+
+/// [`Gateio`] WebSocket v4 connector, generic over the [`ExchangeServer`] since spot & each
+/// perpetual settlement currency are served from different endpoints.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Gateio<Server> {
+    server: PhantomData<Server>,
+}
+
+impl<Server> Connector for Gateio<Server>
+where
+    Server: ExchangeServer,
+{
+    const ID: ExchangeId = Server::ID;
+    type Channel = GateioChannel;
+    type Market = GateioMarket;
+    type Subscriber = WsSubscriber;
+    type SubscriptionValidator = WsSubscriptionValidator;
+    type SubscriptionResponse = GateioSubscriptionResponse;
+
+    fn url() -> Result<Url, SocketError> {
+        Url::parse(Server::ws_url()).map_err(SocketError::UrlParse)
+    }
+
+    fn requests(exchange_subscriptions: Vec<ExchangeSubscription<Self::Channel, Self::Market>>) -> Vec<WsMessage> {
+        // Gate.io responds to every subscribe request, so one is sent per subscription to match
+        // the default expected_responses
+        gateio_requests("subscribe", exchange_subscriptions)
+    }
+
+    fn dynamic_subscriptions() -> bool {
+        true
+    }
+
+    fn unsubscribe_requests(exchange_subscriptions: Vec<ExchangeSubscription<Self::Channel, Self::Market>>) -> Vec<WsMessage> {
+        gateio_requests("unsubscribe", exchange_subscriptions)
+    }
+
+    fn ping_interval() -> Option<PingInterval> {
+        Some(PingInterval {
+            interval: time::interval(PING_INTERVAL_GATEIO),
+            ping: || {
+                WsMessage::Text(
+                    serde_json::json!({
+                        "time": Utc::now().timestamp(),
+                        "channel": GateioChannel::ping(Server::ID).as_ref(),
+                    })
+                    .to_string(),
+                )
+            },
+        })
+    }
+}
+
+/// One request per subscription with the provided "subscribe" or "unsubscribe" event.
+fn gateio_requests(event: &str, exchange_subscriptions: Vec<ExchangeSubscription<GateioChannel, GateioMarket>>) -> Vec<WsMessage> {
+    exchange_subscriptions
+        .into_iter()
+        .map(|sub| {
+            let payload = match sub.channel {
+                GateioChannel::SPOT_ORDER_BOOK_L2 => vec![sub.market.as_ref(), ORDER_BOOK_L2_INTERVAL_GATEIO],
+                GateioChannel::FUTURES_ORDER_BOOK_L2 => vec![sub.market.as_ref(), ORDER_BOOK_L2_INTERVAL_GATEIO, "100"],
+                _ => vec![sub.market.as_ref()],
+            };
+
+            WsMessage::Text(
+                serde_json::json!({
+                    "time": Utc::now().timestamp(),
+                    "channel": sub.channel.as_ref(),
+                    "event": event,
+                    "payload": payload,
+                })
+                .to_string(),
+            )
+        })
+        .collect()
+}
+
+impl<'de, Server> serde::Deserialize<'de> for Gateio<Server>
+where
+    Server: ExchangeServer,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let input = <&str as serde::Deserialize>::deserialize(deserializer)?;
+        let expected = Self::ID.as_str();
+
+        if input == Self::ID.as_str() {
+            Ok(Self::default())
+        } else {
+            Err(serde::de::Error::invalid_value(serde::de::Unexpected::Str(input), &expected))
+        }
+    }
+}
+
+impl<Server> serde::Serialize for Gateio<Server>
+where
+    Server: ExchangeServer,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        let exchange_id = Self::ID.as_str();
+        serializer.serialize_str(exchange_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{perpetual::GateioPerpetualsUsd, spot::GateioSpot, *};
+
+    #[test]
+    fn test_gateio_requests() {
+        struct TestCase {
+            input: ExchangeSubscription<GateioChannel, GateioMarket>,
+            expected_channel: &'static str,
+            expected_payload: Vec<&'static str>,
+        }
+
+        let tests = vec![
+            // TC0: spot trades
+            TestCase {
+                input: ExchangeSubscription::from((GateioChannel::SPOT_TRADES, GateioMarket("BTC_USDT".to_string()))),
+                expected_channel: "spot.trades",
+                expected_payload: vec!["BTC_USDT"],
+            },
+            // TC1: spot order book includes the update frequency
+            TestCase {
+                input: ExchangeSubscription::from((GateioChannel::SPOT_ORDER_BOOK_L2, GateioMarket("BTC_USDT".to_string()))),
+                expected_channel: "spot.order_book_update",
+                expected_payload: vec!["BTC_USDT", "100ms"],
+            },
+            // TC2: futures order book includes the update frequency & depth
+            TestCase {
+                input: ExchangeSubscription::from((GateioChannel::FUTURES_ORDER_BOOK_L2, GateioMarket("BTC_USDT".to_string()))),
+                expected_channel: "futures.order_book_update",
+                expected_payload: vec!["BTC_USDT", "100ms", "100"],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let requests = GateioSpot::requests(vec![test.input]);
+            let request = match requests.as_slice() {
+                [WsMessage::Text(request)] => serde_json::from_str::<serde_json::Value>(request).unwrap(),
+                _ => panic!("TC{index} failed because exactly one text request was expected"),
+            };
+
+            assert_eq!(request["channel"], test.expected_channel, "TC{} failed", index);
+            assert_eq!(request["event"], "subscribe", "TC{} failed", index);
+            assert_eq!(request["payload"], serde_json::json!(test.expected_payload), "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_gateio_unsubscribe_requests() {
+        let requests = GateioSpot::unsubscribe_requests(vec![ExchangeSubscription::from((
+            GateioChannel::SPOT_ORDER_BOOK_L2,
+            GateioMarket("BTC_USDT".to_string()),
+        ))]);
+        let request = match requests.as_slice() {
+            [WsMessage::Text(request)] => serde_json::from_str::<serde_json::Value>(request).unwrap(),
+            _ => panic!("exactly one text request was expected"),
+        };
+
+        assert!(GateioSpot::dynamic_subscriptions());
+        assert_eq!(request["channel"], "spot.order_book_update");
+        assert_eq!(request["event"], "unsubscribe");
+        assert_eq!(request["payload"], serde_json::json!(["BTC_USDT", "100ms"]));
+    }
+
+    #[test]
+    fn test_gateio_id() {
+        assert_eq!(GateioSpot::ID, ExchangeId::GateioSpot);
+        assert_eq!(GateioPerpetualsUsd::ID, ExchangeId::GateioPerpetualsUsd);
+        assert_eq!(serde_json::to_string(&GateioPerpetualsUsd::default()).unwrap(), r#""gateio_perpetuals_usd""#);
+    }
+}
@@ -0,0 +1,272 @@
+use std::marker::PhantomData;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::UnboundedSender;
+use wednesday_model::{
+    deserialization,
+    error::{DataError, SocketError},
+    identifiers::{Identifier, SubscriptionId},
+    instruments::Instrument,
+    orderbook::{Level, OrderBook},
+};
+
+use crate::{
+    exchange::gateio::{
+        book::{GateioBookSequencer, GateioOrderBookL2Snapshot},
+        channel::GateioChannel,
+        message::GateioMessage,
+    },
+    protocol::http::websocket::WsMessage,
+    subscriber::subscription::ExchangeSubscription,
+    transformer::updater::{InstrumentOrderBook, OrderBookUpdater},
+};
+
+use super::GateioPerpetualServer;
+
+pub const REST_BASE_URL_GATEIO_FUTURES: &str = "https://api.gateio.ws/api/v4/futures";
+
+pub type GateioPerpetualsOrderBookL2 = GateioMessage<GateioPerpetualsOrderBookL2Delta>;
+
+/// [`Gateio`](super::super::Gateio) perpetual order book level, the "s" amount is a number of
+/// contracts.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Deserialize, Serialize)]
+pub struct GateioPerpetualsLevel {
+    #[serde(rename = "p", deserialize_with = "deserialization::de_str")]
+    pub price: f64,
+    #[serde(rename = "s")]
+    pub amount: f64,
+}
+
+impl From<GateioPerpetualsLevel> for Level {
+    fn from(level: GateioPerpetualsLevel) -> Self {
+        Level::new(level.price, level.amount)
+    }
+}
+
+/// [`Gateio`](super::super::Gateio) perpetual L2 order book delta, the "result" of a
+/// "futures.order_book_update" update.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.gate.io/docs/developers/futures/ws/en/#order-book-update-notification>
+/// ```json
+/// {
+///     "t": 1615366381417,
+///     "s": "BTC_USD",
+///     "U": 2517661101,
+///     "u": 2517661113,
+///     "b": [{"p": "54672.1", "s": 0}, {"p": "54664.5", "s": 58794}],
+///     "a": [{"p": "54743.6", "s": 0}]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, PartialOrd, Deserialize, Serialize)]
+pub struct GateioPerpetualsOrderBookL2Delta {
+    #[serde(rename = "s", deserialize_with = "de_ob_l2_subscription_id")]
+    pub subscription_id: SubscriptionId,
+    #[serde(rename = "t", deserialize_with = "deserialization::de_u64_epoch_ms_as_datetime_utc")]
+    pub time: DateTime<Utc>,
+    #[serde(rename = "U")]
+    pub first_update_id: u64,
+    #[serde(rename = "u")]
+    pub last_update_id: u64,
+    #[serde(rename = "b")]
+    pub bids: Vec<GateioPerpetualsLevel>,
+    #[serde(rename = "a")]
+    pub asks: Vec<GateioPerpetualsLevel>,
+}
+
+impl Identifier<Option<SubscriptionId>> for GateioPerpetualsOrderBookL2Delta {
+    fn id(&self) -> Option<SubscriptionId> {
+        Some(self.subscription_id.clone())
+    }
+}
+
+/// [`Gateio`](super::super::Gateio) perpetual [`OrderBookUpdater`], see
+/// [`GateioBookSequencer`] for how the local order book is maintained.
+///
+/// Generic over the [`GateioPerpetualServer`] so the REST snapshot is requested from the
+/// matching settlement currency.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub struct GateioPerpetualsBookUpdater<Server> {
+    pub sequencer: GateioBookSequencer,
+    server: PhantomData<Server>,
+}
+
+impl<Server> GateioPerpetualsBookUpdater<Server> {
+    /// Construct a new Gateio perpetual [`OrderBookUpdater`] using the provided baseID from a
+    /// HTTP snapshot.
+    pub fn new(last_update_id: u64) -> Self {
+        Self {
+            sequencer: GateioBookSequencer::new(last_update_id),
+            server: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<Server> OrderBookUpdater for GateioPerpetualsBookUpdater<Server>
+where
+    Server: GateioPerpetualServer + Sync,
+{
+    type OrderBook = OrderBook;
+    type Update = GateioPerpetualsOrderBookL2;
+
+    async fn init<Exchange, Kind>(_: UnboundedSender<WsMessage>, instrument: Instrument) -> Result<InstrumentOrderBook<Self>, DataError>
+    where
+        Exchange: Send,
+        Kind: Send,
+    {
+        let snapshot_url = format!(
+            "{}/{}/order_book?contract={}_{}&limit=100&with_id=true",
+            REST_BASE_URL_GATEIO_FUTURES,
+            Server::SETTLE,
+            instrument.base_currency.as_ref().to_uppercase(),
+            instrument.quote_currency.as_ref().to_uppercase()
+        );
+
+        let snapshot = reqwest::get(&snapshot_url)
+            .await
+            .map_err(SocketError::Http)?
+            .json::<GateioOrderBookL2Snapshot<GateioPerpetualsLevel>>()
+            .await
+            .map_err(SocketError::Http)?;
+
+        Ok(InstrumentOrderBook {
+            instrument,
+            updater: Self::new(snapshot.last_update_id),
+            book: OrderBook::from(snapshot),
+        })
+    }
+
+    fn update(&mut self, book: &mut Self::OrderBook, update: Self::Update) -> Result<Option<Self::OrderBook>, DataError> {
+        let update = match update {
+            GateioMessage::Data(update) => update,
+            GateioMessage::Event => return Ok(None),
+        };
+
+        if !self.sequencer.validate(update.first_update_id, update.last_update_id)? {
+            return Ok(None);
+        }
+
+        book.last_update_ts = update.time;
+        book.bids.upsert(update.bids);
+        book.asks.upsert(update.asks);
+
+        Ok(Some(book.snapshot()))
+    }
+}
+
+/// Deserialize a [`GateioPerpetualsOrderBookL2Delta`] "s" (eg/ "BTC_USDT") as the associated
+/// [`SubscriptionId`] (eg/ "futures.order_book_update|BTC_USDT").
+pub fn de_ob_l2_subscription_id<'de, D>(deserializer: D) -> Result<SubscriptionId, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    <&str as Deserialize>::deserialize(deserializer).map(|market| ExchangeSubscription::from((GateioChannel::FUTURES_ORDER_BOOK_L2, market)).id())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::gateio::perpetual::GateioServerPerpetualsUsd;
+
+    mod de {
+        use super::*;
+
+        #[test]
+        fn test_gateio_perpetuals_order_book_l2_snapshot() {
+            let input = r#"
+            {
+                "id": 123456,
+                "current": 1623898993.123,
+                "update": 1623898993.121,
+                "asks": [{"p": "1.52", "s": 100}],
+                "bids": [{"p": "1.17", "s": 150}]
+            }
+            "#;
+
+            assert_eq!(
+                serde_json::from_str::<GateioOrderBookL2Snapshot<GateioPerpetualsLevel>>(input).unwrap(),
+                GateioOrderBookL2Snapshot {
+                    last_update_id: 123456,
+                    bids: vec![GateioPerpetualsLevel { price: 1.17, amount: 150.0 }],
+                    asks: vec![GateioPerpetualsLevel { price: 1.52, amount: 100.0 }],
+                }
+            );
+        }
+
+        #[test]
+        fn test_gateio_perpetuals_order_book_l2() {
+            let input = r#"
+            {
+                "time": 1615366381,
+                "time_ms": 1615366381123,
+                "channel": "futures.order_book_update",
+                "event": "update",
+                "result": {
+                    "t": 1615366381417,
+                    "s": "BTC_USD",
+                    "U": 2517661101,
+                    "u": 2517661113,
+                    "b": [{"p": "54672.1", "s": 0}, {"p": "54664.5", "s": 58794}],
+                    "a": [{"p": "54743.6", "s": 0}]
+                }
+            }
+            "#;
+
+            assert_eq!(
+                serde_json::from_str::<GateioPerpetualsOrderBookL2>(input).unwrap(),
+                GateioMessage::Data(GateioPerpetualsOrderBookL2Delta {
+                    subscription_id: SubscriptionId::from("futures.order_book_update|BTC_USD"),
+                    time: deserialization::datetime_utc_from_epoch_duration(std::time::Duration::from_millis(1615366381417)),
+                    first_update_id: 2517661101,
+                    last_update_id: 2517661113,
+                    bids: vec![
+                        GateioPerpetualsLevel { price: 54672.1, amount: 0.0 },
+                        GateioPerpetualsLevel {
+                            price: 54664.5,
+                            amount: 58794.0
+                        },
+                    ],
+                    asks: vec![GateioPerpetualsLevel { price: 54743.6, amount: 0.0 }],
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_update_gateio_perpetuals_order_book_l2() {
+        let mut updater = GateioPerpetualsBookUpdater::<GateioServerPerpetualsUsd>::new(10);
+        let mut book = OrderBook::from(GateioOrderBookL2Snapshot {
+            last_update_id: 10,
+            bids: vec![GateioPerpetualsLevel { price: 99.0, amount: 5.0 }],
+            asks: vec![GateioPerpetualsLevel { price: 101.0, amount: 5.0 }],
+        });
+
+        let delta = |first_update_id, last_update_id, asks: Vec<GateioPerpetualsLevel>| {
+            GateioMessage::Data(GateioPerpetualsOrderBookL2Delta {
+                subscription_id: SubscriptionId::from("futures.order_book_update|BTC_USDT"),
+                time: Utc::now(),
+                first_update_id,
+                last_update_id,
+                bids: vec![],
+                asks,
+            })
+        };
+
+        let snapshot = updater
+            .update(&mut book, delta(11, 11, vec![GateioPerpetualsLevel { price: 100.5, amount: 1.0 }]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(snapshot.asks.levels(), vec![Level::new(100.5, 1.0), Level::new(101.0, 5.0)]);
+
+        assert!(matches!(
+            updater.update(&mut book, delta(11, 12, vec![])),
+            Err(DataError::InvalidSequence {
+                prev_last_update_id: 11,
+                first_update_id: 11
+            })
+        ));
+    }
+}
@@ -0,0 +1,79 @@
+use std::fmt::Debug;
+
+use wednesday_model::identifiers::ExchangeId;
+
+use crate::{
+    exchange::connector::ExchangeServer,
+    stream::{protocol::ws_stream::ExchangeWsStream, selector::StreamSelector},
+    subscriber::subscription::kind::{OrderBooksL2, PublicTrades},
+    transformer::{stateful::MultiBookTransformer, stateless::StatelessTransformer},
+};
+
+use self::{l2::GateioPerpetualsBookUpdater, trade::GateioPerpetualsTrades};
+
+use super::Gateio;
+
+pub mod l2;
+pub mod trade;
+
+/// See docs: <https://www.gate.io/docs/developers/futures/ws/en/#api-overview>
+pub const WS_BASE_URL_GATEIO_PERPETUALS_USD: &str = "wss://fx-ws.gateio.ws/v4/ws/usdt";
+
+/// See docs: <https://www.gate.io/docs/developers/futures/ws/en/#api-overview>
+pub const WS_BASE_URL_GATEIO_PERPETUALS_BTC: &str = "wss://fx-ws.gateio.ws/v4/ws/btc";
+
+/// [`Gateio`] perpetual [`ExchangeServer`], identified by the settlement currency used to
+/// route REST requests, eg/ "usdt".
+pub trait GateioPerpetualServer: ExchangeServer {
+    const SETTLE: &'static str;
+}
+
+pub type GateioPerpetualsUsd = Gateio<GateioServerPerpetualsUsd>;
+
+/// USDT settled perpetual contracts, eg/ "BTC_USDT".
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct GateioServerPerpetualsUsd;
+
+impl ExchangeServer for GateioServerPerpetualsUsd {
+    const ID: ExchangeId = ExchangeId::GateioPerpetualsUsd;
+
+    fn ws_url() -> &'static str {
+        WS_BASE_URL_GATEIO_PERPETUALS_USD
+    }
+}
+
+impl GateioPerpetualServer for GateioServerPerpetualsUsd {
+    const SETTLE: &'static str = "usdt";
+}
+
+pub type GateioPerpetualsBtc = Gateio<GateioServerPerpetualsBtc>;
+
+/// BTC settled (inverse) perpetual contracts, eg/ "BTC_USD".
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct GateioServerPerpetualsBtc;
+
+impl ExchangeServer for GateioServerPerpetualsBtc {
+    const ID: ExchangeId = ExchangeId::GateioPerpetualsBtc;
+
+    fn ws_url() -> &'static str {
+        WS_BASE_URL_GATEIO_PERPETUALS_BTC
+    }
+}
+
+impl GateioPerpetualServer for GateioServerPerpetualsBtc {
+    const SETTLE: &'static str = "btc";
+}
+
+impl<Server> StreamSelector<PublicTrades> for Gateio<Server>
+where
+    Server: GateioPerpetualServer + Debug + Send + Sync,
+{
+    type Stream = ExchangeWsStream<StatelessTransformer<Self, PublicTrades, GateioPerpetualsTrades>>;
+}
+
+impl<Server> StreamSelector<OrderBooksL2> for Gateio<Server>
+where
+    Server: GateioPerpetualServer + Debug + Send + Sync,
+{
+    type Stream = ExchangeWsStream<MultiBookTransformer<Self, OrderBooksL2, GateioPerpetualsBookUpdater<Server>>>;
+}
@@ -0,0 +1,207 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use wednesday_model::{
+    deserialization,
+    enums::AggressorSide,
+    events::MarketEvent,
+    identifiers::{Exchange, ExchangeId, Identifier, SubscriptionId},
+    instruments::Instrument,
+    trade::PublicTrade,
+};
+
+use crate::{
+    exchange::gateio::{channel::GateioChannel, message::GateioMessage},
+    subscriber::subscription::ExchangeSubscription,
+    transformer::iterator::MarketIter,
+};
+
+pub type GateioPerpetualsTrades = GateioMessage<GateioPerpetualsTradeBatch>;
+
+/// Batch of [`GateioPerpetualsTrade`]s, the "result" of a "futures.trades" update.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct GateioPerpetualsTradeBatch(pub Vec<GateioPerpetualsTrade>);
+
+impl Identifier<Option<SubscriptionId>> for GateioPerpetualsTradeBatch {
+    fn id(&self) -> Option<SubscriptionId> {
+        self.0.first().map(|trade| trade.subscription_id.clone())
+    }
+}
+
+/// [`Gateio`](super::super::Gateio) perpetual real-time trade.
+///
+/// Note:
+/// The "size" is a number of contracts, and is negative if the aggressor was the seller.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.gate.io/docs/developers/futures/ws/en/#trades-notification>
+/// ```json
+/// {
+///     "size": -108,
+///     "id": 27753479,
+///     "create_time": 1545136464,
+///     "create_time_ms": 1545136464123,
+///     "price": "96.4",
+///     "contract": "BTC_USD"
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct GateioPerpetualsTrade {
+    #[serde(rename = "contract", deserialize_with = "de_trade_subscription_id")]
+    pub subscription_id: SubscriptionId,
+    pub id: u64,
+    #[serde(rename = "create_time_ms", deserialize_with = "deserialization::de_u64_epoch_ms_as_datetime_utc")]
+    pub time: DateTime<Utc>,
+    #[serde(deserialize_with = "deserialization::de_str")]
+    pub price: f64,
+    pub size: f64,
+}
+
+impl From<(ExchangeId, Instrument, GateioPerpetualsTrades)> for MarketIter<PublicTrade> {
+    fn from((exchange_id, instrument, trades): (ExchangeId, Instrument, GateioPerpetualsTrades)) -> Self {
+        let trades = match trades {
+            GateioMessage::Data(trades) => trades,
+            GateioMessage::Event => return Self(vec![]),
+        };
+
+        trades
+            .0
+            .into_iter()
+            .map(|trade| MarketEvent {
+                exchange_ts: trade.time,
+                local_ts: Utc::now(),
+                exchange: Exchange::from(exchange_id),
+                instrument: instrument.clone(),
+                kind: PublicTrade {
+                    id: trade.id.to_string(),
+                    price: trade.price,
+                    quantity: trade.size.abs(),
+                    aggressor_side: if trade.size < 0.0 { AggressorSide::Sell } else { AggressorSide::Buy },
+                },
+            })
+            .map(Ok)
+            .collect()
+    }
+}
+
+/// Deserialize a [`GateioPerpetualsTrade`] "contract" (eg/ "BTC_USDT") as the associated
+/// [`SubscriptionId`] (eg/ "futures.trades|BTC_USDT").
+pub fn de_trade_subscription_id<'de, D>(deserializer: D) -> Result<SubscriptionId, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    <&str as Deserialize>::deserialize(deserializer).map(|market| ExchangeSubscription::from((GateioChannel::FUTURES_TRADES, market)).id())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use wednesday_model::error::SocketError;
+
+        use super::*;
+
+        #[test]
+        fn test_gateio_perpetuals_trades() {
+            struct TestCase {
+                input: &'static str,
+                expected: Result<GateioPerpetualsTrades, SocketError>,
+            }
+
+            let tests = vec![
+                // TC0: input GateioPerpetualsTrades is deserialised
+                TestCase {
+                    input: r#"
+                    {
+                        "channel": "futures.trades",
+                        "event": "update",
+                        "time": 1541503698,
+                        "time_ms": 1541503698123,
+                        "result": [
+                            {
+                                "size": -108,
+                                "id": 27753479,
+                                "create_time": 1545136464,
+                                "create_time_ms": 1545136464123,
+                                "price": "96.4",
+                                "contract": "BTC_USD"
+                            }
+                        ]
+                    }
+                    "#,
+                    expected: Ok(GateioMessage::Data(GateioPerpetualsTradeBatch(vec![GateioPerpetualsTrade {
+                        subscription_id: SubscriptionId::from("futures.trades|BTC_USD"),
+                        id: 27753479,
+                        time: deserialization::datetime_utc_from_epoch_duration(std::time::Duration::from_millis(1545136464123)),
+                        price: 96.4,
+                        size: -108.0,
+                    }]))),
+                },
+                // TC1: input GateioPerpetualsTrades is invalid w/ missing contract
+                TestCase {
+                    input: r#"
+                    {
+                        "channel": "futures.trades",
+                        "event": "update",
+                        "time": 1541503698,
+                        "result": [{"size": 1, "id": 27753479, "create_time_ms": 1545136464123, "price": "96.4"}]
+                    }
+                    "#,
+                    expected: Err(SocketError::Unsupported {
+                        entity: "",
+                        item: "".to_string(),
+                    }),
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = serde_json::from_str::<GateioPerpetualsTrades>(test.input);
+                match (actual, test.expected) {
+                    (Ok(actual), Ok(expected)) => {
+                        assert_eq!(actual, expected, "TC{} failed", index)
+                    },
+                    (Err(_), Err(_)) => {
+                        // Test passed
+                    },
+                    (actual, expected) => {
+                        // Test failed
+                        panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                    },
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_gateio_perpetuals_trades_to_market_iter() {
+        let trades = GateioMessage::Data(GateioPerpetualsTradeBatch(vec![
+            GateioPerpetualsTrade {
+                subscription_id: SubscriptionId::from("futures.trades|BTC_USDT"),
+                id: 1,
+                time: Utc::now(),
+                price: 100.0,
+                size: -2.0,
+            },
+            GateioPerpetualsTrade {
+                subscription_id: SubscriptionId::from("futures.trades|BTC_USDT"),
+                id: 2,
+                time: Utc::now(),
+                price: 100.0,
+                size: 3.0,
+            },
+        ]));
+
+        let instrument = Instrument::from(("btc", "usdt", wednesday_model::instruments::InstrumentKind::CryptoPerpetual));
+        let actual = MarketIter::<PublicTrade>::from((ExchangeId::GateioPerpetualsUsd, instrument, trades))
+            .0
+            .into_iter()
+            .map(|event| {
+                let trade = event.unwrap().kind;
+                (trade.quantity, trade.aggressor_side)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(actual, vec![(2.0, AggressorSide::Sell), (3.0, AggressorSide::Buy)]);
+    }
+}
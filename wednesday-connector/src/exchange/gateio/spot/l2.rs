@@ -0,0 +1,279 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::UnboundedSender;
+use wednesday_model::{
+    deserialization,
+    error::{DataError, SocketError},
+    identifiers::{Identifier, SubscriptionId},
+    instruments::Instrument,
+    orderbook::{Level, OrderBook},
+};
+
+use crate::{
+    exchange::gateio::{
+        book::{GateioBookSequencer, GateioOrderBookL2Snapshot},
+        channel::GateioChannel,
+        message::GateioMessage,
+    },
+    protocol::http::websocket::WsMessage,
+    subscriber::subscription::ExchangeSubscription,
+    transformer::updater::{InstrumentOrderBook, OrderBookUpdater},
+};
+
+pub const REST_BOOK_L2_SNAPSHOT_URL_GATEIO_SPOT: &str = "https://api.gateio.ws/api/v4/spot/order_book";
+
+pub type GateioSpotOrderBookL2 = GateioMessage<GateioSpotOrderBookL2Delta>;
+
+#[derive(Debug, Clone, PartialEq, PartialOrd, Deserialize, Serialize)]
+pub struct GateioSpotLevel {
+    #[serde(deserialize_with = "deserialization::de_str")]
+    pub price: f64,
+    #[serde(deserialize_with = "deserialization::de_str")]
+    pub amount: f64,
+}
+
+impl From<GateioSpotLevel> for Level {
+    fn from(level: GateioSpotLevel) -> Self {
+        Level::new(level.price, level.amount)
+    }
+}
+
+/// [`GateioSpot`](super::GateioSpot) L2 order book delta, the "result" of a
+/// "spot.order_book_update" update.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.gate.io/docs/developers/apiv4/ws/en/#changed-order-book-levels>
+/// ```json
+/// {
+///     "t": 1606294781123,
+///     "e": "depthUpdate",
+///     "E": 1606294781,
+///     "s": "BTC_USDT",
+///     "U": 48776301,
+///     "u": 48776306,
+///     "b": [["19137.74", "0.0001"], ["19088.37", "0"]],
+///     "a": [["19137.75", "0.6135"]]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, PartialOrd, Deserialize, Serialize)]
+pub struct GateioSpotOrderBookL2Delta {
+    #[serde(rename = "s", deserialize_with = "de_ob_l2_subscription_id")]
+    pub subscription_id: SubscriptionId,
+    #[serde(rename = "t", deserialize_with = "deserialization::de_u64_epoch_ms_as_datetime_utc")]
+    pub time: DateTime<Utc>,
+    #[serde(rename = "U")]
+    pub first_update_id: u64,
+    #[serde(rename = "u")]
+    pub last_update_id: u64,
+    #[serde(rename = "b")]
+    pub bids: Vec<GateioSpotLevel>,
+    #[serde(rename = "a")]
+    pub asks: Vec<GateioSpotLevel>,
+}
+
+impl Identifier<Option<SubscriptionId>> for GateioSpotOrderBookL2Delta {
+    fn id(&self) -> Option<SubscriptionId> {
+        Some(self.subscription_id.clone())
+    }
+}
+
+/// [`GateioSpot`](super::GateioSpot) [`OrderBookUpdater`], see [`GateioBookSequencer`] for
+/// how the local order book is maintained.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub struct GateioSpotBookUpdater {
+    pub sequencer: GateioBookSequencer,
+}
+
+impl GateioSpotBookUpdater {
+    /// Construct a new GateioSpot [`OrderBookUpdater`] using the provided baseID from a HTTP
+    /// snapshot.
+    pub fn new(last_update_id: u64) -> Self {
+        Self {
+            sequencer: GateioBookSequencer::new(last_update_id),
+        }
+    }
+}
+
+#[async_trait]
+impl OrderBookUpdater for GateioSpotBookUpdater {
+    type OrderBook = OrderBook;
+    type Update = GateioSpotOrderBookL2;
+
+    async fn init<Exchange, Kind>(_: UnboundedSender<WsMessage>, instrument: Instrument) -> Result<InstrumentOrderBook<Self>, DataError>
+    where
+        Exchange: Send,
+        Kind: Send,
+    {
+        let snapshot_url = format!(
+            "{}?currency_pair={}_{}&limit=100&with_id=true",
+            REST_BOOK_L2_SNAPSHOT_URL_GATEIO_SPOT,
+            instrument.base_currency.as_ref().to_uppercase(),
+            instrument.quote_currency.as_ref().to_uppercase()
+        );
+
+        let snapshot = reqwest::get(&snapshot_url)
+            .await
+            .map_err(SocketError::Http)?
+            .json::<GateioOrderBookL2Snapshot<GateioSpotLevel>>()
+            .await
+            .map_err(SocketError::Http)?;
+
+        Ok(InstrumentOrderBook {
+            instrument,
+            updater: Self::new(snapshot.last_update_id),
+            book: OrderBook::from(snapshot),
+        })
+    }
+
+    fn update(&mut self, book: &mut Self::OrderBook, update: Self::Update) -> Result<Option<Self::OrderBook>, DataError> {
+        let update = match update {
+            GateioMessage::Data(update) => update,
+            GateioMessage::Event => return Ok(None),
+        };
+
+        if !self.sequencer.validate(update.first_update_id, update.last_update_id)? {
+            return Ok(None);
+        }
+
+        book.last_update_ts = update.time;
+        book.bids.upsert(update.bids);
+        book.asks.upsert(update.asks);
+
+        Ok(Some(book.snapshot()))
+    }
+}
+
+/// Deserialize a [`GateioSpotOrderBookL2Delta`] "s" (eg/ "BTC_USDT") as the associated
+/// [`SubscriptionId`] (eg/ "spot.order_book_update|BTC_USDT").
+pub fn de_ob_l2_subscription_id<'de, D>(deserializer: D) -> Result<SubscriptionId, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    <&str as Deserialize>::deserialize(deserializer).map(|market| ExchangeSubscription::from((GateioChannel::SPOT_ORDER_BOOK_L2, market)).id())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use super::*;
+
+        #[test]
+        fn test_gateio_spot_order_book_l2_snapshot() {
+            let input = r#"
+            {
+                "id": 123456,
+                "current": 1623898993123,
+                "update": 1623898993121,
+                "asks": [["1.52", "1.151"]],
+                "bids": [["1.17", "201.863"], ["1.16", "10"]]
+            }
+            "#;
+
+            assert_eq!(
+                serde_json::from_str::<GateioOrderBookL2Snapshot<GateioSpotLevel>>(input).unwrap(),
+                GateioOrderBookL2Snapshot {
+                    last_update_id: 123456,
+                    bids: vec![GateioSpotLevel { price: 1.17, amount: 201.863 }, GateioSpotLevel { price: 1.16, amount: 10.0 }],
+                    asks: vec![GateioSpotLevel { price: 1.52, amount: 1.151 }],
+                }
+            );
+        }
+
+        #[test]
+        fn test_gateio_spot_order_book_l2() {
+            let input = r#"
+            {
+                "time": 1606294781,
+                "time_ms": 1606294781236,
+                "channel": "spot.order_book_update",
+                "event": "update",
+                "result": {
+                    "t": 1606294781123,
+                    "e": "depthUpdate",
+                    "E": 1606294781,
+                    "s": "BTC_USDT",
+                    "U": 48776301,
+                    "u": 48776306,
+                    "b": [["19137.74", "0.0001"], ["19088.37", "0"]],
+                    "a": [["19137.75", "0.6135"]]
+                }
+            }
+            "#;
+
+            assert_eq!(
+                serde_json::from_str::<GateioSpotOrderBookL2>(input).unwrap(),
+                GateioMessage::Data(GateioSpotOrderBookL2Delta {
+                    subscription_id: SubscriptionId::from("spot.order_book_update|BTC_USDT"),
+                    time: deserialization::datetime_utc_from_epoch_duration(std::time::Duration::from_millis(1606294781123)),
+                    first_update_id: 48776301,
+                    last_update_id: 48776306,
+                    bids: vec![
+                        GateioSpotLevel {
+                            price: 19137.74,
+                            amount: 0.0001
+                        },
+                        GateioSpotLevel { price: 19088.37, amount: 0.0 },
+                    ],
+                    asks: vec![GateioSpotLevel {
+                        price: 19137.75,
+                        amount: 0.6135
+                    }],
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_update_gateio_spot_order_book_l2() {
+        let mut updater = GateioSpotBookUpdater::new(100);
+        let mut book = OrderBook::from(GateioOrderBookL2Snapshot {
+            last_update_id: 100,
+            bids: vec![GateioSpotLevel { price: 99.0, amount: 1.0 }],
+            asks: vec![GateioSpotLevel { price: 101.0, amount: 1.0 }],
+        });
+
+        let delta = |first_update_id, last_update_id, bids: Vec<GateioSpotLevel>| {
+            GateioMessage::Data(GateioSpotOrderBookL2Delta {
+                subscription_id: SubscriptionId::from("spot.order_book_update|BTC_USDT"),
+                time: Utc::now(),
+                first_update_id,
+                last_update_id,
+                bids,
+                asks: vec![],
+            })
+        };
+
+        // Delta preceding the snapshot is dropped
+        assert_eq!(updater.update(&mut book, delta(95, 100, vec![])).unwrap(), None);
+
+        // First delta overlapping the snapshot is applied
+        let snapshot = updater
+            .update(
+                &mut book,
+                delta(
+                    99,
+                    102,
+                    vec![GateioSpotLevel { price: 99.0, amount: 0.0 }, GateioSpotLevel { price: 98.0, amount: 2.0 }],
+                ),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(snapshot.bids.levels(), vec![Level::new(98.0, 2.0)]);
+        assert_eq!(snapshot.asks.levels(), vec![Level::new(101.0, 1.0)]);
+
+        // Pong & late subscription responses are ignored
+        assert_eq!(updater.update(&mut book, GateioMessage::Event).unwrap(), None);
+
+        // Gap between deltas is terminal
+        assert!(matches!(
+            updater.update(&mut book, delta(104, 105, vec![])),
+            Err(DataError::InvalidSequence {
+                prev_last_update_id: 102,
+                first_update_id: 104
+            })
+        ));
+    }
+}
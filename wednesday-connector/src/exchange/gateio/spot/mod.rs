@@ -0,0 +1,39 @@
+use wednesday_model::identifiers::ExchangeId;
+
+use crate::{
+    exchange::connector::ExchangeServer,
+    stream::{protocol::ws_stream::ExchangeWsStream, selector::StreamSelector},
+    subscriber::subscription::kind::{OrderBooksL2, PublicTrades},
+    transformer::{stateful::MultiBookTransformer, stateless::StatelessTransformer},
+};
+
+use self::{l2::GateioSpotBookUpdater, trade::GateioSpotTrades};
+
+use super::Gateio;
+
+pub mod l2;
+pub mod trade;
+
+/// See docs: <https://www.gate.io/docs/developers/apiv4/ws/en/#api-overview>
+pub const WS_BASE_URL_GATEIO_SPOT: &str = "wss://api.gateio.ws/ws/v4/";
+
+pub type GateioSpot = Gateio<GateioServerSpot>;
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct GateioServerSpot;
+
+impl ExchangeServer for GateioServerSpot {
+    const ID: ExchangeId = ExchangeId::GateioSpot;
+
+    fn ws_url() -> &'static str {
+        WS_BASE_URL_GATEIO_SPOT
+    }
+}
+
+impl StreamSelector<PublicTrades> for GateioSpot {
+    type Stream = ExchangeWsStream<StatelessTransformer<Self, PublicTrades, GateioSpotTrades>>;
+}
+
+impl StreamSelector<OrderBooksL2> for GateioSpot {
+    type Stream = ExchangeWsStream<MultiBookTransformer<Self, OrderBooksL2, GateioSpotBookUpdater>>;
+}
@@ -0,0 +1,180 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use wednesday_model::{
+    deserialization,
+    enums::AggressorSide,
+    events::MarketEvent,
+    identifiers::{Exchange, ExchangeId, Identifier, SubscriptionId},
+    instruments::Instrument,
+    trade::PublicTrade,
+};
+
+use crate::{
+    exchange::gateio::{channel::GateioChannel, message::GateioMessage},
+    subscriber::subscription::ExchangeSubscription,
+    transformer::iterator::MarketIter,
+};
+
+pub type GateioSpotTrades = GateioMessage<GateioSpotTrade>;
+
+/// [`GateioSpot`](super::GateioSpot) real-time trade, the "result" of a "spot.trades" update.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.gate.io/docs/developers/apiv4/ws/en/#public-trades-channel>
+/// ```json
+/// {
+///     "id": 309143071,
+///     "create_time": 1606292218,
+///     "create_time_ms": "1606292218213.4578",
+///     "side": "sell",
+///     "currency_pair": "GT_USDT",
+///     "amount": "16.4700000000",
+///     "price": "0.4705000000",
+///     "range": "2390902-2390902"
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct GateioSpotTrade {
+    #[serde(rename = "currency_pair", deserialize_with = "de_trade_subscription_id")]
+    pub subscription_id: SubscriptionId,
+    pub id: u64,
+    #[serde(rename = "create_time_ms", deserialize_with = "de_str_f64_epoch_ms_as_datetime_utc")]
+    pub time: DateTime<Utc>,
+    pub side: AggressorSide,
+    #[serde(deserialize_with = "deserialization::de_str")]
+    pub price: f64,
+    #[serde(deserialize_with = "deserialization::de_str")]
+    pub amount: f64,
+}
+
+impl Identifier<Option<SubscriptionId>> for GateioSpotTrade {
+    fn id(&self) -> Option<SubscriptionId> {
+        Some(self.subscription_id.clone())
+    }
+}
+
+impl From<(ExchangeId, Instrument, GateioSpotTrades)> for MarketIter<PublicTrade> {
+    fn from((exchange_id, instrument, trades): (ExchangeId, Instrument, GateioSpotTrades)) -> Self {
+        let trade = match trades {
+            GateioMessage::Data(trade) => trade,
+            GateioMessage::Event => return Self(vec![]),
+        };
+
+        Self(vec![Ok(MarketEvent {
+            exchange_ts: trade.time,
+            local_ts: Utc::now(),
+            exchange: Exchange::from(exchange_id),
+            instrument,
+            kind: PublicTrade {
+                id: trade.id.to_string(),
+                price: trade.price,
+                quantity: trade.amount,
+                aggressor_side: trade.side,
+            },
+        })])
+    }
+}
+
+/// Deserialize a [`GateioSpotTrade`] "currency_pair" (eg/ "BTC_USDT") as the associated
+/// [`SubscriptionId`] (eg/ "spot.trades|BTC_USDT").
+pub fn de_trade_subscription_id<'de, D>(deserializer: D) -> Result<SubscriptionId, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    <&str as Deserialize>::deserialize(deserializer).map(|market| ExchangeSubscription::from((GateioChannel::SPOT_TRADES, market)).id())
+}
+
+/// Deserialize a fractional epoch milliseconds string (eg/ "1606292218213.4578") as a
+/// [`DateTime<Utc>`].
+pub fn de_str_f64_epoch_ms_as_datetime_utc<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    deserialization::de_str::<D, f64>(deserializer)
+        .map(|epoch_ms| deserialization::datetime_utc_from_epoch_duration(std::time::Duration::from_micros((epoch_ms * 1_000.0) as u64)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use wednesday_model::error::SocketError;
+
+        use super::*;
+
+        #[test]
+        fn test_gateio_spot_trades() {
+            struct TestCase {
+                input: &'static str,
+                expected: Result<GateioSpotTrades, SocketError>,
+            }
+
+            let tests = vec![
+                // TC0: input GateioSpotTrades is deserialised
+                TestCase {
+                    input: r#"
+                    {
+                        "time": 1606292218,
+                        "time_ms": 1606292218231,
+                        "channel": "spot.trades",
+                        "event": "update",
+                        "result": {
+                            "id": 309143071,
+                            "create_time": 1606292218,
+                            "create_time_ms": "1606292218213.4578",
+                            "side": "sell",
+                            "currency_pair": "GT_USDT",
+                            "amount": "16.4700000000",
+                            "price": "0.4705000000",
+                            "range": "2390902-2390902"
+                        }
+                    }
+                    "#,
+                    expected: Ok(GateioMessage::Data(GateioSpotTrade {
+                        subscription_id: SubscriptionId::from("spot.trades|GT_USDT"),
+                        id: 309143071,
+                        time: deserialization::datetime_utc_from_epoch_duration(std::time::Duration::from_micros(1606292218213457)),
+                        side: AggressorSide::Sell,
+                        price: 0.4705,
+                        amount: 16.47,
+                    })),
+                },
+                // TC1: input GateioSpotTrades is invalid w/ non-numeric price
+                TestCase {
+                    input: r#"
+                    {
+                        "time": 1606292218,
+                        "channel": "spot.trades",
+                        "event": "update",
+                        "result": {
+                            "id": 309143071, "create_time_ms": "1606292218213.4578", "side": "buy",
+                            "currency_pair": "GT_USDT", "amount": "16.47", "price": "invalid"
+                        }
+                    }
+                    "#,
+                    expected: Err(SocketError::Unsupported {
+                        entity: "",
+                        item: "".to_string(),
+                    }),
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = serde_json::from_str::<GateioSpotTrades>(test.input);
+                match (actual, test.expected) {
+                    (Ok(actual), Ok(expected)) => {
+                        assert_eq!(actual, expected, "TC{} failed", index)
+                    },
+                    (Err(_), Err(_)) => {
+                        // Test passed
+                    },
+                    (actual, expected) => {
+                        // Test failed
+                        panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                    },
+                }
+            }
+        }
+    }
+}
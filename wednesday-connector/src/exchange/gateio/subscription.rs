@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+use wednesday_model::error::SocketError;
+
+use crate::subscriber::validator::Validator;
+
+/// [`Gateio`](super::Gateio) WebSocket subscription response, one is received per subscribe
+/// request.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.gate.io/docs/developers/apiv4/ws/en/#server-response>
+/// #### Subscription Success
+/// ```json
+/// {
+///     "time": 1606292218,
+///     "time_ms": 1606292218231,
+///     "channel": "spot.trades",
+///     "event": "subscribe",
+///     "result": {"status": "success"}
+/// }
+/// ```
+///
+/// #### Subscription Failure
+/// ```json
+/// {
+///     "time": 1606292218,
+///     "time_ms": 1606292218231,
+///     "channel": "spot.trades",
+///     "event": "subscribe",
+///     "error": {"code": 2, "message": "unknown currency pair GT_USDT"},
+///     "result": null
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(tag = "event", rename_all = "lowercase")]
+pub enum GateioSubscriptionResponse {
+    Subscribe {
+        channel: String,
+        #[serde(default)]
+        error: Option<GateioError>,
+    },
+    Unsubscribe {
+        channel: String,
+        #[serde(default)]
+        error: Option<GateioError>,
+    },
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct GateioError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl Validator for GateioSubscriptionResponse {
+    fn validate(self) -> Result<Self, SocketError>
+    where
+        Self: Sized,
+    {
+        match &self {
+            GateioSubscriptionResponse::Subscribe { error: None, .. } | GateioSubscriptionResponse::Unsubscribe { error: None, .. } => Ok(self),
+            GateioSubscriptionResponse::Subscribe {
+                channel,
+                error: Some(GateioError { code, message }),
+            }
+            | GateioSubscriptionResponse::Unsubscribe {
+                channel,
+                error: Some(GateioError { code, message }),
+            } => Err(SocketError::Subscribe(format!(
+                "received failure subscription response for channel: {channel} code: {code} with message: {message}",
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use super::*;
+
+        #[test]
+        fn test_gateio_sub_response() {
+            struct TestCase {
+                input: &'static str,
+                expected: Result<GateioSubscriptionResponse, SocketError>,
+            }
+
+            let cases = vec![
+                TestCase {
+                    // TC0: input response is Subscribe success
+                    input: r#"
+                    {
+                        "time": 1606292218,
+                        "time_ms": 1606292218231,
+                        "channel": "spot.trades",
+                        "event": "subscribe",
+                        "result": {"status": "success"}
+                    }
+                    "#,
+                    expected: Ok(GateioSubscriptionResponse::Subscribe {
+                        channel: "spot.trades".to_string(),
+                        error: None,
+                    }),
+                },
+                TestCase {
+                    // TC1: input response is Subscribe failure
+                    input: r#"
+                    {
+                        "time": 1606292218,
+                        "time_ms": 1606292218231,
+                        "channel": "spot.trades",
+                        "event": "subscribe",
+                        "error": {"code": 2, "message": "unknown currency pair GT_USDT"},
+                        "result": null
+                    }
+                    "#,
+                    expected: Ok(GateioSubscriptionResponse::Subscribe {
+                        channel: "spot.trades".to_string(),
+                        error: Some(GateioError {
+                            code: 2,
+                            message: "unknown currency pair GT_USDT".to_string(),
+                        }),
+                    }),
+                },
+                TestCase {
+                    // TC2: input is a pong
+                    input: r#"{"time": 1545404023, "time_ms": 1545404023123, "channel": "spot.pong", "event": "", "result": null}"#,
+                    expected: Err(SocketError::Subscribe("".to_string())),
+                },
+                TestCase {
+                    // TC3: input is a market data update
+                    input: r#"{"time": 1606292218, "channel": "spot.trades", "event": "update", "result": {}}"#,
+                    expected: Err(SocketError::Subscribe("".to_string())),
+                },
+                TestCase {
+                    // TC4: input response is Unsubscribe success
+                    input: r#"{"time": 1606292218, "channel": "spot.trades", "event": "unsubscribe", "result": {"status": "success"}}"#,
+                    expected: Ok(GateioSubscriptionResponse::Unsubscribe {
+                        channel: "spot.trades".to_string(),
+                        error: None,
+                    }),
+                },
+            ];
+
+            for (index, test) in cases.into_iter().enumerate() {
+                let actual = serde_json::from_str::<GateioSubscriptionResponse>(test.input);
+                match (actual, test.expected) {
+                    (Ok(actual), Ok(expected)) => {
+                        assert_eq!(actual, expected, "TC{} failed", index)
+                    },
+                    (Err(_), Err(_)) => {
+                        // Test passed
+                    },
+                    (actual, expected) => {
+                        // Test failed
+                        panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                    },
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_gateio_sub_response() {
+        struct TestCase {
+            input_response: GateioSubscriptionResponse,
+            is_valid: bool,
+        }
+
+        let cases = vec![
+            TestCase {
+                // TC0: input response is successful subscription
+                input_response: GateioSubscriptionResponse::Subscribe {
+                    channel: "spot.trades".to_string(),
+                    error: None,
+                },
+                is_valid: true,
+            },
+            TestCase {
+                // TC1: input response is failed subscription
+                input_response: GateioSubscriptionResponse::Subscribe {
+                    channel: "spot.trades".to_string(),
+                    error: Some(GateioError {
+                        code: 2,
+                        message: "unknown currency pair GT_USDT".to_string(),
+                    }),
+                },
+                is_valid: false,
+            },
+        ];
+
+        for (index, test) in cases.into_iter().enumerate() {
+            let actual = test.input_response.validate().is_ok();
+            assert_eq!(actual, test.is_valid, "TestCase {} failed", index);
+        }
+    }
+}
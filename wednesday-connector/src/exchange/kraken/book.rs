@@ -0,0 +1,415 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::value::RawValue;
+use tokio::sync::mpsc::UnboundedSender;
+use wednesday_model::{
+    enums::BookSide,
+    error::DataError,
+    instruments::Instrument,
+    orderbook::{Level, OrderBook, OrderBookSide},
+};
+
+use crate::{
+    exchange::checksum::LevelPrecision,
+    protocol::http::websocket::WsMessage,
+    transformer::updater::{InstrumentOrderBook, OrderBookUpdater},
+};
+
+use super::message::KrakenMessage;
+
+/// Depth of the [`Kraken`](super::Kraken) book subscription, which is also the number of levels
+/// per side included in the checksum.
+pub const KRAKEN_BOOK_DEPTH: usize = 10;
+
+pub type KrakenOrderBookL2 = KrakenMessage<KrakenBookData>;
+
+/// [`Kraken`](super::Kraken) L2 order book level.
+///
+/// The raw price & quantity JSON numbers are retained to learn the [`LevelPrecision`] of the
+/// instrument, since the exchange checksum is calculated from them with trailing zeros.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {"price": 0.05005, "qty": 0.00000500}
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Serialize)]
+pub struct KrakenLevel {
+    pub price: f64,
+    pub amount: f64,
+    pub raw_price: String,
+    pub raw_amount: String,
+}
+
+impl<'de> Deserialize<'de> for KrakenLevel {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct RawLevel {
+            price: Box<RawValue>,
+            qty: Box<RawValue>,
+        }
+
+        let RawLevel { price, qty } = RawLevel::deserialize(deserializer)?;
+
+        Ok(Self {
+            price: price.get().parse().map_err(serde::de::Error::custom)?,
+            amount: qty.get().parse().map_err(serde::de::Error::custom)?,
+            raw_price: price.get().to_owned(),
+            raw_amount: qty.get().to_owned(),
+        })
+    }
+}
+
+impl From<KrakenLevel> for Level {
+    fn from(level: KrakenLevel) -> Self {
+        Level::new(level.price, level.amount)
+    }
+}
+
+/// [`Kraken`](super::Kraken) "book" snapshot or update data.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.kraken.com/api/docs/websocket-v2/book>
+/// ```json
+/// {
+///     "symbol": "MATIC/USD",
+///     "bids": [{"price": 0.5657, "qty": 1098.3947558}],
+///     "asks": [],
+///     "checksum": 2114181697,
+///     "timestamp": "2023-10-06T17:35:55.440295Z"
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct KrakenBookData {
+    pub symbol: String,
+    pub bids: Vec<KrakenLevel>,
+    pub asks: Vec<KrakenLevel>,
+    pub checksum: u32,
+    #[serde(default)]
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+/// [`Kraken`](super::Kraken) L2 [`OrderBookUpdater`].
+///
+/// Keeps the book truncated to the subscribed depth, so the CRC32 checksum can be verified after
+/// each snapshot & update.
+///
+/// See docs: <https://docs.kraken.com/api/docs/guides/spot-ws-book-v2>
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct KrakenBookUpdater {
+    pub updates_processed: u64,
+    pub precision: LevelPrecision,
+}
+
+impl KrakenBookUpdater {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl OrderBookUpdater for KrakenBookUpdater {
+    type OrderBook = OrderBook;
+    type Update = KrakenOrderBookL2;
+
+    async fn init<Exchange, Kind>(_: UnboundedSender<WsMessage>, instrument: Instrument) -> Result<InstrumentOrderBook<Self>, DataError>
+    where
+        Exchange: Send,
+        Kind: Send,
+    {
+        // Kraken pushes a full snapshot as the first message after subscribing, so no REST
+        // snapshot is required to initialise the book
+        Ok(InstrumentOrderBook {
+            instrument,
+            updater: Self::new(),
+            book: OrderBook::default(),
+        })
+    }
+
+    fn update(&mut self, book: &mut Self::OrderBook, update: Self::Update) -> Result<Option<Self::OrderBook>, DataError> {
+        let payload = match update {
+            KrakenMessage::Data(payload) => payload,
+            KrakenMessage::Event => return Ok(None),
+        };
+        let is_snapshot = payload.kind == "snapshot";
+
+        for data in payload.data {
+            data.bids
+                .iter()
+                .chain(&data.asks)
+                .for_each(|level| self.precision.observe(&level.raw_price, &level.raw_amount));
+
+            if is_snapshot {
+                book.bids = OrderBookSide::new(BookSide::Bid, data.bids);
+                book.asks = OrderBookSide::new(BookSide::Ask, data.asks);
+            } else {
+                book.bids.upsert(data.bids);
+                book.asks.upsert(data.asks);
+            }
+
+            // Levels pushed out of the subscribed depth are not removed by Kraken
+            book.bids.truncate(KRAKEN_BOOK_DEPTH);
+            book.asks.truncate(KRAKEN_BOOK_DEPTH);
+
+            let actual = kraken_checksum(book, &self.precision);
+            if actual != data.checksum {
+                return Err(DataError::InvalidChecksum {
+                    expected: data.checksum as i64,
+                    actual: actual as i64,
+                });
+            }
+
+            self.updates_processed += 1;
+            book.last_update_ts = data.timestamp.unwrap_or_else(Utc::now);
+        }
+
+        Ok(Some(book.snapshot()))
+    }
+}
+
+/// Calculate the [`Kraken`](super::Kraken) CRC32 checksum of the top 10 asks followed by the top
+/// 10 bids of the book, formatted with the [`LevelPrecision`] of the instrument.
+///
+/// Each level contributes its price then quantity, with the decimal point & leading zeros
+/// removed, eg/ {"price": 0.05005, "qty": 0.00000500} -> "5005500".
+///
+/// See docs: <https://docs.kraken.com/api/docs/guides/spot-ws-book-v2>
+pub fn kraken_checksum(book: &OrderBook, precision: &LevelPrecision) -> u32 {
+    let mut hasher = crc32fast::Hasher::new();
+
+    book.asks
+        .top(KRAKEN_BOOK_DEPTH)
+        .chain(book.bids.top(KRAKEN_BOOK_DEPTH))
+        .flat_map(|level| precision.format(level))
+        .for_each(|field| hasher.update(field.replace('.', "").trim_start_matches('0').as_bytes()));
+
+    hasher.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::kraken::message::KrakenPayload;
+
+    fn level(price: &str, amount: &str) -> KrakenLevel {
+        KrakenLevel {
+            price: price.parse().unwrap(),
+            amount: amount.parse().unwrap(),
+            raw_price: price.to_string(),
+            raw_amount: amount.to_string(),
+        }
+    }
+
+    mod de {
+        use wednesday_model::{error::SocketError, identifiers::SubscriptionId};
+
+        use super::*;
+
+        #[test]
+        fn test_kraken_order_book_l2() {
+            struct TestCase {
+                input: &'static str,
+                expected: Result<KrakenOrderBookL2, SocketError>,
+            }
+
+            let tests = vec![
+                // TC0: input book snapshot is deserialised w/ raw numbers retained
+                TestCase {
+                    input: r#"
+                    {
+                        "channel": "book",
+                        "type": "snapshot",
+                        "data": [
+                            {
+                                "symbol": "MATIC/USD",
+                                "bids": [{"price": 0.5666, "qty": 4831.75496356}],
+                                "asks": [{"price": 0.5668, "qty": 4410.00000000}],
+                                "checksum": 2439117997
+                            }
+                        ]
+                    }
+                    "#,
+                    expected: Ok(KrakenOrderBookL2::Data(KrakenPayload {
+                        subscription_id: SubscriptionId::from("book|MATIC/USD"),
+                        kind: "snapshot".to_string(),
+                        data: vec![KrakenBookData {
+                            symbol: "MATIC/USD".to_string(),
+                            bids: vec![level("0.5666", "4831.75496356")],
+                            asks: vec![level("0.5668", "4410.00000000")],
+                            checksum: 2439117997,
+                            timestamp: None,
+                        }],
+                    })),
+                },
+                // TC1: input book update is deserialised
+                TestCase {
+                    input: r#"
+                    {
+                        "channel": "book",
+                        "type": "update",
+                        "data": [
+                            {
+                                "symbol": "MATIC/USD",
+                                "bids": [{"price": 0.5657, "qty": 1098.3947558}],
+                                "asks": [],
+                                "checksum": 2114181697,
+                                "timestamp": "2023-10-06T17:35:55.440295Z"
+                            }
+                        ]
+                    }
+                    "#,
+                    expected: Ok(KrakenOrderBookL2::Data(KrakenPayload {
+                        subscription_id: SubscriptionId::from("book|MATIC/USD"),
+                        kind: "update".to_string(),
+                        data: vec![KrakenBookData {
+                            symbol: "MATIC/USD".to_string(),
+                            bids: vec![level("0.5657", "1098.3947558")],
+                            asks: vec![],
+                            checksum: 2114181697,
+                            timestamp: Some("2023-10-06T17:35:55.440295Z".parse().unwrap()),
+                        }],
+                    })),
+                },
+                // TC2: input book update w/ string price is invalid
+                TestCase {
+                    input: r#"
+                    {
+                        "channel": "book",
+                        "type": "update",
+                        "data": [{"symbol": "MATIC/USD", "bids": [{"price": "0.5657", "qty": 1}], "asks": [], "checksum": 1}]
+                    }
+                    "#,
+                    expected: Err(SocketError::Unsupported {
+                        entity: "",
+                        item: "".to_string(),
+                    }),
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = serde_json::from_str::<KrakenOrderBookL2>(test.input);
+                match (actual, test.expected) {
+                    (Ok(actual), Ok(expected)) => {
+                        assert_eq!(actual, expected, "TC{} failed", index)
+                    },
+                    (Err(_), Err(_)) => {
+                        // Test passed
+                    },
+                    (actual, expected) => {
+                        // Test failed
+                        panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                    },
+                }
+            }
+        }
+    }
+
+    /// Checksum of the book with the levels, formatted as the raw levels.
+    fn checksum(bids: &[KrakenLevel], asks: &[KrakenLevel]) -> u32 {
+        let mut precision = LevelPrecision::default();
+        bids.iter().chain(asks).for_each(|level| precision.observe(&level.raw_price, &level.raw_amount));
+
+        let book = OrderBook {
+            bids: OrderBookSide::new(BookSide::Bid, bids.iter().cloned()),
+            asks: OrderBookSide::new(BookSide::Ask, asks.iter().cloned()),
+            ..OrderBook::default()
+        };
+        kraken_checksum(&book, &precision)
+    }
+
+    #[test]
+    fn test_kraken_checksum() {
+        let bids = vec![level("0.05000", "0.00000500")];
+        let asks = vec![level("0.05005", "0.00000500"), level("0.05010", "0.00000500")];
+
+        // crc32("5005500" + "5010500" + "5000500")
+        assert_eq!(checksum(&bids, &asks), 1725113685);
+
+        // Snapshot w/ a quantity padded to the instrument precision, where the checksum is
+        // crc32("56684410000000000" + "5666483175496356")
+        let snapshot = serde_json::from_str::<KrakenOrderBookL2>(
+            r#"{"channel": "book", "type": "snapshot", "data": [{"symbol": "MATIC/USD", "bids": [{"price": 0.5666, "qty": 4831.75496356}], "asks": [{"price": 0.5668, "qty": 4410.00000000}], "checksum": 2578388827}]}"#,
+        )
+        .unwrap();
+        assert!(KrakenBookUpdater::new().update(&mut OrderBook::default(), snapshot).is_ok());
+    }
+
+    fn book_message(kind: &str, bids: Vec<KrakenLevel>, asks: Vec<KrakenLevel>, checksum: u32) -> KrakenOrderBookL2 {
+        KrakenMessage::Data(KrakenPayload {
+            subscription_id: "book|BTC/USD".into(),
+            kind: kind.to_string(),
+            data: vec![KrakenBookData {
+                symbol: "BTC/USD".to_string(),
+                bids,
+                asks,
+                checksum,
+                timestamp: None,
+            }],
+        })
+    }
+
+    #[test]
+    fn test_kraken_book_updater_update() {
+        let mut updater = KrakenBookUpdater::new();
+        let mut book = OrderBook::default();
+
+        // Snapshot initialises the book
+        let (bids, asks) = (vec![level("0.05000", "0.00000500")], vec![level("0.05005", "0.00000500")]);
+        let snapshot = book_message("snapshot", bids.clone(), asks.clone(), checksum(&bids, &asks));
+        let output = updater.update(&mut book, snapshot).unwrap().unwrap();
+        assert_eq!(output.bids.levels(), vec![Level::new(0.05, 0.000005)]);
+        assert_eq!(output.asks.levels(), vec![Level::new(0.05005, 0.000005)]);
+
+        // Update removes & inserts levels, checksum reflects the post-update book
+        let expected_bids = vec![level("0.04990", "1.00000000")];
+        let expected_asks = vec![level("0.05005", "0.00000500"), level("0.05010", "2.00000000")];
+        let update = book_message(
+            "update",
+            vec![level("0.05000", "0.00000000"), level("0.04990", "1.00000000")],
+            vec![level("0.05010", "2.00000000")],
+            checksum(&expected_bids, &expected_asks),
+        );
+        let output = updater.update(&mut book, update).unwrap().unwrap();
+        assert_eq!(output.bids.levels(), vec![Level::new(0.0499, 1.0)]);
+        assert_eq!(output.asks.levels(), vec![Level::new(0.05005, 0.000005), Level::new(0.0501, 2.0)]);
+        assert_eq!(updater.updates_processed, 2);
+
+        // Heartbeats, status & pongs are ignored
+        assert_eq!(updater.update(&mut book, KrakenMessage::Event).unwrap(), None);
+    }
+
+    #[test]
+    fn test_kraken_book_updater_truncates_to_depth() {
+        let mut updater = KrakenBookUpdater::new();
+        let mut book = OrderBook::default();
+
+        let bids = (0..KRAKEN_BOOK_DEPTH).map(|index| level(&format!("{}", 100 - index), "1")).collect::<Vec<_>>();
+        let snapshot = book_message("snapshot", bids.clone(), vec![], checksum(&bids, &[]));
+        updater.update(&mut book, snapshot).unwrap();
+
+        // Better bid pushes the worst bid out of the subscribed depth
+        let mut expected_bids = bids.clone();
+        expected_bids.insert(0, level("101", "1"));
+        expected_bids.truncate(KRAKEN_BOOK_DEPTH);
+        let update = book_message("update", vec![level("101", "1")], vec![], checksum(&expected_bids, &[]));
+        let output = updater.update(&mut book, update).unwrap().unwrap();
+
+        assert_eq!(output.bids.levels().len(), KRAKEN_BOOK_DEPTH);
+        assert_eq!(output.bids.levels().last(), Some(&Level::new(92.0, 1.0)));
+    }
+
+    #[test]
+    fn test_kraken_book_updater_invalid_checksum() {
+        let mut updater = KrakenBookUpdater::new();
+        let mut book = OrderBook::default();
+
+        let snapshot = book_message("snapshot", vec![level("0.05000", "0.00000500")], vec![], 12345);
+        let error = updater.update(&mut book, snapshot).unwrap_err();
+
+        assert!(error.is_terminal());
+        assert!(matches!(error, DataError::InvalidChecksum { expected: 12345, .. }));
+    }
+}
@@ -0,0 +1,41 @@
+use wednesday_model::identifiers::Identifier;
+
+use crate::subscriber::subscription::{
+    kind::{OrderBooksL2, PublicTrades},
+    Subscription,
+};
+
+use super::Kraken;
+
+/// Type that defines how to translate a [`Subscription`] into a [`Kraken`] channel to be
+/// subscribed to.
+///
+/// See docs: <https://docs.kraken.com/api/docs/websocket-v2/trade>
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct KrakenChannel(pub &'static str);
+
+impl KrakenChannel {
+    /// [`Kraken`] real-time trades channel.
+    pub const TRADES: Self = Self("trade");
+    /// [`Kraken`] L2 channel, initial snapshot followed by incremental updates, each carrying a
+    /// CRC32 checksum of the top 10 levels.
+    pub const ORDER_BOOK_L2: Self = Self("book");
+}
+
+impl Identifier<KrakenChannel> for Subscription<Kraken, PublicTrades> {
+    fn id(&self) -> KrakenChannel {
+        KrakenChannel::TRADES
+    }
+}
+
+impl Identifier<KrakenChannel> for Subscription<Kraken, OrderBooksL2> {
+    fn id(&self) -> KrakenChannel {
+        KrakenChannel::ORDER_BOOK_L2
+    }
+}
+
+impl AsRef<str> for KrakenChannel {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use wednesday_model::{identifiers::Identifier, instruments::Symbol};
+
+use crate::subscriber::subscription::Subscription;
+
+use super::Kraken;
+
+/// Type that defines how to translate a [`Subscription`] into a [`Kraken`] symbol that can be
+/// subscribed to.
+///
+/// eg/ "BTC/USD"
+///
+/// See docs: <https://docs.kraken.com/api/docs/websocket-v2/instrument>
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct KrakenMarket(pub String);
+
+impl<Kind> Identifier<KrakenMarket> for Subscription<Kraken, Kind> {
+    fn id(&self) -> KrakenMarket {
+        KrakenMarket::new(&self.instrument.base_currency, &self.instrument.quote_currency)
+    }
+}
+
+impl KrakenMarket {
+    pub fn new(base_currency: &Symbol, quote_currency: &Symbol) -> Self {
+        Self(format!("{}/{}", base_currency, quote_currency).to_uppercase())
+    }
+}
+
+impl AsRef<str> for KrakenMarket {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
@@ -0,0 +1,166 @@
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use serde_json::value::RawValue;
+use wednesday_model::identifiers::{Identifier, SubscriptionId};
+
+use crate::subscriber::subscription::ExchangeSubscription;
+
+use super::channel::KrakenChannel;
+
+/// [`Kraken`](super::Kraken) WebSocket v2 message.
+///
+/// Every message received on the connection must be deserialisable, so heartbeats, status
+/// updates, pongs & late subscription responses are all consumed as [`KrakenMessage::Event`].
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.kraken.com/api/docs/websocket-v2/heartbeat>
+/// #### Heartbeat
+/// ```json
+/// {"channel": "heartbeat"}
+/// ```
+///
+/// #### Status
+/// ```json
+/// {
+///     "channel": "status",
+///     "type": "update",
+///     "data": [{"api_version": "v2", "connection_id": 1, "system": "online", "version": "2.0.0"}]
+/// }
+/// ```
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub enum KrakenMessage<T> {
+    Data(KrakenPayload<T>),
+    Event,
+}
+
+/// [`Kraken`](super::Kraken) market data message of a subscribed channel.
+///
+/// Note:
+/// Kraken sends a separate message per symbol, so the [`SubscriptionId`] is derived from the
+/// channel & the symbol of the data.
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct KrakenPayload<T> {
+    pub subscription_id: SubscriptionId,
+    /// "snapshot" or "update".
+    pub kind: String,
+    pub data: Vec<T>,
+}
+
+impl<T> Identifier<Option<SubscriptionId>> for KrakenMessage<T> {
+    fn id(&self) -> Option<SubscriptionId> {
+        match self {
+            KrakenMessage::Data(payload) => Some(payload.subscription_id.clone()),
+            KrakenMessage::Event => None,
+        }
+    }
+}
+
+impl<'de, T> Deserialize<'de> for KrakenMessage<T>
+where
+    T: DeserializeOwned,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Envelope {
+            channel: Option<String>,
+            #[serde(rename = "type")]
+            kind: Option<String>,
+            // Retained as raw JSON so the book level numbers are not re-formatted before
+            // their text is used to calculate the checksum
+            data: Option<Box<RawValue>>,
+        }
+
+        #[derive(Deserialize)]
+        struct Symbol<'a> {
+            symbol: &'a str,
+        }
+
+        let Envelope { channel, kind, data } = Envelope::deserialize(deserializer)?;
+
+        let (channel, kind, data) = match (channel, kind, data) {
+            (Some(channel), Some(kind), Some(data)) if [KrakenChannel::TRADES.0, KrakenChannel::ORDER_BOOK_L2.0].contains(&channel.as_str()) => {
+                (channel, kind, data)
+            },
+            _ => return Ok(Self::Event),
+        };
+
+        let symbol = serde_json::from_str::<Vec<Symbol<'_>>>(data.get())
+            .map_err(serde::de::Error::custom)?
+            .first()
+            .map(|symbol| symbol.symbol.to_owned())
+            .ok_or_else(|| serde::de::Error::custom("Kraken message contains no data"))?;
+
+        Ok(Self::Data(KrakenPayload {
+            subscription_id: ExchangeSubscription::from((channel.as_str(), symbol.as_str())).id(),
+            kind,
+            data: serde_json::from_str(data.get()).map_err(serde::de::Error::custom)?,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use super::*;
+
+        #[derive(Debug, PartialEq, Deserialize, Serialize)]
+        struct Data {
+            symbol: String,
+        }
+
+        #[test]
+        fn test_kraken_message() {
+            struct TestCase {
+                input: &'static str,
+                expected: Option<KrakenMessage<Data>>,
+            }
+
+            let tests = vec![
+                // TC0: heartbeat is an Event
+                TestCase {
+                    input: r#"{"channel": "heartbeat"}"#,
+                    expected: Some(KrakenMessage::Event),
+                },
+                // TC1: status is an Event
+                TestCase {
+                    input: r#"
+                    {
+                        "channel": "status",
+                        "type": "update",
+                        "data": [{"api_version": "v2", "connection_id": 1, "system": "online", "version": "2.0.0"}]
+                    }
+                    "#,
+                    expected: Some(KrakenMessage::Event),
+                },
+                // TC2: pong is an Event
+                TestCase {
+                    input: r#"{"method": "pong", "req_id": 1, "time_in": "2023-09-24T14:10:23.799685Z", "time_out": "2023-09-24T14:10:23.799703Z"}"#,
+                    expected: Some(KrakenMessage::Event),
+                },
+                // TC3: trade data is identified by channel & symbol
+                TestCase {
+                    input: r#"{"channel": "trade", "type": "update", "data": [{"symbol": "BTC/USD"}]}"#,
+                    expected: Some(KrakenMessage::Data(KrakenPayload {
+                        subscription_id: SubscriptionId::from("trade|BTC/USD"),
+                        kind: "update".to_string(),
+                        data: vec![Data { symbol: "BTC/USD".to_string() }],
+                    })),
+                },
+                // TC4: data w/o symbol is invalid
+                TestCase {
+                    input: r#"{"channel": "book", "type": "update", "data": []}"#,
+                    expected: None,
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = serde_json::from_str::<KrakenMessage<Data>>(test.input).ok();
+                assert_eq!(actual, test.expected, "TC{} failed", index);
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use tokio::time;
+use url::Url;
+use wednesday_macro::{DeExchange, SerExchange};
+use wednesday_model::{error::SocketError, identifiers::ExchangeId};
+
+use crate::{
+    protocol::http::websocket::{PingInterval, WsMessage},
+    stream::{protocol::ws_stream::ExchangeWsStream, selector::StreamSelector},
+    subscriber::{
+        protocol::websocket::WsSubscriber,
+        subscription::{
+            kind::{OrderBooksL2, PublicTrades},
+            ExchangeSubscription,
+        },
+        validator::WsSubscriptionValidator,
+    },
+    transformer::{stateful::MultiBookTransformer, stateless::StatelessTransformer},
+};
+
+use self::{
+    book::{KrakenBookUpdater, KRAKEN_BOOK_DEPTH},
+    channel::KrakenChannel,
+    market::KrakenMarket,
+    subscription::KrakenSubscriptionResponse,
+    trade::KrakenTrades,
+};
+
+use super::connector::Connector;
+
+pub mod book;
+pub mod channel;
+pub mod market;
+pub mod message;
+pub mod subscription;
+pub mod trade;
+
+pub const WEBSOCKET_BASE_URL_KRAKEN: &str = "wss://ws.kraken.com/v2";
+
+/// [`Kraken`] application-level ping interval.
+///
+/// See docs: <https://docs.kraken.com/api/docs/websocket-v2/ping>
+pub const PING_INTERVAL_KRAKEN: Duration = Duration::from_secs(30);
+
+/// [`Kraken`] spot WebSocket v2 public market data connector.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default, DeExchange, SerExchange)]
+pub struct Kraken;
+
+impl Connector for Kraken {
+    const ID: ExchangeId = ExchangeId::Kraken;
+    type Channel = KrakenChannel;
+    type Market = KrakenMarket;
+    type Subscriber = WsSubscriber;
+    type SubscriptionValidator = WsSubscriptionValidator;
+    type SubscriptionResponse = KrakenSubscriptionResponse;
+
+    fn url() -> Result<Url, SocketError> {
+        Url::parse(WEBSOCKET_BASE_URL_KRAKEN).map_err(SocketError::UrlParse)
+    }
+
+    fn requests(exchange_subscriptions: Vec<ExchangeSubscription<Self::Channel, Self::Market>>) -> Vec<WsMessage> {
+        kraken_requests("subscribe", exchange_subscriptions)
+    }
+
+    fn dynamic_subscriptions() -> bool {
+        true
+    }
+
+    fn unsubscribe_requests(exchange_subscriptions: Vec<ExchangeSubscription<Self::Channel, Self::Market>>) -> Vec<WsMessage> {
+        kraken_requests("unsubscribe", exchange_subscriptions)
+    }
+
+    fn ping_interval() -> Option<PingInterval> {
+        Some(PingInterval {
+            interval: time::interval(PING_INTERVAL_KRAKEN),
+            ping: || {
+                WsMessage::Text(
+                    serde_json::json!({
+                        "method": "ping"
+                    })
+                    .to_string(),
+                )
+            },
+        })
+    }
+}
+
+/// One request per subscription with the provided "subscribe" or "unsubscribe" method. The book
+/// depth must match the subscribed depth to be unsubscribed.
+fn kraken_requests(method: &str, exchange_subscriptions: Vec<ExchangeSubscription<KrakenChannel, KrakenMarket>>) -> Vec<WsMessage> {
+    exchange_subscriptions
+        .into_iter()
+        .map(|sub| {
+            let mut params = serde_json::json!({
+                "channel": sub.channel.as_ref(),
+                "symbol": [sub.market.as_ref()],
+            });
+            match sub.channel {
+                KrakenChannel::ORDER_BOOK_L2 => params["depth"] = serde_json::json!(KRAKEN_BOOK_DEPTH),
+                // Historic trades snapshot is not required
+                _ if method == "subscribe" => params["snapshot"] = serde_json::json!(false),
+                _ => {},
+            }
+
+            WsMessage::Text(
+                serde_json::json!({
+                    "method": method,
+                    "params": params
+                })
+                .to_string(),
+            )
+        })
+        .collect()
+}
+
+impl StreamSelector<PublicTrades> for Kraken {
+    type Stream = ExchangeWsStream<StatelessTransformer<Self, PublicTrades, KrakenTrades>>;
+}
+
+impl StreamSelector<OrderBooksL2> for Kraken {
+    type Stream = ExchangeWsStream<MultiBookTransformer<Self, OrderBooksL2, KrakenBookUpdater>>;
+}
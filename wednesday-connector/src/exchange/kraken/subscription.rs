@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+use wednesday_model::error::SocketError;
+
+use crate::subscriber::validator::Validator;
+
+/// [`Kraken`](super::Kraken) WebSocket subscription response, one is received per subscribed
+/// symbol.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.kraken.com/api/docs/websocket-v2/book>
+/// #### Subscription Success
+/// ```json
+/// {
+///     "method": "subscribe",
+///     "result": {"channel": "book", "depth": 10, "snapshot": true, "symbol": "BTC/USD"},
+///     "success": true,
+///     "time_in": "2023-09-25T09:04:31.742599Z",
+///     "time_out": "2023-09-25T09:04:31.742648Z"
+/// }
+/// ```
+///
+/// #### Subscription Failure
+/// ```json
+/// {
+///     "error": "Currency pair not supported XBT/USDD",
+///     "method": "subscribe",
+///     "success": false,
+///     "symbol": "XBT/USDD",
+///     "time_in": "2023-09-25T09:04:31.742599Z",
+///     "time_out": "2023-09-25T09:04:31.742648Z"
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct KrakenSubscriptionResponse {
+    pub method: String,
+    pub success: bool,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl Validator for KrakenSubscriptionResponse {
+    fn validate(self) -> Result<Self, SocketError>
+    where
+        Self: Sized,
+    {
+        if self.success && matches!(self.method.as_str(), "subscribe" | "unsubscribe") {
+            Ok(self)
+        } else {
+            Err(SocketError::Subscribe(format!(
+                "received failure subscription response for method: {} with error: {}",
+                self.method,
+                self.error.as_deref().unwrap_or_default(),
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use super::*;
+
+        #[test]
+        fn test_kraken_sub_response() {
+            struct TestCase {
+                input: &'static str,
+                expected: Result<KrakenSubscriptionResponse, SocketError>,
+            }
+
+            let cases = vec![
+                TestCase {
+                    // TC0: input response is Subscribed
+                    input: r#"
+                    {
+                        "method": "subscribe",
+                        "result": {"channel": "book", "depth": 10, "snapshot": true, "symbol": "BTC/USD"},
+                        "success": true,
+                        "time_in": "2023-09-25T09:04:31.742599Z",
+                        "time_out": "2023-09-25T09:04:31.742648Z"
+                    }
+                    "#,
+                    expected: Ok(KrakenSubscriptionResponse {
+                        method: "subscribe".to_string(),
+                        success: true,
+                        error: None,
+                    }),
+                },
+                TestCase {
+                    // TC1: input response is failed subscription
+                    input: r#"
+                    {
+                        "error": "Currency pair not supported XBT/USDD",
+                        "method": "subscribe",
+                        "success": false,
+                        "symbol": "XBT/USDD",
+                        "time_in": "2023-09-25T09:04:31.742599Z",
+                        "time_out": "2023-09-25T09:04:31.742648Z"
+                    }
+                    "#,
+                    expected: Ok(KrakenSubscriptionResponse {
+                        method: "subscribe".to_string(),
+                        success: false,
+                        error: Some("Currency pair not supported XBT/USDD".to_string()),
+                    }),
+                },
+                TestCase {
+                    // TC2: input is a status message
+                    input: r#"
+                    {
+                        "channel": "status",
+                        "type": "update",
+                        "data": [{"api_version": "v2", "connection_id": 1, "system": "online", "version": "2.0.0"}]
+                    }
+                    "#,
+                    expected: Err(SocketError::Subscribe("".to_string())),
+                },
+            ];
+
+            for (index, test) in cases.into_iter().enumerate() {
+                let actual = serde_json::from_str::<KrakenSubscriptionResponse>(test.input);
+                match (actual, test.expected) {
+                    (Ok(actual), Ok(expected)) => {
+                        assert_eq!(actual, expected, "TC{} failed", index)
+                    },
+                    (Err(_), Err(_)) => {
+                        // Test passed
+                    },
+                    (actual, expected) => {
+                        // Test failed
+                        panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                    },
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_kraken_sub_response() {
+        struct TestCase {
+            input_response: KrakenSubscriptionResponse,
+            is_valid: bool,
+        }
+
+        let cases = vec![
+            TestCase {
+                // TC0: input response is successful subscription
+                input_response: KrakenSubscriptionResponse {
+                    method: "subscribe".to_string(),
+                    success: true,
+                    error: None,
+                },
+                is_valid: true,
+            },
+            TestCase {
+                // TC1: input response is failed subscription
+                input_response: KrakenSubscriptionResponse {
+                    method: "subscribe".to_string(),
+                    success: false,
+                    error: Some("Currency pair not supported XBT/USDD".to_string()),
+                },
+                is_valid: false,
+            },
+            TestCase {
+                // TC2: input response is a successful pong
+                input_response: KrakenSubscriptionResponse {
+                    method: "pong".to_string(),
+                    success: true,
+                    error: None,
+                },
+                is_valid: false,
+            },
+            TestCase {
+                // TC3: input response is successful unsubscription
+                input_response: KrakenSubscriptionResponse {
+                    method: "unsubscribe".to_string(),
+                    success: true,
+                    error: None,
+                },
+                is_valid: true,
+            },
+        ];
+
+        for (index, test) in cases.into_iter().enumerate() {
+            let actual = test.input_response.validate().is_ok();
+            assert_eq!(actual, test.is_valid, "TestCase {} failed", index);
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use wednesday_model::{
+    enums::AggressorSide,
+    events::MarketEvent,
+    identifiers::{Exchange, ExchangeId},
+    instruments::Instrument,
+    trade::PublicTrade,
+};
+
+use crate::transformer::iterator::MarketIter;
+
+use super::message::KrakenMessage;
+
+pub type KrakenTrades = KrakenMessage<KrakenTrade>;
+
+/// [`Kraken`](super::Kraken) real-time trade.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.kraken.com/api/docs/websocket-v2/trade>
+/// ```json
+/// {
+///     "symbol": "MATIC/USD",
+///     "side": "sell",
+///     "price": 0.5117,
+///     "qty": 40.0,
+///     "ord_type": "market",
+///     "trade_id": 4665906,
+///     "timestamp": "2023-09-25T07:49:37.708706Z"
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct KrakenTrade {
+    pub symbol: String,
+    pub side: AggressorSide,
+    pub price: f64,
+    #[serde(rename = "qty")]
+    pub amount: f64,
+    #[serde(rename = "trade_id")]
+    pub id: u64,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl From<(ExchangeId, Instrument, KrakenTrades)> for MarketIter<PublicTrade> {
+    fn from((exchange_id, instrument, trades): (ExchangeId, Instrument, KrakenTrades)) -> Self {
+        let payload = match trades {
+            KrakenMessage::Data(payload) => payload,
+            KrakenMessage::Event => return Self(vec![]),
+        };
+
+        payload
+            .data
+            .into_iter()
+            .map(|trade| MarketEvent {
+                exchange_ts: trade.timestamp,
+                local_ts: Utc::now(),
+                exchange: Exchange::from(exchange_id),
+                instrument: instrument.clone(),
+                kind: PublicTrade {
+                    id: trade.id.to_string(),
+                    price: trade.price,
+                    quantity: trade.amount,
+                    aggressor_side: trade.side,
+                },
+            })
+            .map(Ok)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use wednesday_model::{error::SocketError, identifiers::SubscriptionId};
+
+        use super::*;
+        use crate::exchange::kraken::message::KrakenPayload;
+
+        #[test]
+        fn test_kraken_trades() {
+            struct TestCase {
+                input: &'static str,
+                expected: Result<KrakenTrades, SocketError>,
+            }
+
+            let tests = vec![
+                // TC0: input KrakenTrades is deserialised
+                TestCase {
+                    input: r#"
+                    {
+                        "channel": "trade",
+                        "type": "update",
+                        "data": [
+                            {
+                                "symbol": "MATIC/USD",
+                                "side": "sell",
+                                "price": 0.5117,
+                                "qty": 40.0,
+                                "ord_type": "market",
+                                "trade_id": 4665906,
+                                "timestamp": "2023-09-25T07:49:37.708706Z"
+                            }
+                        ]
+                    }
+                    "#,
+                    expected: Ok(KrakenTrades::Data(KrakenPayload {
+                        subscription_id: SubscriptionId::from("trade|MATIC/USD"),
+                        kind: "update".to_string(),
+                        data: vec![KrakenTrade {
+                            symbol: "MATIC/USD".to_string(),
+                            side: AggressorSide::Sell,
+                            price: 0.5117,
+                            amount: 40.0,
+                            id: 4665906,
+                            timestamp: "2023-09-25T07:49:37.708706Z".parse().unwrap(),
+                        }],
+                    })),
+                },
+                // TC1: input KrakenTrades is invalid w/ missing trade_id
+                TestCase {
+                    input: r#"
+                    {
+                        "channel": "trade",
+                        "type": "update",
+                        "data": [
+                            {
+                                "symbol": "MATIC/USD", "side": "buy", "price": 0.5117, "qty": 40.0,
+                                "ord_type": "limit", "timestamp": "2023-09-25T07:49:37.708706Z"
+                            }
+                        ]
+                    }
+                    "#,
+                    expected: Err(SocketError::Unsupported {
+                        entity: "",
+                        item: "".to_string(),
+                    }),
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = serde_json::from_str::<KrakenTrades>(test.input);
+                match (actual, test.expected) {
+                    (Ok(actual), Ok(expected)) => {
+                        assert_eq!(actual, expected, "TC{} failed", index)
+                    },
+                    (Err(_), Err(_)) => {
+                        // Test passed
+                    },
+                    (actual, expected) => {
+                        // Test failed
+                        panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                    },
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::UnboundedSender;
+use wednesday_model::{
+    enums::BookSide,
+    error::DataError,
+    instruments::Instrument,
+    orderbook::{Level, OrderBook, OrderBookSide},
+};
+
+use crate::{
+    protocol::http::websocket::WsMessage,
+    transformer::updater::{InstrumentOrderBook, OrderBookUpdater},
+};
+
+use super::{
+    channel::KrxChannel,
+    message::{parse_field, KrxMessage, KrxRecord},
+    session::{datetime_utc_from_kst, kst},
+};
+
+/// Number of price levels per side of a [`Krx`](super::Krx) order book record.
+pub const KRX_BOOK_DEPTH: usize = 10;
+
+pub type KrxOrderBookL2 = KrxMessage<KrxOrderBookSnapshot>;
+
+/// [`Krx`](super::Krx) real-time ten level stock order book (주식호가), a "H0STASP0" record.
+///
+/// Every record is a full snapshot of the top ten levels, empty levels have a zero price.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://apiportal.koreainvestment.com/apiservice/apiservice-domestic-stock-real2>
+/// ```text
+/// MKSC_SHRN_ISCD^BSOP_HOUR^HOUR_CLS_CODE^ASKP1..10^BIDP1..10^ASKP_RSQN1..10^BIDP_RSQN1..10^...
+/// 005930^093354^0^71900^72000^...^71800^71700^...^1000^2037^...^2000^4053^...
+/// ```
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct KrxOrderBookSnapshot {
+    pub code: String,
+    pub time: DateTime<Utc>,
+    /// Time classification (HOUR_CLS_CODE), "0" during continuous trading, otherwise the book
+    /// belongs to a single price auction & the expected price is being determined.
+    pub hour_class: String,
+    pub bids: Vec<Level>,
+    pub asks: Vec<Level>,
+}
+
+impl KrxOrderBookSnapshot {
+    /// Index of the KST time "HHMMSS" (BSOP_HOUR).
+    pub const TIME: usize = 1;
+    /// Index of the time classification (HOUR_CLS_CODE).
+    pub const HOUR_CLASS: usize = 2;
+    /// Index of the best ask price (ASKP1), followed by the next nine ask prices.
+    pub const ASK_PRICES: usize = 3;
+    /// Index of the best bid price (BIDP1), followed by the next nine bid prices.
+    pub const BID_PRICES: usize = Self::ASK_PRICES + KRX_BOOK_DEPTH;
+    /// Index of the best ask quantity (ASKP_RSQN1), followed by the next nine ask quantities.
+    pub const ASK_AMOUNTS: usize = Self::BID_PRICES + KRX_BOOK_DEPTH;
+    /// Index of the best bid quantity (BIDP_RSQN1), followed by the next nine bid quantities.
+    pub const BID_AMOUNTS: usize = Self::ASK_AMOUNTS + KRX_BOOK_DEPTH;
+
+    /// `true` if the book belongs to a single price auction.
+    pub fn is_auction(&self) -> bool {
+        self.hour_class != "0"
+    }
+
+    fn levels(fields: &[&str], prices: usize, amounts: usize) -> Result<Vec<Level>, String> {
+        (0..KRX_BOOK_DEPTH)
+            .map(|level| {
+                Ok(Level::new(
+                    parse_field::<f64>(fields, prices + level, "price")?,
+                    parse_field::<f64>(fields, amounts + level, "quantity")?,
+                ))
+            })
+            .filter(|level| !matches!(level, Ok(level) if level.price == 0.0))
+            .collect()
+    }
+}
+
+impl KrxRecord for KrxOrderBookSnapshot {
+    const CHANNEL: KrxChannel = KrxChannel::ORDER_BOOK_L2;
+    const FIELDS: usize = 59;
+
+    fn from_fields(fields: &[&str]) -> Result<Self, String> {
+        let time = NaiveTime::parse_from_str(fields[Self::TIME], "%H%M%S").map_err(|error| format!("invalid field BSOP_HOUR: {error}"))?;
+
+        Ok(Self {
+            code: fields[0].to_string(),
+            // Order book records do not include the business date
+            time: datetime_utc_from_kst(Utc::now().with_timezone(&kst()).date_naive(), time),
+            hour_class: fields[Self::HOUR_CLASS].to_string(),
+            bids: Self::levels(fields, Self::BID_PRICES, Self::BID_AMOUNTS)?,
+            asks: Self::levels(fields, Self::ASK_PRICES, Self::ASK_AMOUNTS)?,
+        })
+    }
+}
+
+/// [`Krx`](super::Krx) L2 [`OrderBookUpdater`], each record replaces the book.
+///
+/// Books published during single price auctions (see [`KrxOrderBookSnapshot::is_auction`])
+/// contain resting orders that are not matched until the auction ends, so they may be crossed.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct KrxBookUpdater {
+    pub updates_processed: u64,
+}
+
+impl KrxBookUpdater {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl OrderBookUpdater for KrxBookUpdater {
+    type OrderBook = OrderBook;
+    type Update = KrxOrderBookL2;
+
+    async fn init<Exchange, Kind>(_: UnboundedSender<WsMessage>, instrument: Instrument) -> Result<InstrumentOrderBook<Self>, DataError>
+    where
+        Exchange: Send,
+        Kind: Send,
+    {
+        // Every record is a full snapshot, so no REST snapshot is required to initialise the book
+        Ok(InstrumentOrderBook {
+            instrument,
+            updater: Self::new(),
+            book: OrderBook::default(),
+        })
+    }
+
+    fn update(&mut self, book: &mut Self::OrderBook, update: Self::Update) -> Result<Option<Self::OrderBook>, DataError> {
+        let snapshot = match update {
+            KrxMessage::Data { mut data, .. } => match data.pop() {
+                Some(snapshot) => snapshot,
+                None => return Ok(None),
+            },
+            KrxMessage::Event => return Ok(None),
+        };
+
+        book.last_update_ts = snapshot.time;
+        book.bids = OrderBookSide::new(BookSide::Bid, snapshot.bids);
+        book.asks = OrderBookSide::new(BookSide::Ask, snapshot.asks);
+        self.updates_processed += 1;
+
+        Ok(Some(book.snapshot()))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use serde::de::{value::StrDeserializer, IntoDeserializer};
+    use wednesday_model::identifiers::SubscriptionId;
+
+    use super::*;
+
+    /// Recorded "H0STASP0" real-time data message of Samsung Electronics (005930).
+    pub(crate) const RECORDED_KRX_ORDER_BOOK: &str = "0|H0STASP0|001|005930^093354^0^71900^72000^72100^72200^72300^72400^72500^72600^72700^72800^71800^71700^71600^71500^71400^71300^71200^71100^71000^70900^1000^2037^3074^4111^5148^6185^7222^8259^9296^10333^2000^4053^6106^8159^10212^12265^14318^16371^18424^20477^94370^169385^0^0^0^0^3052507^0^0^-1^0^0^-52^0^0^0";
+
+    fn deserialize(input: &str) -> KrxOrderBookL2 {
+        let deserializer: StrDeserializer<serde::de::value::Error> = input.into_deserializer();
+        <KrxOrderBookL2 as serde::Deserialize>::deserialize(deserializer).unwrap()
+    }
+
+    #[test]
+    fn test_krx_order_book_snapshot() {
+        let KrxMessage::Data { subscription_id, data } = deserialize(RECORDED_KRX_ORDER_BOOK) else {
+            panic!("expected KrxMessage::Data");
+        };
+
+        assert_eq!(subscription_id, SubscriptionId::from("H0STASP0|005930"));
+        assert_eq!(data.len(), 1);
+        assert!(!data[0].is_auction());
+        assert_eq!(data[0].bids.len(), KRX_BOOK_DEPTH);
+        assert_eq!(data[0].bids[0], Level::new(71800.0, 2000.0));
+        assert_eq!(data[0].asks[0], Level::new(71900.0, 1000.0));
+        assert_eq!(data[0].asks[9], Level::new(72800.0, 10333.0));
+    }
+
+    #[test]
+    fn test_update_krx_order_book_l2() {
+        let mut updater = KrxBookUpdater::new();
+        let mut book = OrderBook::default();
+
+        // Events are ignored
+        assert_eq!(updater.update(&mut book, KrxMessage::Event).unwrap(), None);
+
+        // Snapshot replaces the book
+        let output = updater.update(&mut book, deserialize(RECORDED_KRX_ORDER_BOOK)).unwrap().unwrap();
+        assert_eq!(output.bids.levels().len(), KRX_BOOK_DEPTH);
+        assert_eq!(output.bids.levels()[0], Level::new(71800.0, 2000.0));
+
+        // Empty levels (zero price) are removed
+        let sparse = RECORDED_KRX_ORDER_BOOK.replacen("^71800^71700^", "^0^71700^", 1);
+        let output = updater.update(&mut book, deserialize(&sparse)).unwrap().unwrap();
+        assert_eq!(output.bids.levels().len(), KRX_BOOK_DEPTH - 1);
+        assert_eq!(output.bids.levels()[0], Level::new(71700.0, 4053.0));
+        assert_eq!(updater.updates_processed, 2);
+    }
+}
@@ -0,0 +1,42 @@
+use wednesday_model::identifiers::Identifier;
+
+use crate::subscriber::subscription::{
+    kind::{OrderBooksL2, PublicTrades},
+    Subscription,
+};
+
+use super::Krx;
+
+/// Type that defines how to translate a [`Subscription`] into a [`Krx`] real-time transaction
+/// id ("tr_id") to be subscribed to.
+///
+/// See docs: <https://apiportal.koreainvestment.com/apiservice/apiservice-domestic-stock-real2>
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct KrxChannel(pub &'static str);
+
+impl KrxChannel {
+    /// [`Krx`] real-time stock trades (주식체결가) transaction.
+    pub const TRADES: Self = Self("H0STCNT0");
+    /// [`Krx`] real-time stock ten level order book (주식호가) transaction.
+    pub const ORDER_BOOK_L2: Self = Self("H0STASP0");
+    /// [`Krx`] application level heartbeat transaction.
+    pub const PING: Self = Self("PINGPONG");
+}
+
+impl<Server> Identifier<KrxChannel> for Subscription<Krx<Server>, PublicTrades> {
+    fn id(&self) -> KrxChannel {
+        KrxChannel::TRADES
+    }
+}
+
+impl<Server> Identifier<KrxChannel> for Subscription<Krx<Server>, OrderBooksL2> {
+    fn id(&self) -> KrxChannel {
+        KrxChannel::ORDER_BOOK_L2
+    }
+}
+
+impl AsRef<str> for KrxChannel {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
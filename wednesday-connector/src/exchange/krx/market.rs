@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use wednesday_model::identifiers::Identifier;
+
+use crate::subscriber::subscription::Subscription;
+
+use super::Krx;
+
+/// Type that defines how to translate a [`Subscription`] into a [`Krx`] market ("tr_key") that
+/// can be subscribed to.
+///
+/// Stocks are identified by their six character short code (단축코드), which is expected as the
+/// [`Instrument`](wednesday_model::instruments::Instrument) base currency, with "krw" as the
+/// quote currency, eg/ ("005930", "krw", InstrumentKind::Stock) for Samsung Electronics.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct KrxMarket(pub String);
+
+impl<Server, Kind> Identifier<KrxMarket> for Subscription<Krx<Server>, Kind> {
+    fn id(&self) -> KrxMarket {
+        KrxMarket(self.instrument.base_currency.as_ref().to_uppercase())
+    }
+}
+
+impl AsRef<str> for KrxMarket {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
@@ -0,0 +1,246 @@
+use std::{fmt, marker::PhantomData, str::FromStr};
+
+use serde::{
+    de::{IgnoredAny, MapAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+use wednesday_model::identifiers::{Identifier, SubscriptionId};
+
+use crate::subscriber::subscription::ExchangeSubscription;
+
+use super::channel::KrxChannel;
+
+/// Record of a [`Krx`](super::Krx) real-time transaction, parsed from its "^" separated fields.
+pub trait KrxRecord: Sized {
+    /// Real-time transaction the record belongs to.
+    const CHANNEL: KrxChannel;
+
+    /// Minimum number of fields in a record, newer fields appended by the broker are ignored.
+    const FIELDS: usize;
+
+    fn from_fields(fields: &[&str]) -> Result<Self, String>;
+}
+
+/// Parse the field at `index` of a [`KrxRecord`].
+pub fn parse_field<T>(fields: &[&str], index: usize, name: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    fields
+        .get(index)
+        .ok_or_else(|| format!("missing field {name} at index {index}"))?
+        .parse()
+        .map_err(|error| format!("invalid field {name}: {error}"))
+}
+
+/// [`Krx`](super::Krx) WebSocket message.
+///
+/// Real-time data is sent as "|" separated text rather than JSON, with a header of the encryption
+/// flag, transaction id & record count, followed by the "^" separated fields of every record.
+/// Every message received on the connection must be deserialisable, so JSON messages (eg/ late
+/// subscription responses & "PINGPONG" heartbeats) & encrypted transactions (only used for
+/// private execution notices) are consumed as [`KrxMessage::Event`].
+///
+/// ### Raw Payload Examples
+/// See docs: <https://apiportal.koreainvestment.com/apiservice/apiservice-domestic-stock-real2>
+/// #### Real-time Data
+/// ```text
+/// 0|H0STCNT0|001|005930^093354^71900^5^-100^...
+/// ```
+///
+/// #### Heartbeat
+/// ```json
+/// {"header": {"tr_id": "PINGPONG", "datetime": "20230612093354"}}
+/// ```
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub enum KrxMessage<T> {
+    Data { subscription_id: SubscriptionId, data: Vec<T> },
+    Event,
+}
+
+impl<T> Identifier<Option<SubscriptionId>> for KrxMessage<T> {
+    fn id(&self) -> Option<SubscriptionId> {
+        match self {
+            KrxMessage::Data { subscription_id, .. } => Some(subscription_id.clone()),
+            KrxMessage::Event => None,
+        }
+    }
+}
+
+impl<T> KrxMessage<T>
+where
+    T: KrxRecord,
+{
+    /// Parse a "|" separated real-time data message.
+    pub fn parse(payload: &str) -> Result<Self, String> {
+        let (encrypted, tr_id, count, body) = match payload.splitn(4, '|').collect::<Vec<_>>().as_slice() {
+            [encrypted, tr_id, count, body] => (*encrypted == "1", *tr_id, *count, *body),
+            _ => return Err(format!("invalid real-time data header: {payload}")),
+        };
+
+        if encrypted || tr_id != T::CHANNEL.as_ref() {
+            return Ok(Self::Event);
+        }
+
+        let count = count.parse::<usize>().map_err(|error| format!("invalid record count {count}: {error}"))?;
+        let fields = body.split('^').collect::<Vec<_>>();
+
+        if count == 0 || fields.len() % count != 0 || fields.len() / count < T::FIELDS {
+            return Err(format!(
+                "expected {count} records of at least {} fields, found {} fields",
+                T::FIELDS,
+                fields.len()
+            ));
+        }
+
+        // Every real-time record starts with the stock short code
+        let market = fields[0];
+        let records = fields.chunks(fields.len() / count);
+
+        Ok(Self::Data {
+            subscription_id: ExchangeSubscription::from((tr_id, market)).id(),
+            data: records.map(T::from_fields).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl<'de, T> Deserialize<'de> for KrxMessage<T>
+where
+    T: KrxRecord,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct MessageVisitor<T>(PhantomData<T>);
+
+        impl<'de, T> Visitor<'de> for MessageVisitor<T>
+        where
+            T: KrxRecord,
+        {
+            type Value = KrxMessage<T>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a real-time data string or a JSON event")
+            }
+
+            fn visit_str<E>(self, payload: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                KrxMessage::parse(payload).map_err(E::custom)
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+                Ok(KrxMessage::Event)
+            }
+        }
+
+        deserializer.deserialize_any(MessageVisitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use serde::de::{value::StrDeserializer, IntoDeserializer};
+
+        use super::*;
+
+        #[derive(Debug, PartialEq, Serialize)]
+        struct Data {
+            code: String,
+            price: f64,
+        }
+
+        impl KrxRecord for Data {
+            const CHANNEL: KrxChannel = KrxChannel::TRADES;
+            const FIELDS: usize = 2;
+
+            fn from_fields(fields: &[&str]) -> Result<Self, String> {
+                Ok(Self {
+                    code: fields[0].to_string(),
+                    price: parse_field(fields, 1, "price")?,
+                })
+            }
+        }
+
+        #[test]
+        fn test_krx_message() {
+            struct TestCase {
+                input: &'static str,
+                expected: Option<KrxMessage<Data>>,
+            }
+
+            let tests = vec![
+                // TC0: single record
+                TestCase {
+                    input: "0|H0STCNT0|001|005930^71900",
+                    expected: Some(KrxMessage::Data {
+                        subscription_id: SubscriptionId::from("H0STCNT0|005930"),
+                        data: vec![Data {
+                            code: "005930".to_string(),
+                            price: 71900.0,
+                        }],
+                    }),
+                },
+                // TC1: multiple records w/ appended fields
+                TestCase {
+                    input: "0|H0STCNT0|002|005930^71900^N^005930^72000^N",
+                    expected: Some(KrxMessage::Data {
+                        subscription_id: SubscriptionId::from("H0STCNT0|005930"),
+                        data: vec![
+                            Data {
+                                code: "005930".to_string(),
+                                price: 71900.0,
+                            },
+                            Data {
+                                code: "005930".to_string(),
+                                price: 72000.0,
+                            },
+                        ],
+                    }),
+                },
+                // TC2: encrypted transaction is an Event
+                TestCase {
+                    input: "1|H0STCNI0|001|ciphertext",
+                    expected: Some(KrxMessage::Event),
+                },
+                // TC3: record count does not match the fields
+                TestCase {
+                    input: "0|H0STCNT0|002|005930^71900^005930",
+                    expected: None,
+                },
+                // TC4: invalid field
+                TestCase {
+                    input: "0|H0STCNT0|001|005930^price",
+                    expected: None,
+                },
+                // TC5: missing header
+                TestCase {
+                    input: "005930^71900",
+                    expected: None,
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let deserializer: StrDeserializer<serde::de::value::Error> = test.input.into_deserializer();
+                let actual = KrxMessage::<Data>::deserialize(deserializer).ok();
+                assert_eq!(actual, test.expected, "TC{} failed", index);
+            }
+        }
+
+        #[test]
+        fn test_krx_message_json_event() {
+            let input = r#"{"header": {"tr_id": "PINGPONG", "datetime": "20230612093354"}}"#;
+            assert_eq!(serde_json::from_str::<KrxMessage<Data>>(input).unwrap(), KrxMessage::Event);
+        }
+    }
+}
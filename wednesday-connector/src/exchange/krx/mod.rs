@@ -0,0 +1,302 @@
+use std::marker::PhantomData;
+
+use chrono::Utc;
+use tokio::time;
+use tracing::warn;
+use url::Url;
+use wednesday_model::{error::SocketError, identifiers::ExchangeId};
+
+use crate::{
+    protocol::http::websocket::{PingInterval, WsMessage},
+    stream::{protocol::ws_stream::ExchangeWsStream, selector::StreamSelector},
+    subscriber::{
+        protocol::websocket::WsSubscriber,
+        subscription::{
+            kind::{OrderBooksL2, PublicTrades},
+            ExchangeSubscription,
+        },
+        validator::WsSubscriptionValidator,
+    },
+    transformer::{stateful::MultiBookTransformer, stateless::StatelessTransformer},
+};
+
+use self::{
+    book::KrxBookUpdater,
+    channel::KrxChannel,
+    market::KrxMarket,
+    parser::KrxWsParser,
+    session::{kst, KrxCalendar},
+    subscription::{approval_key_from_env, KrxSubscriptionRequest, KrxSubscriptionResponse, ENV_APPROVAL_KEY_KRX},
+    trade::KrxTrades,
+};
+
+use super::connector::{Connector, ExchangeServer};
+
+pub mod book;
+pub mod channel;
+pub mod market;
+pub mod message;
+pub mod parser;
+pub mod session;
+pub mod subscription;
+pub mod tick;
+pub mod trade;
+
+/// Real account WebSocket endpoint of the Korea Investment & Securities Open API.
+///
+/// See docs: <https://apiportal.koreainvestment.com/apiservice/apiservice-domestic-stock-real2>
+pub const WS_BASE_URL_KRX_REAL: &str = "ws://ops.koreainvestment.com:21000";
+
+/// Paper trading (모의투자) WebSocket endpoint of the Korea Investment & Securities Open API.
+pub const WS_BASE_URL_KRX_PAPER: &str = "ws://ops.koreainvestment.com:31000";
+
+/// Application level heartbeat interval of the [`Krx`] WebSocket.
+pub const PING_INTERVAL_KRX: std::time::Duration = std::time::Duration::from_secs(60);
+
+pub type KrxReal = Krx<KrxServerReal>;
+pub type KrxPaper = Krx<KrxServerPaper>;
+
+/// [`Krx`] equity (KOSPI & KOSDAQ) market data connector, via the real-time WebSocket of a
+/// domestic broker (Korea Investment & Securities Open API).
+///
+/// Generic over the [`ExchangeServer`] since real & paper trading accounts are served from
+/// different endpoints. Subscriptions require the approval key of the account, which is read
+/// from the "KRX_APPROVAL_KEY" environment variable.
+///
+/// See docs: <https://apiportal.koreainvestment.com/apiservice/apiservice-domestic-stock-real2>
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Krx<Server> {
+    server: PhantomData<Server>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct KrxServerReal;
+
+impl ExchangeServer for KrxServerReal {
+    const ID: ExchangeId = ExchangeId::Krx;
+
+    fn ws_url() -> &'static str {
+        WS_BASE_URL_KRX_REAL
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct KrxServerPaper;
+
+impl ExchangeServer for KrxServerPaper {
+    const ID: ExchangeId = ExchangeId::Krx;
+
+    fn ws_url() -> &'static str {
+        WS_BASE_URL_KRX_PAPER
+    }
+}
+
+impl<Server> Connector for Krx<Server>
+where
+    Server: ExchangeServer,
+{
+    const ID: ExchangeId = Server::ID;
+    type Channel = KrxChannel;
+    type Market = KrxMarket;
+    type Subscriber = WsSubscriber;
+    type SubscriptionValidator = WsSubscriptionValidator;
+    type SubscriptionResponse = KrxSubscriptionResponse;
+
+    fn url() -> Result<Url, SocketError> {
+        Url::parse(Server::ws_url()).map_err(SocketError::UrlParse)
+    }
+
+    fn requests(exchange_subscriptions: Vec<ExchangeSubscription<Self::Channel, Self::Market>>) -> Vec<WsMessage> {
+        // A missing approval key is rejected by the broker, which fails subscription validation
+        let approval_key = approval_key_from_env().unwrap_or_else(|| {
+            warn!(exchange = %Self::ID, "{ENV_APPROVAL_KEY_KRX} environment variable is not set");
+            String::new()
+        });
+
+        let session = KrxCalendar::default().session(Utc::now());
+        if !session.is_trading() {
+            warn!(exchange = %Self::ID, ?session, "subscribing outside of trading hours, no market data is published until the next session");
+        }
+
+        exchange_subscriptions
+            .into_iter()
+            .map(|sub| {
+                let request = KrxSubscriptionRequest::subscribe(&approval_key, sub.channel.as_ref(), sub.market.as_ref());
+                WsMessage::Text(serde_json::to_string(&request).expect("KrxSubscriptionRequest is serialisable"))
+            })
+            .collect()
+    }
+
+    fn ping_interval() -> Option<PingInterval> {
+        // Mirrors the "PINGPONG" heartbeat the server sends to idle connections
+        Some(PingInterval {
+            interval: time::interval(PING_INTERVAL_KRX),
+            ping: || {
+                WsMessage::Text(
+                    serde_json::json!({
+                        "header": {
+                            "tr_id": KrxChannel::PING.as_ref(),
+                            "datetime": Utc::now().with_timezone(&kst()).format("%Y%m%d%H%M%S").to_string(),
+                        }
+                    })
+                    .to_string(),
+                )
+            },
+        })
+    }
+}
+
+impl<Server> StreamSelector<PublicTrades> for Krx<Server>
+where
+    Server: ExchangeServer + Sync,
+{
+    type Stream = ExchangeWsStream<StatelessTransformer<Self, PublicTrades, KrxTrades>, KrxWsParser>;
+}
+
+impl<Server> StreamSelector<OrderBooksL2> for Krx<Server>
+where
+    Server: ExchangeServer + Sync,
+{
+    type Stream = ExchangeWsStream<MultiBookTransformer<Self, OrderBooksL2, KrxBookUpdater>, KrxWsParser>;
+}
+
+impl<'de, Server> serde::Deserialize<'de> for Krx<Server>
+where
+    Server: ExchangeServer,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let input = <&str as serde::Deserialize>::deserialize(deserializer)?;
+        let expected = Self::ID.as_str();
+
+        if input == Self::ID.as_str() {
+            Ok(Self::default())
+        } else {
+            Err(serde::de::Error::invalid_value(serde::de::Unexpected::Str(input), &expected))
+        }
+    }
+}
+
+impl<Server> serde::Serialize for Krx<Server>
+where
+    Server: ExchangeServer,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        let exchange_id = Self::ID.as_str();
+        serializer.serialize_str(exchange_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::OnceLock;
+
+    use futures::{SinkExt, StreamExt};
+    use tokio::net::TcpListener;
+    use wednesday_model::instruments::{Instrument, InstrumentKind};
+
+    use super::{book::tests::RECORDED_KRX_ORDER_BOOK, trade::tests::RECORDED_KRX_TRADE, *};
+    use crate::{stream::market::MarketStream, subscriber::subscription::Subscription};
+
+    /// Url of the local WebSocket stand-in, bound to a random port by [`serve_recorded_payloads`].
+    static WS_URL_KRX_LOCAL: OnceLock<String> = OnceLock::new();
+
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+    struct KrxServerLocal;
+
+    impl ExchangeServer for KrxServerLocal {
+        const ID: ExchangeId = ExchangeId::Krx;
+
+        fn ws_url() -> &'static str {
+            WS_URL_KRX_LOCAL.get().expect("local WebSocket stand-in is running")
+        }
+    }
+
+    type KrxLocal = Krx<KrxServerLocal>;
+
+    /// Start a local WebSocket stand-in for the broker, that accepts every subscription and then
+    /// replays a heartbeat & the recorded payload of the subscribed transaction.
+    async fn serve_recorded_payloads() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        WS_URL_KRX_LOCAL.set(format!("ws://{}", listener.local_addr().unwrap())).unwrap();
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut websocket = tokio_tungstenite::accept_async(stream).await.unwrap();
+
+                    let Some(Ok(WsMessage::Text(request))) = websocket.next().await else {
+                        return;
+                    };
+                    let request = serde_json::from_str::<serde_json::Value>(&request).unwrap();
+                    let (tr_id, tr_key) = (&request["body"]["input"]["tr_id"], &request["body"]["input"]["tr_key"]);
+
+                    let response = serde_json::json!({
+                        "header": {"tr_id": tr_id, "tr_key": tr_key, "encrypt": "N"},
+                        "body": {"rt_cd": "0", "msg_cd": "OPSP0000", "msg1": "SUBSCRIBE SUCCESS"}
+                    });
+                    let heartbeat = r#"{"header": {"tr_id": "PINGPONG", "datetime": "20230612093354"}}"#;
+                    let payload = match tr_id.as_str() {
+                        Some("H0STCNT0") => RECORDED_KRX_TRADE,
+                        _ => RECORDED_KRX_ORDER_BOOK,
+                    };
+
+                    for message in [response.to_string(), heartbeat.to_string(), payload.to_string()] {
+                        websocket.send(WsMessage::Text(message)).await.unwrap();
+                    }
+
+                    // Keep the connection open until the client disconnects
+                    while let Some(Ok(_)) = websocket.next().await {}
+                });
+            }
+        });
+    }
+
+    #[tokio::test]
+    async fn test_krx_streams_with_local_ws() {
+        serve_recorded_payloads().await;
+        let instrument = Instrument::from(("005930", "krw", InstrumentKind::Stock));
+
+        // Trades
+        let subscriptions = [Subscription::new(KrxLocal::default(), instrument.clone(), PublicTrades)];
+        let mut trades = <KrxLocal as StreamSelector<PublicTrades>>::Stream::init(&subscriptions).await.unwrap();
+
+        let trade = trades.next().await.unwrap().unwrap();
+        assert_eq!(trade.instrument, instrument);
+        assert_eq!(trade.kind.price, 71900.0);
+        assert_eq!(trade.kind.id, "20230612-3052507");
+
+        // Order books
+        let subscriptions = [Subscription::new(KrxLocal::default(), instrument.clone(), OrderBooksL2)];
+        let mut books = <KrxLocal as StreamSelector<OrderBooksL2>>::Stream::init(&subscriptions).await.unwrap();
+
+        let book = books.next().await.unwrap().unwrap();
+        assert_eq!(book.instrument, instrument);
+        assert_eq!(book.kind.bids.levels()[0].price, 71800.0);
+        assert_eq!(book.kind.asks.levels()[0].price, 71900.0);
+    }
+
+    #[test]
+    fn test_krx_requests() {
+        let requests = KrxReal::requests(vec![ExchangeSubscription::from((KrxChannel::TRADES, KrxMarket("005930".to_string())))]);
+        let request = match requests.as_slice() {
+            [WsMessage::Text(request)] => serde_json::from_str::<serde_json::Value>(request).unwrap(),
+            _ => panic!("exactly one text request was expected"),
+        };
+
+        assert_eq!(request["header"]["tr_type"], "1");
+        assert_eq!(request["body"]["input"]["tr_id"], "H0STCNT0");
+        assert_eq!(request["body"]["input"]["tr_key"], "005930");
+    }
+
+    #[test]
+    fn test_krx_id() {
+        assert_eq!(KrxReal::ID, ExchangeId::Krx);
+        assert_eq!(serde_json::to_string(&KrxPaper::default()).unwrap(), r#""krx""#);
+    }
+}
@@ -0,0 +1,75 @@
+use std::rc::Rc;
+
+use serde::{de::IntoDeserializer, Deserialize, Serialize};
+use tracing::debug;
+use wednesday_model::error::SocketError;
+
+use crate::{
+    protocol::http::websocket::{WsClient, WsError, WsMessage, WsParser},
+    stream::parser::StreamParser,
+};
+
+/// [`StreamParser`] for the [`Krx`](super::Krx) WebSocket.
+///
+/// Real-time data is sent as "|" separated text frames, eg/ "0|H0STCNT0|001|005930^...", which
+/// are deserialised from the raw string. Every other message is JSON, and is parsed by the
+/// [`WsParser`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct KrxWsParser;
+
+impl KrxWsParser {
+    /// `true` if the text frame is real-time data, which starts with the "0" (plain) or "1"
+    /// (encrypted) flag.
+    pub fn is_real_time_data(text: &str) -> bool {
+        text.starts_with("0|") || text.starts_with("1|")
+    }
+}
+
+impl StreamParser for KrxWsParser {
+    type Stream = WsClient;
+    type Message = WsMessage;
+    type Error = WsError;
+
+    fn parse<Output>(input: Rc<Result<Self::Message, Self::Error>>) -> Option<Result<Output, SocketError>>
+    where
+        Output: serde::de::DeserializeOwned,
+    {
+        match &*input {
+            Ok(WsMessage::Text(text)) if Self::is_real_time_data(text) => Some(Output::deserialize(text.as_str().into_deserializer()).map_err(
+                |error: serde::de::value::Error| {
+                    debug!(
+                        ?error,
+                        payload = ?text,
+                        action = "returning Some(Err(err))",
+                        "failed to deserialize Krx real-time data into domain specific Message"
+                    );
+                    SocketError::DeserializingJson {
+                        error: error.to_string(),
+                        payload: text.clone(),
+                    }
+                },
+            )),
+            _ => WsParser::parse(input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::krx::trade::KrxTrades;
+
+    #[test]
+    fn test_krx_ws_parser() {
+        // Real-time data is parsed from the raw text
+        let input = Rc::new(Ok(WsMessage::Text("0|H0STCNT0|001|005930^093354".to_string())));
+        assert!(matches!(
+            KrxWsParser::parse::<KrxTrades>(input),
+            Some(Err(SocketError::DeserializingJson { .. }))
+        ));
+
+        // JSON is parsed by the WsParser
+        let input = Rc::new(Ok(WsMessage::Text(r#"{"header": {"tr_id": "PINGPONG"}}"#.to_string())));
+        assert!(matches!(KrxWsParser::parse::<KrxTrades>(input), Some(Ok(KrxTrades::Event))));
+    }
+}
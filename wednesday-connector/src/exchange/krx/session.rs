@@ -0,0 +1,201 @@
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Korea Standard Time offset in seconds, Korea does not observe daylight saving time.
+pub const KST_OFFSET_SECS: i32 = 9 * 60 * 60;
+
+/// Korea Standard Time (UTC+09:00), the timezone of every [`Krx`](super::Krx) timestamp.
+pub fn kst() -> FixedOffset {
+    FixedOffset::east_opt(KST_OFFSET_SECS).expect("KST offset is within bounds")
+}
+
+/// Construct a [`DateTime<Utc>`] from a KST date & time, eg/ the "BSOP_DATE" & "STCK_CNTG_HOUR"
+/// fields of a [`Krx`](super::Krx) real-time record.
+pub fn datetime_utc_from_kst(date: NaiveDate, time: NaiveTime) -> DateTime<Utc> {
+    kst()
+        .from_local_datetime(&NaiveDateTime::new(date, time))
+        .single()
+        .expect("fixed offsets are never ambiguous")
+        .with_timezone(&Utc)
+}
+
+/// [`Krx`](super::Krx) equity market trading session.
+///
+/// | Session                  | KST           |
+/// |--------------------------|---------------|
+/// | `OpeningAuction`         | 08:30 - 09:00 |
+/// | `Continuous`             | 09:00 - 15:20 |
+/// | `ClosingAuction`         | 15:20 - 15:30 |
+/// | `PostMarketClosingPrice` | 15:40 - 16:00 |
+/// | `AfterHoursSinglePrice`  | 16:00 - 18:00 |
+///
+/// See docs: <https://global.krx.co.kr/contents/GLB/06/0602/0602010101/GLB0602010101.jsp>
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KrxSession {
+    Closed,
+    /// Single price call auction that determines the opening price, orders are accepted but
+    /// not matched. Pre-market closing price trading also runs from 08:30 to 08:40.
+    OpeningAuction,
+    /// Continuous double auction.
+    Continuous,
+    /// Single price call auction that determines the closing price.
+    ClosingAuction,
+    /// Off-hours trading at the closing price.
+    PostMarketClosingPrice,
+    /// Off-hours single price auctions, executed every 10 minutes.
+    AfterHoursSinglePrice,
+}
+
+impl KrxSession {
+    /// `true` if orders can be matched during the session.
+    pub fn is_trading(&self) -> bool {
+        !matches!(self, KrxSession::Closed)
+    }
+
+    /// `true` if orders are matched in single price call auctions, rather than continuously.
+    pub fn is_auction(&self) -> bool {
+        matches!(
+            self,
+            KrxSession::OpeningAuction | KrxSession::ClosingAuction | KrxSession::AfterHoursSinglePrice
+        )
+    }
+
+    /// Determine the [`KrxSession`] of a trading day at the provided KST time.
+    pub fn at_kst_time(time: NaiveTime) -> Self {
+        let hm = |hour, minute| NaiveTime::from_hms_opt(hour, minute, 0).expect("valid session boundary");
+
+        match time {
+            time if time < hm(8, 30) => KrxSession::Closed,
+            time if time < hm(9, 0) => KrxSession::OpeningAuction,
+            time if time < hm(15, 20) => KrxSession::Continuous,
+            time if time < hm(15, 30) => KrxSession::ClosingAuction,
+            time if time < hm(15, 40) => KrxSession::Closed,
+            time if time < hm(16, 0) => KrxSession::PostMarketClosingPrice,
+            time if time < hm(18, 0) => KrxSession::AfterHoursSinglePrice,
+            _ => KrxSession::Closed,
+        }
+    }
+}
+
+/// [`Krx`](super::Krx) trading calendar, used to determine the [`KrxSession`] at a point in
+/// time.
+///
+/// Weekends are always closed. Exchange holidays change every year, so they must be provided
+/// (see the KRX "Market Holidays" schedule), and irregular trading days (eg/ the delayed open on
+/// the national college entrance exam day) are not modelled.
+#[derive(Clone, Eq, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct KrxCalendar {
+    pub holidays: Vec<NaiveDate>,
+}
+
+impl KrxCalendar {
+    pub fn new<Iter>(holidays: Iter) -> Self
+    where
+        Iter: IntoIterator<Item = NaiveDate>,
+    {
+        Self {
+            holidays: holidays.into_iter().collect(),
+        }
+    }
+
+    /// `true` if the provided KST date is a trading day.
+    pub fn is_trading_day(&self, date: NaiveDate) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !self.holidays.contains(&date)
+    }
+
+    /// Determine the [`KrxSession`] at the provided time.
+    pub fn session(&self, time: DateTime<Utc>) -> KrxSession {
+        let time = time.with_timezone(&kst());
+
+        if self.is_trading_day(time.date_naive()) {
+            KrxSession::at_kst_time(time.time())
+        } else {
+            KrxSession::Closed
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kst_time(date: &str, time: &str) -> DateTime<Utc> {
+        datetime_utc_from_kst(
+            NaiveDate::parse_from_str(date, "%Y%m%d").unwrap(),
+            NaiveTime::parse_from_str(time, "%H%M%S").unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_datetime_utc_from_kst() {
+        assert_eq!(kst_time("20230612", "093354"), "2023-06-12T00:33:54Z".parse::<DateTime<Utc>>().unwrap());
+    }
+
+    #[test]
+    fn test_krx_calendar_session() {
+        struct TestCase {
+            input: DateTime<Utc>,
+            expected: KrxSession,
+        }
+
+        let calendar = KrxCalendar::new([NaiveDate::from_ymd_opt(2023, 6, 6).unwrap()]);
+
+        let tests = vec![
+            // TC0: before the opening auction
+            TestCase {
+                input: kst_time("20230612", "082959"),
+                expected: KrxSession::Closed,
+            },
+            // TC1: opening auction
+            TestCase {
+                input: kst_time("20230612", "083000"),
+                expected: KrxSession::OpeningAuction,
+            },
+            // TC2: continuous trading
+            TestCase {
+                input: kst_time("20230612", "093354"),
+                expected: KrxSession::Continuous,
+            },
+            // TC3: closing auction
+            TestCase {
+                input: kst_time("20230612", "152000"),
+                expected: KrxSession::ClosingAuction,
+            },
+            // TC4: break between the closing auction & off-hours trading
+            TestCase {
+                input: kst_time("20230612", "153500"),
+                expected: KrxSession::Closed,
+            },
+            // TC5: post-market closing price trading
+            TestCase {
+                input: kst_time("20230612", "154000"),
+                expected: KrxSession::PostMarketClosingPrice,
+            },
+            // TC6: after-hours single price auctions
+            TestCase {
+                input: kst_time("20230612", "175959"),
+                expected: KrxSession::AfterHoursSinglePrice,
+            },
+            // TC7: after the market close
+            TestCase {
+                input: kst_time("20230612", "180000"),
+                expected: KrxSession::Closed,
+            },
+            // TC8: weekend
+            TestCase {
+                input: kst_time("20230610", "100000"),
+                expected: KrxSession::Closed,
+            },
+            // TC9: holiday
+            TestCase {
+                input: kst_time("20230606", "100000"),
+                expected: KrxSession::Closed,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            assert_eq!(calendar.session(test.input), test.expected, "TC{} failed", index);
+        }
+    }
+}
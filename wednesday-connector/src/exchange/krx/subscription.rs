@@ -0,0 +1,243 @@
+use serde::{Deserialize, Serialize};
+use wednesday_model::error::SocketError;
+
+use crate::subscriber::validator::Validator;
+
+/// Environment variable containing the WebSocket approval key (웹소켓 접속키) of the broker
+/// account, issued by the "POST /oauth2/Approval" REST endpoint.
+pub const ENV_APPROVAL_KEY_KRX: &str = "KRX_APPROVAL_KEY";
+
+/// Load the [`Krx`](super::Krx) WebSocket approval key from the environment.
+pub fn approval_key_from_env() -> Option<String> {
+    std::env::var(ENV_APPROVAL_KEY_KRX).ok().filter(|key| !key.is_empty())
+}
+
+/// [`Krx`](super::Krx) real-time registration request, one is sent per subscription.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://apiportal.koreainvestment.com/apiservice/apiservice-domestic-stock-real2>
+/// ```json
+/// {
+///     "header": {"approval_key": "...", "custtype": "P", "tr_type": "1", "content-type": "utf-8"},
+///     "body": {"input": {"tr_id": "H0STCNT0", "tr_key": "005930"}}
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
+pub struct KrxSubscriptionRequest<'a> {
+    pub header: KrxRequestHeader<'a>,
+    pub body: KrxRequestBody<'a>,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
+pub struct KrxRequestHeader<'a> {
+    pub approval_key: &'a str,
+    /// Customer type, "P" for individuals.
+    pub custtype: &'static str,
+    /// "1" to register, "2" to deregister.
+    pub tr_type: &'static str,
+    #[serde(rename = "content-type")]
+    pub content_type: &'static str,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
+pub struct KrxRequestBody<'a> {
+    pub input: KrxRequestInput<'a>,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
+pub struct KrxRequestInput<'a> {
+    pub tr_id: &'a str,
+    pub tr_key: &'a str,
+}
+
+impl<'a> KrxSubscriptionRequest<'a> {
+    pub fn subscribe(approval_key: &'a str, tr_id: &'a str, tr_key: &'a str) -> Self {
+        Self {
+            header: KrxRequestHeader {
+                approval_key,
+                custtype: "P",
+                tr_type: "1",
+                content_type: "utf-8",
+            },
+            body: KrxRequestBody {
+                input: KrxRequestInput { tr_id, tr_key },
+            },
+        }
+    }
+}
+
+/// [`Krx`](super::Krx) real-time registration response, one is received per request.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://apiportal.koreainvestment.com/apiservice/apiservice-domestic-stock-real2>
+/// #### Subscription Success
+/// ```json
+/// {
+///     "header": {"tr_id": "H0STCNT0", "tr_key": "005930", "encrypt": "N"},
+///     "body": {
+///         "rt_cd": "0",
+///         "msg_cd": "OPSP0000",
+///         "msg1": "SUBSCRIBE SUCCESS",
+///         "output": {"iv": "0123456789abcdef", "key": "abcdefghijklmnopabcdefghijklmnop"}
+///     }
+/// }
+/// ```
+///
+/// #### Subscription Failure
+/// ```json
+/// {
+///     "header": {"tr_id": "H0STCNT0", "tr_key": "005930", "encrypt": "N"},
+///     "body": {"rt_cd": "1", "msg_cd": "OPSP0011", "msg1": "invalid approval : NOT FOUND"}
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct KrxSubscriptionResponse {
+    pub header: KrxResponseHeader,
+    pub body: KrxResponseBody,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct KrxResponseHeader {
+    pub tr_id: String,
+    #[serde(default)]
+    pub tr_key: String,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct KrxResponseBody {
+    /// Result code, "0" on success.
+    pub rt_cd: String,
+    pub msg_cd: String,
+    pub msg1: String,
+}
+
+impl Validator for KrxSubscriptionResponse {
+    fn validate(self) -> Result<Self, SocketError>
+    where
+        Self: Sized,
+    {
+        if self.body.rt_cd == "0" {
+            Ok(self)
+        } else {
+            Err(SocketError::Subscribe(format!(
+                "received failure subscription response for {}|{} code: {} with message: {}",
+                self.header.tr_id, self.header.tr_key, self.body.msg_cd, self.body.msg1,
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use super::*;
+
+        #[test]
+        fn test_krx_sub_response() {
+            struct TestCase {
+                input: &'static str,
+                expected: Result<KrxSubscriptionResponse, SocketError>,
+            }
+
+            let cases = vec![
+                TestCase {
+                    // TC0: input response is Subscribed
+                    input: r#"
+                    {
+                        "header": {"tr_id": "H0STCNT0", "tr_key": "005930", "encrypt": "N"},
+                        "body": {
+                            "rt_cd": "0", "msg_cd": "OPSP0000", "msg1": "SUBSCRIBE SUCCESS",
+                            "output": {"iv": "0123456789abcdef", "key": "abcdefghijklmnopabcdefghijklmnop"}
+                        }
+                    }
+                    "#,
+                    expected: Ok(KrxSubscriptionResponse {
+                        header: KrxResponseHeader {
+                            tr_id: "H0STCNT0".to_string(),
+                            tr_key: "005930".to_string(),
+                        },
+                        body: KrxResponseBody {
+                            rt_cd: "0".to_string(),
+                            msg_cd: "OPSP0000".to_string(),
+                            msg1: "SUBSCRIBE SUCCESS".to_string(),
+                        },
+                    }),
+                },
+                TestCase {
+                    // TC1: input response is failed subscription
+                    input: r#"
+                    {
+                        "header": {"tr_id": "H0STCNT0", "tr_key": "005930", "encrypt": "N"},
+                        "body": {"rt_cd": "1", "msg_cd": "OPSP0011", "msg1": "invalid approval : NOT FOUND"}
+                    }
+                    "#,
+                    expected: Ok(KrxSubscriptionResponse {
+                        header: KrxResponseHeader {
+                            tr_id: "H0STCNT0".to_string(),
+                            tr_key: "005930".to_string(),
+                        },
+                        body: KrxResponseBody {
+                            rt_cd: "1".to_string(),
+                            msg_cd: "OPSP0011".to_string(),
+                            msg1: "invalid approval : NOT FOUND".to_string(),
+                        },
+                    }),
+                },
+                TestCase {
+                    // TC2: input is a heartbeat
+                    input: r#"{"header": {"tr_id": "PINGPONG", "datetime": "20230612093354"}}"#,
+                    expected: Err(SocketError::Subscribe("".to_string())),
+                },
+            ];
+
+            for (index, test) in cases.into_iter().enumerate() {
+                let actual = serde_json::from_str::<KrxSubscriptionResponse>(test.input);
+                match (actual, test.expected) {
+                    (Ok(actual), Ok(expected)) => {
+                        assert_eq!(actual, expected, "TC{} failed", index)
+                    },
+                    (Err(_), Err(_)) => {
+                        // Test passed
+                    },
+                    (actual, expected) => {
+                        // Test failed
+                        panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                    },
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_krx_sub_response() {
+        let response = |rt_cd: &str| KrxSubscriptionResponse {
+            header: KrxResponseHeader {
+                tr_id: "H0STCNT0".to_string(),
+                tr_key: "005930".to_string(),
+            },
+            body: KrxResponseBody {
+                rt_cd: rt_cd.to_string(),
+                msg_cd: "OPSP0000".to_string(),
+                msg1: "".to_string(),
+            },
+        };
+
+        assert!(response("0").validate().is_ok());
+        assert!(response("1").validate().is_err());
+    }
+
+    #[test]
+    fn test_krx_subscription_request() {
+        let request = serde_json::to_value(KrxSubscriptionRequest::subscribe("key", "H0STCNT0", "005930")).unwrap();
+
+        assert_eq!(
+            request,
+            serde_json::json!({
+                "header": {"approval_key": "key", "custtype": "P", "tr_type": "1", "content-type": "utf-8"},
+                "body": {"input": {"tr_id": "H0STCNT0", "tr_key": "005930"}}
+            })
+        );
+    }
+}
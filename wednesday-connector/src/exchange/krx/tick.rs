@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+
+/// [`Krx`](super::Krx) KRW tick size (호가가격단위) rule.
+///
+/// Since 2023-01-25 KOSPI & KOSDAQ listed stocks share a single price banded tick size table,
+/// while exchange traded products have a fixed tick size.
+///
+/// | Stock Price (KRW)   | Tick Size (KRW) |
+/// |---------------------|-----------------|
+/// | < 2,000             | 1               |
+/// | 2,000 - 4,999       | 5               |
+/// | 5,000 - 19,999      | 10              |
+/// | 20,000 - 49,999     | 50              |
+/// | 50,000 - 199,999    | 100             |
+/// | 200,000 - 499,999   | 500             |
+/// | >= 500,000          | 1,000           |
+///
+/// See docs: <https://global.krx.co.kr/contents/GLB/06/0602/0602010101/GLB0602010101.jsp>
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KrxTickRule {
+    /// KOSPI & KOSDAQ listed stocks.
+    #[default]
+    Stock,
+    /// ETFs, ETNs & ELWs.
+    ExchangeTradedProduct,
+}
+
+impl KrxTickRule {
+    /// Tick size of the provided price, in KRW.
+    pub fn tick_size(&self, price: f64) -> f64 {
+        match self {
+            KrxTickRule::ExchangeTradedProduct => 5.0,
+            KrxTickRule::Stock => match price {
+                price if price < 2_000.0 => 1.0,
+                price if price < 5_000.0 => 5.0,
+                price if price < 20_000.0 => 10.0,
+                price if price < 50_000.0 => 50.0,
+                price if price < 200_000.0 => 100.0,
+                price if price < 500_000.0 => 500.0,
+                _ => 1_000.0,
+            },
+        }
+    }
+
+    /// `true` if the provided price is a positive multiple of its tick size.
+    pub fn is_valid_price(&self, price: f64) -> bool {
+        price > 0.0 && price % self.tick_size(price) == 0.0
+    }
+
+    /// Round the provided price down to the nearest valid price, eg/ for a bid.
+    pub fn round_down(&self, price: f64) -> f64 {
+        let tick_size = self.tick_size(price);
+        (price / tick_size).floor() * tick_size
+    }
+
+    /// Round the provided price up to the nearest valid price, eg/ for an ask.
+    ///
+    /// Note:
+    /// Rounding up may cross into a band with a larger tick size (eg/ 1,999.5 -> 2,000), so the
+    /// result is rounded again with the tick size of the rounded price.
+    pub fn round_up(&self, price: f64) -> f64 {
+        let tick_size = self.tick_size(price);
+        let rounded = (price / tick_size).ceil() * tick_size;
+
+        let tick_size = self.tick_size(rounded);
+        (rounded / tick_size).ceil() * tick_size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_krx_tick_size() {
+        struct TestCase {
+            input: (KrxTickRule, f64),
+            expected: f64,
+        }
+
+        let tests = vec![
+            // TC0: lowest stock band
+            TestCase {
+                input: (KrxTickRule::Stock, 1_999.0),
+                expected: 1.0,
+            },
+            // TC1: lower band boundary is inclusive
+            TestCase {
+                input: (KrxTickRule::Stock, 2_000.0),
+                expected: 5.0,
+            },
+            // TC2: 50,000 - 199,999 band
+            TestCase {
+                input: (KrxTickRule::Stock, 71_900.0),
+                expected: 100.0,
+            },
+            // TC3: highest stock band
+            TestCase {
+                input: (KrxTickRule::Stock, 500_000.0),
+                expected: 1_000.0,
+            },
+            // TC4: exchange traded products have a fixed tick size
+            TestCase {
+                input: (KrxTickRule::ExchangeTradedProduct, 71_900.0),
+                expected: 5.0,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            assert_eq!(test.input.0.tick_size(test.input.1), test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_krx_tick_rounding() {
+        let rule = KrxTickRule::Stock;
+
+        assert!(rule.is_valid_price(71_900.0));
+        assert!(!rule.is_valid_price(71_950.0));
+        assert!(!rule.is_valid_price(0.0));
+
+        assert_eq!(rule.round_down(71_950.0), 71_900.0);
+        assert_eq!(rule.round_up(71_950.0), 72_000.0);
+        assert_eq!(rule.round_down(2_003.0), 2_000.0);
+        assert_eq!(rule.round_up(1_999.5), 2_000.0);
+        assert_eq!(rule.round_up(49_990.0), 50_000.0);
+        assert_eq!(rule.round_up(199_950.0), 200_000.0);
+    }
+}
@@ -0,0 +1,146 @@
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::Serialize;
+use wednesday_model::{
+    enums::AggressorSide,
+    events::MarketEvent,
+    identifiers::{Exchange, ExchangeId},
+    instruments::Instrument,
+    trade::PublicTrade,
+};
+
+use crate::transformer::iterator::MarketIter;
+
+use super::{
+    channel::KrxChannel,
+    message::{parse_field, KrxMessage, KrxRecord},
+    session::datetime_utc_from_kst,
+};
+
+pub type KrxTrades = KrxMessage<KrxTrade>;
+
+/// [`Krx`](super::Krx) real-time stock trade (주식체결가), a "H0STCNT0" record.
+///
+/// Trades have no exchange id, so the id is derived from the business date & the cumulative
+/// volume, which increases with every trade of the day.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://apiportal.koreainvestment.com/apiservice/apiservice-domestic-stock-real2>
+/// ```text
+/// MKSC_SHRN_ISCD^STCK_CNTG_HOUR^STCK_PRPR^...^CNTG_VOL^ACML_VOL^...^CCLD_DVSN^...^BSOP_DATE^...
+/// 005930^093354^71900^5^-100^-0.14^72023.83^72100^72400^71700^71900^71800^1^3052507^...^5^...^20230612^...
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Serialize)]
+pub struct KrxTrade {
+    pub code: String,
+    pub time: DateTime<Utc>,
+    pub price: f64,
+    pub amount: f64,
+    pub side: AggressorSide,
+    pub cumulative_volume: u64,
+}
+
+impl KrxTrade {
+    /// Index of the stock short code (MKSC_SHRN_ISCD).
+    pub const CODE: usize = 0;
+    /// Index of the KST trade time "HHMMSS" (STCK_CNTG_HOUR).
+    pub const TIME: usize = 1;
+    /// Index of the trade price (STCK_PRPR).
+    pub const PRICE: usize = 2;
+    /// Index of the trade volume (CNTG_VOL).
+    pub const AMOUNT: usize = 12;
+    /// Index of the cumulative volume of the day (ACML_VOL).
+    pub const CUMULATIVE_VOLUME: usize = 13;
+    /// Index of the trade classification (CCLD_DVSN), "1" buy, "3" auction & "5" sell.
+    pub const SIDE: usize = 21;
+    /// Index of the KST business date "YYYYMMDD" (BSOP_DATE).
+    pub const DATE: usize = 33;
+}
+
+impl KrxRecord for KrxTrade {
+    const CHANNEL: KrxChannel = KrxChannel::TRADES;
+    const FIELDS: usize = 46;
+
+    fn from_fields(fields: &[&str]) -> Result<Self, String> {
+        let date = NaiveDate::parse_from_str(fields[Self::DATE], "%Y%m%d").map_err(|error| format!("invalid field BSOP_DATE: {error}"))?;
+        let time = NaiveTime::parse_from_str(fields[Self::TIME], "%H%M%S").map_err(|error| format!("invalid field STCK_CNTG_HOUR: {error}"))?;
+
+        Ok(Self {
+            code: fields[Self::CODE].to_string(),
+            time: datetime_utc_from_kst(date, time),
+            price: parse_field(fields, Self::PRICE, "STCK_PRPR")?,
+            amount: parse_field(fields, Self::AMOUNT, "CNTG_VOL")?,
+            side: match fields[Self::SIDE] {
+                "1" => AggressorSide::Buy,
+                "5" => AggressorSide::Sell,
+                // Auction trades are matched at a single price, without an aggressor
+                _ => AggressorSide::None,
+            },
+            cumulative_volume: parse_field(fields, Self::CUMULATIVE_VOLUME, "ACML_VOL")?,
+        })
+    }
+}
+
+impl From<(ExchangeId, Instrument, KrxTrades)> for MarketIter<PublicTrade> {
+    fn from((exchange_id, instrument, trades): (ExchangeId, Instrument, KrxTrades)) -> Self {
+        let trades = match trades {
+            KrxMessage::Data { data, .. } => data,
+            KrxMessage::Event => return Self(vec![]),
+        };
+
+        trades
+            .into_iter()
+            .map(|trade| MarketEvent {
+                exchange_ts: trade.time,
+                local_ts: Utc::now(),
+                exchange: Exchange::from(exchange_id),
+                instrument: instrument.clone(),
+                kind: PublicTrade {
+                    id: format!("{}-{}", trade.time.format("%Y%m%d"), trade.cumulative_volume),
+                    price: trade.price,
+                    quantity: trade.amount,
+                    aggressor_side: trade.side,
+                },
+            })
+            .map(Ok)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use wednesday_model::identifiers::SubscriptionId;
+
+    use super::*;
+
+    /// Recorded "H0STCNT0" real-time data message of Samsung Electronics (005930).
+    pub(crate) const RECORDED_KRX_TRADE: &str = "0|H0STCNT0|001|005930^093354^71900^5^-100^-0.14^72023.83^72100^72400^71700^71900^71800^1^3052507^219853241700^5105^6937^1832^84.90^1366314^1159958^5^0.89^35.86^090000^5^-200^090211^5^-500^092806^2^200^20230612^20^N^65945^216924^1118750^2199171^0.05^2424638^125.90^0^^72100";
+
+    mod de {
+        use serde::{
+            de::{value::StrDeserializer, IntoDeserializer},
+            Deserialize,
+        };
+
+        use super::*;
+
+        #[test]
+        fn test_krx_trades() {
+            let deserializer: StrDeserializer<serde::de::value::Error> = RECORDED_KRX_TRADE.into_deserializer();
+
+            assert_eq!(
+                KrxTrades::deserialize(deserializer).unwrap(),
+                KrxMessage::Data {
+                    subscription_id: SubscriptionId::from("H0STCNT0|005930"),
+                    data: vec![KrxTrade {
+                        code: "005930".to_string(),
+                        time: "2023-06-12T00:33:54Z".parse().unwrap(),
+                        price: 71900.0,
+                        amount: 1.0,
+                        side: AggressorSide::Sell,
+                        cumulative_volume: 3052507,
+                    }],
+                }
+            );
+        }
+    }
+}
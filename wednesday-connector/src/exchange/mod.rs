@@ -1,5 +1,13 @@
 pub mod binance;
+pub mod bitfinex;
+pub mod bitmex;
 pub mod bybit;
+pub mod coinbase;
+pub mod gateio;
+pub mod kraken;
+pub mod krx;
+pub mod okx;
 
 pub mod channel;
+pub mod checksum;
 pub mod connector;
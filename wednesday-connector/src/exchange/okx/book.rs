@@ -0,0 +1,456 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use tokio::sync::mpsc::UnboundedSender;
+use wednesday_model::{
+    deserialization,
+    enums::BookSide,
+    error::DataError,
+    instruments::Instrument,
+    orderbook::{Level, OrderBook, OrderBookSide},
+};
+
+use crate::{
+    exchange::checksum::LevelPrecision,
+    protocol::http::websocket::WsMessage,
+    transformer::updater::{InstrumentOrderBook, OrderBookUpdater},
+};
+
+use super::message::OkxMessage;
+
+/// Number of levels per side included in the [`Okx`](super::Okx) order book checksum.
+pub const OKX_CHECKSUM_DEPTH: usize = 25;
+
+pub type OkxOrderBookL2 = OkxMessage<OkxOrderBookData>;
+
+/// [`Okx`](super::Okx) L2 order book level.
+///
+/// The raw price & amount strings are retained to learn the [`LevelPrecision`] the exchange
+/// formats them with, since its checksum is calculated from them verbatim.
+///
+/// ### Raw Payload Examples
+/// ["price", "size", "deprecated", "number of orders"]
+/// ```json
+/// ["8476.98", "415", "0", "13"]
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Serialize)]
+pub struct OkxLevel {
+    pub price: f64,
+    pub amount: f64,
+    pub raw_price: String,
+    pub raw_amount: String,
+}
+
+impl<'de> Deserialize<'de> for OkxLevel {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut fields = Vec::<String>::deserialize(deserializer)?.into_iter();
+
+        let (raw_price, raw_amount) = match (fields.next(), fields.next()) {
+            (Some(price), Some(amount)) => (price, amount),
+            _ => return Err(serde::de::Error::custom("OkxLevel requires at least price & size")),
+        };
+
+        Ok(Self {
+            price: raw_price.parse().map_err(serde::de::Error::custom)?,
+            amount: raw_amount.parse().map_err(serde::de::Error::custom)?,
+            raw_price,
+            raw_amount,
+        })
+    }
+}
+
+impl From<OkxLevel> for Level {
+    fn from(level: OkxLevel) -> Self {
+        Level::new(level.price, level.amount)
+    }
+}
+
+/// [`Okx`](super::Okx) "books" & "books5" order book data.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.okx.com/docs-v5/en/#order-book-trading-market-data-ws-order-book-channel>
+/// #### books snapshot
+/// ```json
+/// {
+///     "asks": [["8476.98", "415", "0", "13"]],
+///     "bids": [["8476.97", "256", "0", "12"]],
+///     "ts": "1597026383085",
+///     "checksum": -855196043,
+///     "prevSeqId": -1,
+///     "seqId": 123456
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OkxOrderBookData {
+    pub asks: Vec<OkxLevel>,
+    pub bids: Vec<OkxLevel>,
+    #[serde(deserialize_with = "deserialization::de_str_u64_epoch_ms_as_datetime_utc")]
+    pub ts: DateTime<Utc>,
+    #[serde(default)]
+    pub checksum: Option<i32>,
+    #[serde(default)]
+    pub prev_seq_id: Option<i64>,
+    #[serde(default)]
+    pub seq_id: Option<i64>,
+}
+
+/// [`Okx`](super::Okx) L2 [`OrderBookUpdater`] used for both the "books" & "books5" channels.
+///
+/// A "books5" push (no action) or a "books" snapshot replaces the book, whereas a "books" update
+/// is upserted into the book after its prevSeqId is validated. The book is then validated against
+/// the exchange checksum.
+///
+/// See docs: <https://www.okx.com/docs-v5/en/#order-book-trading-market-data-ws-order-book-channel>
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct OkxBookUpdater {
+    pub updates_processed: u64,
+    pub last_seq_id: Option<i64>,
+    pub precision: LevelPrecision,
+}
+
+impl OkxBookUpdater {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl OrderBookUpdater for OkxBookUpdater {
+    type OrderBook = OrderBook;
+    type Update = OkxOrderBookL2;
+
+    async fn init<Exchange, Kind>(_: UnboundedSender<WsMessage>, instrument: Instrument) -> Result<InstrumentOrderBook<Self>, DataError>
+    where
+        Exchange: Send,
+        Kind: Send,
+    {
+        // Okx pushes a full snapshot as the first message after subscribing, so no REST
+        // snapshot is required to initialise the book
+        Ok(InstrumentOrderBook {
+            instrument,
+            updater: Self::new(),
+            book: OrderBook::default(),
+        })
+    }
+
+    fn update(&mut self, book: &mut Self::OrderBook, update: Self::Update) -> Result<Option<Self::OrderBook>, DataError> {
+        let is_delta = update.action.as_deref() == Some("update");
+
+        for data in update.data {
+            data.bids
+                .iter()
+                .chain(&data.asks)
+                .for_each(|level| self.precision.observe(&level.raw_price, &level.raw_amount));
+
+            if is_delta {
+                // Validate the update prevSeqId follows on from the last processed seqId
+                if let (Some(last_seq_id), Some(prev_seq_id)) = (self.last_seq_id, data.prev_seq_id) {
+                    if last_seq_id != prev_seq_id {
+                        return Err(DataError::InvalidSequence {
+                            prev_last_update_id: last_seq_id as u64,
+                            first_update_id: prev_seq_id as u64,
+                        });
+                    }
+                }
+                book.bids.upsert(data.bids);
+                book.asks.upsert(data.asks);
+            } else {
+                book.bids = OrderBookSide::new(BookSide::Bid, data.bids);
+                book.asks = OrderBookSide::new(BookSide::Ask, data.asks);
+            }
+
+            if let Some(expected) = data.checksum {
+                let actual = okx_checksum(book, &self.precision);
+                if actual != expected {
+                    return Err(DataError::InvalidChecksum {
+                        expected: expected as i64,
+                        actual: actual as i64,
+                    });
+                }
+            }
+
+            self.updates_processed += 1;
+            self.last_seq_id = data.seq_id;
+            book.last_update_ts = data.ts;
+            book.update_id = data.seq_id.map(|seq_id| seq_id as u64);
+        }
+
+        Ok(Some(book.snapshot()))
+    }
+}
+
+/// Calculate the [`Okx`](super::Okx) CRC32 checksum of the top 25 levels of each side of the
+/// book, formatted with the [`LevelPrecision`] of the exchange.
+///
+/// Levels are interleaved as "bid_price:bid_size:ask_price:ask_size:..." and, once a side is
+/// exhausted, the remaining levels of the other side are appended on their own.
+///
+/// See docs: <https://www.okx.com/docs-v5/en/#order-book-trading-market-data-ws-order-book-channel>
+pub fn okx_checksum(book: &OrderBook, precision: &LevelPrecision) -> i32 {
+    let mut bids = book.bids.top(OKX_CHECKSUM_DEPTH);
+    let mut asks = book.asks.top(OKX_CHECKSUM_DEPTH);
+    let mut fields = Vec::with_capacity(OKX_CHECKSUM_DEPTH * 4);
+
+    loop {
+        match (bids.next(), asks.next()) {
+            (None, None) => break,
+            (bid, ask) => fields.extend([bid, ask].into_iter().flatten().flat_map(|level| precision.format(level))),
+        }
+    }
+
+    crc32fast::hash(fields.join(":").as_bytes()) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(price: &str, amount: &str) -> OkxLevel {
+        OkxLevel {
+            price: price.parse().unwrap(),
+            amount: amount.parse().unwrap(),
+            raw_price: price.to_string(),
+            raw_amount: amount.to_string(),
+        }
+    }
+
+    mod de {
+        use std::time::Duration;
+
+        use wednesday_model::{deserialization::datetime_utc_from_epoch_duration, error::SocketError, identifiers::SubscriptionId};
+
+        use super::*;
+
+        #[test]
+        fn test_okx_order_book_l2() {
+            struct TestCase {
+                input: &'static str,
+                expected: Result<OkxOrderBookL2, SocketError>,
+            }
+
+            let tests = vec![
+                // TC0: input "books" snapshot is deserialised
+                TestCase {
+                    input: r#"
+                    {
+                        "arg": {"channel": "books", "instId": "BTC-USDT"},
+                        "action": "snapshot",
+                        "data": [
+                            {
+                                "asks": [["8476.98", "415", "0", "13"], ["8477", "7", "0", "2"]],
+                                "bids": [["8476.97", "256", "0", "12"]],
+                                "ts": "1597026383085",
+                                "checksum": -855196043,
+                                "prevSeqId": -1,
+                                "seqId": 123456
+                            }
+                        ]
+                    }
+                    "#,
+                    expected: Ok(OkxOrderBookL2 {
+                        subscription_id: SubscriptionId::from("books|BTC-USDT"),
+                        action: Some("snapshot".to_string()),
+                        data: vec![OkxOrderBookData {
+                            asks: vec![level("8476.98", "415"), level("8477", "7")],
+                            bids: vec![level("8476.97", "256")],
+                            ts: datetime_utc_from_epoch_duration(Duration::from_millis(1597026383085)),
+                            checksum: Some(-855196043),
+                            prev_seq_id: Some(-1),
+                            seq_id: Some(123456),
+                        }],
+                    }),
+                },
+                // TC1: input perpetual swap "books5" push is deserialised
+                TestCase {
+                    input: r#"
+                    {
+                        "arg": {"channel": "books5", "instId": "BTC-USDT-SWAP"},
+                        "data": [
+                            {
+                                "asks": [["8446", "95", "0", "3"]],
+                                "bids": [["8445.5", "1", "0", "1"]],
+                                "instId": "BTC-USDT-SWAP",
+                                "ts": "1597026383085",
+                                "seqId": 123456
+                            }
+                        ]
+                    }
+                    "#,
+                    expected: Ok(OkxOrderBookL2 {
+                        subscription_id: SubscriptionId::from("books5|BTC-USDT-SWAP"),
+                        action: None,
+                        data: vec![OkxOrderBookData {
+                            asks: vec![level("8446", "95")],
+                            bids: vec![level("8445.5", "1")],
+                            ts: datetime_utc_from_epoch_duration(Duration::from_millis(1597026383085)),
+                            checksum: None,
+                            prev_seq_id: None,
+                            seq_id: Some(123456),
+                        }],
+                    }),
+                },
+                // TC2: input "books" update w/ malformed level is invalid
+                TestCase {
+                    input: r#"
+                    {
+                        "arg": {"channel": "books", "instId": "BTC-USD-240329"},
+                        "action": "update",
+                        "data": [
+                            {
+                                "asks": [["8476.98"]],
+                                "bids": [],
+                                "ts": "1597026383085",
+                                "checksum": 1,
+                                "prevSeqId": 1,
+                                "seqId": 2
+                            }
+                        ]
+                    }
+                    "#,
+                    expected: Err(SocketError::Unsupported {
+                        entity: "",
+                        item: "".to_string(),
+                    }),
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = serde_json::from_str::<OkxOrderBookL2>(test.input);
+                match (actual, test.expected) {
+                    (Ok(actual), Ok(expected)) => {
+                        assert_eq!(actual, expected, "TC{} failed", index)
+                    },
+                    (Err(_), Err(_)) => {
+                        // Test passed
+                    },
+                    (actual, expected) => {
+                        // Test failed
+                        panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                    },
+                }
+            }
+        }
+    }
+
+    /// Checksum of the book with the levels, formatted as the raw levels.
+    fn checksum(bids: &[OkxLevel], asks: &[OkxLevel]) -> i32 {
+        let mut precision = LevelPrecision::default();
+        bids.iter().chain(asks).for_each(|level| precision.observe(&level.raw_price, &level.raw_amount));
+
+        let book = OrderBook {
+            bids: OrderBookSide::new(BookSide::Bid, bids.iter().cloned()),
+            asks: OrderBookSide::new(BookSide::Ask, asks.iter().cloned()),
+            ..OrderBook::default()
+        };
+        okx_checksum(&book, &precision)
+    }
+
+    #[test]
+    fn test_okx_checksum() {
+        // Example taken from the Okx order book checksum documentation
+        let bids = vec![level("3366.1", "7"), level("3366", "6")];
+        let asks = vec![level("3366.8", "9"), level("3368", "8")];
+
+        assert_eq!(checksum(&bids, &asks), -1881014294);
+    }
+
+    fn books_message(action: &str, bids: Vec<OkxLevel>, asks: Vec<OkxLevel>, prev_seq_id: i64, seq_id: i64) -> OkxOrderBookL2 {
+        let checksum = checksum(&bids, &asks);
+        OkxOrderBookL2 {
+            subscription_id: "books|ETH-USDT".into(),
+            action: Some(action.to_string()),
+            data: vec![OkxOrderBookData {
+                asks,
+                bids,
+                ts: Utc::now(),
+                checksum: Some(checksum),
+                prev_seq_id: Some(prev_seq_id),
+                seq_id: Some(seq_id),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_okx_book_updater_update() {
+        let mut updater = OkxBookUpdater::new();
+        let mut book = OrderBook::default();
+
+        // Snapshot initialises the book
+        let snapshot = books_message(
+            "snapshot",
+            vec![level("3366.1", "7"), level("3366", "6")],
+            vec![level("3366.8", "9"), level("3368", "8")],
+            -1,
+            10,
+        );
+        let output = updater.update(&mut book, snapshot).unwrap().unwrap();
+        assert_eq!(output.bids.levels(), vec![Level::new(3366.1, 7.0), Level::new(3366.0, 6.0)]);
+        assert_eq!(output.asks.levels(), vec![Level::new(3366.8, 9.0), Level::new(3368.0, 8.0)]);
+
+        // Update removes, replaces & inserts levels, checksum reflects the post-update book
+        let mut update = books_message(
+            "update",
+            vec![level("3366.1", "0"), level("3366.5", "1"), level("3366", "4")],
+            vec![level("3367", "2")],
+            10,
+            11,
+        );
+        update.data[0].checksum = Some(checksum(
+            &[level("3366.5", "1"), level("3366", "4")],
+            &[level("3366.8", "9"), level("3367", "2"), level("3368", "8")],
+        ));
+        let output = updater.update(&mut book, update).unwrap().unwrap();
+        assert_eq!(output.bids.levels(), vec![Level::new(3366.5, 1.0), Level::new(3366.0, 4.0)]);
+        assert_eq!(
+            output.asks.levels(),
+            vec![Level::new(3366.8, 9.0), Level::new(3367.0, 2.0), Level::new(3368.0, 8.0)]
+        );
+        assert_eq!(updater.last_seq_id, Some(11));
+        assert_eq!(updater.updates_processed, 2);
+    }
+
+    #[test]
+    fn test_okx_book_updater_invalid() {
+        struct TestCase {
+            input: OkxOrderBookL2,
+            expected_invalid_checksum: bool,
+        }
+
+        let tests = vec![
+            // TC0: update w/ checksum that does not match the local book
+            TestCase {
+                input: {
+                    let mut update = books_message("update", vec![level("3366", "1")], vec![], 10, 11);
+                    update.data[0].checksum = Some(12345);
+                    update
+                },
+                expected_invalid_checksum: true,
+            },
+            // TC1: update w/ prevSeqId that does not follow on from the last seqId
+            TestCase {
+                input: books_message("update", vec![level("3366", "1")], vec![], 9, 11),
+                expected_invalid_checksum: false,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let mut updater = OkxBookUpdater::new();
+            let mut book = OrderBook::default();
+            let snapshot = books_message("snapshot", vec![level("3366.1", "7")], vec![level("3366.8", "9")], -1, 10);
+            updater.update(&mut book, snapshot).unwrap();
+
+            let error = updater.update(&mut book, test.input).unwrap_err();
+            assert!(error.is_terminal(), "TC{} failed", index);
+            assert_eq!(
+                matches!(error, DataError::InvalidChecksum { .. }),
+                test.expected_invalid_checksum,
+                "TC{} failed",
+                index
+            );
+        }
+    }
+}
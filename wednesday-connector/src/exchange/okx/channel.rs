@@ -0,0 +1,49 @@
+use wednesday_model::identifiers::Identifier;
+
+use crate::subscriber::subscription::{
+    kind::{OrderBooksL2, OrderBooksL2Top5, PublicTrades},
+    Subscription,
+};
+
+use super::Okx;
+
+/// Type that defines how to translate a [`Subscription`] into an [`Okx`] channel to be
+/// subscribed to.
+///
+/// See docs: <https://www.okx.com/docs-v5/en/#websocket-api-public-channel>
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct OkxChannel(pub &'static str);
+
+impl OkxChannel {
+    /// [`Okx`] real-time trades channel.
+    pub const TRADES: Self = Self("trades");
+    /// [`Okx`] 400 depth levels L2 channel, initial snapshot followed by incremental updates
+    /// every 100ms, each carrying a CRC32 checksum of the top 25 levels.
+    pub const ORDER_BOOK_L2: Self = Self("books");
+    /// [`Okx`] 5 depth levels L2 channel, each push is a full snapshot.
+    pub const ORDER_BOOK_L2_TOP5: Self = Self("books5");
+}
+
+impl Identifier<OkxChannel> for Subscription<Okx, PublicTrades> {
+    fn id(&self) -> OkxChannel {
+        OkxChannel::TRADES
+    }
+}
+
+impl Identifier<OkxChannel> for Subscription<Okx, OrderBooksL2> {
+    fn id(&self) -> OkxChannel {
+        OkxChannel::ORDER_BOOK_L2
+    }
+}
+
+impl Identifier<OkxChannel> for Subscription<Okx, OrderBooksL2Top5> {
+    fn id(&self) -> OkxChannel {
+        OkxChannel::ORDER_BOOK_L2_TOP5
+    }
+}
+
+impl AsRef<str> for OkxChannel {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use wednesday_model::{
+    identifiers::Identifier,
+    instruments::{InstrumentKind, Symbol},
+};
+
+use crate::subscriber::subscription::Subscription;
+
+use super::Okx;
+
+/// Type that defines how to translate a [`Subscription`] into an [`Okx`] market (instId) that
+/// can be subscribed to.
+///
+/// Variants:
+/// - Spot: "BTC-USDT"
+/// - Perpetual swap: "BTC-USDT-SWAP"
+/// - Dated future: "BTC-USD-240329"
+///
+/// See docs: <https://www.okx.com/docs-v5/en/#public-data-rest-api-get-instruments>
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct OkxMarket(pub String);
+
+impl<Kind> Identifier<OkxMarket> for Subscription<Okx, Kind> {
+    fn id(&self) -> OkxMarket {
+        OkxMarket::new(&self.instrument.base_currency, &self.instrument.quote_currency, self.instrument.kind)
+    }
+}
+
+impl OkxMarket {
+    pub fn new(base_currency: &Symbol, quote_currency: &Symbol, kind: InstrumentKind) -> Self {
+        let market = match kind {
+            InstrumentKind::Stock | InstrumentKind::CryptoSpot => format!("{}-{}", base_currency, quote_currency),
+            InstrumentKind::CryptoFuture(contract) => {
+                format!("{}-{}-{}", base_currency, quote_currency, contract.expiration.format("%y%m%d"))
+            },
+            InstrumentKind::CryptoPerpetual => format!("{}-{}-SWAP", base_currency, quote_currency),
+        };
+
+        Self(market.to_uppercase())
+    }
+}
+
+impl AsRef<str> for OkxMarket {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use wednesday_model::instruments::FuturesContract;
+
+    use super::*;
+
+    #[test]
+    fn test_okx_market() {
+        struct TestCase {
+            input: InstrumentKind,
+            expected: OkxMarket,
+        }
+
+        let tests = vec![
+            // TC0: Spot
+            TestCase {
+                input: InstrumentKind::CryptoSpot,
+                expected: OkxMarket("BTC-USDT".to_string()),
+            },
+            // TC1: Perpetual swap
+            TestCase {
+                input: InstrumentKind::CryptoPerpetual,
+                expected: OkxMarket("BTC-USDT-SWAP".to_string()),
+            },
+            // TC2: Dated future
+            TestCase {
+                input: InstrumentKind::CryptoFuture(FuturesContract {
+                    expiration: Utc.with_ymd_and_hms(2024, 3, 29, 8, 0, 0).unwrap(),
+                }),
+                expected: OkxMarket("BTC-USDT-240329".to_string()),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = OkxMarket::new(&Symbol::from("btc"), &Symbol::from("usdt"), test.input);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+}
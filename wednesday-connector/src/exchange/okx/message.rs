@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+use wednesday_model::identifiers::{Identifier, SubscriptionId};
+
+use crate::subscriber::subscription::ExchangeSubscription;
+
+/// [`Okx`](super::Okx) market data message envelope.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.okx.com/docs-v5/en/#websocket-api-public-channel>
+/// ```json
+/// {
+///     "arg": {"channel": "trades", "instId": "BTC-USDT"},
+///     "action": "update",
+///     "data": [...]
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct OkxMessage<T> {
+    #[serde(rename = "arg", deserialize_with = "de_okx_message_arg_as_subscription_id")]
+    pub subscription_id: SubscriptionId,
+    /// "snapshot" or "update" for the "books" channel, absent for every other channel.
+    #[serde(default)]
+    pub action: Option<String>,
+    pub data: Vec<T>,
+}
+
+impl<T> Identifier<Option<SubscriptionId>> for OkxMessage<T> {
+    fn id(&self) -> Option<SubscriptionId> {
+        Some(self.subscription_id.clone())
+    }
+}
+
+/// Deserialize an [`OkxMessage`] "arg" (eg/ {"channel": "trades", "instId": "BTC-USDT"}) as the
+/// associated [`SubscriptionId`] (eg/ "trades|BTC-USDT").
+pub fn de_okx_message_arg_as_subscription_id<'de, D>(deserializer: D) -> Result<SubscriptionId, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Arg<'a> {
+        channel: &'a str,
+        inst_id: &'a str,
+    }
+
+    Deserialize::deserialize(deserializer).map(|arg: Arg<'_>| ExchangeSubscription::from((arg.channel, arg.inst_id)).id())
+}
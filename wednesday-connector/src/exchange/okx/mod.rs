@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use tokio::time;
+use url::Url;
+use wednesday_macro::{DeExchange, SerExchange};
+use wednesday_model::{error::SocketError, identifiers::ExchangeId};
+
+use crate::{
+    protocol::http::websocket::{PingInterval, WsMessage},
+    stream::{protocol::ws_stream::ExchangeWsStream, rate_limit::RateLimit, selector::StreamSelector},
+    subscriber::{
+        protocol::websocket::WsSubscriber,
+        subscription::{
+            kind::{OrderBooksL2, OrderBooksL2Top5, PublicTrades},
+            ExchangeSubscription,
+        },
+        validator::WsSubscriptionValidator,
+    },
+    transformer::{stateful::MultiBookTransformer, stateless::StatelessTransformer},
+};
+
+use self::{book::OkxBookUpdater, channel::OkxChannel, market::OkxMarket, subscription::OkxSubscriptionResponse, trade::OkxTrades};
+
+use super::connector::Connector;
+
+pub mod book;
+pub mod channel;
+pub mod market;
+pub mod message;
+pub mod subscription;
+pub mod trade;
+
+pub const WEBSOCKET_BASE_URL_OKX: &str = "wss://ws.okx.com:8443/ws/v5/public";
+
+/// [`Okx`] public WebSocket endpoint hosted on AWS, used by every other redundant connection.
+///
+/// See docs: <https://www.okx.com/docs-v5/en/#overview-production-trading-services>
+pub const WEBSOCKET_AWS_URL_OKX: &str = "wss://wsaws.okx.com:8443/ws/v5/public";
+
+/// [`Okx`] server does not accept WebSocket ping frames, and disconnects if nothing is sent
+/// within 30s, so a "ping" text message is sent instead.
+///
+/// See docs: <https://www.okx.com/docs-v5/en/#overview-websocket-connect>
+pub const PING_INTERVAL_OKX: Duration = Duration::from_secs(29);
+
+/// Maximum number of subscribe, unsubscribe & login requests per hour of an [`Okx`] WebSocket
+/// connection.
+///
+/// See docs: <https://www.okx.com/docs-v5/en/#overview-websocket-connection-count-limit>
+pub const MAX_REQUESTS_PER_HOUR_OKX: usize = 480;
+
+/// [`Okx`] public market data connector, serving spot, perpetual swap & dated future markets
+/// from a single WebSocket endpoint.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default, DeExchange, SerExchange)]
+pub struct Okx;
+
+impl Connector for Okx {
+    const ID: ExchangeId = ExchangeId::Okx;
+    type Channel = OkxChannel;
+    type Market = OkxMarket;
+    type Subscriber = WsSubscriber;
+    type SubscriptionValidator = WsSubscriptionValidator;
+    type SubscriptionResponse = OkxSubscriptionResponse;
+
+    fn url() -> Result<Url, SocketError> {
+        Url::parse(WEBSOCKET_BASE_URL_OKX).map_err(SocketError::UrlParse)
+    }
+
+    fn leg_url(leg: usize) -> Result<Url, SocketError> {
+        match leg % 2 {
+            0 => Self::url(),
+            _ => Url::parse(WEBSOCKET_AWS_URL_OKX).map_err(SocketError::UrlParse),
+        }
+    }
+
+    fn requests(exchange_subscriptions: Vec<ExchangeSubscription<Self::Channel, Self::Market>>) -> Vec<WsMessage> {
+        vec![okx_request("subscribe", exchange_subscriptions)]
+    }
+
+    fn dynamic_subscriptions() -> bool {
+        true
+    }
+
+    fn unsubscribe_requests(exchange_subscriptions: Vec<ExchangeSubscription<Self::Channel, Self::Market>>) -> Vec<WsMessage> {
+        vec![okx_request("unsubscribe", exchange_subscriptions)]
+    }
+
+    fn message_rate_limit() -> Option<RateLimit> {
+        Some(RateLimit::new(MAX_REQUESTS_PER_HOUR_OKX, Duration::from_secs(60 * 60)))
+    }
+
+    fn ping_interval() -> Option<PingInterval> {
+        Some(PingInterval {
+            interval: time::interval(PING_INTERVAL_OKX),
+            ping: || WsMessage::Text("ping".to_string()),
+        })
+    }
+}
+
+/// Single request with the provided "subscribe" or "unsubscribe" op for every subscription.
+fn okx_request(op: &str, exchange_subscriptions: Vec<ExchangeSubscription<OkxChannel, OkxMarket>>) -> WsMessage {
+    let args = exchange_subscriptions
+        .into_iter()
+        .map(|sub| {
+            serde_json::json!({
+                "channel": sub.channel.as_ref(),
+                "instId": sub.market.as_ref(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    WsMessage::Text(
+        serde_json::json!({
+            "op": op,
+            "args": args
+        })
+        .to_string(),
+    )
+}
+
+impl StreamSelector<PublicTrades> for Okx {
+    type Stream = ExchangeWsStream<StatelessTransformer<Self, PublicTrades, OkxTrades>>;
+}
+
+impl StreamSelector<OrderBooksL2> for Okx {
+    type Stream = ExchangeWsStream<MultiBookTransformer<Self, OrderBooksL2, OkxBookUpdater>>;
+}
+
+impl StreamSelector<OrderBooksL2Top5> for Okx {
+    type Stream = ExchangeWsStream<MultiBookTransformer<Self, OrderBooksL2Top5, OkxBookUpdater>>;
+}
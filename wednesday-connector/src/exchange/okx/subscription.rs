@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+use wednesday_model::error::SocketError;
+
+use crate::subscriber::validator::Validator;
+
+/// [`Okx`](super::Okx) WebSocket subscription response, one is received per subscribed
+/// channel & market.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.okx.com/docs-v5/en/#overview-websocket-subscribe>
+/// #### Subscription Success
+/// ```json
+/// {
+///     "event": "subscribe",
+///     "arg": {"channel": "trades", "instId": "BTC-USDT"},
+///     "connId": "a4d3ae55"
+/// }
+/// ```
+///
+/// #### Subscription Failure
+/// ```json
+/// {
+///     "event": "error",
+///     "code": "60012",
+///     "msg": "Invalid request: {\"op\": \"subscribe\", \"argss\":[{ \"channel\" : \"trades\", \"instId\" : \"BTC-USDT\"}]}",
+///     "connId": "a4d3ae55"
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(tag = "event", rename_all = "lowercase")]
+pub enum OkxSubscriptionResponse {
+    #[serde(rename = "subscribe")]
+    Subscribed,
+    #[serde(rename = "unsubscribe")]
+    Unsubscribed,
+    Error {
+        code: String,
+        #[serde(alias = "msg")]
+        message: String,
+    },
+}
+
+impl Validator for OkxSubscriptionResponse {
+    fn validate(self) -> Result<Self, SocketError>
+    where
+        Self: Sized,
+    {
+        match self {
+            OkxSubscriptionResponse::Subscribed | OkxSubscriptionResponse::Unsubscribed => Ok(self),
+            OkxSubscriptionResponse::Error { code, message } => Err(SocketError::Subscribe(format!(
+                "received failure subscription response code: {code} with message: {message}",
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use super::*;
+
+        #[test]
+        fn test_okx_sub_response() {
+            struct TestCase {
+                input: &'static str,
+                expected: Result<OkxSubscriptionResponse, SocketError>,
+            }
+
+            let cases = vec![
+                TestCase {
+                    // TC0: input response is Subscribed
+                    input: r#"
+                    {
+                        "event": "subscribe",
+                        "arg": {"channel": "trades", "instId": "BTC-USDT"},
+                        "connId": "a4d3ae55"
+                    }
+                    "#,
+                    expected: Ok(OkxSubscriptionResponse::Subscribed),
+                },
+                TestCase {
+                    // TC1: input response is failed subscription
+                    input: r#"
+                    {
+                        "event": "error",
+                        "code": "60012",
+                        "msg": "Invalid request",
+                        "connId": "a4d3ae55"
+                    }
+                    "#,
+                    expected: Ok(OkxSubscriptionResponse::Error {
+                        code: "60012".to_string(),
+                        message: "Invalid request".to_string(),
+                    }),
+                },
+                TestCase {
+                    // TC2: input is a market data message
+                    input: r#"
+                    {
+                        "arg": {"channel": "trades", "instId": "BTC-USDT"},
+                        "data": []
+                    }
+                    "#,
+                    expected: Err(SocketError::Subscribe("".to_string())),
+                },
+                TestCase {
+                    // TC3: input response is Unsubscribed from a running stream
+                    input: r#"
+                    {
+                        "event": "unsubscribe",
+                        "arg": {"channel": "trades", "instId": "BTC-USDT"},
+                        "connId": "a4d3ae55"
+                    }
+                    "#,
+                    expected: Ok(OkxSubscriptionResponse::Unsubscribed),
+                },
+            ];
+
+            for (index, test) in cases.into_iter().enumerate() {
+                let actual = serde_json::from_str::<OkxSubscriptionResponse>(test.input);
+                match (actual, test.expected) {
+                    (Ok(actual), Ok(expected)) => {
+                        assert_eq!(actual, expected, "TC{} failed", index)
+                    },
+                    (Err(_), Err(_)) => {
+                        // Test passed
+                    },
+                    (actual, expected) => {
+                        // Test failed
+                        panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                    },
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_okx_sub_response() {
+        struct TestCase {
+            input_response: OkxSubscriptionResponse,
+            is_valid: bool,
+        }
+
+        let cases = vec![
+            TestCase {
+                // TC0: input response is successful subscription
+                input_response: OkxSubscriptionResponse::Subscribed,
+                is_valid: true,
+            },
+            TestCase {
+                // TC1: input response is failed subscription
+                input_response: OkxSubscriptionResponse::Error {
+                    code: "60012".to_string(),
+                    message: "Invalid request".to_string(),
+                },
+                is_valid: false,
+            },
+            TestCase {
+                // TC2: input response is successful unsubscription
+                input_response: OkxSubscriptionResponse::Unsubscribed,
+                is_valid: true,
+            },
+        ];
+
+        for (index, test) in cases.into_iter().enumerate() {
+            let actual = test.input_response.validate().is_ok();
+            assert_eq!(actual, test.is_valid, "TestCase {} failed", index);
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use wednesday_model::{
+    deserialization,
+    enums::AggressorSide,
+    events::MarketEvent,
+    identifiers::{Exchange, ExchangeId},
+    instruments::Instrument,
+    trade::PublicTrade,
+};
+
+use crate::transformer::iterator::MarketIter;
+
+use super::message::OkxMessage;
+
+pub type OkxTrades = OkxMessage<OkxTrade>;
+
+/// [`Okx`](super::Okx) real-time trade, shared by spot, perpetual swap & dated future markets.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.okx.com/docs-v5/en/#order-book-trading-market-data-ws-trades-channel>
+/// ```json
+/// {
+///     "instId": "BTC-USDT",
+///     "tradeId": "130639474",
+///     "px": "42219.9",
+///     "sz": "0.12060306",
+///     "side": "buy",
+///     "ts": "1630048897897",
+///     "count": "3"
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct OkxTrade {
+    #[serde(rename = "tradeId")]
+    pub id: String,
+    #[serde(rename = "px", deserialize_with = "deserialization::de_str")]
+    pub price: f64,
+    #[serde(rename = "sz", deserialize_with = "deserialization::de_str")]
+    pub amount: f64,
+    pub side: AggressorSide,
+    #[serde(rename = "ts", deserialize_with = "deserialization::de_str_u64_epoch_ms_as_datetime_utc")]
+    pub time: DateTime<Utc>,
+}
+
+impl From<(ExchangeId, Instrument, OkxTrades)> for MarketIter<PublicTrade> {
+    fn from((exchange_id, instrument, trades): (ExchangeId, Instrument, OkxTrades)) -> Self {
+        trades
+            .data
+            .into_iter()
+            .map(|trade| MarketEvent {
+                exchange_ts: trade.time,
+                local_ts: Utc::now(),
+                exchange: Exchange::from(exchange_id),
+                instrument: instrument.clone(),
+                kind: PublicTrade {
+                    id: trade.id,
+                    price: trade.price,
+                    quantity: trade.amount,
+                    aggressor_side: trade.side,
+                },
+            })
+            .map(Ok)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use std::time::Duration;
+
+        use wednesday_model::{deserialization::datetime_utc_from_epoch_duration, error::SocketError, identifiers::SubscriptionId};
+
+        use super::*;
+
+        #[test]
+        fn test_okx_trades() {
+            struct TestCase {
+                input: &'static str,
+                expected: Result<OkxTrades, SocketError>,
+            }
+
+            let tests = vec![
+                // TC0: input spot OkxTrades is deserialised
+                TestCase {
+                    input: r#"
+                    {
+                        "arg": {"channel": "trades", "instId": "BTC-USDT"},
+                        "data": [
+                            {
+                                "instId": "BTC-USDT", "tradeId": "130639474", "px": "42219.9",
+                                "sz": "0.12060306", "side": "buy", "ts": "1630048897897", "count": "3"
+                            }
+                        ]
+                    }
+                    "#,
+                    expected: Ok(OkxTrades {
+                        subscription_id: SubscriptionId::from("trades|BTC-USDT"),
+                        action: None,
+                        data: vec![OkxTrade {
+                            id: "130639474".to_string(),
+                            price: 42219.9,
+                            amount: 0.12060306,
+                            side: AggressorSide::Buy,
+                            time: datetime_utc_from_epoch_duration(Duration::from_millis(1630048897897)),
+                        }],
+                    }),
+                },
+                // TC1: input perpetual swap OkxTrades w/ multiple trades is deserialised
+                TestCase {
+                    input: r#"
+                    {
+                        "arg": {"channel": "trades", "instId": "BTC-USDT-SWAP"},
+                        "data": [
+                            {
+                                "instId": "BTC-USDT-SWAP", "tradeId": "1", "px": "42219.9",
+                                "sz": "1", "side": "sell", "ts": "1630048897897"
+                            },
+                            {
+                                "instId": "BTC-USDT-SWAP", "tradeId": "2", "px": "42220.1",
+                                "sz": "2", "side": "buy", "ts": "1630048897898"
+                            }
+                        ]
+                    }
+                    "#,
+                    expected: Ok(OkxTrades {
+                        subscription_id: SubscriptionId::from("trades|BTC-USDT-SWAP"),
+                        action: None,
+                        data: vec![
+                            OkxTrade {
+                                id: "1".to_string(),
+                                price: 42219.9,
+                                amount: 1.0,
+                                side: AggressorSide::Sell,
+                                time: datetime_utc_from_epoch_duration(Duration::from_millis(1630048897897)),
+                            },
+                            OkxTrade {
+                                id: "2".to_string(),
+                                price: 42220.1,
+                                amount: 2.0,
+                                side: AggressorSide::Buy,
+                                time: datetime_utc_from_epoch_duration(Duration::from_millis(1630048897898)),
+                            },
+                        ],
+                    }),
+                },
+                // TC2: input dated future OkxTrades is deserialised
+                TestCase {
+                    input: r#"
+                    {
+                        "arg": {"channel": "trades", "instId": "BTC-USD-240329"},
+                        "data": [
+                            {
+                                "instId": "BTC-USD-240329", "tradeId": "3", "px": "70000.5",
+                                "sz": "10", "side": "sell", "ts": "1710000000000", "count": "1"
+                            }
+                        ]
+                    }
+                    "#,
+                    expected: Ok(OkxTrades {
+                        subscription_id: SubscriptionId::from("trades|BTC-USD-240329"),
+                        action: None,
+                        data: vec![OkxTrade {
+                            id: "3".to_string(),
+                            price: 70000.5,
+                            amount: 10.0,
+                            side: AggressorSide::Sell,
+                            time: datetime_utc_from_epoch_duration(Duration::from_millis(1710000000000)),
+                        }],
+                    }),
+                },
+                // TC3: input OkxTrades is invalid w/ non-numeric price
+                TestCase {
+                    input: r#"
+                    {
+                        "arg": {"channel": "trades", "instId": "BTC-USDT"},
+                        "data": [
+                            {
+                                "instId": "BTC-USDT", "tradeId": "1", "px": "unknown",
+                                "sz": "1", "side": "sell", "ts": "1630048897897"
+                            }
+                        ]
+                    }
+                    "#,
+                    expected: Err(SocketError::Unsupported {
+                        entity: "",
+                        item: "".to_string(),
+                    }),
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = serde_json::from_str::<OkxTrades>(test.input);
+                match (actual, test.expected) {
+                    (Ok(actual), Ok(expected)) => {
+                        assert_eq!(actual, expected, "TC{} failed", index)
+                    },
+                    (Err(_), Err(_)) => {
+                        // Test passed
+                    },
+                    (actual, expected) => {
+                        // Test failed
+                        panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                    },
+                }
+            }
+        }
+    }
+}
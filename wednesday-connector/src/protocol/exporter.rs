@@ -0,0 +1,226 @@
+use std::net::SocketAddr;
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream, UdpSocket},
+    sync::mpsc,
+};
+use tracing::{debug, info, warn};
+use wednesday_model::{
+    error::SocketError,
+    metric::{MetricRegistry, MetricRx, MetricTx},
+};
+
+/// Path of the Prometheus text endpoint served by an [`Exporter::Prometheus`].
+pub const PROMETHEUS_PATH: &str = "/metrics";
+
+/// Largest request head read by an [`Exporter::Prometheus`] before the request is rejected.
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// Optional exporter of the [`Metric`](wednesday_model::metric::Metric)s of the connectors &
+/// engine, eg/ the [`StreamMetrics`](crate::stream::telemetry::StreamMetrics), the Http request
+/// durations of a [`RestClient`](super::http::rest::client::RestClient), &
+/// [`LatencyRecorder`](wednesday_model::latency::LatencyRecorder) snapshots.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Exporter {
+    /// Serve the latest value of every series as Prometheus text at [`PROMETHEUS_PATH`] over
+    /// Http on the address, eg/ "0.0.0.0:9100".
+    Prometheus(SocketAddr),
+    /// Push every metric as an InfluxDB line protocol datagram to the local UDP listener on the
+    /// address, eg/ a Telegraf socket listener on "127.0.0.1:8094".
+    Influx(SocketAddr),
+}
+
+impl Exporter {
+    /// Bind the exporter & spawn the tasks that export the [`Metric`](wednesday_model::metric::Metric)s
+    /// sent via the returned [`MetricTx`], which is cheap to clone into every component. The
+    /// tasks end once every [`MetricTx`] is dropped.
+    pub async fn spawn(self) -> Result<MetricTx, SocketError> {
+        let (metric_tx, metric_rx) = mpsc::unbounded_channel();
+
+        match self {
+            Exporter::Prometheus(addr) => {
+                let listener = TcpListener::bind(addr).await?;
+                info!(addr = %listener.local_addr()?, path = PROMETHEUS_PATH, "serving Prometheus metrics");
+
+                let registry = MetricRegistry::new();
+                tokio::spawn(serve_prometheus(listener, registry.clone()));
+                tokio::spawn(record(metric_rx, registry));
+            },
+            Exporter::Influx(addr) => {
+                let socket = UdpSocket::bind(if addr.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" }).await?;
+                socket.connect(addr).await?;
+                info!(%addr, "pushing InfluxDB line protocol metrics");
+
+                tokio::spawn(push_influx(metric_rx, socket));
+            },
+        }
+
+        Ok(metric_tx)
+    }
+}
+
+/// Record every metric into the [`MetricRegistry`] served by [`serve_prometheus`].
+async fn record(mut metric_rx: MetricRx, registry: MetricRegistry) {
+    while let Some(metric) = metric_rx.recv().await {
+        registry.record(&metric);
+    }
+    debug!(why = "every MetricTx dropped", "stopping Prometheus metrics recording");
+}
+
+/// Accept Http connections & respond to each request with the Prometheus text of the
+/// [`MetricRegistry`].
+async fn serve_prometheus(listener: TcpListener, registry: MetricRegistry) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                let registry = registry.clone();
+                tokio::spawn(async move {
+                    if let Err(error) = respond(stream, &registry).await {
+                        debug!(%error, "failed to respond to Prometheus scrape");
+                    }
+                });
+            },
+            Err(error) => warn!(%error, "failed to accept Prometheus scrape connection"),
+        }
+    }
+}
+
+/// Respond to a single Http/1.1 request, closing the connection afterwards.
+async fn respond(mut stream: TcpStream, registry: &MetricRegistry) -> Result<(), SocketError> {
+    let mut request = Vec::with_capacity(1024);
+    let mut buffer = [0u8; 1024];
+
+    // Read until the end of the request head, the request body (if any) is irrelevant
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 || request.len() + read > MAX_REQUEST_BYTES {
+            return Ok(());
+        }
+        request.extend_from_slice(&buffer[..read]);
+    }
+
+    let response = match parse_request_line(&request) {
+        Some(("GET", path)) if is_metrics_path(path) => response("200 OK", "text/plain; version=0.0.4; charset=utf-8", registry.prometheus()),
+        Some((_, path)) if is_metrics_path(path) => response("405 Method Not Allowed", "text/plain", String::new()),
+        Some(_) => response("404 Not Found", "text/plain", String::new()),
+        None => response("400 Bad Request", "text/plain", String::new()),
+    };
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Method & path of the request line of an Http request head.
+fn parse_request_line(request: &[u8]) -> Option<(&str, &str)> {
+    let line = std::str::from_utf8(request).ok()?.lines().next()?;
+    let mut parts = line.split_whitespace();
+    Some((parts.next()?, parts.next()?))
+}
+
+fn is_metrics_path(path: &str) -> bool {
+    path.split('?').next() == Some(PROMETHEUS_PATH)
+}
+
+fn response(status: &str, content_type: &str, body: String) -> String {
+    format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+/// Send every metric as an InfluxDB line protocol datagram via the connected `socket`.
+async fn push_influx(mut metric_rx: MetricRx, socket: UdpSocket) {
+    while let Some(metric) = metric_rx.recv().await {
+        if let Err(error) = socket.send(metric.to_line_protocol().as_bytes()).await {
+            warn!(%error, metric = metric.name, "failed to push InfluxDB line protocol metric");
+        }
+    }
+    debug!(why = "every MetricTx dropped", "stopping InfluxDB line protocol metrics");
+}
+
+#[cfg(test)]
+mod tests {
+    use wednesday_model::metric::{Field, Metric, Tag};
+
+    use super::*;
+
+    fn metric() -> Metric {
+        Metric {
+            name: "stream",
+            time: 1_700_000_000_000,
+            tags: vec![Tag::new("exchange", "okx")],
+            fields: vec![Field::new("messages_total", 7_u64)],
+        }
+    }
+
+    async fn get(addr: SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(format!("GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").as_bytes())
+            .await
+            .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[test]
+    fn test_parse_request_line() {
+        struct TestCase {
+            input: &'static [u8],
+            expected: Option<(&'static str, &'static str)>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: valid request line
+                input: b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n",
+                expected: Some(("GET", "/metrics")),
+            },
+            TestCase {
+                // TC1: missing path
+                input: b"GET\r\n\r\n",
+                expected: None,
+            },
+            TestCase {
+                // TC2: invalid utf8
+                input: &[0xff, 0xfe],
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            assert_eq!(parse_request_line(test.input), test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prometheus_exporter() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let registry = MetricRegistry::new();
+        registry.record(&metric());
+        tokio::spawn(serve_prometheus(listener, registry));
+
+        let response = get(addr, "/metrics").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.ends_with("\r\n\r\n# TYPE stream_messages_total counter\nstream_messages_total{exchange=\"okx\"} 7\n"));
+
+        assert!(get(addr, "/").await.starts_with("HTTP/1.1 404 Not Found\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_influx_exporter() {
+        let listener = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let metric_tx = Exporter::Influx(listener.local_addr().unwrap()).spawn().await.unwrap();
+
+        metric_tx.send(metric()).unwrap();
+
+        let mut buffer = [0u8; 1024];
+        let read = listener.recv(&mut buffer).await.unwrap();
+        assert_eq!(&buffer[..read], b"stream,exchange=okx messages_total=7u 1700000000000000000");
+    }
+}
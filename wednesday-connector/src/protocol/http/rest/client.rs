@@ -2,7 +2,7 @@ use bytes::Bytes;
 use chrono::Utc;
 use std::borrow::Cow;
 use tracing::debug;
-use wednesday_model::error::SocketError;
+use wednesday_model::{error::SocketError, metric::MetricTx};
 
 use crate::protocol::{
     http::{builder::HttpRequestBuilder, parser::HttpParser},
@@ -36,6 +36,10 @@ pub struct RestClient<'a, Strategy, Parser> {
     /// [`HttpParser`] that deserialises [`RestRequest::Response`]s, and upon failure parses
     /// API errors returned from the server.
     pub parser: Parser,
+
+    /// Optional [`MetricTx`] that is sent the Http request duration [`Metric`] of every executed
+    /// request, eg/ to publish it via an [`Exporter`](crate::protocol::exporter::Exporter).
+    pub metric_tx: Option<MetricTx>,
 }
 
 impl<'a, Strategy, Parser> RestClient<'a, Strategy, Parser>
@@ -67,9 +71,7 @@ where
         }
 
         // Attempt to parse API Success or Error response
-        self.parser
-            .parse::<Request::Response>(status, &payload)
-            .map(|response| (response, latency))
+        self.parser.parse::<Request::Response>(status, &payload).map(|response| (response, latency))
     }
 
     /// Use the provided [`RestRequest`] to construct a signed Http [`reqwest::Request`].
@@ -128,6 +130,10 @@ where
         latency.tags.push(Tag::new("status_code", response.status().as_str()));
         latency.fields.push(Field::new("duration", duration));
 
+        if let Some(metric_tx) = &self.metric_tx {
+            let _ = metric_tx.send(latency.clone());
+        }
+
         // Extract Status Code & reqwest::Response Bytes
         let status_code = response.status();
         let payload = response.bytes().await?;
@@ -144,6 +150,15 @@ impl<'a, Strategy, Parser> RestClient<'a, Strategy, Parser> {
             base_url: base_url.into(),
             strategy,
             parser,
+            metric_tx: None,
+        }
+    }
+
+    /// Send the Http request duration [`Metric`] of every executed request via the `metric_tx`.
+    pub fn with_metric_tx(self, metric_tx: MetricTx) -> Self {
+        Self {
+            metric_tx: Some(metric_tx),
+            ..self
         }
     }
 }
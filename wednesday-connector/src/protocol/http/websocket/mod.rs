@@ -51,10 +51,17 @@ impl StreamParser for WsParser {
 }
 
 /// Process a payload of `String` by deserialising into an `ExchangeMessage`.
+///
+/// Note: plain text application-level pongs (eg/ OKX "pong") are safe-to-skip.
 pub fn process_text<ExchangeMessage>(payload: &String) -> Option<Result<ExchangeMessage, SocketError>>
 where
     ExchangeMessage: DeserializeOwned,
 {
+    if payload == "pong" {
+        debug!(?payload, "received application-level Pong WebSocket message");
+        return None;
+    }
+
     Some(serde_json::from_str::<ExchangeMessage>(&payload).map_err(|error| {
         debug!(
             ?error,
@@ -121,10 +128,7 @@ where
     Request: IntoClientRequest + Unpin + Debug,
 {
     debug!(?request, "attempting to establish WebSocket connection");
-    connect_async(request)
-        .await
-        .map(|(websocket, _)| websocket)
-        .map_err(SocketError::WebSocket)
+    connect_async(request).await.map(|(websocket, _)| websocket).map_err(SocketError::from)
 }
 
 /// Determine whether a [`WsError`] indicates the [`WebSocket`] has disconnected.
@@ -1,2 +1,3 @@
+pub mod exporter;
 pub mod http;
 pub mod metric;
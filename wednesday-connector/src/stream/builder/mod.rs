@@ -4,13 +4,22 @@ use std::fmt::Debug;
 use std::{collections::HashMap, pin::Pin};
 
 use futures::Future;
+use tokio::sync::mpsc;
 use tracing::debug;
+use wednesday_model::channel::{channel, ChannelConfig, ChannelRx, ChannelTx};
 use wednesday_model::error::DataError;
 use wednesday_model::events::MarketEvent;
 use wednesday_model::identifiers::{ExchangeId, Identifier};
+use wednesday_model::latency::{short_type_name, LatencyRecorder};
+use wednesday_model::status::StatusChannel;
 
 use crate::exchange::channel::ExchangeChannel;
+use crate::stream::conflation::{Conflate, Conflation};
+use crate::stream::handle::{fan_out, SubscriptionHandles};
 use crate::stream::market::consume;
+use crate::stream::reconnect::ReconnectPolicy;
+use crate::stream::redundancy::{Deduplicate, LegCounter, Redundancy};
+use crate::stream::telemetry::{StreamCounter, Telemetry};
 use crate::subscriber::subscription::{Subscription, SubscriptionKind};
 use crate::subscriber::validator::validate;
 
@@ -19,6 +28,14 @@ use super::Streams;
 
 pub type SubscribeFuture = Pin<Box<dyn Future<Output = Result<(), DataError>>>>;
 
+/// Maps the receiver of each exchange channel to the receiver of a conflated channel, see
+/// [`StreamBuilder::conflate`].
+pub type ConflateFn<T> = fn(Conflation, ChannelRx<T>) -> ChannelRx<T>;
+
+/// Merges the receivers of each redundant leg into the exchange channel, see
+/// [`StreamBuilder::redundancy`].
+pub type DeduplicateFn<T> = fn(Redundancy, Vec<ChannelRx<T>>, ChannelTx<T>, LegCounter);
+
 #[derive(Default)]
 pub struct StreamBuilder<Kind>
 where
@@ -26,6 +43,15 @@ where
 {
     pub channels: HashMap<ExchangeId, ExchangeChannel<MarketEvent<Kind::Event>>>,
     pub futures: Vec<SubscribeFuture>,
+    pub channel_config: ChannelConfig,
+    pub conflation: Option<(Conflation, ConflateFn<MarketEvent<Kind::Event>>)>,
+    pub handles: SubscriptionHandles,
+    pub redundancy: Option<(Redundancy, DeduplicateFn<MarketEvent<Kind::Event>>)>,
+    pub leg_counters: HashMap<ExchangeId, LegCounter>,
+    pub reconnect: ReconnectPolicy,
+    pub status: StatusChannel,
+    pub latency: Option<LatencyRecorder>,
+    pub stream_counters: HashMap<ExchangeId, StreamCounter>,
 }
 
 impl<Kind> Debug for StreamBuilder<Kind>
@@ -36,6 +62,12 @@ where
         f.debug_struct("StreamBuilder<SubscriptionKind>")
             .field("channels", &self.channels)
             .field("num_futures", &self.futures.len())
+            .field("channel_config", &self.channel_config)
+            .field("conflation", &self.conflation.as_ref().map(|(conflation, _)| conflation))
+            .field("handles", &self.handles)
+            .field("redundancy", &self.redundancy.as_ref().map(|(redundancy, _)| redundancy))
+            .field("reconnect", &self.reconnect)
+            .field("latency", &self.latency.is_some())
             .finish()
     }
 }
@@ -48,9 +80,66 @@ where
         Self {
             channels: HashMap::new(),
             futures: Vec::new(),
+            channel_config: ChannelConfig::default(),
+            conflation: None,
+            handles: SubscriptionHandles::default(),
+            redundancy: None,
+            leg_counters: HashMap::new(),
+            reconnect: ReconnectPolicy::default(),
+            status: StatusChannel::default(),
+            latency: None,
+            stream_counters: HashMap::new(),
         }
     }
 
+    /// Configure the capacity & [`OverflowPolicy`](wednesday_model::channel::OverflowPolicy) of
+    /// the channel of each exchange, which is unbounded by default. Applies to the exchanges of
+    /// every subsequent [`StreamBuilder::subscribe`] call.
+    pub fn channel(mut self, config: ChannelConfig) -> Self {
+        self.channel_config = config;
+        self
+    }
+
+    /// Conflate the events of every exchange stream before they are delivered, so that slow
+    /// consumers receive the latest book of each instrument rather than falling behind on every
+    /// intermediate update. See [`Conflation`] for the available modes.
+    pub fn conflate(mut self, conflation: Conflation) -> Self
+    where
+        Kind::Event: Conflate + Send + 'static,
+    {
+        self.conflation = Some((conflation, Conflation::spawn::<Kind::Event>));
+        self
+    }
+
+    /// Consume each subscription set over multiple redundant connections, merged by delivering
+    /// the first copy of each event, so that a disconnect of one connection causes no data gap.
+    /// Applies to the exchanges of every subsequent [`StreamBuilder::subscribe`] call, see
+    /// [`Redundancy`] & [`Streams::legs`] for the events won by each connection.
+    pub fn redundancy(mut self, redundancy: Redundancy) -> Self
+    where
+        Kind::Event: Deduplicate + Send + 'static,
+    {
+        self.redundancy = (redundancy.legs > 1).then_some((redundancy, Redundancy::spawn::<Kind::Event>));
+        self
+    }
+
+    /// Configure how each connection re-connects after failing to connect, being disconnected,
+    /// or going stale, which by default retries forever with capped & jittered exponential
+    /// backoff. Applies to the exchanges of every subsequent [`StreamBuilder::subscribe`] call,
+    /// see [`Streams::status`] for the resulting connection status events.
+    pub fn reconnect(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect = policy;
+        self
+    }
+
+    /// Record the latency of each event from the exchange until it was parsed into the shared
+    /// [`LatencyRecorder`], per exchange instrument & subscription kind. Applies to the exchanges
+    /// of every subsequent [`StreamBuilder::subscribe`] call.
+    pub fn latency(mut self, recorder: LatencyRecorder) -> Self {
+        self.latency = Some(recorder);
+        self
+    }
+
     // Note: This part is definitely needed a refactoring.
     pub fn subscribe<SubscriptionIter, SubscriptionItem, Exchange>(mut self, subscriptions: SubscriptionIter) -> Self
     where
@@ -66,17 +155,63 @@ where
             .map(|subscription| subscription.into())
             .collect::<Vec<Subscription<Exchange, Kind>>>();
 
-        let exchange_tx = self.channels.entry(Exchange::ID).or_default().tx.clone();
+        subscriptions.sort();
+        subscriptions.dedup();
+
+        debug!("Validating subscriptions before subscribing.");
+        let validation = validate(&subscriptions);
+
+        let channel_config = self.channel_config;
+        let exchange_tx = self
+            .channels
+            .entry(Exchange::ID)
+            .or_insert_with(|| ExchangeChannel::with_config(channel_config))
+            .tx
+            .clone();
+
+        // Each shard is consumed over its own connection, with its own SubscriptionHandle command
+        // channel, & merged back into the exchange channel
+        let shards = shard(subscriptions, Exchange::max_subscriptions_per_connection())
+            .into_iter()
+            .map(|shard| {
+                let (command_tx, command_rx) = mpsc::unbounded_channel();
+                self.handles.insert::<Exchange, Kind>(command_tx);
+                (shard, command_rx)
+            })
+            .collect::<Vec<_>>();
+
+        let (redundancy, policy) = (self.redundancy, self.reconnect);
+        let telemetry = Telemetry {
+            status_tx: self.status.tx.clone(),
+            counter: self.stream_counters.entry(Exchange::ID).or_default().clone(),
+            latency: self.latency.clone(),
+        };
+        let leg_counter = self.leg_counters.entry(Exchange::ID).or_default().clone();
 
         self.futures.push(Box::pin(async move {
-            debug!("Validating subscriptions before subscribing.");
-            validate(&subscriptions)?;
+            validation?;
+
+            debug!(exchange = %Exchange::ID, connections = shards.len(), "Spawning tasks to consume subscriptions");
+            for (shard, command_rx) in shards {
+                let Some((redundancy, deduplicate)) = redundancy else {
+                    tokio::spawn(consume::<Exchange, Kind>(shard, exchange_tx.clone(), command_rx, 0, policy, telemetry.clone()));
+                    continue;
+                };
 
-            subscriptions.sort();
-            subscriptions.dedup();
+                // Each leg is consumed over its own connection into a leg channel, & the legs
+                // are merged into the exchange channel by the deduplication task
+                let (leg_rxs, leg_command_txs): (Vec<_>, Vec<_>) = (0..redundancy.legs)
+                    .map(|leg| {
+                        let (leg_tx, leg_rx) = channel(ChannelConfig::default());
+                        let (leg_command_tx, leg_command_rx) = mpsc::unbounded_channel();
+                        tokio::spawn(consume::<Exchange, Kind>(shard.clone(), leg_tx, leg_command_rx, leg, policy, telemetry.clone()));
+                        (leg_rx, leg_command_tx)
+                    })
+                    .unzip();
 
-            debug!("Spawning task to consume subscriptions for exchange: {:?}", Exchange::ID);
-            tokio::spawn(consume::<Exchange, Kind>(subscriptions, exchange_tx));
+                deduplicate(redundancy, leg_rxs, exchange_tx.clone(), leg_counter.clone());
+                tokio::spawn(fan_out(command_rx, leg_command_txs));
+            }
 
             Ok(())
         }));
@@ -88,12 +223,82 @@ where
         // Await Stream initialization perpetual and ensure success
         futures::future::join_all(self.futures).await;
 
-        Ok(Streams {
-            streams: self
-                .channels
+        let conflation = self.conflation;
+
+        Ok(Streams::new(
+            self.channels
                 .into_iter()
-                .map(|(exchange_id, channel)| (exchange_id, channel.rx))
+                .map(|(exchange_id, channel)| match conflation {
+                    Some((conflation, conflator)) => (exchange_id, conflator(conflation, channel.rx)),
+                    None => (exchange_id, channel.rx),
+                })
                 .collect(),
-        })
+        )
+        .with_handles(self.handles)
+        .with_leg_counters(self.leg_counters)
+        .with_status(self.status.rx)
+        .with_stream_counters(
+            self.stream_counters
+                .into_iter()
+                .map(|(exchange, counter)| (exchange, short_type_name::<Kind>(), counter)),
+        ))
+    }
+}
+
+/// Split the subscriptions into shards of at most `max_per_connection` subscriptions, each of
+/// which is consumed over a separate connection. There is always at least one shard so that
+/// empty subscriptions are still validated.
+pub fn shard<T>(subscriptions: Vec<T>, max_per_connection: Option<usize>) -> Vec<Vec<T>> {
+    match max_per_connection {
+        Some(max) if subscriptions.len() > max => {
+            let mut subscriptions = subscriptions.into_iter().peekable();
+            let mut shards = Vec::new();
+            while subscriptions.peek().is_some() {
+                shards.push(subscriptions.by_ref().take(max.max(1)).collect());
+            }
+            shards
+        },
+        _ => vec![subscriptions],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shard() {
+        struct TestCase {
+            input: (Vec<u32>, Option<usize>),
+            expected: Vec<Vec<u32>>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: no limit
+                input: ((0..5).collect(), None),
+                expected: vec![vec![0, 1, 2, 3, 4]],
+            },
+            TestCase {
+                // TC1: within the limit
+                input: ((0..5).collect(), Some(5)),
+                expected: vec![vec![0, 1, 2, 3, 4]],
+            },
+            TestCase {
+                // TC2: beyond the limit
+                input: ((0..5).collect(), Some(2)),
+                expected: vec![vec![0, 1], vec![2, 3], vec![4]],
+            },
+            TestCase {
+                // TC3: empty subscriptions
+                input: (vec![], Some(2)),
+                expected: vec![vec![]],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = shard(test.input.0, test.input.1);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
     }
 }
@@ -1,8 +1,12 @@
 use std::{collections::HashMap, fmt::Debug, future::Future, pin::Pin};
 
-use wednesday_model::{error::DataError, events::MarketEvent, identifiers::ExchangeId};
+use wednesday_model::{channel::ChannelKey, error::DataError, events::MarketEvent, identifiers::ExchangeId, latency::short_type_name, status::StatusChannel};
 
-use crate::{exchange::channel::ExchangeChannel, stream::Streams, subscriber::subscription::SubscriptionKind};
+use crate::{
+    exchange::channel::ExchangeChannel,
+    stream::{handle::SubscriptionHandles, redundancy::LegCounter, telemetry::StreamCounter, Streams},
+    subscriber::subscription::SubscriptionKind,
+};
 
 use super::StreamBuilder;
 
@@ -16,6 +20,10 @@ pub type BuilderInitFuture = Pin<Box<dyn Future<Output = Result<(), DataError>>>
 pub struct MultiStreamBuilder<Output> {
     pub channels: HashMap<ExchangeId, ExchangeChannel<Output>>,
     pub futures: Vec<BuilderInitFuture>,
+    pub handles: SubscriptionHandles,
+    pub leg_counters: Vec<(ExchangeId, LegCounter)>,
+    pub status: StatusChannel,
+    pub stream_counters: Vec<(ExchangeId, &'static str, StreamCounter)>,
 }
 
 impl<Output> Debug for MultiStreamBuilder<Output>
@@ -26,6 +34,7 @@ where
         f.debug_struct("MultiStreamBuilder<Output>")
             .field("channels", &self.channels)
             .field("num_futures", &self.futures.len())
+            .field("handles", &self.handles)
             .finish()
     }
 }
@@ -36,6 +45,10 @@ impl<Output> MultiStreamBuilder<Output> {
         Self {
             channels: HashMap::new(),
             futures: Vec::new(),
+            handles: SubscriptionHandles::default(),
+            leg_counters: Vec::new(),
+            status: StatusChannel::default(),
+            stream_counters: Vec::new(),
         }
     }
 
@@ -46,9 +59,9 @@ impl<Output> MultiStreamBuilder<Output> {
     /// Note that the created [`Future`] is not awaited until the [`MultiStreamBuilder::init`]
     /// method is invoked.
     #[allow(clippy::should_implement_trait)]
-    pub fn add<Kind>(mut self, builder: StreamBuilder<Kind>) -> Self
+    pub fn add<Kind>(mut self, mut builder: StreamBuilder<Kind>) -> Self
     where
-        Output: From<MarketEvent<Kind::Event>> + Send + 'static,
+        Output: From<MarketEvent<Kind::Event>> + ChannelKey + Send + 'static,
         Kind: SubscriptionKind + 'static,
         Kind::Event: Send,
     {
@@ -57,16 +70,45 @@ impl<Output> MultiStreamBuilder<Output> {
 
         // Iterate over each StreamBuilder exchange present
         for exchange in builder.channels.keys().copied() {
-            // Insert ExchangeChannel<Output> Entry to Self for each exchange
-            let exchange_tx = self.channels.entry(exchange).or_default().tx.clone();
+            // Insert ExchangeChannel<Output> Entry to Self for each exchange, with the channel
+            // config of the first StreamBuilder of the exchange
+            let exchange_tx = self
+                .channels
+                .entry(exchange)
+                .or_insert_with(|| ExchangeChannel::with_config(builder.channel_config))
+                .tx
+                .clone();
 
             // Insert new exchange_tx<Output> into HashMap for each exchange
             exchange_txs.insert(exchange, exchange_tx);
         }
 
+        // Subscriptions of the StreamBuilder remain changeable via the common Streams<Output>
+        self.handles.extend(std::mem::take(&mut builder.handles));
+        self.leg_counters.extend(std::mem::take(&mut builder.leg_counters));
+        self.stream_counters.extend(
+            std::mem::take(&mut builder.stream_counters)
+                .into_iter()
+                .map(|(exchange, counter)| (exchange, short_type_name::<Kind>(), counter)),
+        );
+
         // Init Streams<Kind::Event> & send mapped Outputs to the associated exchange_tx
+        let status_tx = self.status.tx.clone();
         self.futures.push(Box::pin(async move {
-            builder.init().await?.streams.into_iter().for_each(|(exchange, mut exchange_rx)| {
+            let mut streams = builder.init().await?;
+
+            // Task to forward the StatusEvents of the StreamBuilder to the common status channel
+            if let Some(mut status_rx) = streams.status() {
+                tokio::spawn(async move {
+                    while let Some(status) = status_rx.recv().await {
+                        if status_tx.send(status).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+
+            streams.streams.into_iter().for_each(|(exchange, mut exchange_rx)| {
                 // Remove exchange_tx<Output> from HashMap that's associated with this tuple:
                 // (ExchangeId, exchange_rx<MarketEvent<SubKind::Event>>)
                 let exchange_tx = exchange_txs.remove(&exchange).expect("all exchange_txs should be present here");
@@ -74,7 +116,9 @@ impl<Output> MultiStreamBuilder<Output> {
                 // Task to receive MarketEvent<SubKind::Event> and send Outputs via exchange_tx
                 tokio::spawn(async move {
                     while let Some(event) = exchange_rx.recv().await {
-                        let _ = exchange_tx.send(Output::from(event));
+                        if exchange_tx.send(Output::from(event)).await.is_err() {
+                            break;
+                        }
                     }
                 });
             });
@@ -93,12 +137,12 @@ impl<Output> MultiStreamBuilder<Output> {
         futures::future::try_join_all(self.futures).await?;
 
         // Construct Streams<Output> using each ExchangeChannel receiver
-        Ok(Streams {
-            streams: self
-                .channels
-                .into_iter()
-                .map(|(exchange, channel)| (exchange, channel.rx))
-                .collect(),
-        })
+        Ok(
+            Streams::new(self.channels.into_iter().map(|(exchange, channel)| (exchange, channel.rx)).collect())
+                .with_handles(self.handles)
+                .with_leg_counters(self.leg_counters)
+                .with_status(self.status.rx)
+                .with_stream_counters(self.stream_counters),
+        )
     }
 }
@@ -0,0 +1,322 @@
+use std::{collections::HashMap, time::Duration};
+
+use tracing::debug;
+use wednesday_model::{
+    channel::{channel, ChannelConfig, ChannelRx, OverflowPolicy},
+    events::MarketEvent,
+    identifiers::Exchange,
+    instruments::Instrument,
+    orderbook::{Level, OrderBook, OrderBookSide},
+};
+
+/// Configuration of how a [`StreamBuilder`](super::builder::StreamBuilder) conflates the order
+/// books of a stream before they are delivered to the consumer, see
+/// [`StreamBuilder::conflate`](super::builder::StreamBuilder::conflate).
+///
+/// Books are conflated per instrument, so that only the latest state of each instrument is
+/// delivered and stale intermediate states are dropped. The modes can be combined, eg/
+/// `Conflation::interval(100).with_depth(10).with_best_change()`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Conflation {
+    /// Emit at most one book per instrument every interval. Without an interval, books are
+    /// emitted as soon as the consumer keeps up, with any backlog conflated to the latest book.
+    pub interval: Option<Duration>,
+    /// Emit only the best `depth` levels of each side.
+    pub depth: Option<usize>,
+    /// Emit only when the best bid or best ask changes.
+    pub best_change: bool,
+}
+
+impl Conflation {
+    /// Emit at most one book per instrument every `interval_ms` milliseconds.
+    pub fn interval(interval_ms: u64) -> Self {
+        Self {
+            interval: Some(Duration::from_millis(interval_ms)),
+            ..Self::default()
+        }
+    }
+
+    /// Emit only the best `depth` levels of each side.
+    pub fn depth(depth: usize) -> Self {
+        Self::default().with_depth(depth)
+    }
+
+    /// Emit only when the best bid or best ask changes.
+    pub fn best_change() -> Self {
+        Self::default().with_best_change()
+    }
+
+    pub fn with_depth(self, depth: usize) -> Self {
+        Self { depth: Some(depth), ..self }
+    }
+
+    pub fn with_best_change(self) -> Self {
+        Self { best_change: true, ..self }
+    }
+
+    /// Spawn a task that conflates the events of `rx`, and returns the receiver of the conflated
+    /// events. The task ends once the latest events are flushed after `rx` is closed, or when the
+    /// returned receiver is dropped.
+    ///
+    /// The conflated channel holds a single event, so that a slow consumer applies backpressure
+    /// to the task & the backlog builds up in `rx`, where it is conflated.
+    pub fn spawn<T>(self, mut rx: ChannelRx<MarketEvent<T>>) -> ChannelRx<MarketEvent<T>>
+    where
+        T: Conflate + Send + 'static,
+    {
+        let (conflated_tx, conflated_rx) = channel(ChannelConfig::bounded(1, OverflowPolicy::Block));
+
+        tokio::spawn(async move {
+            let mut conflator = Conflator::new(self);
+            let mut ticker = self.interval.map(|interval| {
+                let mut ticker = tokio::time::interval(interval);
+                ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+                ticker
+            });
+
+            loop {
+                let open = match ticker.as_mut() {
+                    Some(ticker) => tokio::select! {
+                        event = rx.recv() => match event {
+                            Some(event) => {
+                                conflator.push(event);
+                                continue;
+                            },
+                            None => false,
+                        },
+                        _ = ticker.tick() => true,
+                    },
+                    None => match rx.recv().await {
+                        Some(event) => {
+                            conflator.push(event);
+                            // Conflate the backlog that built up whilst the consumer was busy
+                            while let Ok(event) = rx.try_recv() {
+                                conflator.push(event);
+                            }
+                            true
+                        },
+                        None => false,
+                    },
+                };
+
+                for event in conflator.flush() {
+                    if conflated_tx.send(event).await.is_err() {
+                        debug!(why = "receiver dropped", "stopping order book conflation");
+                        return;
+                    }
+                }
+
+                if !open {
+                    return;
+                }
+            }
+        });
+
+        conflated_rx
+    }
+}
+
+/// Market event kind that can be conflated, see [`Conflation`].
+pub trait Conflate: Clone {
+    /// Best bid & best ask, used to detect a change of the top of book.
+    fn best(&self) -> (Option<Level>, Option<Level>);
+
+    /// Keep only the best `depth` levels of each side.
+    fn truncate(&self, depth: usize) -> Self;
+}
+
+impl Conflate for OrderBook {
+    fn best(&self) -> (Option<Level>, Option<Level>) {
+        (self.bids.best().copied(), self.asks.best().copied())
+    }
+
+    fn truncate(&self, depth: usize) -> Self {
+        Self {
+            last_update_ts: self.last_update_ts,
+            update_id: None,
+            bids: OrderBookSide::new(self.bids.side(), self.bids.top(depth).copied()),
+            asks: OrderBookSide::new(self.asks.side(), self.asks.top(depth).copied()),
+        }
+    }
+}
+
+/// Latest state of each instrument that is waiting to be flushed by a [`Conflation`] task.
+#[derive(Debug)]
+pub struct Conflator<T> {
+    config: Conflation,
+    pending: HashMap<(Exchange, Instrument), MarketEvent<T>>,
+    last_best: HashMap<(Exchange, Instrument), (Option<Level>, Option<Level>)>,
+}
+
+impl<T> Conflator<T>
+where
+    T: Conflate,
+{
+    pub fn new(config: Conflation) -> Self {
+        Self {
+            config,
+            pending: HashMap::new(),
+            last_best: HashMap::new(),
+        }
+    }
+
+    /// Replace any pending (now stale) event of the instrument with the latest event.
+    pub fn push(&mut self, event: MarketEvent<T>) {
+        self.pending.insert((event.exchange.clone(), event.instrument.clone()), event);
+    }
+
+    /// Take the latest event of each instrument, dropping those whose best bid & best ask are
+    /// unchanged if [`Conflation::best_change`] is set.
+    pub fn flush(&mut self) -> Vec<MarketEvent<T>> {
+        let mut events = self
+            .pending
+            .drain()
+            .filter_map(|(key, mut event)| {
+                if self.config.best_change {
+                    let best = event.kind.best();
+                    if self.last_best.get(&key) == Some(&best) {
+                        return None;
+                    }
+                    self.last_best.insert(key, best);
+                }
+
+                if let Some(depth) = self.config.depth {
+                    event.kind = event.kind.truncate(depth);
+                }
+
+                Some(event)
+            })
+            .collect::<Vec<_>>();
+
+        // Deliver the instruments in the order they were last updated
+        events.sort_by_key(|event| event.local_ts);
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+    use wednesday_model::{enums::BookSide, identifiers::ExchangeId, instruments::InstrumentKind};
+
+    use super::*;
+
+    fn event(base: &str, time: i64, bids: Vec<Level>, asks: Vec<Level>) -> MarketEvent<OrderBook> {
+        let time = DateTime::<Utc>::from_timestamp(time, 0).unwrap();
+        MarketEvent {
+            exchange_ts: time,
+            local_ts: time,
+            exchange: Exchange::from(ExchangeId::BinanceFuturesUsd),
+            instrument: Instrument::from((base, "usdt", InstrumentKind::CryptoPerpetual)),
+            kind: OrderBook {
+                last_update_ts: time,
+                update_id: None,
+                bids: OrderBookSide::new(BookSide::Bid, bids),
+                asks: OrderBookSide::new(BookSide::Ask, asks),
+            },
+        }
+    }
+
+    #[test]
+    fn test_conflator() {
+        struct TestCase {
+            config: Conflation,
+            input: Vec<MarketEvent<OrderBook>>,
+            expected: Vec<MarketEvent<OrderBook>>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: latest book of each instrument is delivered
+                config: Conflation::default(),
+                input: vec![
+                    event("btc", 0, vec![Level::new(99, 1)], vec![Level::new(101, 1)]),
+                    event("eth", 1, vec![Level::new(9, 1)], vec![Level::new(11, 1)]),
+                    event("btc", 2, vec![Level::new(100, 1)], vec![Level::new(101, 1)]),
+                ],
+                expected: vec![
+                    event("eth", 1, vec![Level::new(9, 1)], vec![Level::new(11, 1)]),
+                    event("btc", 2, vec![Level::new(100, 1)], vec![Level::new(101, 1)]),
+                ],
+            },
+            TestCase {
+                // TC1: only the best levels are delivered
+                config: Conflation::depth(1),
+                input: vec![event(
+                    "btc",
+                    0,
+                    vec![Level::new(99, 1), Level::new(98, 1)],
+                    vec![Level::new(101, 1), Level::new(102, 1)],
+                )],
+                expected: vec![event("btc", 0, vec![Level::new(99, 1)], vec![Level::new(101, 1)])],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let mut conflator = Conflator::new(test.config);
+            test.input.into_iter().for_each(|event| conflator.push(event));
+            assert_eq!(conflator.flush(), test.expected, "TC{} failed", index);
+            assert!(conflator.flush().is_empty(), "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_conflator_best_change() {
+        let mut conflator = Conflator::new(Conflation::best_change());
+
+        // First book is always delivered
+        conflator.push(event("btc", 0, vec![Level::new(99, 1)], vec![Level::new(101, 1)]));
+        assert_eq!(conflator.flush().len(), 1);
+
+        // Change beyond the best levels is dropped
+        conflator.push(event("btc", 1, vec![Level::new(99, 1), Level::new(98, 5)], vec![Level::new(101, 1)]));
+        assert!(conflator.flush().is_empty());
+
+        // Change of the best bid amount is delivered
+        conflator.push(event("btc", 2, vec![Level::new(99, 2)], vec![Level::new(101, 1)]));
+        assert_eq!(conflator.flush(), vec![event("btc", 2, vec![Level::new(99, 2)], vec![Level::new(101, 1)])]);
+    }
+
+    #[tokio::test]
+    async fn test_spawn_delivers_latest() {
+        let (tx, rx) = channel(ChannelConfig::default());
+        let mut conflated_rx = Conflation::interval(10).spawn(rx);
+
+        for time in 0..100 {
+            tx.try_send(event("btc", time, vec![Level::new(time as f64, 1.0)], vec![Level::new(1000, 1)]))
+                .unwrap();
+        }
+        drop(tx);
+
+        let mut events = Vec::new();
+        while let Some(event) = conflated_rx.recv().await {
+            events.push(event);
+        }
+
+        assert!(events.len() < 100);
+        assert_eq!(events.last(), Some(&event("btc", 99, vec![Level::new(99, 1)], vec![Level::new(1000, 1)])));
+    }
+
+    #[tokio::test]
+    async fn test_spawn_conflates_for_slow_consumer() {
+        let (tx, rx) = channel(ChannelConfig::default());
+        let mut conflated_rx = Conflation::default().spawn(rx);
+
+        tokio::spawn(async move {
+            for time in 0..100 {
+                tx.try_send(event("btc", time, vec![Level::new(time as f64, 1.0)], vec![Level::new(1000, 1)]))
+                    .unwrap();
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        });
+
+        let mut events = Vec::new();
+        while let Some(event) = conflated_rx.recv().await {
+            events.push(event);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        assert!(events.len() < 50, "slow consumer received {} of 100 events", events.len());
+        assert_eq!(events.last(), Some(&event("btc", 99, vec![Level::new(99, 1)], vec![Level::new(1000, 1)])));
+    }
+}
@@ -4,14 +4,20 @@ use std::rc::Rc;
 use std::task::Poll;
 use std::{collections::VecDeque, marker::PhantomData};
 
+use chrono::{DateTime, Utc};
 use futures::Stream;
 use pin_project::pin_project;
+use tokio::sync::mpsc;
 use tracing::debug;
 use wednesday_model::error::SocketError;
 
-use crate::transformer::Transformer;
+use crate::{protocol::http::websocket::WsMessage, transformer::Transformer};
 
-use super::parser::StreamParser;
+use super::{parser::StreamParser, rate_limit::RateLimiter};
+
+/// Result of the validation of a message if it is a response to a subscription, see
+/// [`subscription_response`](crate::subscriber::validator::subscription_response).
+pub type ResponseFilter<Protocol> = fn(Rc<Result<<Protocol as StreamParser>::Message, <Protocol as StreamParser>::Error>>) -> Option<Result<(), SocketError>>;
 
 #[pin_project]
 pub struct ExchangeStream<Protocol, InnerStream, StreamTransformer>
@@ -24,6 +30,20 @@ where
     pub stream: InnerStream,
     pub transformer: StreamTransformer,
     pub buffer: VecDeque<Result<StreamTransformer::Output, StreamTransformer::Error>>,
+    /// Messages received (& the time they were received) whilst awaiting the responses to
+    /// subscriptions added to the running stream, which are consumed before the inner stream.
+    pub backlog: VecDeque<(DateTime<Utc>, InnerStream::Item)>,
+    /// Sender of messages to the exchange over the connection of the stream, used to change the
+    /// subscriptions of the running stream.
+    pub ws_sink_tx: Option<mpsc::UnboundedSender<WsMessage>>,
+    /// Limits the (un)subscription requests sent over the connection of the stream, see
+    /// [`Connector::message_rate_limit`](crate::exchange::connector::Connector::message_rate_limit).
+    pub rate_limiter: Option<RateLimiter>,
+    /// Time the message of the buffered outputs was received.
+    pub received_ts: Option<DateTime<Utc>>,
+    /// Identifies the responses to subscriptions changed on the running stream, which are
+    /// dropped rather than passed downstream unless the exchange rejected the change.
+    pub responses: Option<ResponseFilter<Protocol>>,
     pub protocol_marker: PhantomData<Protocol>,
 }
 
@@ -43,10 +63,19 @@ where
                 return Poll::Ready(Some(output));
             }
 
-            let input = Rc::new(match self.as_mut().project().stream.poll_next(cx) {
-                Poll::Ready(Some(input)) => input,
-                Poll::Ready(None) => return Poll::Ready(None),
-                Poll::Pending => return Poll::Pending,
+            let input = Rc::new(match self.backlog.pop_front() {
+                Some((received_ts, input)) => {
+                    self.received_ts = Some(received_ts);
+                    input
+                },
+                None => match self.as_mut().project().stream.poll_next(cx) {
+                    Poll::Ready(Some(input)) => {
+                        self.received_ts = Some(Utc::now());
+                        input
+                    },
+                    Poll::Ready(None) => return Poll::Ready(None),
+                    Poll::Pending => return Poll::Pending,
+                },
             });
 
             // NOTE_0002: NOTE_0001 구현을 위해서 StreamTransforemr::Pong 를 추가하였음.
@@ -54,16 +83,15 @@ where
 
             let exchange_message = match parsed_input {
                 // `StreamParser` successfully deserialized the `ExchagneMessage`
-                Some(Ok(exchange_message)) => { exchange_message }
+                Some(Ok(exchange_message)) => exchange_message,
                 // if `StreamParser` return an Err pass it downstream
                 Some(Err(err)) => {
                     // NOTE_0001: 원래 이 에러 처리는 Bybit 에서 PONG 메시지가 오는 것을 처리하기 위한 것임.
                     // KOSCOM 이나 다른 국내 증권사 WS API 를 사용할 때는 이 부분이 필요가 없을 수도 있음.
                     // ExchangeWsStream 이나 Protocol::parse layer 에서 처리하는게 맞는데 이쪽 레이어에서 처리하게함. 일단 보이는게 여기밖에 없었음.
                     // {"timestamp":"2024-07-14T16:32:31.797854Z","level":"DEBUG","fields":{"message":"failed to deserialize WebSocket Message into domain specific Message","error":"Error(\"missing field `subscription_id`\", line: 1, column: 106)","payload":"\"{\\\"success\\\":true,\\\"ret_msg\\\":\\\"pong\\\",\\\"conn_id\\\":\\\"81d5da61-f4c2-482a-95b6-8ac4f60eded3\\\",\\\"req_id\\\":\\\"\\\",\\\"op\\\":\\\"ping\\\"}\"","action":"returning Some(Err(err))"},"target":"wednesday_connector::protocol::http::websocket"}
-                    panic!("Error: {:?}", err);
-
-
+                    // Messages that are neither an `ExchangeMessage` nor a pong (eg/ the response to a
+                    // subscription added to the running stream) are passed downstream as errors
                     let _message = match Protocol::parse::<StreamTransformer::Pong>(Rc::clone(&input)) {
                         Some(Ok(message)) => {
                             debug!("Received PONG message from exchange");
@@ -77,7 +105,15 @@ where
                         },
                     };
 
-                    return Poll::Ready(Some(Err(err.into())));
+                    // Responses to subscriptions changed on the running stream are not market data
+                    match self.responses.and_then(|response| response(Rc::clone(&input))) {
+                        Some(Ok(())) => {
+                            debug!("Received subscription response from exchange");
+                            continue;
+                        },
+                        Some(Err(error)) => return Poll::Ready(Some(Err(error.into()))),
+                        None => return Poll::Ready(Some(Err(err.into()))),
+                    }
                 },
                 // if `StreamParser` returns None it's a safe-to-skip message
                 None => {
@@ -86,15 +122,16 @@ where
                 },
             };
 
-            debug!(r#type="debug_msg: Parsed exchange_message", ?exchange_message);
+            debug!(r#type = "debug_msg: Parsed exchange_message", ?exchange_message);
 
             // Transform `ExchangeMessage` into `Transformer::OutputIter`
             // ie/ IntoIterator<Item = Result<Output, SocketError>>
-            self.transformer.transform(exchange_message).into_iter().for_each(
-                |output_result: Result<StreamTransformer::Output, StreamTransformer::Error>| {
+            self.transformer
+                .transform(exchange_message)
+                .into_iter()
+                .for_each(|output_result: Result<StreamTransformer::Output, StreamTransformer::Error>| {
                     self.buffer.push_back(output_result);
-                },
-            );
+                });
         }
     }
 }
@@ -110,7 +147,30 @@ where
             stream,
             transformer,
             buffer: VecDeque::with_capacity(6),
+            backlog: VecDeque::new(),
+            ws_sink_tx: None,
+            rate_limiter: None,
+            received_ts: None,
+            responses: None,
             protocol_marker: PhantomData,
         }
     }
+
+    pub fn with_ws_sink_tx(self, ws_sink_tx: mpsc::UnboundedSender<WsMessage>) -> Self {
+        Self {
+            ws_sink_tx: Some(ws_sink_tx),
+            ..self
+        }
+    }
+
+    pub fn with_rate_limiter(self, rate_limiter: Option<RateLimiter>) -> Self {
+        Self { rate_limiter, ..self }
+    }
+
+    pub fn with_responses(self, responses: ResponseFilter<Protocol>) -> Self {
+        Self {
+            responses: Some(responses),
+            ..self
+        }
+    }
 }
@@ -0,0 +1,495 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt::{self, Debug},
+};
+
+use tokio::sync::{mpsc, oneshot};
+use wednesday_model::error::{DataError, SocketError};
+
+use crate::subscriber::{
+    subscription::{Subscription, SubscriptionKind},
+    validator::validate,
+};
+
+use super::selector::StreamSelector;
+
+/// Command sent by a [`SubscriptionHandle`] to the consumer task of a running stream, which
+/// replies with the result once the exchange has been sent the (un)subscription requests, & the
+/// subscriptions have been acknowledged.
+#[derive(Debug)]
+pub enum SubscriptionCommand<Exchange, Kind> {
+    Subscribe {
+        subscriptions: Vec<Subscription<Exchange, Kind>>,
+        reply_tx: oneshot::Sender<Result<(), DataError>>,
+    },
+    Unsubscribe {
+        subscriptions: Vec<Subscription<Exchange, Kind>>,
+        reply_tx: oneshot::Sender<Result<(), DataError>>,
+    },
+    /// Active subscriptions of the running stream.
+    Subscriptions {
+        reply_tx: oneshot::Sender<Vec<Subscription<Exchange, Kind>>>,
+    },
+}
+
+pub type CommandTx<Exchange, Kind> = mpsc::UnboundedSender<SubscriptionCommand<Exchange, Kind>>;
+pub type CommandRx<Exchange, Kind> = mpsc::UnboundedReceiver<SubscriptionCommand<Exchange, Kind>>;
+
+/// Handle to add [`Subscription`]s to, & remove them from, the running streams of an exchange
+/// without re-connecting, see [`Streams::handle`](super::Streams::handle).
+///
+/// Only exchanges that support [`Connector::dynamic_subscriptions`](crate::exchange::connector::Connector::dynamic_subscriptions)
+/// can change the subscriptions of a running stream.
+pub struct SubscriptionHandle<Exchange, Kind> {
+    /// Command sender of the consumer task of every [`StreamBuilder::subscribe`](super::builder::StreamBuilder::subscribe)
+    /// call for the exchange, each of which owns a connection.
+    command_txs: Vec<CommandTx<Exchange, Kind>>,
+}
+
+impl<Exchange, Kind> Debug for SubscriptionHandle<Exchange, Kind> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SubscriptionHandle").field("connections", &self.command_txs.len()).finish()
+    }
+}
+
+impl<Exchange, Kind> Clone for SubscriptionHandle<Exchange, Kind> {
+    fn clone(&self) -> Self {
+        Self {
+            command_txs: self.command_txs.clone(),
+        }
+    }
+}
+
+impl<Exchange, Kind> SubscriptionHandle<Exchange, Kind>
+where
+    Exchange: StreamSelector<Kind>,
+    Kind: SubscriptionKind,
+{
+    /// Add subscriptions to the running stream, over the first connection of the exchange that
+    /// has capacity for them (see [`Connector::max_subscriptions_per_connection`](crate::exchange::connector::Connector::max_subscriptions_per_connection)).
+    /// New order books are initialised before this returns, & subscriptions that are already
+    /// active on any connection are ignored.
+    pub async fn subscribe<SubscriptionIter, SubscriptionItem>(&self, subscriptions: SubscriptionIter) -> Result<(), DataError>
+    where
+        SubscriptionIter: IntoIterator<Item = SubscriptionItem>,
+        SubscriptionItem: Into<Subscription<Exchange, Kind>>,
+        Subscription<Exchange, Kind>: PartialEq,
+    {
+        let subscriptions = subscriptions.into_iter().map(Into::into).collect::<Vec<_>>();
+        validate(&subscriptions)?;
+
+        // Connections only ignore their own active subscriptions, so those of every other
+        // connection are removed to avoid consuming duplicate feeds
+        let active = self.subscriptions().await;
+        let subscriptions = subscriptions.into_iter().filter(|sub| !active.contains(sub)).collect::<Vec<_>>();
+        if subscriptions.is_empty() {
+            return Ok(());
+        }
+
+        let mut result = Err(not_running());
+        for command_tx in &self.command_txs {
+            let (reply_tx, reply_rx) = oneshot::channel();
+            if command_tx
+                .send(SubscriptionCommand::Subscribe {
+                    subscriptions: subscriptions.clone(),
+                    reply_tx,
+                })
+                .is_err()
+            {
+                continue;
+            }
+
+            result = reply_rx.await.unwrap_or_else(|_| Err(not_running()));
+            if result.is_ok() {
+                break;
+            }
+        }
+
+        result
+    }
+
+    /// Remove subscriptions from the running stream, from whichever connection of the exchange
+    /// consumes them. Subscriptions that are not active are ignored, as are connections whose
+    /// consumer task has stopped, unless no connection is running.
+    pub async fn unsubscribe<SubscriptionIter, SubscriptionItem>(&self, subscriptions: SubscriptionIter) -> Result<(), DataError>
+    where
+        SubscriptionIter: IntoIterator<Item = SubscriptionItem>,
+        SubscriptionItem: Into<Subscription<Exchange, Kind>>,
+    {
+        let subscriptions = subscriptions.into_iter().map(Into::into).collect::<Vec<_>>();
+
+        let (mut running, mut result) = (false, Ok(()));
+        for command_tx in &self.command_txs {
+            let (reply_tx, reply_rx) = oneshot::channel();
+            if command_tx
+                .send(SubscriptionCommand::Unsubscribe {
+                    subscriptions: subscriptions.clone(),
+                    reply_tx,
+                })
+                .is_err()
+            {
+                continue;
+            }
+
+            let Ok(reply) = reply_rx.await else {
+                continue;
+            };
+
+            // Keep the first error of a running connection, whilst still unsubscribing the others
+            running = true;
+            if result.is_ok() {
+                result = reply;
+            }
+        }
+
+        match running {
+            true => result,
+            false => Err(not_running()),
+        }
+    }
+
+    /// Active subscriptions of every running connection of the exchange.
+    pub async fn subscriptions(&self) -> Vec<Subscription<Exchange, Kind>> {
+        let mut active = Vec::new();
+        for command_tx in &self.command_txs {
+            let (reply_tx, reply_rx) = oneshot::channel();
+            if command_tx.send(SubscriptionCommand::Subscriptions { reply_tx }).is_err() {
+                continue;
+            }
+            if let Ok(subscriptions) = reply_rx.await {
+                active.extend(subscriptions);
+            }
+        }
+        active
+    }
+}
+
+/// Forward the commands of a [`SubscriptionHandle`] to each redundant leg of a subscription set,
+/// replying with the first error of any running leg, or the active subscriptions of the first
+/// running leg, see [`Redundancy`](super::redundancy::Redundancy).
+///
+/// Subscriptions rejected by any leg are unsubscribed from the legs that accepted them, so every
+/// leg consumes the same subscriptions & the caller can retry them on another connection.
+pub async fn fan_out<Exchange, Kind>(mut command_rx: CommandRx<Exchange, Kind>, leg_command_txs: Vec<CommandTx<Exchange, Kind>>)
+where
+    Subscription<Exchange, Kind>: Clone,
+{
+    while let Some(command) = command_rx.recv().await {
+        let (subscribe, subscriptions, reply_tx) = match command {
+            SubscriptionCommand::Subscribe { subscriptions, reply_tx } => (true, subscriptions, reply_tx),
+            SubscriptionCommand::Unsubscribe { subscriptions, reply_tx } => (false, subscriptions, reply_tx),
+            SubscriptionCommand::Subscriptions { reply_tx } => {
+                let mut active = Vec::new();
+                for leg_command_tx in &leg_command_txs {
+                    let (leg_reply_tx, leg_reply_rx) = oneshot::channel();
+                    if leg_command_tx.send(SubscriptionCommand::Subscriptions { reply_tx: leg_reply_tx }).is_ok() {
+                        if let Ok(subscriptions) = leg_reply_rx.await {
+                            active = subscriptions;
+                            break;
+                        }
+                    }
+                }
+                let _ = reply_tx.send(active);
+                continue;
+            },
+        };
+
+        // Legs that are no longer running are skipped, since the others remain redundant
+        let mut replies = Vec::with_capacity(leg_command_txs.len());
+        for leg_command_tx in &leg_command_txs {
+            if let Some(reply) = command_leg(leg_command_tx, subscribe, subscriptions.clone()).await {
+                replies.push((leg_command_tx, reply));
+            }
+        }
+
+        let result = match replies.iter().position(|(_, reply)| reply.is_err()) {
+            None if replies.is_empty() => Err(not_running()),
+            None => Ok(()),
+            Some(rejected) => {
+                if subscribe {
+                    for (leg_command_tx, _) in replies.iter().filter(|(_, reply)| reply.is_ok()) {
+                        let _ = command_leg(leg_command_tx, false, subscriptions.clone()).await;
+                    }
+                }
+                replies.swap_remove(rejected).1
+            },
+        };
+
+        let _ = reply_tx.send(result);
+    }
+}
+
+/// Send a subscribe or unsubscribe command to a leg, returning its reply, or [`None`] if the leg
+/// is not running.
+async fn command_leg<Exchange, Kind>(
+    leg_command_tx: &CommandTx<Exchange, Kind>,
+    subscribe: bool,
+    subscriptions: Vec<Subscription<Exchange, Kind>>,
+) -> Option<Result<(), DataError>> {
+    let (reply_tx, reply_rx) = oneshot::channel();
+    let command = match subscribe {
+        true => SubscriptionCommand::Subscribe { subscriptions, reply_tx },
+        false => SubscriptionCommand::Unsubscribe { subscriptions, reply_tx },
+    };
+    leg_command_tx.send(command).ok()?;
+    reply_rx.await.ok()
+}
+
+fn not_running() -> DataError {
+    DataError::Socket(SocketError::Subscribe("MarketStream consumer is not running".to_owned()))
+}
+
+/// Type erased [`SubscriptionHandle`]s of each exchange & [`SubscriptionKind`] of a
+/// [`Streams`](super::Streams), since a single [`Streams`](super::Streams) can be built from
+/// several kinds with a [`MultiStreamBuilder`](super::builder::multiple::MultiStreamBuilder).
+#[derive(Default)]
+pub struct SubscriptionHandles(HashMap<TypeId, Vec<Box<dyn Any + Send + Sync>>>);
+
+impl Debug for SubscriptionHandles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SubscriptionHandles").field(&self.0.len()).finish()
+    }
+}
+
+impl SubscriptionHandles {
+    pub fn insert<Exchange, Kind>(&mut self, command_tx: CommandTx<Exchange, Kind>)
+    where
+        Exchange: Send + 'static,
+        Kind: Send + 'static,
+    {
+        self.0
+            .entry(TypeId::of::<SubscriptionCommand<Exchange, Kind>>())
+            .or_default()
+            .push(Box::new(command_tx));
+    }
+
+    pub fn get<Exchange, Kind>(&self) -> Option<SubscriptionHandle<Exchange, Kind>>
+    where
+        Exchange: 'static,
+        Kind: 'static,
+    {
+        let command_txs = self
+            .0
+            .get(&TypeId::of::<SubscriptionCommand<Exchange, Kind>>())?
+            .iter()
+            .filter_map(|command_tx| command_tx.downcast_ref::<CommandTx<Exchange, Kind>>().cloned())
+            .collect();
+
+        Some(SubscriptionHandle { command_txs })
+    }
+
+    pub fn extend(&mut self, other: SubscriptionHandles) {
+        other
+            .0
+            .into_iter()
+            .for_each(|(type_id, command_txs)| self.0.entry(type_id).or_default().extend(command_txs));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wednesday_model::instruments::InstrumentKind;
+
+    use crate::{
+        exchange::{
+            binance::{futures::BinanceFuturesUsd, spot::BinanceSpot},
+            okx::Okx,
+        },
+        subscriber::subscription::kind::{OrderBooksL2, PublicTrades},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_subscription_handles() {
+        let mut handles = SubscriptionHandles::default();
+        let (command_tx, _command_rx) = mpsc::unbounded_channel::<SubscriptionCommand<Okx, PublicTrades>>();
+        handles.insert(command_tx);
+
+        let mut other = SubscriptionHandles::default();
+        let (command_tx, _command_rx) = mpsc::unbounded_channel::<SubscriptionCommand<Okx, PublicTrades>>();
+        other.insert(command_tx);
+        let (command_tx, _command_rx) = mpsc::unbounded_channel::<SubscriptionCommand<Okx, OrderBooksL2>>();
+        other.insert(command_tx);
+        handles.extend(other);
+
+        assert_eq!(handles.get::<Okx, PublicTrades>().unwrap().command_txs.len(), 2);
+        assert_eq!(handles.get::<Okx, OrderBooksL2>().unwrap().command_txs.len(), 1);
+        assert!(handles.get::<BinanceSpot, OrderBooksL2>().is_none());
+        assert!(handles.get::<BinanceFuturesUsd, OrderBooksL2>().is_none());
+    }
+
+    type Sub = Subscription<Okx, PublicTrades>;
+
+    fn sub(base: &str) -> Sub {
+        Subscription::from((Okx, base, "usdt", InstrumentKind::CryptoSpot, PublicTrades))
+    }
+
+    // Mock consumer task that replies to every command, accepting subscriptions if `accept`,
+    // & returns the (un)subscribe commands it received
+    async fn mock_consumer(mut command_rx: CommandRx<Okx, PublicTrades>, mut active: Vec<Sub>, accept: bool) -> Vec<(&'static str, Vec<Sub>)> {
+        let mut commands = Vec::new();
+        while let Some(command) = command_rx.recv().await {
+            match command {
+                SubscriptionCommand::Subscribe { subscriptions, reply_tx } => {
+                    commands.push(("subscribe", subscriptions.clone()));
+                    let _ = reply_tx.send(match accept {
+                        true => {
+                            active.extend(subscriptions);
+                            Ok(())
+                        },
+                        false => Err(not_running()),
+                    });
+                },
+                SubscriptionCommand::Unsubscribe { subscriptions, reply_tx } => {
+                    active.retain(|sub| !subscriptions.contains(sub));
+                    commands.push(("unsubscribe", subscriptions));
+                    let _ = reply_tx.send(Ok(()));
+                },
+                SubscriptionCommand::Subscriptions { reply_tx } => {
+                    let _ = reply_tx.send(active.clone());
+                },
+            }
+        }
+        commands
+    }
+
+    #[tokio::test]
+    async fn test_subscription_handle_ignores_active_subscriptions_of_other_connections() {
+        let mut handles = SubscriptionHandles::default();
+
+        // First connection is full, & the second already consumes btc
+        let (command_tx, command_rx) = mpsc::unbounded_channel();
+        handles.insert(command_tx);
+        let full = tokio::spawn(mock_consumer(command_rx, vec![], false));
+        let (command_tx, command_rx) = mpsc::unbounded_channel();
+        handles.insert(command_tx);
+        let consuming = tokio::spawn(mock_consumer(command_rx, vec![sub("btc")], true));
+
+        let handle = handles.get::<Okx, PublicTrades>().unwrap();
+        assert_eq!(handle.subscriptions().await, vec![sub("btc")]);
+
+        // Active subscriptions are not sent to any connection
+        handle.subscribe([sub("btc")]).await.unwrap();
+        handle.subscribe([sub("btc"), sub("eth")]).await.unwrap();
+        assert_eq!(handle.subscriptions().await, vec![sub("btc"), sub("eth")]);
+
+        drop((handles, handle));
+        assert_eq!(full.await.unwrap(), vec![("subscribe", vec![sub("eth")])]);
+        assert_eq!(consuming.await.unwrap(), vec![("subscribe", vec![sub("eth")])]);
+    }
+
+    #[tokio::test]
+    async fn test_subscription_handle() {
+        let mut handles = SubscriptionHandles::default();
+        let (command_tx, command_rx) = mpsc::unbounded_channel::<SubscriptionCommand<Okx, PublicTrades>>();
+        handles.insert(command_tx);
+        let handle = handles.get::<Okx, PublicTrades>().unwrap();
+
+        let consumer = tokio::spawn(mock_consumer(command_rx, vec![], true));
+
+        let subscription = Subscription::from((Okx, "btc", "usdt", InstrumentKind::CryptoSpot, PublicTrades));
+        handle.subscribe([subscription.clone()]).await.unwrap();
+        handle.unsubscribe([subscription.clone()]).await.unwrap();
+
+        // Empty subscriptions are rejected before reaching the consumer
+        assert!(handle.subscribe(Vec::<Subscription<Okx, PublicTrades>>::new()).await.is_err());
+
+        drop((handles, handle));
+        assert_eq!(
+            consumer.await.unwrap(),
+            vec![("subscribe", vec![subscription.clone()]), ("unsubscribe", vec![subscription])]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_subscription_handle_skips_stopped_connections() {
+        let mut handles = SubscriptionHandles::default();
+
+        // First connection's consumer task has stopped
+        let (command_tx, command_rx) = mpsc::unbounded_channel::<SubscriptionCommand<Okx, PublicTrades>>();
+        handles.insert(command_tx);
+        drop(command_rx);
+
+        let (command_tx, mut command_rx) = mpsc::unbounded_channel::<SubscriptionCommand<Okx, PublicTrades>>();
+        handles.insert(command_tx);
+        let handle = handles.get::<Okx, PublicTrades>().unwrap();
+
+        let consumer = tokio::spawn(async move {
+            let mut unsubscribed = 0;
+            while let Some(command) = command_rx.recv().await {
+                match command {
+                    SubscriptionCommand::Unsubscribe { reply_tx, .. } => {
+                        unsubscribed += 1;
+                        let _ = reply_tx.send(Ok(()));
+                    },
+                    SubscriptionCommand::Subscribe { reply_tx, .. } => {
+                        let _ = reply_tx.send(Ok(()));
+                    },
+                    SubscriptionCommand::Subscriptions { reply_tx } => {
+                        let _ = reply_tx.send(vec![]);
+                    },
+                }
+            }
+            unsubscribed
+        });
+
+        let subscription = Subscription::from((Okx, "btc", "usdt", InstrumentKind::CryptoSpot, PublicTrades));
+        assert!(handle.unsubscribe([subscription.clone()]).await.is_ok());
+
+        drop((handles, handle));
+        assert_eq!(consumer.await.unwrap(), 1);
+
+        // No connection is running
+        let mut handles = SubscriptionHandles::default();
+        let (command_tx, command_rx) = mpsc::unbounded_channel::<SubscriptionCommand<Okx, PublicTrades>>();
+        handles.insert(command_tx);
+        drop(command_rx);
+        assert!(handles.get::<Okx, PublicTrades>().unwrap().unsubscribe([subscription]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fan_out() {
+        let (command_tx, command_rx) = mpsc::unbounded_channel::<SubscriptionCommand<Okx, PublicTrades>>();
+        let (leg_txs, leg_rxs): (Vec<_>, Vec<_>) = (0..3).map(|_| mpsc::unbounded_channel()).unzip();
+        let leg_txs_view = leg_txs.clone();
+        tokio::spawn(fan_out(command_rx, leg_txs));
+
+        // Leg 1 rejects the subscriptions, & leg 2 is no longer running
+        let mut leg_rxs = leg_rxs.into_iter();
+        let accepting = tokio::spawn(mock_consumer(leg_rxs.next().unwrap(), vec![sub("eth")], true));
+        let rejecting = tokio::spawn(mock_consumer(leg_rxs.next().unwrap(), vec![sub("eth")], false));
+        drop(leg_rxs);
+
+        let mut handles = SubscriptionHandles::default();
+        handles.insert(command_tx);
+        let handle = handles.get::<Okx, PublicTrades>().unwrap();
+
+        // Rejected subscriptions are rolled back on the accepting leg
+        assert!(handle.subscribe([sub("btc")]).await.is_err());
+        for leg_command_tx in &leg_txs_view[..2] {
+            let (reply_tx, reply_rx) = oneshot::channel();
+            leg_command_tx.send(SubscriptionCommand::Subscriptions { reply_tx }).unwrap();
+            assert_eq!(reply_rx.await.unwrap(), vec![sub("eth")]);
+        }
+
+        // Unsubscribing is forwarded to every running leg
+        handle.unsubscribe([sub("eth")]).await.unwrap();
+        assert!(handle.subscriptions().await.is_empty());
+
+        drop((handles, handle, leg_txs_view));
+        assert_eq!(
+            accepting.await.unwrap(),
+            vec![
+                ("subscribe", vec![sub("btc")]),
+                ("unsubscribe", vec![sub("btc")]),
+                ("unsubscribe", vec![sub("eth")])
+            ]
+        );
+        assert_eq!(
+            rejecting.await.unwrap(),
+            vec![("subscribe", vec![sub("btc")]), ("unsubscribe", vec![sub("eth")])]
+        );
+    }
+}
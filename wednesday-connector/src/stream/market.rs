@@ -1,16 +1,30 @@
+use std::time::Duration;
+
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use futures::Stream;
-use tokio::sync::mpsc;
+use tokio::time::Instant;
 use tokio_stream::StreamExt;
 use tracing::{error, info, warn};
-use wednesday_model::{error::DataError, events::MarketEvent, identifiers::Identifier};
+use wednesday_model::{
+    channel::ChannelTx,
+    error::{DataError, SocketError},
+    events::MarketEvent,
+    identifiers::Identifier,
+    latency::short_type_name,
+};
 
 use crate::{
     exchange::connector::Connector,
     subscriber::subscription::{Subscription, SubscriptionKind},
 };
 
-use super::selector::StreamSelector;
+use super::{
+    handle::{CommandRx, SubscriptionCommand},
+    reconnect::{Reconnect, ReconnectPolicy, Reconnector},
+    selector::StreamSelector,
+    telemetry::Telemetry,
+};
 
 #[async_trait]
 pub trait MarketStream<Exchange, Kind>
@@ -22,96 +36,221 @@ where
     async fn init(subscriptions: &[Subscription<Exchange, Kind>]) -> Result<Self, DataError>
     where
         Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>;
-}
 
-pub const STARTING_RECONNECT_BACKOFF_MS: u64 = 1000;
+    /// Initialise the stream as the connection `leg` of redundant connections, see
+    /// [`Connector::leg_url`].
+    async fn init_leg(subscriptions: &[Subscription<Exchange, Kind>], leg: usize) -> Result<Self, DataError>
+    where
+        Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>;
+
+    /// Add subscriptions to the running stream over its existing connection.
+    async fn subscribe(&mut self, subscriptions: &[Subscription<Exchange, Kind>]) -> Result<(), DataError>
+    where
+        Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>;
+
+    /// Remove subscriptions from the running stream over its existing connection.
+    async fn unsubscribe(&mut self, subscriptions: &[Subscription<Exchange, Kind>]) -> Result<(), DataError>
+    where
+        Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>;
+
+    /// Time the message of the most recently yielded event was received, used to measure its
+    /// [`LatencyStage`](wednesday_model::latency::LatencyStage)s.
+    fn received_ts(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+}
 
+/// Consume the [`MarketStream`] of the subscriptions, re-connecting following the
+/// [`ReconnectPolicy`] if it fails to connect, ends, or goes stale, and apply the
+/// [`SubscriptionCommand`]s of its [`SubscriptionHandle`](super::handle::SubscriptionHandle) to
+/// the running stream. The subscriptions added at runtime are kept when re-connecting.
+///
+/// Every change of the connection status is sent as a
+/// [`StatusEvent`](wednesday_model::status::StatusEvent) via the [`Telemetry`], which also counts
+/// the messages, errors, reconnects & resyncs. The `leg` identifies the connection amongst
+/// redundant connections, see [`Connector::leg_url`].
+///
+/// With a [`LatencyRecorder`](wednesday_model::latency::LatencyRecorder), the latency of each
+/// event from the exchange until it was parsed is recorded, see
+/// [`LatencyStage`](wednesday_model::latency::LatencyStage).
 pub async fn consume<Exchange, Kind>(
-    subscriptions: Vec<Subscription<Exchange, Kind>>,
-    exchange_tx: mpsc::UnboundedSender<MarketEvent<Kind::Event>>,
+    mut subscriptions: Vec<Subscription<Exchange, Kind>>,
+    exchange_tx: ChannelTx<MarketEvent<Kind::Event>>,
+    mut command_rx: CommandRx<Exchange, Kind>,
+    leg: usize,
+    policy: ReconnectPolicy,
+    telemetry: Telemetry,
 ) -> DataError
 where
     Exchange: StreamSelector<Kind>,
     Kind: SubscriptionKind,
-    Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+    Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market> + PartialEq,
 {
     let exchange = Exchange::ID;
 
-    info!(%exchange, ?subscriptions,
-        policy = "retry connection with exponential backoff",
-        "MarketStream consumer loop running",
-    );
+    info!(%exchange, leg, ?subscriptions, ?policy, "MarketStream consumer loop running");
 
-    let mut attempt: u32 = 0;
-    let mut backoff_ms: u64 = STARTING_RECONNECT_BACKOFF_MS;
+    let mut reconnector = Reconnector::new(policy);
 
     loop {
-        attempt += 1;
-        backoff_ms *= 2;
-        info!(%exchange, attempt,"attempting to initialize MarketStream");
+        info!(%exchange, leg, "attempting to initialize MarketStream");
 
-        let mut stream = match Exchange::Stream::init(&subscriptions).await {
-            Ok(stream) => {
-                info!(%exchange, attempt, "successfully initialized MarketStream");
+        let error = match Exchange::Stream::init_leg(&subscriptions, leg).await {
+            Ok(mut stream) => {
+                info!(%exchange, leg, "successfully initialized MarketStream");
+                telemetry.status(exchange, leg, reconnector.connected());
 
-                backoff_ms = STARTING_RECONNECT_BACKOFF_MS;
-                stream
+                consume_stream(&mut stream, &mut subscriptions, &exchange_tx, &mut command_rx, &mut reconnector, &telemetry).await
             },
             Err(error) => {
-                info!(%exchange, attempt, %error, "failed to initialize MarketStream");
-
-                // If MarketStream Occurred DataError, retry connection after backoff_ms
-                warn!(
-                    %exchange,
-                    attempt,
-                    action = "attempting re-connection after backoff",
-                    "exchange MarketStream Occurred DataError when Initialization.",
-                );
-                tokio::time::sleep(std::time::Duration::from_millis(backoff_ms)).await;
-
-                if attempt == 5 {
-                    return error;
-                } else {
-                    continue;
-                }
+                warn!(%exchange, leg, %error, "failed to initialize MarketStream");
+                error
             },
         };
 
-        while let Some(event) = stream.next().await {
-            match event {
-                Ok(market_event) => {
-                    let _ = exchange_tx.send(market_event).map_err(|error| {
-                        error!(
-                            payload = ?error.0,
-                            why = "receiver dropped",
-                            "failed to send Event<MarketData> to Exchange Receiver"
+        let reconnect = reconnector.failed();
+        telemetry.status(exchange, leg, reconnect.into());
+
+        match reconnect {
+            Reconnect::Retry { attempt, backoff } => {
+                warn!(%exchange, leg, attempt, ?backoff, action = "attempting re-connection after backoff", "MarketStream disconnected");
+                tokio::time::sleep(backoff).await;
+            },
+            Reconnect::CircuitOpen { attempt, cooldown } => {
+                error!(%exchange, leg, attempt, ?cooldown, action = "attempting re-connection after cooldown", "MarketStream circuit open");
+                tokio::time::sleep(cooldown).await;
+            },
+            Reconnect::GiveUp { attempt } => {
+                error!(%exchange, leg, attempt, %error, action = "giving up re-connection", "MarketStream disconnected");
+                return error;
+            },
+        }
+    }
+}
+
+/// Consume the connected [`MarketStream`] until it ends, consumes a terminal [`DataError`], or
+/// goes stale, returning the reason to re-connect.
+async fn consume_stream<Exchange, Kind, Stream>(
+    stream: &mut Stream,
+    subscriptions: &mut Vec<Subscription<Exchange, Kind>>,
+    exchange_tx: &ChannelTx<MarketEvent<Kind::Event>>,
+    command_rx: &mut CommandRx<Exchange, Kind>,
+    reconnector: &mut Reconnector,
+    telemetry: &Telemetry,
+) -> DataError
+where
+    Exchange: Connector,
+    Kind: SubscriptionKind,
+    Stream: MarketStream<Exchange, Kind>,
+    Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market> + PartialEq,
+{
+    let exchange = Exchange::ID;
+
+    // Disabled branch without a stale timeout, so the deadline is irrelevant
+    let stale_timeout = reconnector.policy().stale_timeout;
+    let stale = tokio::time::sleep(stale_timeout.unwrap_or(Duration::MAX));
+    tokio::pin!(stale);
+
+    loop {
+        tokio::select! {
+            event = stream.next() => {
+                if let Some(timeout) = stale_timeout {
+                    stale.as_mut().reset(Instant::now() + timeout);
+                }
+
+                match event {
+                    Some(Ok(market_event)) => {
+                        reconnector.healthy();
+                        telemetry.message();
+                        if let (Some(latency), Some(received_ts)) = (&telemetry.latency, stream.received_ts()) {
+                            latency.record_market_event(&market_event, short_type_name::<Kind>(), received_ts);
+                        }
+                        let _ = exchange_tx.send(market_event).await.map_err(|error| {
+                            error!(
+                                payload = ?error.0,
+                                why = "receiver dropped",
+                                "failed to send Event<MarketData> to Exchange Receiver"
+                            );
+                        });
+                    },
+                    Some(Err(error)) if error.is_terminal() => {
+                        error!(%exchange, %error,
+                            action = "re-initializing Stream",
+                            "consumed DataError from MarketStream",
                         );
-                    });
-                },
-                Err(error) if error.is_terminal() => {
-                    error!(%exchange, %error,
-                        action = "re-initializing Stream",
-                        "consumed DataError from MarketStream",
-                    );
-                    break;
-                },
-                Err(error) => {
-                    warn!(%exchange, %error,
-                        action = "skipping message",
-                        "consumed DataError from MarketStream",
-                    );
-                    continue;
-                },
-            }
+                        return error;
+                    },
+                    Some(Err(error)) => {
+                        telemetry.error();
+                        warn!(%exchange, %error,
+                            action = "skipping message",
+                            "consumed DataError from MarketStream",
+                        );
+                    },
+                    None => return DataError::Socket(SocketError::Terminated(format!("{exchange} MarketStream ended"))),
+                }
+            },
+            Some(command) = command_rx.recv() => {
+                execute_command(stream, subscriptions, command).await;
+            },
+            _ = &mut stale, if stale_timeout.is_some() => {
+                return DataError::Socket(SocketError::Terminated(format!(
+                    "{exchange} MarketStream received no message within {:?}", stale_timeout.unwrap_or_default()
+                )));
+            },
         }
+    }
+}
+
+/// Apply a [`SubscriptionCommand`] to the running stream & keep track of the resulting
+/// subscriptions, ignoring subscriptions that are already (or not) active.
+async fn execute_command<Exchange, Kind, Stream>(
+    stream: &mut Stream,
+    subscriptions: &mut Vec<Subscription<Exchange, Kind>>,
+    command: SubscriptionCommand<Exchange, Kind>,
+) where
+    Exchange: Connector,
+    Kind: SubscriptionKind,
+    Stream: MarketStream<Exchange, Kind>,
+    Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market> + PartialEq,
+{
+    let exchange = Exchange::ID;
+
+    match command {
+        SubscriptionCommand::Subscribe {
+            subscriptions: added,
+            reply_tx,
+        } => {
+            let added = added.into_iter().filter(|sub| !subscriptions.contains(sub)).collect::<Vec<_>>();
+            info!(%exchange, subscriptions = ?added, "adding subscriptions to running MarketStream");
+
+            let result = match Exchange::max_subscriptions_per_connection() {
+                _ if added.is_empty() => Ok(()),
+                // Connection is full, the SubscriptionHandle tries the next connection
+                Some(max) if subscriptions.len() + added.len() > max => Err(DataError::Socket(SocketError::Subscribe(format!(
+                    "{exchange} connection is limited to {max} subscriptions"
+                )))),
+                _ => stream.subscribe(&added).await.map(|()| subscriptions.extend(added)),
+            };
+            let _ = reply_tx.send(result);
+        },
+        SubscriptionCommand::Unsubscribe {
+            subscriptions: removed,
+            reply_tx,
+        } => {
+            let removed = removed.into_iter().filter(|sub| subscriptions.contains(sub)).collect::<Vec<_>>();
+            if !removed.is_empty() {
+                info!(%exchange, subscriptions = ?removed, "removing subscriptions from running MarketStream");
+            }
 
-        // If MarketStream ends unexpectedly, retry connection after backoff_ms
-        warn!(
-            %exchange,
-            attempt,
-            action = "attempting re-connection after backoff",
-            "exchange MarketStream unexpectedly ended",
-        );
-        tokio::time::sleep(std::time::Duration::from_millis(backoff_ms)).await;
+            let result = match removed.is_empty() {
+                true => Ok(()),
+                false => stream.unsubscribe(&removed).await.map(|()| subscriptions.retain(|sub| !removed.contains(sub))),
+            };
+            let _ = reply_tx.send(result);
+        },
+        SubscriptionCommand::Subscriptions { reply_tx } => {
+            let _ = reply_tx.send(subscriptions.clone());
+        },
     }
 }
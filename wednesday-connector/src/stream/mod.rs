@@ -1,26 +1,100 @@
 pub mod builder;
+pub mod conflation;
 pub mod exchange;
+pub mod handle;
 pub mod market;
 pub mod parser;
 pub mod protocol;
+pub mod rate_limit;
+pub mod reconnect;
+pub mod redundancy;
 pub mod selector;
+pub mod telemetry;
 
 use std::collections::HashMap;
 
-use tokio::sync::mpsc;
-use tokio_stream::{wrappers::UnboundedReceiverStream, StreamMap};
-use wednesday_model::identifiers::ExchangeId;
+use tokio_stream::StreamMap;
+use wednesday_model::{
+    channel::{channel, ChannelKey, ChannelRx, DropCounter, DroppedMessages},
+    identifiers::ExchangeId,
+    status::StatusRx,
+};
 
 use crate::subscriber::subscription::SubscriptionKind;
 
-use self::builder::{multiple::MultiStreamBuilder, StreamBuilder};
+use self::{
+    builder::{multiple::MultiStreamBuilder, StreamBuilder},
+    handle::{SubscriptionHandle, SubscriptionHandles},
+    redundancy::{LegCounter, LegStats},
+    telemetry::{StreamCounter, StreamMetrics},
+};
 
 #[derive(Debug)]
 pub struct Streams<T> {
-    pub streams: HashMap<ExchangeId, mpsc::UnboundedReceiver<T>>,
+    pub streams: HashMap<ExchangeId, ChannelRx<T>>,
+    pub drop_counters: HashMap<ExchangeId, DropCounter>,
+    pub handles: SubscriptionHandles,
+    pub leg_counters: HashMap<ExchangeId, Vec<LegCounter>>,
+    pub status_rx: Option<StatusRx>,
+    pub metrics: StreamMetrics,
 }
 
 impl<T> Streams<T> {
+    pub fn new(streams: HashMap<ExchangeId, ChannelRx<T>>) -> Self {
+        let drop_counters = streams.iter().map(|(exchange, rx)| (*exchange, rx.drop_counter())).collect();
+        Self {
+            streams,
+            drop_counters,
+            handles: SubscriptionHandles::default(),
+            leg_counters: HashMap::new(),
+            status_rx: None,
+            metrics: StreamMetrics::default(),
+        }
+    }
+
+    pub fn with_handles(self, handles: SubscriptionHandles) -> Self {
+        Self { handles, ..self }
+    }
+
+    pub fn with_status(self, status_rx: StatusRx) -> Self {
+        Self {
+            status_rx: Some(status_rx),
+            ..self
+        }
+    }
+
+    pub fn with_leg_counters(mut self, leg_counters: impl IntoIterator<Item = (ExchangeId, LegCounter)>) -> Self {
+        leg_counters
+            .into_iter()
+            .for_each(|(exchange, counter)| self.leg_counters.entry(exchange).or_default().push(counter));
+        self
+    }
+
+    pub fn with_stream_counters(mut self, counters: impl IntoIterator<Item = (ExchangeId, &'static str, StreamCounter)>) -> Self {
+        self.metrics.extend(counters);
+        self
+    }
+
+    /// [`LegStats`] of each redundant connection of each exchange, see
+    /// [`StreamBuilder::redundancy`](builder::StreamBuilder::redundancy).
+    pub fn legs(&self) -> HashMap<ExchangeId, Vec<LegStats>> {
+        self.leg_counters
+            .iter()
+            .map(|(exchange, counters)| (*exchange, LegCounter::merge(counters)))
+            .filter(|(_, legs)| !legs.is_empty())
+            .collect()
+    }
+
+    /// [`SubscriptionHandle`] to add subscriptions to, & remove them from, the running streams of
+    /// the `Exchange` & `Kind`, which remains usable after the streams are selected or joined.
+    pub fn handle<Exchange, Kind>(&self) -> Option<SubscriptionHandle<Exchange, Kind>>
+    where
+        Exchange: 'static,
+        Kind: 'static,
+    {
+        self.handles.get::<Exchange, Kind>()
+    }
+
     pub fn builder<Kind>() -> StreamBuilder<Kind>
     where
         Kind: SubscriptionKind,
@@ -33,30 +107,56 @@ impl<T> Streams<T> {
         MultiStreamBuilder::<T>::new()
     }
 
-    pub fn select(&mut self, exchange: ExchangeId) -> Option<mpsc::UnboundedReceiver<T>> {
+    /// Take the receiver of the [`StatusEvent`](wednesday_model::status::StatusEvent)s of every
+    /// connection, eg/ to surface reconnects to a consumer, see
+    /// [`StreamBuilder::reconnect`](builder::StreamBuilder::reconnect).
+    pub fn status(&mut self) -> Option<StatusRx> {
+        self.status_rx.take()
+    }
+
+    /// [`StreamMetrics`] of the connections of every exchange & subscription kind, which remain
+    /// usable after the streams are selected or joined, eg/ to publish them via an
+    /// [`Exporter`](crate::protocol::exporter::Exporter).
+    pub fn metrics(&self) -> StreamMetrics {
+        self.metrics.clone()
+    }
+
+    pub fn select(&mut self, exchange: ExchangeId) -> Option<ChannelRx<T>> {
         self.streams.remove(&exchange)
     }
 
-    pub async fn join(self) -> mpsc::UnboundedReceiver<T>
+    /// [`DroppedMessages`] of each exchange channel due to its
+    /// [`OverflowPolicy`](wednesday_model::channel::OverflowPolicy), including the channels that
+    /// were already selected or joined.
+    pub fn dropped(&self) -> HashMap<ExchangeId, DroppedMessages> {
+        self.drop_counters.iter().map(|(exchange, counter)| (*exchange, counter.dropped())).collect()
+    }
+
+    /// Join the exchange channels into a single channel with the same
+    /// [`ChannelConfig`](wednesday_model::channel::ChannelConfig) as the exchange channels.
+    pub async fn join(self) -> ChannelRx<T>
     where
-        T: Send + 'static,
+        T: ChannelKey + Send + 'static,
     {
-        let (joined_tx, joined_rx) = mpsc::unbounded_channel();
+        let config = self.streams.values().next().map(ChannelRx::config).unwrap_or_default();
+        let (joined_tx, joined_rx) = channel(config);
 
         for mut exchange_rx in self.streams.into_values() {
             let joined_tx = joined_tx.clone();
             tokio::spawn(async move {
                 while let Some(event) = exchange_rx.recv().await {
-                    let _ = joined_tx.send(event);
+                    if joined_tx.send(event).await.is_err() {
+                        break;
+                    }
                 }
             });
         }
         joined_rx
     }
 
-    pub async fn join_map(self) -> StreamMap<ExchangeId, UnboundedReceiverStream<T>> {
+    pub async fn join_map(self) -> StreamMap<ExchangeId, ChannelRx<T>> {
         self.streams.into_iter().fold(StreamMap::new(), |mut map, (exchange, rx)| {
-            map.insert(exchange, UnboundedReceiverStream::new(rx));
+            map.insert(exchange, rx);
             map
         })
     }
@@ -1,32 +1,47 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use futures::SinkExt;
 use futures::StreamExt;
 use tokio::sync::mpsc;
 use tracing::{debug, error, info};
 use wednesday_model::error::DataError;
+use wednesday_model::error::SocketError;
 use wednesday_model::identifiers::ExchangeId;
 use wednesday_model::identifiers::Identifier;
+use wednesday_model::identifiers::SubscriptionId;
 
 use crate::exchange::connector::Connector;
 use crate::protocol::http::websocket::is_ws_disconnected;
 use crate::protocol::http::websocket::PingInterval;
+use crate::protocol::http::websocket::WsError;
 use crate::protocol::http::websocket::WsMessage;
 use crate::protocol::http::websocket::WsParser;
 use crate::protocol::http::websocket::WsSink;
 use crate::protocol::http::websocket::WsStream;
 use crate::stream::exchange::ExchangeStream;
 use crate::stream::market::MarketStream;
+use crate::stream::rate_limit::RateLimiter;
+use crate::subscriber::mapper::SubscriptionMapper;
+use crate::subscriber::subscription::ExchangeSubscription;
 use crate::subscriber::subscription::Subscription;
 use crate::subscriber::subscription::SubscriptionKind;
+use crate::subscriber::subscription::SubscriptionMeta;
+use crate::subscriber::validator::await_responses;
+use crate::subscriber::validator::subscription_response;
 use crate::subscriber::Subscriber;
 use crate::transformer::ExchangeTransformer;
 
-pub type ExchangeWsStream<Transformer> = ExchangeStream<WsParser, WsStream, Transformer>;
+use crate::stream::parser::StreamParser;
 use std::fmt::Debug;
 
+/// WebSocket [`ExchangeStream`], parsed with the [`WsParser`] unless the exchange sends
+/// messages that require a dedicated [`StreamParser`] (eg/ non-JSON text frames).
+pub type ExchangeWsStream<Transformer, Parser = WsParser> = ExchangeStream<Parser, WsStream, Transformer>;
+
 #[async_trait]
-impl<Exchange, Kind, Transformer> MarketStream<Exchange, Kind> for ExchangeWsStream<Transformer>
+impl<Exchange, Kind, Transformer, Parser> MarketStream<Exchange, Kind> for ExchangeWsStream<Transformer, Parser>
 where
+    Parser: StreamParser<Message = WsMessage, Error = WsError> + Send,
     Exchange: Connector + Send + Sync,
     Kind: SubscriptionKind + Send + Sync,
     Transformer: ExchangeTransformer<Exchange, Kind> + Send,
@@ -37,7 +52,14 @@ where
     where
         Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
     {
-        let (ws, map) = Exchange::Subscriber::subscribe(subscriptions).await?;
+        Self::init_leg(subscriptions, 0).await
+    }
+
+    async fn init_leg(subscriptions: &[Subscription<Exchange, Kind>], leg: usize) -> Result<Self, DataError>
+    where
+        Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+    {
+        let (ws, map) = Exchange::Subscriber::subscribe(Exchange::leg_url(leg)?, subscriptions).await?;
         let (ws_sink, ws_stream) = ws.split();
         let (ws_sink_tx, ws_sink_rx) = mpsc::unbounded_channel();
 
@@ -52,17 +74,102 @@ where
             debug!(exchange=%Exchange::ID, "No ping interval specified for exchange, skipping ping scheduling");
         }
 
-        let transformer = Transformer::new(ws_sink_tx, map).await?;
+        let transformer = Transformer::new(ws_sink_tx.clone(), map).await?;
+
+        Ok(ExchangeWsStream::new(ws_stream, transformer)
+            .with_ws_sink_tx(ws_sink_tx)
+            .with_rate_limiter(Exchange::message_rate_limit().map(RateLimiter::new))
+            .with_responses(subscription_response::<Exchange, Parser>))
+    }
+
+    fn received_ts(&self) -> Option<DateTime<Utc>> {
+        self.received_ts
+    }
+
+    async fn subscribe(&mut self, subscriptions: &[Subscription<Exchange, Kind>]) -> Result<(), DataError>
+    where
+        Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+    {
+        let ws_sink_tx = dynamic_ws_sink_tx::<Exchange>(&self.ws_sink_tx)?;
+
+        let SubscriptionMeta {
+            instrument_map,
+            subscriptions: requests,
+        } = <Exchange::Subscriber as Subscriber>::SubscriptionMapper::map::<Exchange, Kind>(subscriptions);
+
+        for request in requests {
+            if let Some(rate_limiter) = &mut self.rate_limiter {
+                rate_limiter.acquire().await;
+            }
+            debug!(exchange = %Exchange::ID, payload = ?request, "sending subscription to running stream");
+            ws_sink_tx.send(request).map_err(|_| SocketError::Sink)?;
+        }
+
+        // Validate the responses of the exchange like the initial subscriptions, keeping the
+        // market data received in the meantime to be consumed afterwards
+        let backlog = &mut self.backlog;
+        await_responses::<Exchange, Parser, _>(&mut self.stream, Exchange::expected_responses(&instrument_map), |message| {
+            backlog.push_back((Utc::now(), message))
+        })
+        .await?;
 
-        Ok(ExchangeWsStream::new(ws_stream, transformer))
+        // Initialise the state of the new subscriptions (eg/ order book snapshots) so that their
+        // messages can be transformed as soon as they are received
+        self.transformer.subscribe(ws_sink_tx.clone(), instrument_map).await?;
+
+        info!(exchange = %Exchange::ID, ?subscriptions, "subscribed on running MarketStream");
+        Ok(())
+    }
+
+    async fn unsubscribe(&mut self, subscriptions: &[Subscription<Exchange, Kind>]) -> Result<(), DataError>
+    where
+        Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+    {
+        let ws_sink_tx = dynamic_ws_sink_tx::<Exchange>(&self.ws_sink_tx)?;
+
+        // Exchange subscriptions are not held across the rate limited sends, since they need not be Send
+        let (subscription_ids, requests) = {
+            let exchange_subscriptions = subscriptions
+                .iter()
+                .map(ExchangeSubscription::new)
+                .collect::<Vec<ExchangeSubscription<Exchange::Channel, Exchange::Market>>>();
+            let subscription_ids = exchange_subscriptions.iter().map(Identifier::id).collect::<Vec<SubscriptionId>>();
+            (subscription_ids, Exchange::unsubscribe_requests(exchange_subscriptions))
+        };
+
+        for request in requests {
+            if let Some(rate_limiter) = &mut self.rate_limiter {
+                rate_limiter.acquire().await;
+            }
+            debug!(exchange = %Exchange::ID, payload = ?request, "sending unsubscription to running stream");
+            ws_sink_tx.send(request).map_err(|_| SocketError::Sink)?;
+        }
+
+        // Any messages of the subscriptions still in flight are unidentifiable from now on
+        self.transformer.unsubscribe(&subscription_ids);
+
+        info!(exchange = %Exchange::ID, ?subscriptions, "unsubscribed on running MarketStream");
+        Ok(())
     }
 }
 
-pub async fn distribute_messages_to_exchange(
-    exchange: ExchangeId,
-    mut ws_sink: WsSink,
-    mut ws_sink_rx: mpsc::UnboundedReceiver<WsMessage>,
-) {
+/// Sender of messages to the exchange over the connection of a running stream, if the exchange
+/// supports [`Connector::dynamic_subscriptions`].
+fn dynamic_ws_sink_tx<Exchange>(ws_sink_tx: &Option<mpsc::UnboundedSender<WsMessage>>) -> Result<&mpsc::UnboundedSender<WsMessage>, SocketError>
+where
+    Exchange: Connector,
+{
+    if !Exchange::dynamic_subscriptions() {
+        return Err(SocketError::Unsupported {
+            entity: Exchange::ID.as_str(),
+            item: "subscriptions of a running stream".to_string(),
+        });
+    }
+
+    ws_sink_tx.as_ref().ok_or(SocketError::Sink)
+}
+
+pub async fn distribute_messages_to_exchange(exchange: ExchangeId, mut ws_sink: WsSink, mut ws_sink_rx: mpsc::UnboundedReceiver<WsMessage>) {
     while let Some(message) = ws_sink_rx.recv().await {
         if let Err(error) = ws_sink.send(message).await {
             if is_ws_disconnected(&error) {
@@ -0,0 +1,76 @@
+use std::{collections::VecDeque, time::Duration};
+
+use tokio::time::Instant;
+
+/// Maximum number of `messages` a client may send to an exchange over a single connection within
+/// any window of duration `per`, see [`Connector::message_rate_limit`](crate::exchange::connector::Connector::message_rate_limit).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RateLimit {
+    pub messages: usize,
+    pub per: Duration,
+}
+
+impl RateLimit {
+    pub fn new(messages: usize, per: Duration) -> Self {
+        Self {
+            messages: messages.max(1),
+            per,
+        }
+    }
+}
+
+/// Sliding window [`RateLimit`] of the messages sent over a connection.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    limit: RateLimit,
+    /// Time each message within the current window was sent, oldest first.
+    sent: VecDeque<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            sent: VecDeque::with_capacity(limit.messages),
+        }
+    }
+
+    /// Wait until another message can be sent without exceeding the [`RateLimit`], & record it as
+    /// sent.
+    pub async fn acquire(&mut self) {
+        loop {
+            let now = Instant::now();
+            while self.sent.front().is_some_and(|sent| now.duration_since(*sent) >= self.limit.per) {
+                self.sent.pop_front();
+            }
+
+            match self.sent.front() {
+                Some(oldest) if self.sent.len() >= self.limit.messages => tokio::time::sleep_until(*oldest + self.limit.per).await,
+                _ => {
+                    self.sent.push_back(now);
+                    return;
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_rate_limiter() {
+        let mut limiter = RateLimiter::new(RateLimit::new(2, Duration::from_millis(50)));
+        let start = Instant::now();
+
+        // Messages within the limit are sent immediately
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert!(start.elapsed() < Duration::from_millis(50));
+
+        // Next message waits until the first leaves the window
+        limiter.acquire().await;
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+}
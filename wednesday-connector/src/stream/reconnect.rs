@@ -0,0 +1,312 @@
+use std::time::Duration;
+
+use rand::Rng;
+use wednesday_model::status::StreamStatus;
+
+pub const DEFAULT_INITIAL_BACKOFF_MS: u64 = 1000;
+pub const DEFAULT_MAX_BACKOFF_MS: u64 = 60_000;
+pub const DEFAULT_JITTER: f64 = 0.2;
+
+/// Configuration of how a [`MarketStream`](super::market::MarketStream) consumer re-connects
+/// after failing to connect or being disconnected, see
+/// [`StreamBuilder::reconnect`](super::builder::StreamBuilder::reconnect).
+///
+/// The backoff doubles with every consecutive failed attempt up to the `max_backoff`, & is
+/// randomised by the `jitter` so that many connections don't re-connect in lockstep. By default
+/// the consumer retries forever, without a circuit breaker or stale stream detection.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ReconnectPolicy {
+    /// Backoff after the first failed attempt.
+    pub initial_backoff: Duration,
+    /// Cap of the backoff.
+    pub max_backoff: Duration,
+    /// Fraction of the backoff that is randomly added or subtracted, in the range [0, 1].
+    pub jitter: f64,
+    /// Consecutive failed attempts after which the consumer gives up, or [`None`] to retry
+    /// forever.
+    pub max_attempts: Option<u32>,
+    /// Circuit breaker that pauses re-connecting after too many consecutive failed attempts.
+    pub circuit_breaker: Option<CircuitBreaker>,
+    /// Re-connect if no message is received within the timeout, which must exceed the largest
+    /// expected gap between the messages of the subscriptions.
+    pub stale_timeout: Option<Duration>,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_millis(DEFAULT_INITIAL_BACKOFF_MS),
+            max_backoff: Duration::from_millis(DEFAULT_MAX_BACKOFF_MS),
+            jitter: DEFAULT_JITTER,
+            max_attempts: None,
+            circuit_breaker: None,
+            stale_timeout: None,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_backoff(self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        Self {
+            initial_backoff,
+            max_backoff: max_backoff.max(initial_backoff),
+            ..self
+        }
+    }
+
+    pub fn with_jitter(self, jitter: f64) -> Self {
+        Self {
+            jitter: jitter.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    pub fn with_max_attempts(self, max_attempts: u32) -> Self {
+        Self {
+            max_attempts: Some(max_attempts.max(1)),
+            ..self
+        }
+    }
+
+    pub fn with_circuit_breaker(self, failures: u32, cooldown: Duration) -> Self {
+        Self {
+            circuit_breaker: Some(CircuitBreaker {
+                failures: failures.max(1),
+                cooldown,
+            }),
+            ..self
+        }
+    }
+
+    pub fn with_stale_timeout(self, stale_timeout: Duration) -> Self {
+        Self {
+            stale_timeout: Some(stale_timeout),
+            ..self
+        }
+    }
+
+    /// Backoff after the consecutive failed `attempt` (starting from 1), before jitter.
+    pub fn base_backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+
+    /// Backoff after the consecutive failed `attempt` (starting from 1), with jitter.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let backoff = self.base_backoff(attempt);
+        if self.jitter <= 0.0 {
+            return backoff;
+        }
+
+        let jitter = rand::thread_rng().gen_range(-self.jitter..=self.jitter);
+        backoff.mul_f64(1.0 + jitter).min(self.max_backoff)
+    }
+}
+
+/// Opens after `failures` consecutive failed attempts, pausing re-connecting for the `cooldown`
+/// before a single trial attempt. The circuit re-opens if the trial fails, & closes once the
+/// stream is healthy again.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CircuitBreaker {
+    pub failures: u32,
+    pub cooldown: Duration,
+}
+
+/// Next step of a consumer after a failed attempt, see [`Reconnector::failed`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Reconnect {
+    Retry { attempt: u32, backoff: Duration },
+    CircuitOpen { attempt: u32, cooldown: Duration },
+    GiveUp { attempt: u32 },
+}
+
+impl From<Reconnect> for StreamStatus {
+    fn from(reconnect: Reconnect) -> Self {
+        match reconnect {
+            Reconnect::Retry { attempt, backoff } => StreamStatus::Reconnecting { attempt, backoff },
+            Reconnect::CircuitOpen { attempt, cooldown } => StreamStatus::CircuitOpen { attempt, cooldown },
+            Reconnect::GiveUp { attempt } => StreamStatus::GaveUp { attempt },
+        }
+    }
+}
+
+/// Re-connection state of a consumer following a [`ReconnectPolicy`].
+#[derive(Debug, Clone)]
+pub struct Reconnector {
+    policy: ReconnectPolicy,
+    failures: u32,
+    connected: bool,
+}
+
+impl Reconnector {
+    pub fn new(policy: ReconnectPolicy) -> Self {
+        Self {
+            policy,
+            failures: 0,
+            connected: false,
+        }
+    }
+
+    pub fn policy(&self) -> &ReconnectPolicy {
+        &self.policy
+    }
+
+    /// Record a successful connection, which is [`StreamStatus::Resynced`] if the stream was
+    /// connected before.
+    pub fn connected(&mut self) -> StreamStatus {
+        match std::mem::replace(&mut self.connected, true) {
+            true => StreamStatus::Resynced,
+            false => StreamStatus::Connected,
+        }
+    }
+
+    /// Record that the connected stream delivered an event, which resets the consecutive failed
+    /// attempts & closes the circuit. A stream that disconnects before delivering an event
+    /// therefore keeps backing off.
+    pub fn healthy(&mut self) {
+        self.failures = 0;
+    }
+
+    /// Record a failed attempt to connect, or a disconnect, & determine the next step.
+    pub fn failed(&mut self) -> Reconnect {
+        self.failures = self.failures.saturating_add(1);
+        let attempt = self.failures;
+
+        if self.policy.max_attempts.is_some_and(|max| attempt >= max) {
+            return Reconnect::GiveUp { attempt };
+        }
+
+        match self.policy.circuit_breaker {
+            Some(breaker) if attempt >= breaker.failures => Reconnect::CircuitOpen {
+                attempt,
+                cooldown: breaker.cooldown,
+            },
+            _ => Reconnect::Retry {
+                attempt,
+                backoff: self.policy.backoff(attempt),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        struct TestCase {
+            attempt: u32,
+            expected: Duration,
+        }
+
+        let policy = ReconnectPolicy::default()
+            .with_backoff(Duration::from_millis(100), Duration::from_millis(1000))
+            .with_jitter(0.0);
+
+        let tests = vec![
+            TestCase {
+                // TC0: first attempt
+                attempt: 1,
+                expected: Duration::from_millis(100),
+            },
+            TestCase {
+                // TC1: doubled
+                attempt: 3,
+                expected: Duration::from_millis(400),
+            },
+            TestCase {
+                // TC2: capped
+                attempt: 5,
+                expected: Duration::from_millis(1000),
+            },
+            TestCase {
+                // TC3: capped without overflow
+                attempt: u32::MAX,
+                expected: Duration::from_millis(1000),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            assert_eq!(policy.backoff(test.attempt), test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_backoff_jitter() {
+        let policy = ReconnectPolicy::default()
+            .with_backoff(Duration::from_millis(1000), Duration::from_millis(1500))
+            .with_jitter(0.5);
+
+        for _ in 0..100 {
+            let backoff = policy.backoff(1);
+            assert!(backoff >= Duration::from_millis(500) && backoff <= Duration::from_millis(1500));
+
+            // Jitter never exceeds the cap
+            assert!(policy.backoff(10) <= Duration::from_millis(1500));
+        }
+    }
+
+    #[test]
+    fn test_reconnector() {
+        let policy = ReconnectPolicy::default()
+            .with_backoff(Duration::from_millis(100), Duration::from_millis(1000))
+            .with_jitter(0.0)
+            .with_circuit_breaker(3, Duration::from_secs(60))
+            .with_max_attempts(5);
+        let mut reconnector = Reconnector::new(policy);
+
+        assert_eq!(reconnector.connected(), StreamStatus::Connected);
+        assert_eq!(
+            reconnector.failed(),
+            Reconnect::Retry {
+                attempt: 1,
+                backoff: Duration::from_millis(100)
+            }
+        );
+        assert_eq!(
+            reconnector.failed(),
+            Reconnect::Retry {
+                attempt: 2,
+                backoff: Duration::from_millis(200)
+            }
+        );
+
+        // Circuit opens after 3 consecutive failures, & re-opens whilst the trial fails
+        let open = |attempt| Reconnect::CircuitOpen {
+            attempt,
+            cooldown: Duration::from_secs(60),
+        };
+        assert_eq!(reconnector.failed(), open(3));
+        assert_eq!(reconnector.failed(), open(4));
+
+        // Delivering an event closes the circuit
+        assert_eq!(reconnector.connected(), StreamStatus::Resynced);
+        reconnector.healthy();
+        assert_eq!(
+            reconnector.failed(),
+            Reconnect::Retry {
+                attempt: 1,
+                backoff: Duration::from_millis(100)
+            }
+        );
+
+        // Gives up after 5 consecutive failures
+        (2..5).for_each(|_| {
+            reconnector.failed();
+        });
+        assert_eq!(reconnector.failed(), Reconnect::GiveUp { attempt: 5 });
+    }
+
+    #[test]
+    fn test_reconnector_unlimited() {
+        let mut reconnector = Reconnector::new(ReconnectPolicy::default());
+        for _ in 0..1000 {
+            assert!(matches!(reconnector.failed(), Reconnect::Retry { backoff, .. } if backoff <= Duration::from_millis(DEFAULT_MAX_BACKOFF_MS)));
+        }
+    }
+}
@@ -0,0 +1,504 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use tracing::{debug, trace};
+use wednesday_model::{
+    bar::Bar,
+    channel::{ChannelRx, ChannelTx},
+    events::MarketEvent,
+    instruments::Instrument,
+    orderbook::{OrderBook, OrderBookL1},
+    orderbook_l3::OrderBookL3,
+    perpetual::{MarkPrice, OpenInterest},
+    trade::{AggregatedTrade, PublicTrade},
+};
+
+/// Default number of recent events remembered to detect the copies of a redundant leg.
+pub const DEFAULT_DEDUP_WINDOW: usize = 10_000;
+
+/// Configuration of redundant (hot-standby) connections, see
+/// [`StreamBuilder::redundancy`](super::builder::StreamBuilder::redundancy).
+///
+/// Each subscription set is consumed over `legs` identical connections (optionally to different
+/// endpoints, see [`Connector::leg_url`](crate::exchange::connector::Connector::leg_url)), which
+/// are merged by delivering the first copy of each event & dropping the copies of the other legs.
+/// A disconnect of one leg therefore causes no gap whilst another leg is connected.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Redundancy {
+    /// Number of identical connections per subscription set.
+    pub legs: usize,
+    /// Number of recent events remembered to detect copies, which must cover the largest lag
+    /// between the legs.
+    pub window: usize,
+}
+
+impl Redundancy {
+    pub fn legs(legs: usize) -> Self {
+        Self {
+            legs: legs.max(1),
+            window: DEFAULT_DEDUP_WINDOW,
+        }
+    }
+
+    pub fn with_window(self, window: usize) -> Self {
+        Self { window: window.max(1), ..self }
+    }
+
+    /// Spawn a task that merges the events of every leg into the `exchange_tx`, delivering the
+    /// first copy of each event. The task ends once every leg is closed or the `exchange_tx`
+    /// receiver is dropped.
+    pub fn spawn<T>(self, leg_rxs: Vec<ChannelRx<MarketEvent<T>>>, exchange_tx: ChannelTx<MarketEvent<T>>, counter: LegCounter)
+    where
+        T: Deduplicate + Send + 'static,
+    {
+        tokio::spawn(async move {
+            let mut legs = futures::stream::select_all(leg_rxs.into_iter().enumerate().map(|(leg, leg_rx)| leg_rx.map(move |event| (leg, event))));
+            let mut deduplicator = Deduplicator::new(self.window, counter);
+
+            while let Some((leg, event)) = legs.next().await {
+                let Some(event) = deduplicator.push(leg, event) else {
+                    continue;
+                };
+
+                if exchange_tx.send(event).await.is_err() {
+                    debug!(why = "receiver dropped", "stopping redundant leg deduplication");
+                    return;
+                }
+            }
+        });
+    }
+}
+
+/// Identity of an event that is equal for the copies of the event received over different legs,
+/// see [`Deduplicate`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DedupId {
+    /// Exchange assigned id, eg/ a trade id.
+    Id(String),
+    /// Exchange sequence id, eg/ the update id of an order book.
+    Sequence(u64),
+    /// Exchange timestamp, for events without an exchange assigned id.
+    Time(DateTime<Utc>),
+}
+
+/// Market event kind whose copies received over redundant legs can be detected, see
+/// [`Redundancy`]. By default the copies of an instrument are identified by the exchange
+/// timestamp.
+pub trait Deduplicate: Sized {
+    fn dedup_id(event: &MarketEvent<Self>) -> DedupId {
+        DedupId::Time(event.exchange_ts)
+    }
+}
+
+impl Deduplicate for PublicTrade {
+    fn dedup_id(event: &MarketEvent<Self>) -> DedupId {
+        DedupId::Id(event.kind.id.clone())
+    }
+}
+
+impl Deduplicate for AggregatedTrade {
+    fn dedup_id(event: &MarketEvent<Self>) -> DedupId {
+        DedupId::Id(event.kind.id.clone())
+    }
+}
+
+// Order books of exchanges without an update id fall back to the last update timestamp
+impl Deduplicate for OrderBook {
+    fn dedup_id(event: &MarketEvent<Self>) -> DedupId {
+        match event.kind.update_id {
+            Some(update_id) => DedupId::Sequence(update_id),
+            None => DedupId::Time(event.kind.last_update_ts),
+        }
+    }
+}
+
+impl Deduplicate for OrderBookL1 {
+    fn dedup_id(event: &MarketEvent<Self>) -> DedupId {
+        DedupId::Time(event.kind.last_update_ts)
+    }
+}
+
+impl Deduplicate for OrderBookL3 {
+    fn dedup_id(event: &MarketEvent<Self>) -> DedupId {
+        DedupId::Time(event.kind.last_update_ts)
+    }
+}
+
+impl Deduplicate for Bar {
+    fn dedup_id(event: &MarketEvent<Self>) -> DedupId {
+        DedupId::Time(event.kind.close_time)
+    }
+}
+
+impl Deduplicate for MarkPrice {}
+
+impl Deduplicate for OpenInterest {}
+
+/// Which leg won the events of a [`Redundancy`], ie/ delivered them first, & by how much the
+/// copies of the leg lagged the winning copies.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct LegStats {
+    /// Events delivered from this leg.
+    pub won: u64,
+    /// Copies received from this leg after the event was delivered from another leg.
+    pub lost: u64,
+    /// Total time by which the lost copies of this leg lagged the delivered copies.
+    pub lag: Duration,
+}
+
+impl LegStats {
+    /// Mean time by which the lost copies of this leg lagged the delivered copies.
+    pub fn mean_lag(&self) -> Option<Duration> {
+        (self.lost > 0).then(|| self.lag / self.lost as u32)
+    }
+
+    fn merge(&mut self, other: &LegStats) {
+        self.won += other.won;
+        self.lost += other.lost;
+        self.lag += other.lag;
+    }
+}
+
+/// Shared [`LegStats`] of each leg of the [`Redundancy`] of an exchange.
+#[derive(Debug, Clone, Default)]
+pub struct LegCounter(Arc<Mutex<Vec<LegStats>>>);
+
+impl LegCounter {
+    /// Snapshot of the [`LegStats`] of each leg.
+    pub fn stats(&self) -> Vec<LegStats> {
+        self.0.lock().expect("LegCounter lock poisoned").clone()
+    }
+
+    /// Sum the [`LegStats`] of each leg of several counters (eg/ of each shard).
+    pub fn merge<'a>(counters: impl IntoIterator<Item = &'a LegCounter>) -> Vec<LegStats> {
+        counters.into_iter().fold(Vec::new(), |mut merged, counter| {
+            for (leg, stats) in counter.stats().iter().enumerate() {
+                if merged.len() <= leg {
+                    merged.resize(leg + 1, LegStats::default());
+                }
+                merged[leg].merge(stats);
+            }
+            merged
+        })
+    }
+
+    fn record(&self, leg: usize, lag: Option<Duration>) {
+        let mut legs = self.0.lock().expect("LegCounter lock poisoned");
+        if legs.len() <= leg {
+            legs.resize(leg + 1, LegStats::default());
+        }
+
+        match lag {
+            None => legs[leg].won += 1,
+            Some(lag) => {
+                legs[leg].lost += 1;
+                legs[leg].lag += lag;
+            },
+        }
+    }
+}
+
+/// Copies of an event received from each leg.
+#[derive(Debug)]
+struct Copies {
+    /// Number of events with the same [`DedupId`] that were delivered, since distinct events of
+    /// a leg may share a timestamp.
+    delivered: usize,
+    /// Number of events with the same [`DedupId`] received from each leg.
+    received: Vec<usize>,
+    delivered_at: Instant,
+}
+
+/// Detects the copies of the events received over the legs of a [`Redundancy`] within a window
+/// of the most recent events.
+#[derive(Debug)]
+pub struct Deduplicator {
+    window: usize,
+    copies: HashMap<(Instrument, DedupId), Copies>,
+    order: VecDeque<(Instrument, DedupId)>,
+    counter: LegCounter,
+}
+
+impl Deduplicator {
+    pub fn new(window: usize, counter: LegCounter) -> Self {
+        Self {
+            window,
+            copies: HashMap::with_capacity(window),
+            order: VecDeque::with_capacity(window),
+            counter,
+        }
+    }
+
+    /// Returns the event if it is the first copy received, or [`None`] if another leg already
+    /// delivered it.
+    pub fn push<T>(&mut self, leg: usize, event: MarketEvent<T>) -> Option<MarketEvent<T>>
+    where
+        T: Deduplicate,
+    {
+        let key = (event.instrument.clone(), T::dedup_id(&event));
+
+        let copies = match self.copies.get_mut(&key) {
+            Some(copies) => copies,
+            None => {
+                if self.order.len() == self.window {
+                    if let Some(oldest) = self.order.pop_front() {
+                        self.copies.remove(&oldest);
+                    }
+                }
+                self.order.push_back(key.clone());
+                self.copies.entry(key).or_insert(Copies {
+                    delivered: 0,
+                    received: Vec::new(),
+                    delivered_at: Instant::now(),
+                })
+            },
+        };
+
+        if copies.received.len() <= leg {
+            copies.received.resize(leg + 1, 0);
+        }
+        copies.received[leg] += 1;
+
+        if copies.received[leg] > copies.delivered {
+            copies.delivered += 1;
+            copies.delivered_at = Instant::now();
+            trace!(leg, instrument = %event.instrument, "redundant leg won event");
+            self.counter.record(leg, None);
+            Some(event)
+        } else {
+            self.counter.record(leg, Some(copies.delivered_at.elapsed()));
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wednesday_model::{
+        enums::AggressorSide,
+        identifiers::{Exchange, ExchangeId},
+        instruments::InstrumentKind,
+    };
+
+    use super::*;
+
+    fn trade(id: &str) -> MarketEvent<PublicTrade> {
+        MarketEvent {
+            exchange_ts: DateTime::<Utc>::MIN_UTC,
+            local_ts: DateTime::<Utc>::MIN_UTC,
+            exchange: Exchange::from(ExchangeId::BinanceSpot),
+            instrument: Instrument::from(("btc", "usdt", InstrumentKind::CryptoSpot)),
+            kind: PublicTrade {
+                id: id.to_string(),
+                price: 100.0,
+                quantity: 1.0,
+                aggressor_side: AggressorSide::Buy,
+            },
+        }
+    }
+
+    fn mark_price(time: i64) -> MarketEvent<MarkPrice> {
+        let time = DateTime::<Utc>::from_timestamp(time, 0).unwrap();
+        MarketEvent {
+            exchange_ts: time,
+            local_ts: time,
+            exchange: Exchange::from(ExchangeId::BinanceFuturesUsd),
+            instrument: Instrument::from(("btc", "usdt", InstrumentKind::CryptoPerpetual)),
+            kind: MarkPrice {
+                mark_price: 100.0,
+                index_price: 100.0,
+                funding_rate: 0.0,
+                next_funding_time: time,
+            },
+        }
+    }
+
+    #[test]
+    fn test_deduplicator() {
+        struct TestCase {
+            input: (usize, &'static str),
+            expected: bool,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: first copy is delivered
+                input: (0, "1"),
+                expected: true,
+            },
+            TestCase {
+                // TC1: copy of another leg is dropped
+                input: (1, "1"),
+                expected: false,
+            },
+            TestCase {
+                // TC2: leg 1 wins the next event
+                input: (1, "2"),
+                expected: true,
+            },
+            TestCase {
+                // TC3: late copy of leg 0 is dropped
+                input: (0, "2"),
+                expected: false,
+            },
+            TestCase {
+                // TC4: leg 0 continues alone after leg 1 disconnects
+                input: (0, "3"),
+                expected: true,
+            },
+        ];
+
+        let counter = LegCounter::default();
+        let mut deduplicator = Deduplicator::new(DEFAULT_DEDUP_WINDOW, counter.clone());
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let (leg, id) = test.input;
+            let actual = deduplicator.push(leg, trade(id));
+            assert_eq!(actual.is_some(), test.expected, "TC{} failed", index);
+        }
+
+        let stats = counter.stats();
+        assert_eq!((stats[0].won, stats[0].lost), (2, 1));
+        assert_eq!((stats[1].won, stats[1].lost), (1, 1));
+        assert!(stats[0].mean_lag().is_some());
+    }
+
+    #[test]
+    fn test_deduplicator_distinct_events_with_same_time() {
+        let mut deduplicator = Deduplicator::new(DEFAULT_DEDUP_WINDOW, LegCounter::default());
+
+        // Leg 0 delivers two distinct events with the same exchange timestamp, & the copies of
+        // leg 1 are dropped
+        assert!(deduplicator.push(0, mark_price(1)).is_some());
+        assert!(deduplicator.push(0, mark_price(1)).is_some());
+        assert!(deduplicator.push(1, mark_price(1)).is_none());
+        assert!(deduplicator.push(1, mark_price(1)).is_none());
+
+        // A third event is delivered by whichever leg receives it first
+        assert!(deduplicator.push(1, mark_price(1)).is_some());
+        assert!(deduplicator.push(0, mark_price(1)).is_none());
+    }
+
+    #[test]
+    fn test_deduplicator_order_book() {
+        fn order_book(update_id: Option<u64>, time: i64) -> MarketEvent<OrderBook> {
+            let time = DateTime::<Utc>::from_timestamp(time, 0).unwrap();
+            MarketEvent {
+                exchange_ts: time,
+                local_ts: time,
+                exchange: Exchange::from(ExchangeId::BinanceSpot),
+                instrument: Instrument::from(("btc", "usdt", InstrumentKind::CryptoSpot)),
+                kind: OrderBook {
+                    last_update_ts: time,
+                    update_id,
+                    ..OrderBook::default()
+                },
+            }
+        }
+
+        struct TestCase {
+            input: (usize, Option<u64>, i64),
+            expected: bool,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: first copy is delivered
+                input: (0, Some(1), 1),
+                expected: true,
+            },
+            TestCase {
+                // TC1: copy of another leg with a different local update time is dropped
+                input: (1, Some(1), 2),
+                expected: false,
+            },
+            TestCase {
+                // TC2: next update with the same update time is delivered
+                input: (1, Some(2), 2),
+                expected: true,
+            },
+            TestCase {
+                // TC3: late copy of leg 0 is dropped
+                input: (0, Some(2), 3),
+                expected: false,
+            },
+            TestCase {
+                // TC4: book without an update id is identified by its update time
+                input: (0, None, 4),
+                expected: true,
+            },
+            TestCase {
+                // TC5: copy of a book without an update id is dropped
+                input: (1, None, 4),
+                expected: false,
+            },
+        ];
+
+        let mut deduplicator = Deduplicator::new(DEFAULT_DEDUP_WINDOW, LegCounter::default());
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let (leg, update_id, time) = test.input;
+            let actual = deduplicator.push(leg, order_book(update_id, time));
+            assert_eq!(actual.is_some(), test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_deduplicator_window() {
+        let mut deduplicator = Deduplicator::new(2, LegCounter::default());
+
+        assert!(deduplicator.push(0, trade("1")).is_some());
+        assert!(deduplicator.push(0, trade("2")).is_some());
+        assert!(deduplicator.push(0, trade("3")).is_some());
+
+        // Copy of an event outside of the window can no longer be detected
+        assert!(deduplicator.push(1, trade("1")).is_some());
+        assert!(deduplicator.push(1, trade("3")).is_none());
+    }
+
+    #[test]
+    fn test_leg_counter_merge() {
+        let (first, second) = (LegCounter::default(), LegCounter::default());
+        first.record(0, None);
+        second.record(0, None);
+        second.record(1, Some(Duration::from_millis(4)));
+        second.record(1, Some(Duration::from_millis(2)));
+
+        let merged = LegCounter::merge([&first, &second]);
+        assert_eq!(merged[0].won, 2);
+        assert_eq!(merged[1].lost, 2);
+        assert_eq!(merged[1].mean_lag(), Some(Duration::from_millis(3)));
+    }
+
+    #[tokio::test]
+    async fn test_spawn_merges_legs() {
+        use wednesday_model::channel::{channel, ChannelConfig};
+
+        let (leg_0_tx, leg_0_rx) = channel(ChannelConfig::default());
+        let (leg_1_tx, leg_1_rx) = channel(ChannelConfig::default());
+        let (exchange_tx, mut exchange_rx) = channel(ChannelConfig::default());
+        Redundancy::legs(2).spawn(vec![leg_0_rx, leg_1_rx], exchange_tx, LegCounter::default());
+
+        // Leg 0 disconnects after trade 2, & leg 1 fills the gap
+        for id in ["1", "2"] {
+            leg_0_tx.try_send(trade(id)).unwrap();
+        }
+        drop(leg_0_tx);
+        for id in ["1", "2", "3", "4"] {
+            leg_1_tx.try_send(trade(id)).unwrap();
+        }
+        drop(leg_1_tx);
+
+        let mut ids = Vec::new();
+        while let Some(event) = exchange_rx.recv().await {
+            ids.push(event.kind.id);
+        }
+        ids.sort();
+        assert_eq!(ids, vec!["1", "2", "3", "4"]);
+    }
+}
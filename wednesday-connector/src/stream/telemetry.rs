@@ -0,0 +1,206 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use chrono::Utc;
+use tracing::debug;
+use wednesday_model::{
+    identifiers::ExchangeId,
+    latency::LatencyRecorder,
+    metric::{Field, Metric, MetricTx, Tag},
+    status::{StatusEvent, StatusTx, StreamStatus},
+};
+
+/// Counts of the connections of the subscriptions of a kind (eg/ "PublicTrades") to an exchange.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct StreamStats {
+    /// Events delivered by the connections.
+    pub messages: u64,
+    /// Messages that failed to be parsed, or otherwise yielded a non-terminal error.
+    pub errors: u64,
+    /// Failed attempts to connect, or disconnects, after which the connection was retried.
+    pub reconnects: u64,
+    /// Successful re-connections, each of which re-synchronises the state of the subscriptions
+    /// (eg/ the order books).
+    pub resyncs: u64,
+}
+
+impl StreamStats {
+    fn merge(&mut self, other: &StreamStats) {
+        self.messages += other.messages;
+        self.errors += other.errors;
+        self.reconnects += other.reconnects;
+        self.resyncs += other.resyncs;
+    }
+}
+
+/// Shared [`StreamStats`] of the connections of the subscriptions of a kind to an exchange.
+#[derive(Debug, Clone, Default)]
+pub struct StreamCounter(Arc<[AtomicU64; 4]>);
+
+impl StreamCounter {
+    const MESSAGES: usize = 0;
+    const ERRORS: usize = 1;
+    const RECONNECTS: usize = 2;
+    const RESYNCS: usize = 3;
+
+    /// Snapshot of the [`StreamStats`].
+    pub fn stats(&self) -> StreamStats {
+        let count = |index: usize| self.0[index].load(Ordering::Relaxed);
+        StreamStats {
+            messages: count(Self::MESSAGES),
+            errors: count(Self::ERRORS),
+            reconnects: count(Self::RECONNECTS),
+            resyncs: count(Self::RESYNCS),
+        }
+    }
+
+    fn increment(&self, index: usize) {
+        self.0[index].fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// [`StreamCounter`]s of every exchange & subscription kind of a [`Streams`](super::Streams),
+/// published as [`Metric`]s.
+#[derive(Debug, Clone, Default)]
+pub struct StreamMetrics(Vec<(ExchangeId, &'static str, StreamCounter)>);
+
+impl StreamMetrics {
+    pub fn extend(&mut self, counters: impl IntoIterator<Item = (ExchangeId, &'static str, StreamCounter)>) {
+        self.0.extend(counters);
+    }
+
+    /// [`StreamStats`] of each exchange & subscription kind, summed over the counters of each
+    /// [`StreamBuilder`](super::builder::StreamBuilder).
+    pub fn stats(&self) -> BTreeMap<(ExchangeId, &'static str), StreamStats> {
+        self.0.iter().fold(BTreeMap::new(), |mut stats, (exchange, kind, counter)| {
+            stats.entry((*exchange, *kind)).or_default().merge(&counter.stats());
+            stats
+        })
+    }
+
+    /// "stream" [`Metric`] of each exchange & subscription kind, whose fields are counters.
+    pub fn metrics(&self) -> Vec<Metric> {
+        let time = Utc::now().timestamp_millis() as u64;
+
+        self.stats()
+            .into_iter()
+            .map(|((exchange, kind), stats)| Metric {
+                name: "stream",
+                time,
+                tags: vec![Tag::new("exchange", exchange.as_str()), Tag::new("kind", kind)],
+                fields: vec![
+                    Field::new("messages_total", stats.messages),
+                    Field::new("errors_total", stats.errors),
+                    Field::new("reconnects_total", stats.reconnects),
+                    Field::new("resyncs_total", stats.resyncs),
+                ],
+            })
+            .collect()
+    }
+
+    /// Spawn a task that sends the [`Metric`]s of every counter via the `metric_tx` each
+    /// `interval`. The task ends once the `metric_tx` receiver is dropped.
+    pub fn spawn(&self, interval: Duration, metric_tx: MetricTx) {
+        let metrics = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                ticker.tick().await;
+                if metrics.metrics().into_iter().any(|metric| metric_tx.send(metric).is_err()) {
+                    debug!(why = "receiver dropped", "stopping stream metrics");
+                    return;
+                }
+            }
+        });
+    }
+}
+
+/// Sinks of the telemetry of a [`MarketStream`](super::market::MarketStream) consumer, see
+/// [`consume`](super::market::consume).
+#[derive(Debug, Clone)]
+pub struct Telemetry {
+    pub status_tx: StatusTx,
+    pub counter: StreamCounter,
+    pub latency: Option<LatencyRecorder>,
+}
+
+impl Telemetry {
+    /// Send the [`StreamStatus`] of the connection `leg` as a [`StatusEvent`], & count the
+    /// reconnects & resyncs.
+    pub fn status(&self, exchange: ExchangeId, leg: usize, status: StreamStatus) {
+        match status {
+            StreamStatus::Reconnecting { .. } | StreamStatus::CircuitOpen { .. } => self.counter.increment(StreamCounter::RECONNECTS),
+            StreamStatus::Resynced => self.counter.increment(StreamCounter::RESYNCS),
+            StreamStatus::Connected | StreamStatus::GaveUp { .. } => {},
+        }
+        let _ = self.status_tx.send(StatusEvent::new(exchange, leg, status));
+    }
+
+    pub fn message(&self) {
+        self.counter.increment(StreamCounter::MESSAGES);
+    }
+
+    pub fn error(&self) {
+        self.counter.increment(StreamCounter::ERRORS);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+    use wednesday_model::metric::Value;
+
+    use super::*;
+
+    #[test]
+    fn test_stream_metrics() {
+        let (status_tx, mut status_rx) = mpsc::unbounded_channel();
+        let (trades, shard) = (StreamCounter::default(), StreamCounter::default());
+        let telemetry = |counter: &StreamCounter| Telemetry {
+            status_tx: status_tx.clone(),
+            counter: counter.clone(),
+            latency: None,
+        };
+
+        let (first, second) = (telemetry(&trades), telemetry(&shard));
+        first.status(ExchangeId::Okx, 0, StreamStatus::Connected);
+        (0..3).for_each(|_| first.message());
+        first.error();
+        first.status(ExchangeId::Okx, 0, StreamStatus::GaveUp { attempt: 1 });
+        second.status(
+            ExchangeId::Okx,
+            0,
+            StreamStatus::Reconnecting {
+                attempt: 1,
+                backoff: Duration::from_secs(1),
+            },
+        );
+        second.status(ExchangeId::Okx, 0, StreamStatus::Resynced);
+        second.message();
+
+        assert_eq!(std::iter::from_fn(|| status_rx.try_recv().ok()).count(), 4);
+
+        let mut metrics = StreamMetrics::default();
+        metrics.extend([(ExchangeId::Okx, "PublicTrades", trades), (ExchangeId::Okx, "PublicTrades", shard)]);
+
+        let expected = StreamStats {
+            messages: 4,
+            errors: 1,
+            reconnects: 1,
+            resyncs: 1,
+        };
+        assert_eq!(metrics.stats().get(&(ExchangeId::Okx, "PublicTrades")), Some(&expected));
+
+        let metrics = metrics.metrics();
+        assert_eq!(metrics.len(), 1);
+        assert!(metrics[0].fields.contains(&Field::new("messages_total", Value::UInt(4))));
+    }
+}
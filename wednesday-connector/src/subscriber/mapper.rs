@@ -45,11 +45,75 @@ impl SubscriptionMapper for WsSubscriptionMapper {
             })
             .collect::<Vec<ExchangeSubscription<Exchange::Channel, Exchange::Market>>>();
 
-        let subscriptions = Exchange::requests(exchange_subscriptions);
+        // Split the exchange subscriptions across multiple requests if the exchange limits the
+        // number of subscriptions per request
+        let subscriptions = match Exchange::max_subscriptions_per_request() {
+            Some(max) => {
+                let mut exchange_subscriptions = exchange_subscriptions.into_iter().peekable();
+                let mut requests = Vec::new();
+                while exchange_subscriptions.peek().is_some() {
+                    requests.extend(Exchange::requests(exchange_subscriptions.by_ref().take(max.max(1)).collect()));
+                }
+                requests
+            },
+            None => Exchange::requests(exchange_subscriptions),
+        };
 
-        SubscriptionMeta {
+        SubscriptionMeta { instrument_map, subscriptions }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wednesday_model::instruments::InstrumentKind;
+
+    use crate::{
+        exchange::{binance::spot::BinanceSpot, bybit::spot::BybitSpot},
+        protocol::http::websocket::WsMessage,
+        subscriber::subscription::kind::PublicTrades,
+    };
+
+    use super::*;
+
+    fn bases(count: usize) -> Vec<String> {
+        (0..count).map(|index| format!("base{index}")).collect()
+    }
+
+    #[test]
+    fn test_map_splits_requests() {
+        // Bybit is limited to 10 args per subscription request
+        let subscriptions = bases(25)
+            .into_iter()
+            .map(|base| Subscription::from((BybitSpot::default(), base, "usdt".to_string(), InstrumentKind::CryptoSpot, PublicTrades)))
+            .collect::<Vec<_>>();
+
+        let SubscriptionMeta {
             instrument_map,
-            subscriptions,
-        }
+            subscriptions: requests,
+        } = WsSubscriptionMapper::map(&subscriptions);
+
+        let args = requests
+            .iter()
+            .map(|request| match request {
+                WsMessage::Text(request) => serde_json::from_str::<serde_json::Value>(request).unwrap()["args"].as_array().unwrap().len(),
+                _ => panic!("text request was expected"),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(instrument_map.0.len(), 25);
+        assert_eq!(args, vec![10, 10, 5]);
+        assert_eq!(BybitSpot::expected_responses(&instrument_map), 3);
+    }
+
+    #[test]
+    fn test_map_without_request_limit() {
+        let subscriptions = bases(25)
+            .into_iter()
+            .map(|base| Subscription::from((BinanceSpot::default(), base, "usdt".to_string(), InstrumentKind::CryptoSpot, PublicTrades)))
+            .collect::<Vec<_>>();
+
+        let SubscriptionMeta { subscriptions: requests, .. } = WsSubscriptionMapper::map(&subscriptions);
+
+        assert_eq!(requests.len(), 1);
     }
 }
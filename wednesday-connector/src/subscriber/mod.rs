@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use url::Url;
 use wednesday_model::{error::SocketError, identifiers::Identifier, instruments::Instrument};
 
 use crate::{exchange::connector::Connector, protocol::http::websocket::WsClient};
@@ -17,7 +18,7 @@ pub mod validator;
 pub trait Subscriber {
     type SubscriptionMapper: SubscriptionMapper;
 
-    async fn subscribe<Exchange, Kind>(subscriptions: &[Subscription<Exchange, Kind>]) -> Result<(WsClient, Map<Instrument>), SocketError>
+    async fn subscribe<Exchange, Kind>(url: Url, subscriptions: &[Subscription<Exchange, Kind>]) -> Result<(WsClient, Map<Instrument>), SocketError>
     where
         Exchange: Connector + Send + Sync,
         Kind: SubscriptionKind + Send + Sync,
@@ -2,6 +2,7 @@ use async_trait::async_trait;
 use futures::SinkExt;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info};
+use url::Url;
 use wednesday_model::{error::SocketError, identifiers::Identifier, instruments::Instrument};
 
 use crate::subscriber::mapper::SubscriptionMapper;
@@ -23,23 +24,20 @@ pub struct WsSubscriber;
 impl Subscriber for WsSubscriber {
     type SubscriptionMapper = WsSubscriptionMapper;
 
-    async fn subscribe<Exchange, Kind>(subscriptions: &[Subscription<Exchange, Kind>]) -> Result<(WsClient, Map<Instrument>), SocketError>
+    async fn subscribe<Exchange, Kind>(url: Url, subscriptions: &[Subscription<Exchange, Kind>]) -> Result<(WsClient, Map<Instrument>), SocketError>
     where
         Exchange: Connector + Send + Sync,
         Kind: SubscriptionKind + Send + Sync,
         Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
     {
         let exchange = Exchange::ID;
-        let url = Exchange::url()?.to_string();
+        let url = url.to_string();
         debug!(%exchange, %url, ?subscriptions, "subscribing to WebSocket");
 
         let mut websocket = connect(url).await?;
         debug!(%exchange, ?subscriptions, "WebSocket connection established");
 
-        let SubscriptionMeta {
-            instrument_map,
-            subscriptions,
-        } = Self::SubscriptionMapper::map::<Exchange, Kind>(subscriptions);
+        let SubscriptionMeta { instrument_map, subscriptions } = Self::SubscriptionMapper::map::<Exchange, Kind>(subscriptions);
 
         for subscription in subscriptions {
             debug!(%exchange, payload = ?subscription, "sending exchange to subscription");
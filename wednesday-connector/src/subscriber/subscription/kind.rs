@@ -3,7 +3,9 @@ use wednesday_macro::{DeSubscriptionKind, SerSubscriptionKind};
 use wednesday_model::{
     bar::Bar,
     orderbook::{OrderBook, OrderBookL1},
-    trade::PublicTrade,
+    orderbook_l3::OrderBookL3,
+    perpetual::{MarkPrice, OpenInterest},
+    trade::{AggregatedTrade, PublicTrade},
 };
 
 use super::SubscriptionKind;
@@ -20,10 +22,20 @@ impl SubscriptionKind for OrderBooksL2 {
     type Event = OrderBook;
 }
 
+/// Level 2 [`OrderBook`] limited to the top five levels of each side, for exchanges that provide a
+/// dedicated depth limited channel (eg/ OKX "books5").
+#[derive(Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Debug, Hash, DeSubscriptionKind, SerSubscriptionKind)]
+pub struct OrderBooksL2Top5;
+impl SubscriptionKind for OrderBooksL2Top5 {
+    type Event = OrderBook;
+}
+
+/// Market-by-order [`OrderBookL3`] of every individual resting order, which can be aggregated
+/// into a price level [`OrderBook`] for consumers of [`OrderBooksL2`].
 #[derive(Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Debug, Hash, DeSubscriptionKind, SerSubscriptionKind)]
 pub struct OrderBooksL3;
 impl SubscriptionKind for OrderBooksL3 {
-    type Event = OrderBook;
+    type Event = OrderBookL3;
 }
 
 #[derive(Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Debug, Hash, SerSubscriptionKind, DeSubscriptionKind)]
@@ -33,9 +45,30 @@ impl SubscriptionKind for PublicTrades {
     type Event = PublicTrade;
 }
 
+#[derive(Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Debug, Hash, SerSubscriptionKind, DeSubscriptionKind)]
+pub struct AggTrades;
+
+impl SubscriptionKind for AggTrades {
+    type Event = AggregatedTrade;
+}
+
 #[derive(Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Debug, Hash, SerSubscriptionKind, DeSubscriptionKind)]
 pub struct Bars;
 
 impl SubscriptionKind for Bars {
     type Event = Bar;
 }
+
+#[derive(Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Debug, Hash, SerSubscriptionKind, DeSubscriptionKind)]
+pub struct MarkPrices;
+
+impl SubscriptionKind for MarkPrices {
+    type Event = MarkPrice;
+}
+
+#[derive(Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Debug, Hash, SerSubscriptionKind, DeSubscriptionKind)]
+pub struct OpenInterests;
+
+impl SubscriptionKind for OpenInterests {
+    type Event = OpenInterest;
+}
@@ -117,7 +117,23 @@ impl<T> FromIterator<(SubscriptionId, T)> for Map<T> {
     }
 }
 
+impl<T> Extend<(SubscriptionId, T)> for Map<T> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (SubscriptionId, T)>,
+    {
+        self.0.extend(iter)
+    }
+}
+
 impl<T> Map<T> {
+    /// Remove the `T` associated with each of the provided [`SubscriptionId`]s.
+    pub fn remove(&mut self, ids: &[SubscriptionId]) {
+        ids.iter().for_each(|id| {
+            self.0.remove(id);
+        });
+    }
+
     /// Find the `T` associated with the provided [`SubscriptionId`].
     pub fn find(&self, id: &SubscriptionId) -> Result<T, SocketError>
     where
@@ -1,6 +1,7 @@
 use std::rc::Rc;
 
 use async_trait::async_trait;
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 use tokio_stream::StreamExt;
 use tracing::{debug, error, info};
@@ -90,81 +91,201 @@ impl SubscriptionValidator for WsSubscriptionValidator {
         Exchange: Connector + Send,
         Kind: SubscriptionKind + Send,
     {
-        // Establish exchange specific subscription validation parameters
-        let timeout = Exchange::subscription_timeout();
         let expected_responses = Exchange::expected_responses(&instrument_map);
 
-        // Parameter to keep track of successful Subscription outcomes
-        let mut success_responses = 0usize;
+        // Messages received before the initial subscriptions are validated are dropped
+        await_responses::<Exchange, Self::Parser, _>(ws_client, expected_responses, |_| ()).await?;
 
-        loop {
-            // Break if all Subscriptions were a success
-            if success_responses == expected_responses {
-                info!(exchange = %Exchange::ID, "validated exchange WebSocket subscriptions");
-                break Ok(instrument_map);
-            }
+        Ok(instrument_map)
+    }
+}
 
-            tokio::select! {
-                // If timeout reached, return SubscribeError
-                _ = tokio::time::sleep(timeout) => {
-                    break Err(SocketError::Subscribe(
-                        format!("subscription validation timeout reached: {:?}", timeout)
-                    ))
-                },
-                // Parse incoming messages and determine subscription outcomes
-                message = ws_client.next() => {
-                    let response = Rc::new(match message {
-                        Some(response) => response,
-                        None => break Err(SocketError::Subscribe("WebSocket stream terminated unexpectedly".to_string()))
-                    });
-
-                    match Self::Parser::parse::<Exchange::SubscriptionResponse>(response) {
-                        Some(Ok(response)) => match response.validate() {
-                            // Subscription success
-                            Ok(response) => {
-                                success_responses += 1;
-                                debug!(
-                                    exchange = %Exchange::ID,
-                                    %success_responses,
-                                    %expected_responses,
-                                    payload = ?response,
-                                    "received valid Ok subscription response",
-                                );
-                            }
-
-                            // Subscription failure
-                            Err(err) => {
-                                error!(exchange = %Exchange::ID, %err, "received invalid subscription response");
-                                break Err(err)
-                            }
-                        }
-                        // NOTE: We need refactor this code.
-                        Some(Err(SocketError::DeserializingJson { error, payload })) => {
-                            // 이 부분은 꺼도 괜찮을 것 같음.
+/// Await the `expected_responses` of the subscriptions actioned over the `messages` stream,
+/// validating each [`Connector::SubscriptionResponse`] within the
+/// [`Connector::subscription_timeout`].
+///
+/// Every other message is passed to `skipped`, eg/ to keep the market data received whilst
+/// subscribing on a running stream.
+pub async fn await_responses<Exchange, Parser, Messages>(
+    messages: &mut Messages,
+    expected_responses: usize,
+    mut skipped: impl FnMut(Result<Parser::Message, Parser::Error>),
+) -> Result<(), SocketError>
+where
+    Exchange: Connector,
+    Parser: StreamParser,
+    Messages: Stream<Item = Result<Parser::Message, Parser::Error>> + Unpin,
+{
+    let timeout = Exchange::subscription_timeout();
+    let deadline = tokio::time::sleep(timeout);
+    tokio::pin!(deadline);
+
+    // Parameter to keep track of successful Subscription outcomes
+    let mut success_responses = 0usize;
+
+    loop {
+        // Break if all Subscriptions were a success
+        if success_responses == expected_responses {
+            info!(exchange = %Exchange::ID, "validated exchange WebSocket subscriptions");
+            break Ok(());
+        }
+
+        tokio::select! {
+            // If timeout reached, return SubscribeError
+            _ = &mut deadline => {
+                break Err(SocketError::Subscribe(
+                    format!("subscription validation timeout reached: {:?}", timeout)
+                ))
+            },
+            // Parse incoming messages and determine subscription outcomes
+            message = messages.next() => {
+                let message = Rc::new(match message {
+                    Some(message) => message,
+                    None => break Err(SocketError::Subscribe("WebSocket stream terminated unexpectedly".to_string()))
+                });
+
+                match Parser::parse::<Exchange::SubscriptionResponse>(Rc::clone(&message)) {
+                    Some(Ok(response)) => match response.validate() {
+                        // Subscription success
+                        Ok(response) => {
+                            success_responses += 1;
                             debug!(
                                 exchange = %Exchange::ID,
-                                ?error,
                                 %success_responses,
                                 %expected_responses,
-                                %payload,
-                                "failed to deserialize non SubResponse payload"
+                                payload = ?response,
+                                "received valid Ok subscription response",
                             );
-                            // Continue processing to handle potential late subscription messages
                             continue;
-                        },
-                        Some(Err(SocketError::Terminated(close_frame))) => {
-                            break Err(SocketError::Subscribe(
-                                format!("received WebSocket CloseFrame: {close_frame}")
-                            ))
                         }
-                        _ => {
-                            // Pings, Pongs, Frames, etc.
-                            continue
+
+                        // Subscription failure
+                        Err(err) => {
+                            error!(exchange = %Exchange::ID, %err, "received invalid subscription response");
+                            break Err(err)
                         }
                     }
+                    Some(Err(SocketError::DeserializingJson { error, payload })) => {
+                        debug!(
+                            exchange = %Exchange::ID,
+                            ?error,
+                            %success_responses,
+                            %expected_responses,
+                            %payload,
+                            "failed to deserialize non SubResponse payload"
+                        );
+                    },
+                    Some(Err(SocketError::Terminated(close_frame))) => {
+                        break Err(SocketError::Subscribe(
+                            format!("received WebSocket CloseFrame: {close_frame}")
+                        ))
+                    }
+                    // Pings, Pongs, Frames, etc.
+                    _ => {},
+                }
+
+                // Continue processing to handle potential late subscription messages
+                if let Ok(message) = Rc::try_unwrap(message) {
+                    skipped(message);
                 }
             }
-            info!(exchange = %Exchange::ID, "All subscriptions have been successfully registered.");
+        }
+    }
+}
+
+/// Whether the `message` is a [`Connector::SubscriptionResponse`], & if so the result of it's
+/// validation. Used to filter the responses to subscriptions changed on a running stream out of
+/// it's market data.
+pub fn subscription_response<Exchange, Parser>(message: Rc<Result<Parser::Message, Parser::Error>>) -> Option<Result<(), SocketError>>
+where
+    Exchange: Connector,
+    Parser: StreamParser,
+{
+    match Parser::parse::<Exchange::SubscriptionResponse>(message)? {
+        Ok(response) => Some(response.validate().map(|_| ())),
+        Err(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{exchange::okx::Okx, protocol::http::websocket::WsMessage};
+
+    const TRADE: &str = r#"{"arg": {"channel": "trades", "instId": "BTC-USDT"}, "data": []}"#;
+    const SUBSCRIBED: &str = r#"{"event": "subscribe", "arg": {"channel": "trades", "instId": "BTC-USDT"}, "connId": "a4d3ae55"}"#;
+    const UNSUBSCRIBED: &str = r#"{"event": "unsubscribe", "arg": {"channel": "trades", "instId": "BTC-USDT"}, "connId": "a4d3ae55"}"#;
+    const REJECTED: &str = r#"{"event": "error", "code": "60012", "msg": "Invalid request", "connId": "a4d3ae55"}"#;
+
+    fn text(payload: &str) -> WsMessage {
+        WsMessage::Text(payload.to_owned())
+    }
+
+    #[tokio::test]
+    async fn test_await_responses() {
+        struct TestCase {
+            messages: Vec<&'static str>,
+            expected_responses: usize,
+            expected: Result<usize, ()>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: market data received before the response is skipped
+                messages: vec![TRADE, SUBSCRIBED, TRADE],
+                expected_responses: 1,
+                expected: Ok(1),
+            },
+            TestCase {
+                // TC1: rejected subscription
+                messages: vec![TRADE, REJECTED, SUBSCRIBED],
+                expected_responses: 1,
+                expected: Err(()),
+            },
+            TestCase {
+                // TC2: stream ends before every response is received
+                messages: vec![SUBSCRIBED],
+                expected_responses: 2,
+                expected: Err(()),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let mut messages = tokio_stream::iter(test.messages.into_iter().map(text).map(Ok));
+            let mut skipped = 0;
+            let actual = await_responses::<Okx, WsParser, _>(&mut messages, test.expected_responses, |_| skipped += 1).await;
+            assert_eq!(actual.map(|()| skipped).map_err(|_| ()), test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_subscription_response() {
+        struct TestCase {
+            message: &'static str,
+            expected: Option<Result<(), ()>>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: market data is not a response
+                message: TRADE,
+                expected: None,
+            },
+            TestCase {
+                // TC1: successful unsubscription
+                message: UNSUBSCRIBED,
+                expected: Some(Ok(())),
+            },
+            TestCase {
+                // TC2: rejected subscription
+                message: REJECTED,
+                expected: Some(Err(())),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = subscription_response::<Okx, WsParser>(Rc::new(Ok(text(test.message))));
+            assert_eq!(actual.map(|result| result.map_err(|_| ())), test.expected, "TC{} failed", index);
         }
     }
 }
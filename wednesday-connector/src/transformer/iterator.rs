@@ -5,6 +5,7 @@ use wednesday_model::{
     identifiers::{Exchange, ExchangeId},
     instruments::Instrument,
     orderbook::OrderBook,
+    orderbook_l3::OrderBookL3,
 };
 
 #[derive(Debug)]
@@ -30,3 +31,15 @@ impl From<(ExchangeId, Instrument, OrderBook)> for MarketIter<OrderBook> {
         })])
     }
 }
+
+impl From<(ExchangeId, Instrument, OrderBookL3)> for MarketIter<OrderBookL3> {
+    fn from((exchange_id, instrument, order_book): (ExchangeId, Instrument, OrderBookL3)) -> Self {
+        Self(vec![Ok(MarketEvent {
+            exchange_ts: order_book.last_update_ts,
+            local_ts: Utc::now(),
+            exchange: Exchange::from(exchange_id),
+            instrument,
+            kind: order_book,
+        })])
+    }
+}
@@ -5,9 +5,14 @@ pub mod updater;
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use tokio::sync::mpsc;
-use wednesday_model::{error::DataError, events::MarketEvent, instruments::Instrument};
 use std::fmt::Debug;
+use tokio::sync::mpsc;
+use wednesday_model::{
+    error::{DataError, SocketError},
+    events::MarketEvent,
+    identifiers::SubscriptionId,
+    instruments::Instrument,
+};
 
 use crate::{
     protocol::http::websocket::WsMessage,
@@ -30,4 +35,16 @@ where
     Kind: SubscriptionKind,
 {
     async fn new(ws_sink_tx: mpsc::UnboundedSender<WsMessage>, instrument_map: Map<Instrument>) -> Result<Self, DataError>;
+
+    /// Start transforming the messages of subscriptions added to a running stream, initialising
+    /// any state they require (eg/ order book snapshots).
+    async fn subscribe(&mut self, _ws_sink_tx: mpsc::UnboundedSender<WsMessage>, instrument_map: Map<Instrument>) -> Result<(), DataError> {
+        Err(DataError::Socket(SocketError::Unsupported {
+            entity: "ExchangeTransformer",
+            item: format!("subscribe to {:?}", instrument_map.0.keys().collect::<Vec<_>>()),
+        }))
+    }
+
+    /// Stop transforming the messages of subscriptions removed from a running stream.
+    fn unsubscribe(&mut self, _subscription_ids: &[SubscriptionId]) {}
 }
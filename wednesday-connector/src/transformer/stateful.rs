@@ -1,5 +1,5 @@
-use std::marker::PhantomData;
 use std::fmt::Debug;
+use std::marker::PhantomData;
 
 use async_trait::async_trait;
 use serde::Deserialize;
@@ -7,9 +7,8 @@ use tokio::sync::mpsc;
 use wednesday_model::{
     error::DataError,
     events::MarketEvent,
-    identifiers::{Identifier, SubscriptionId},
+    identifiers::{ExchangeId, Identifier, SubscriptionId},
     instruments::Instrument,
-    orderbook::OrderBook,
 };
 
 use crate::{
@@ -25,18 +24,23 @@ use super::{
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
-pub struct MultiBookTransformer<Exchange, Kind, Updater> {
+#[serde(bound(deserialize = "Updater: Deserialize<'de>, Updater::OrderBook: Deserialize<'de>"))]
+pub struct MultiBookTransformer<Exchange, Kind, Updater>
+where
+    Updater: OrderBookUpdater,
+{
     // Map of instrument order books, NOTE: Shouldn't we change the map variable name?
-    pub book_map: Map<InstrumentOrderBook<Updater>>,
+    pub book_map: Map<InstrumentOrderBook<Updater, Updater::OrderBook>>,
     phantom: PhantomData<(Exchange, Kind)>,
 }
 
 impl<Exchange, Kind, Updater> Transformer for MultiBookTransformer<Exchange, Kind, Updater>
 where
     Exchange: Connector,
-    Kind: SubscriptionKind<Event = OrderBook>,
+    Kind: SubscriptionKind,
     Updater: OrderBookUpdater<OrderBook = Kind::Event>,
     Updater::Update: Identifier<Option<SubscriptionId>> + for<'de> Deserialize<'de> + Debug,
+    MarketIter<Kind::Event>: From<(ExchangeId, Instrument, Kind::Event)>,
 {
     type Error = DataError;
     type Input = Updater::Update;
@@ -59,7 +63,7 @@ where
 
         // Apply update (snapshot or delta) to OrderBook & generate Market<OrderBook> snapshot
         match updater.update(book, update) {
-            Ok(Some(book)) => MarketIter::<OrderBook>::from((Exchange::ID, instrument.clone(), book)).0,
+            Ok(Some(book)) => MarketIter::<Kind::Event>::from((Exchange::ID, instrument.clone(), book)).0,
             // NOTE: Shouldn't we return an error here?
             Ok(None) => vec![],
             Err(error) => vec![Err(error)],
@@ -71,30 +75,50 @@ where
 impl<Exchange, Kind, Updater> ExchangeTransformer<Exchange, Kind> for MultiBookTransformer<Exchange, Kind, Updater>
 where
     Exchange: Connector + Send,
-    Kind: SubscriptionKind<Event = OrderBook> + Send,
+    Kind: SubscriptionKind + Send,
+    Kind::Event: Send,
     Updater: OrderBookUpdater<OrderBook = Kind::Event> + Send,
     Updater::Update: Identifier<Option<SubscriptionId>> + for<'de> Deserialize<'de> + Debug,
+    MarketIter<Kind::Event>: From<(ExchangeId, Instrument, Kind::Event)>,
 {
     async fn new(ws_sink_tx: mpsc::UnboundedSender<WsMessage>, map: Map<Instrument>) -> Result<Self, DataError> {
-        let (subscription_ids, init_book_requests): (Vec<_>, Vec<_>) = map
-            .0
-            .into_iter()
-            .map(|(subscription_id, instrument)| (subscription_id, Updater::init::<Exchange, Kind>(ws_sink_tx.clone(), instrument)))
-            .unzip();
-
-        let init_order_books = futures::future::join_all(init_book_requests)
-            .await
-            .into_iter()
-            .collect::<Result<Vec<InstrumentOrderBook<Updater>>, DataError>>()?;
-
-        let book_map = subscription_ids
-            .into_iter()
-            .zip(init_order_books.into_iter())
-            .collect::<Map<InstrumentOrderBook<Updater>>>();
-
         Ok(Self {
-            book_map,
+            book_map: init_order_books::<Exchange, Kind, Updater>(ws_sink_tx, map).await?,
             phantom: PhantomData::default(),
         })
     }
+
+    async fn subscribe(&mut self, ws_sink_tx: mpsc::UnboundedSender<WsMessage>, map: Map<Instrument>) -> Result<(), DataError> {
+        let book_map = init_order_books::<Exchange, Kind, Updater>(ws_sink_tx, map).await?;
+        self.book_map.extend(book_map.0);
+        Ok(())
+    }
+
+    fn unsubscribe(&mut self, subscription_ids: &[SubscriptionId]) {
+        self.book_map.remove(subscription_ids);
+    }
+}
+
+/// Initialise an [`InstrumentOrderBook`] for every subscription with the [`OrderBookUpdater`].
+async fn init_order_books<Exchange, Kind, Updater>(
+    ws_sink_tx: mpsc::UnboundedSender<WsMessage>,
+    map: Map<Instrument>,
+) -> Result<Map<InstrumentOrderBook<Updater, Updater::OrderBook>>, DataError>
+where
+    Exchange: Connector + Send,
+    Kind: SubscriptionKind + Send,
+    Updater: OrderBookUpdater + Send,
+{
+    let (subscription_ids, init_book_requests): (Vec<_>, Vec<_>) = map
+        .0
+        .into_iter()
+        .map(|(subscription_id, instrument)| (subscription_id, Updater::init::<Exchange, Kind>(ws_sink_tx.clone(), instrument)))
+        .unzip();
+
+    let init_order_books = futures::future::join_all(init_book_requests)
+        .await
+        .into_iter()
+        .collect::<Result<Vec<InstrumentOrderBook<Updater, Updater::OrderBook>>, DataError>>()?;
+
+    Ok(subscription_ids.into_iter().zip(init_order_books).collect())
 }
@@ -31,7 +31,7 @@ impl<Exchange, Kind, Input> ExchangeTransformer<Exchange, Kind> for StatelessTra
 where
     Exchange: Connector + Send,
     Kind: SubscriptionKind + Send,
-    Input: Identifier<Option<SubscriptionId>> + for<'de> Deserialize<'de> + Debug,
+    Input: Identifier<Option<SubscriptionId>> + for<'de> Deserialize<'de> + Debug + Send,
     MarketIter<Kind::Event>: From<(ExchangeId, Instrument, Input)>,
 {
     async fn new(_: mpsc::UnboundedSender<WsMessage>, instrument_map: Map<Instrument>) -> Result<Self, DataError> {
@@ -42,6 +42,15 @@ where
             phantom: PhantomData::default(),
         })
     }
+
+    async fn subscribe(&mut self, _: mpsc::UnboundedSender<WsMessage>, instrument_map: Map<Instrument>) -> Result<(), DataError> {
+        self.instrument_map.extend(instrument_map.0);
+        Ok(())
+    }
+
+    fn unsubscribe(&mut self, subscription_ids: &[SubscriptionId]) {
+        self.instrument_map.remove(subscription_ids);
+    }
 }
 
 impl<Exchange, Kind, Input> Transformer for StatelessTransformer<Exchange, Kind, Input>
@@ -62,8 +71,8 @@ where
             Some(subscription_id) => subscription_id,
             None => {
                 debug!(?input, "No subscription ID found in input, returning empty MarketEvent iterator");
-                return vec![]
-            }
+                return vec![];
+            },
         };
 
         match self.instrument_map.find(&subscription_id) {
@@ -102,7 +111,7 @@ impl<Exchange, Kind, Input, Pong> ExchangeTransformer<Exchange, Kind> for Statel
 where
     Exchange: Connector + Send,
     Kind: SubscriptionKind + Send,
-    Input: Identifier<Option<SubscriptionId>> + for<'de> Deserialize<'de> + Debug,
+    Input: Identifier<Option<SubscriptionId>> + for<'de> Deserialize<'de> + Debug + Send,
     MarketIter<Kind::Event>: From<(ExchangeId, Instrument, Input)>,
     Pong: for<'de> Deserialize<'de> + Send + Debug,
 {
@@ -114,4 +123,12 @@ where
             phantom: PhantomData::default(),
         })
     }
+
+    async fn subscribe(&mut self, ws_sink_tx: mpsc::UnboundedSender<WsMessage>, instrument_map: Map<Instrument>) -> Result<(), DataError> {
+        ExchangeTransformer::<Exchange, Kind>::subscribe(&mut self.inner, ws_sink_tx, instrument_map).await
+    }
+
+    fn unsubscribe(&mut self, subscription_ids: &[SubscriptionId]) {
+        ExchangeTransformer::<Exchange, Kind>::unsubscribe(&mut self.inner, subscription_ids)
+    }
 }
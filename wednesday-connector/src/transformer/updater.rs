@@ -16,7 +16,7 @@ where
     async fn init<Exchange, Kind>(
         ws_sink_tx: mpsc::UnboundedSender<WsMessage>,
         instrument: Instrument,
-    ) -> Result<InstrumentOrderBook<Self>, DataError>
+    ) -> Result<InstrumentOrderBook<Self, Self::OrderBook>, DataError>
     where
         Exchange: Send,
         Kind: Send;
@@ -25,8 +25,8 @@ where
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-pub struct InstrumentOrderBook<Updater> {
+pub struct InstrumentOrderBook<Updater, Book = OrderBook> {
     pub instrument: Instrument,
     pub updater: Updater,
-    pub book: OrderBook,
+    pub book: Book,
 }
@@ -1,17 +1,27 @@
 use tokio::sync::mpsc::error::TryRecvError::{Disconnected, Empty};
-use tokio::sync::mpsc::UnboundedReceiver;
+use tracing::warn;
+use wednesday_model::{channel::ChannelRx, status::StatusRx};
 
 use crate::model::enums::Feed;
 
 use super::FeedGenerator;
 
 pub struct LiveMarketFeed<Event> {
-    pub market_rx: UnboundedReceiver<Event>,
+    pub market_rx: ChannelRx<Event>,
+    pub status_rx: Option<StatusRx>,
 }
 
 impl<Event> FeedGenerator<Event> for LiveMarketFeed<Event> {
     fn next(&mut self) -> Feed<Event> {
         loop {
+            // Surface every connection status change that interrupts the market events
+            if let Some(status) = self.status_rx.as_mut().and_then(|status_rx| status_rx.try_recv().ok()) {
+                if !status.status.is_healthy() {
+                    warn!(exchange = %status.exchange, leg = status.leg, status = ?status.status, "MarketStream unhealthy");
+                    break Feed::Unhealthy;
+                }
+            }
+
             match self.market_rx.try_recv() {
                 Ok(event) => break Feed::Next(event),
                 Err(Empty) => continue,
@@ -22,7 +32,66 @@ impl<Event> FeedGenerator<Event> for LiveMarketFeed<Event> {
 }
 
 impl<Event> LiveMarketFeed<Event> {
-    pub fn new(market_rx: UnboundedReceiver<Event>) -> Self {
-        Self { market_rx }
+    pub fn new(market_rx: ChannelRx<Event>) -> Self {
+        Self { market_rx, status_rx: None }
+    }
+
+    /// Yield [`Feed::Unhealthy`] whenever a [`StatusEvent`](wednesday_model::status::StatusEvent)
+    /// reports that a connection of the market streams is reconnecting or has given up.
+    pub fn with_status(self, status_rx: StatusRx) -> Self {
+        Self {
+            status_rx: Some(status_rx),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::Utc;
+    use wednesday_model::{
+        channel::{channel, ChannelConfig},
+        events::MarketEvent,
+        identifiers::{Exchange, ExchangeId},
+        instruments::{Instrument, InstrumentKind},
+        status::{StatusChannel, StatusEvent, StreamStatus},
+    };
+
+    use super::*;
+
+    fn event(kind: u64) -> MarketEvent<u64> {
+        MarketEvent {
+            exchange_ts: Utc::now(),
+            local_ts: Utc::now(),
+            exchange: Exchange::from(ExchangeId::Okx),
+            instrument: Instrument::from(("btc", "usdt", InstrumentKind::CryptoSpot)),
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_live_market_feed_status() {
+        let (market_tx, market_rx) = channel::<MarketEvent<u64>>(ChannelConfig::default());
+        let status = StatusChannel::default();
+        let mut feed = LiveMarketFeed::new(market_rx).with_status(status.rx);
+
+        let send = |stream_status| status.tx.send(StatusEvent::new(ExchangeId::Okx, 0, stream_status)).unwrap();
+        send(StreamStatus::Connected);
+        market_tx.try_send(event(1)).unwrap();
+        assert!(matches!(feed.next(), Feed::Next(event) if event.kind == 1));
+
+        send(StreamStatus::Reconnecting {
+            attempt: 1,
+            backoff: Duration::from_secs(1),
+        });
+        market_tx.try_send(event(2)).unwrap();
+        assert!(matches!(feed.next(), Feed::Unhealthy));
+        assert!(matches!(feed.next(), Feed::Next(event) if event.kind == 2));
+
+        send(StreamStatus::Resynced);
+        drop(market_tx);
+        assert!(matches!(feed.next(), Feed::Finished));
     }
 }
@@ -7,6 +7,7 @@ use uuid::Uuid;
 use wednesday_model::{
     events::{DataKind, MarketEvent},
     identifiers::Market,
+    latency::LatencyRecorder,
 };
 
 use crate::{
@@ -44,6 +45,7 @@ where
     traders: Option<Vec<Trader<EventTx, Statistic, Portfolio, Data, Strategy, Execution>>>,
     trader_command_txs: Option<HashMap<Market, mpsc::Sender<EngineCommand>>>,
     statistics_summary: Option<Statistic>,
+    latency: Option<LatencyRecorder>,
 }
 
 impl<EventTx, Statistic, Portfolio, Data, Strategy, Execution> EngineBuilder<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
@@ -63,6 +65,7 @@ where
             traders: None,
             trader_command_txs: None,
             statistics_summary: None,
+            latency: None,
         }
     }
 
@@ -108,6 +111,13 @@ where
         }
     }
 
+    pub fn latency(self, value: LatencyRecorder) -> Self {
+        Self {
+            latency: Some(value),
+            ..self
+        }
+    }
+
     pub fn build(self) -> Result<TradingEngine<EventTx, Statistic, Portfolio, Data, Strategy, Execution>, EngineError> {
         Ok(TradingEngine {
             engine_id: self.engine_id.ok_or(EngineError::BuilderIncomplete("engine_id"))?,
@@ -120,6 +130,7 @@ where
             statistics_summary: self
                 .statistics_summary
                 .ok_or(EngineError::BuilderIncomplete("statistics_summary"))?,
+            latency: self.latency,
         })
     }
 }
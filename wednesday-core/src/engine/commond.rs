@@ -9,4 +9,9 @@ pub enum EngineCommand {
     Terminate(String),
     ExitAllPositions,
     ExitPosition(Market),
+    /// Stop generating [`Signal`](crate::model::signal::Signal)s from market data, whilst open
+    /// positions keep being updated & can still be exited.
+    Pause,
+    /// Resume generating [`Signal`](crate::model::signal::Signal)s after a [`EngineCommand::Pause`].
+    Resume,
 }
@@ -9,6 +9,7 @@ use uuid::Uuid;
 use wednesday_model::{
     events::{DataKind, MarketEvent},
     identifiers::{Market, MarketId},
+    latency::LatencyRecorder,
 };
 
 use crate::{
@@ -24,7 +25,7 @@ use crate::{
         repository::{PositionHandler, StatisticHandler},
         updater::{FillUpdater, MarketUpdater},
     },
-    statistic::summary::{combine, PositionSummariser, TableBuilder},
+    statistic::summary::{combine, latency::latency_table, PositionSummariser, TableBuilder},
     strategy::SignalGenerator,
 };
 
@@ -49,6 +50,7 @@ where
     pub traders: Vec<Trader<EventTx, Statistic, Portfolio, Data, Strategy, Execution>>,
     pub trader_command_txs: HashMap<Market, mpsc::Sender<EngineCommand>>,
     pub statistics_summary: Statistic,
+    pub latency: Option<LatencyRecorder>,
 }
 
 #[derive(Debug)]
@@ -67,6 +69,8 @@ where
     pub(crate) traders: Vec<Trader<EventTx, Statistic, Portfolio, Data, Strategy, Execution>>,
     pub(crate) trader_command_txs: HashMap<Market, mpsc::Sender<EngineCommand>>,
     pub(crate) statistics_summary: Statistic,
+    // Optional [`LatencyRecorder`] whose summary is printed at shutdown.
+    pub(crate) latency: Option<LatencyRecorder>,
 }
 
 impl<EventTx, Statistic, Portfolio, Data, Strategy, Execution> TradingEngine<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
@@ -87,6 +91,7 @@ where
             traders: component.traders,
             trader_command_txs: component.trader_command_txs,
             statistics_summary: component.statistics_summary,
+            latency: component.latency,
         }
     }
 
@@ -115,6 +120,10 @@ where
                                 { self.exit_all_positions().await; },
                             EngineCommand::ExitPosition(market) =>
                                 { self.exit_position(market).await; },
+                            EngineCommand::Pause =>
+                                { self.pause_traders(true).await; },
+                            EngineCommand::Resume =>
+                                { self.pause_traders(false).await; },
                         }
                     } else {
                         break;
@@ -123,7 +132,12 @@ where
             }
         }
 
+        let latency = self.latency.take();
         self.generated_session_summary().printstd();
+
+        if let Some(latency) = latency {
+            latency_table(latency.snapshots()).printstd();
+        }
     }
 
     async fn run_traders(&mut self) -> mpsc::Receiver<bool> {
@@ -187,6 +201,19 @@ where
         }
     }
 
+    async fn pause_traders(&self, paused: bool) {
+        for (market, command_tx) in self.trader_command_txs.iter() {
+            let command = if paused { EngineCommand::Pause } else { EngineCommand::Resume };
+            if command_tx.send(command).await.is_err() {
+                error!(
+                    market = &*format!("{:?}", market),
+                    why = "dropped receiver",
+                    "failed to send EngineCommand::Pause/Resume to Trader command_rx"
+                );
+            }
+        }
+    }
+
     async fn exit_position(&self, market: Market) {
         if let Some((market_ref, command_tx)) = self.trader_command_txs.get_key_value(&market) {
             if command_tx.send(EngineCommand::ExitPosition(market)).await.is_err() {
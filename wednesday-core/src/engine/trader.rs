@@ -1,4 +1,6 @@
-use std::{collections::VecDeque, marker::PhantomData, sync::Arc};
+use std::{collections::VecDeque, marker::PhantomData, sync::Arc, time::Instant};
+
+use chrono::Utc;
 
 use parking_lot::Mutex;
 use serde::Serialize;
@@ -8,6 +10,7 @@ use uuid::Uuid;
 use wednesday_model::{
     events::{DataKind, MarketEvent},
     identifiers::Market,
+    latency::{LatencyRecorder, LatencyStage},
 };
 
 use crate::{
@@ -44,6 +47,8 @@ where
     pub data: Data,
     pub strategy: Strategy,
     pub execution: Execution,
+    pub latency: Option<LatencyRecorder>,
+    pub exit_on_finish: Vec<Market>,
     _statistic_marker: PhantomData<Statistic>,
 }
 
@@ -69,6 +74,13 @@ where
     pub(crate) data: Data,
     pub(crate) strategy: Strategy,
     pub(crate) execution: Execution,
+    // Optional [`LatencyRecorder`] of the strategy & order stages of the trading loop.
+    pub(crate) latency: Option<LatencyRecorder>,
+    // Markets whose open Position is exited once the data feed finishes, eg/ at the end of a
+    // backtest.
+    pub(crate) exit_on_finish: Vec<Market>,
+    // Whether signal generation is paused by an [`EngineCommand::Pause`].
+    pub(crate) paused: bool,
     pub(crate) _statistic_marker: PhantomData<Statistic>,
 }
 
@@ -97,6 +109,9 @@ where
             data: components.data,
             strategy: components.strategy,
             execution: components.execution,
+            latency: components.latency,
+            exit_on_finish: components.exit_on_finish,
+            paused: false,
             _statistic_marker: PhantomData::default(),
         }
     }
@@ -120,6 +135,8 @@ where
                 match command {
                     EngineCommand::Terminate(_reasone) => break 'trading,
                     EngineCommand::ExitPosition(market) => self.event_q.push_back(Event::SignalForceExit(SignalForceExit::from(market))),
+                    EngineCommand::Pause => self.paused = true,
+                    EngineCommand::Resume => self.paused = false,
                     // otherwise => continue
                     _ => continue,
                 }
@@ -142,7 +159,12 @@ where
                     );
                     continue 'trading;
                 },
-                Feed::Finished => break 'trading,
+                Feed::Finished if self.exit_on_finish.is_empty() => break 'trading,
+                // Exit the open Positions before stopping, at the last price of each market
+                Feed::Finished => {
+                    let markets = std::mem::take(&mut self.exit_on_finish);
+                    self.event_q.extend(markets.into_iter().map(|market| Event::SignalForceExit(SignalForceExit::from(market))));
+                },
             }
 
             // Handle Events in the event_q
@@ -153,7 +175,13 @@ where
             while let Some(event) = self.event_q.pop_front() {
                 match event {
                     Event::Market(market) => {
-                        if let Some(signal) = self.strategy.generate_signal(&market) {
+                        if let Some(latency) = &self.latency {
+                            let kind = market.kind.kind();
+                            latency.record_between(&market.exchange, &market.instrument, kind, LatencyStage::ParseToStrategy, market.local_ts, Utc::now());
+                        }
+
+                        // Strategy keeps consuming market data whilst paused so its indicators stay warm
+                        if let Some(signal) = self.strategy.generate_signal(&market).filter(|_| !self.paused) {
                             self.event_tx.send(Event::Signal(signal.clone()));
                             self.event_q.push_back(Event::Signal(signal));
                         }
@@ -169,6 +197,8 @@ where
                     },
                     Event::Signal(signal) => {
                         if let Some(order) = self.portfolio.lock().generate_order(&signal).expect("failed to generate order") {
+                            self.record_latency(LatencyStage::SignalToOrder, (Utc::now() - signal.datetime).to_std().unwrap_or_default());
+
                             // NOTE: Clone() occurs here, we need to figure out how to avoid this
                             self.event_tx.send(Event::OrderNew(order.clone()));
                             // self.execution.send_order(order);
@@ -189,7 +219,9 @@ where
                         }
                     },
                     Event::OrderNew(order) => {
+                        let sent = Instant::now();
                         let fill = self.execution.generate_fill(&order).expect("failed to generate fill");
+                        self.record_latency(LatencyStage::OrderToAck, sent.elapsed());
 
                         self.event_tx.send(Event::Fill(fill.clone()));
                         self.event_q.push_back(Event::Fill(fill));
@@ -217,6 +249,13 @@ where
         }
     }
 
+    /// Record the latency of an order stage of the [`Market`] of the [`Trader`].
+    fn record_latency(&self, stage: LatencyStage, latency: std::time::Duration) {
+        if let Some(recorder) = &self.latency {
+            recorder.record(&self.market.exchange, &self.market.instrument, "Order", stage, latency);
+        }
+    }
+
     fn receive_remote_command(&mut self) -> Option<EngineCommand> {
         match self.command_rx.try_recv() {
             Ok(command) => {
@@ -7,6 +7,7 @@ use uuid::Uuid;
 use wednesday_model::{
     events::{DataKind, MarketEvent},
     identifiers::Market,
+    latency::LatencyRecorder,
 };
 
 use crate::{
@@ -43,6 +44,8 @@ where
     data: Option<Data>,
     strategy: Option<Strategy>,
     execution: Option<Execution>,
+    latency: Option<LatencyRecorder>,
+    exit_on_finish: Vec<Market>,
     _statistic_marker: Option<PhantomData<Statistic>>,
 }
 
@@ -65,6 +68,8 @@ where
             data: None,
             strategy: None,
             execution: None,
+            latency: None,
+            exit_on_finish: Vec::new(),
             _statistic_marker: None,
         }
     }
@@ -122,6 +127,22 @@ where
         }
     }
 
+    pub fn latency(self, value: LatencyRecorder) -> Self {
+        Self {
+            latency: Some(value),
+            ..self
+        }
+    }
+
+    /// Exit the open [`Position`](crate::model::position::Position) of each [`Market`] once the
+    /// [`Trader`] data feed finishes, eg/ at the end of a backtest.
+    pub fn exit_on_finish(self, value: Vec<Market>) -> Self {
+        Self {
+            exit_on_finish: value,
+            ..self
+        }
+    }
+
     pub fn build(self) -> Result<Trader<EventTx, Statistic, Portfolio, Data, Strategy, Execution>, EngineError> {
         Ok(Trader {
             engine_id: self.engine_id.ok_or(EngineError::BuilderIncomplete("engine_id"))?,
@@ -133,6 +154,9 @@ where
             data: self.data.ok_or(EngineError::BuilderIncomplete("data"))?,
             strategy: self.strategy.ok_or(EngineError::BuilderIncomplete("strategy"))?,
             execution: self.execution.ok_or(EngineError::BuilderIncomplete("execution"))?,
+            latency: self.latency,
+            exit_on_finish: self.exit_on_finish,
+            paused: false,
             _statistic_marker: PhantomData::default(),
         })
     }
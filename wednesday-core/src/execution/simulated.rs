@@ -1,7 +1,11 @@
+use std::sync::Arc;
+
 use chrono::Utc;
+use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
+use wednesday_model::orderbook::OrderBook;
 
-use crate::model::{fee::Fees, fill_event::FillEvent};
+use crate::model::{fee::Fees, fill_event::FillEvent, order_event::OrderEvent};
 
 use super::ExecutionClient;
 
@@ -10,9 +14,17 @@ pub struct SimExecConfig {
     pub simulated_fees_pct: Fees,
 }
 
-#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+/// [`OrderBook`] of the traded market shared with a [`SimulatedExecution`], kept up to date by
+/// whatever maintains the book, eg/ an order book stream.
+pub type SharedOrderBook = Arc<RwLock<OrderBook>>;
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct SimulatedExecution {
     fees_pct: Fees,
+    /// Opt-in [`OrderBook`] swept to simulate the slippage of each fill, see
+    /// [`SimulatedExecution::with_order_book`].
+    #[serde(skip)]
+    order_book: Option<SharedOrderBook>,
 }
 
 impl ExecutionClient for SimulatedExecution {
@@ -31,7 +43,7 @@ impl ExecutionClient for SimulatedExecution {
             decision: order.decision,
             quantity: order.quantity,
             fill_value_gross,
-            fees: self.calculate_fees(&fill_value_gross),
+            fees: self.calculate_fees(order, &fill_value_gross),
         })
     }
 }
@@ -40,6 +52,16 @@ impl SimulatedExecution {
     pub fn new(config: SimExecConfig) -> Self {
         Self {
             fees_pct: config.simulated_fees_pct,
+            order_book: None,
+        }
+    }
+
+    /// Simulate the slippage of each fill by sweeping the shared [`OrderBook`] of the traded
+    /// market, rather than with the flat [`SimExecConfig`] slippage percentage.
+    pub fn with_order_book(self, order_book: SharedOrderBook) -> Self {
+        Self {
+            order_book: Some(order_book),
+            ..self
         }
     }
 
@@ -48,13 +70,35 @@ impl SimulatedExecution {
         order.quantity.abs() * order.market_meta.close
     }
 
-    fn calculate_fees(&self, fill_value_gross: &f64) -> Fees {
+    fn calculate_fees(&self, order: &OrderEvent, fill_value_gross: &f64) -> Fees {
         Fees {
             exchange: self.fees_pct.exchange * fill_value_gross,
-            slippage: self.fees_pct.slippage * fill_value_gross,
+            slippage: self
+                .calculate_book_slippage(order)
+                .unwrap_or(self.fees_pct.slippage * fill_value_gross),
             // network: self.fees_pct.network * fill_value_gross,
         }
     }
+
+    /// Cost of sweeping the shared [`OrderBook`] to fill the order, relative to the mid price.
+    ///
+    /// Returns [`None`] without a book, or if the book is not deep enough to fill the order, in
+    /// which case the flat slippage percentage is used instead.
+    fn calculate_book_slippage(&self, order: &OrderEvent) -> Option<f64> {
+        let book = self.order_book.as_ref()?.read();
+        let quantity = order.quantity.abs();
+        let sweep = if order.quantity > 0.0 {
+            book.sweep_buy(quantity)
+        } else {
+            book.sweep_sell(quantity)
+        };
+
+        if !sweep.is_complete() {
+            return None;
+        }
+
+        Some((sweep.notional - book.mid_price()? * quantity).abs())
+    }
 }
 
 // NOTE: Commented in purpose.
@@ -141,3 +185,121 @@ impl SimulatedExecution {
 //         assert_eq!(actual_result, expected)
 //     }
 // }
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+    use wednesday_model::{
+        enums::{BookSide, OrderType},
+        identifiers::{Exchange, ExchangeId},
+        instruments::{Instrument, InstrumentKind},
+        orderbook::{Level, OrderBookSide},
+    };
+
+    use super::*;
+    use crate::model::{decision::Decision, market_meta::MarketMeta};
+
+    fn order(quantity: f64) -> OrderEvent {
+        OrderEvent {
+            timestamp: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+            exchange: Exchange::from(ExchangeId::Okx),
+            instrument: Instrument::from(("btc", "usdt", InstrumentKind::CryptoPerpetual)),
+            market_meta: MarketMeta {
+                close: 100.0,
+                timestamp: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+            },
+            decision: if quantity > 0.0 { Decision::Long } else { Decision::Short },
+            quantity,
+            order_type: OrderType::Market,
+        }
+    }
+
+    fn order_book() -> SharedOrderBook {
+        Arc::new(RwLock::new(OrderBook {
+            last_update_ts: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+            update_id: None,
+            bids: OrderBookSide::new(BookSide::Bid, vec![Level::new(99.5, 1.0), Level::new(99.0, 2.0)]),
+            asks: OrderBookSide::new(BookSide::Ask, vec![Level::new(100.5, 1.0), Level::new(101.0, 2.0)]),
+        }))
+    }
+
+    #[test]
+    fn test_generate_fill_fees() {
+        let config = || SimExecConfig {
+            simulated_fees_pct: Fees {
+                exchange: 0.001,
+                slippage: 0.01,
+            },
+        };
+
+        struct TestCase {
+            execution: SimulatedExecution,
+            quantity: f64,
+            expected: Fees,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: flat slippage percentage without a book
+                execution: SimulatedExecution::new(config()),
+                quantity: 2.0,
+                expected: Fees {
+                    exchange: 0.2,
+                    slippage: 2.0,
+                },
+            },
+            TestCase {
+                // TC1: buy sweeps the asks, 100.5 + 101.0 against a mid price of 100.0
+                execution: SimulatedExecution::new(config()).with_order_book(order_book()),
+                quantity: 2.0,
+                expected: Fees {
+                    exchange: 0.2,
+                    slippage: 1.5,
+                },
+            },
+            TestCase {
+                // TC2: sell sweeps the bids, 99.5 + 99.0 against a mid price of 100.0
+                execution: SimulatedExecution::new(config()).with_order_book(order_book()),
+                quantity: -2.0,
+                expected: Fees {
+                    exchange: 0.2,
+                    slippage: 1.5,
+                },
+            },
+            TestCase {
+                // TC3: book not deep enough falls back to the flat slippage percentage
+                execution: SimulatedExecution::new(config()).with_order_book(order_book()),
+                quantity: 5.0,
+                expected: Fees {
+                    exchange: 0.5,
+                    slippage: 5.0,
+                },
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let fill = test.execution.generate_fill(&order(test.quantity)).unwrap();
+            assert_eq!(fill.fill_value_gross, test.quantity.abs() * 100.0, "TC{} failed", index);
+            assert!((fill.fees.exchange - test.expected.exchange).abs() < 1e-9, "TC{} failed", index);
+            assert!((fill.fees.slippage - test.expected.slippage).abs() < 1e-9, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_generate_fill_uses_latest_order_book() {
+        let order_book = order_book();
+        let execution = SimulatedExecution::new(SimExecConfig {
+            simulated_fees_pct: Fees {
+                exchange: 0.0,
+                slippage: 0.0,
+            },
+        })
+        .with_order_book(order_book.clone());
+
+        order_book.write().asks.upsert_single(Level::new(100.5, 0.0));
+
+        // Best ask removed, so the buy sweeps 101.0 twice against a mid price of 100.25
+        let fill = execution.generate_fill(&order(2.0)).unwrap();
+        assert!((fill.fees.slippage - 1.5).abs() < 1e-9);
+    }
+}
@@ -3,6 +3,7 @@ pub mod engine;
 pub mod execution;
 pub mod model;
 pub mod oms;
+pub mod optimise;
 pub mod portfolio;
 pub mod statistic;
 pub mod strategy;
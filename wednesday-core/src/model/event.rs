@@ -1,6 +1,11 @@
+use chrono::{DateTime, Utc};
+use serde::{de, Deserialize, Deserializer, Serialize};
 use tokio::sync::mpsc;
 use tracing::warn;
-use wednesday_model::events::{DataKind, MarketEvent};
+use wednesday_model::{
+    events::{DataKind, MarketEvent},
+    metric::{Field, Metric, MetricTx, Tag},
+};
 
 use super::{
     balance::Balance,
@@ -10,7 +15,19 @@ use super::{
     signal::{Signal, SignalForceExit},
 };
 
-#[derive(Debug)]
+/// Version of the serialised [`Event`] schema, see [`EventRecord`].
+///
+/// Bumped whenever a change to the [`Event`] graph breaks existing consumers, eg/ a renamed or
+/// removed field, or a changed variant tag. Adding a variant does not bump the version.
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+/// Events of the [`Trader`](crate::engine::trader::Trader) event loop, which are serialisable
+/// for external consumers (eg/ remote dashboards).
+///
+/// Serialised adjacently tagged with a snake_case `type`, eg/
+/// `{"type": "balance", "data": {..}}`, see [`EventRecord`] for the versioned envelope.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
 pub enum Event {
     Market(MarketEvent<DataKind>),
     Signal(Signal),
@@ -24,11 +41,104 @@ pub enum Event {
     Balance(Balance),
 }
 
+impl Event {
+    /// Engine level PnL & exposure gauge [`Metric`] of the [`Event`], if any.
+    ///
+    /// Position gauges are tagged with the [`PositionId`](super::position::PositionId), which is
+    /// stable per engine & market, and an exited position leaves its gauges at zero exposure.
+    pub fn metric(&self) -> Option<Metric> {
+        let position = |position_id: &str, time: DateTime<Utc>, fields: Vec<Field>| Metric {
+            name: "engine_position",
+            time: time.timestamp_millis() as u64,
+            tags: vec![Tag::new("position_id", position_id)],
+            fields,
+        };
+
+        match self {
+            Event::Balance(balance) => Some(Metric {
+                name: "engine_balance",
+                time: balance.timestamp.timestamp_millis() as u64,
+                tags: vec![],
+                fields: vec![Field::new("equity", balance.total), Field::new("available", balance.available)],
+            }),
+            Event::PositionNew(new) => Some(position(
+                &new.position_id,
+                new.meta.update_timestamp,
+                vec![
+                    Field::new("exposure", new.current_value_gross),
+                    Field::new("unrealised_pnl", new.unrealised_profit_loss),
+                ],
+            )),
+            Event::PositionUpdate(update) => Some(position(
+                &update.position_id,
+                update.update_timestamp,
+                vec![
+                    Field::new("exposure", update.current_value_gross),
+                    Field::new("unrealised_pnl", update.unrealised_profit_loss),
+                ],
+            )),
+            Event::PositionExit(exit) => Some(position(
+                &exit.position_id,
+                exit.exit_time,
+                vec![
+                    Field::new("exposure", 0.0),
+                    Field::new("unrealised_pnl", 0.0),
+                    Field::new("realised_pnl", exit.realised_profit_loss),
+                ],
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// [`Event`] tagged with the [`EVENT_SCHEMA_VERSION`] it was serialised with, eg/
+/// `{"version": 1, "type": "balance", "data": {..}}`.
+///
+/// Deserialising a record of a newer version than this build understands fails, rather than
+/// silently misreading it.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct EventRecord<E = Event> {
+    #[serde(deserialize_with = "de_supported_version")]
+    pub version: u32,
+    #[serde(flatten)]
+    pub event: E,
+}
+
+impl<E> EventRecord<E> {
+    pub fn new(event: E) -> Self {
+        Self {
+            version: EVENT_SCHEMA_VERSION,
+            event,
+        }
+    }
+}
+
+impl From<Event> for EventRecord {
+    fn from(event: Event) -> Self {
+        Self::new(event)
+    }
+}
+
+fn de_supported_version<'de, D>(deserializer: D) -> Result<u32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let version = u32::deserialize(deserializer)?;
+    if version == 0 || version > EVENT_SCHEMA_VERSION {
+        return Err(de::Error::custom(format!(
+            "unsupported event schema version {version}, expected 1..={EVENT_SCHEMA_VERSION}"
+        )));
+    }
+    Ok(version)
+}
+
 #[derive(Debug, Clone)]
 pub struct EventTx {
     receiver_dropped: bool,
 
     event_tx: mpsc::UnboundedSender<Event>,
+
+    metric_tx: Option<MetricTx>,
 }
 
 impl EventTx {
@@ -36,6 +146,24 @@ impl EventTx {
         Self {
             receiver_dropped: false,
             event_tx,
+            metric_tx: None,
+        }
+    }
+
+    /// Also send the [`Event::metric`] gauges of every sent [`Event`] via the `metric_tx`, eg/ to
+    /// an exporter.
+    pub fn with_metric_tx(self, metric_tx: MetricTx) -> Self {
+        Self {
+            metric_tx: Some(metric_tx),
+            ..self
+        }
+    }
+
+    fn send_metric(&self, message: &Event) {
+        if let Some(metric_tx) = &self.metric_tx {
+            if let Some(metric) = message.metric() {
+                let _ = metric_tx.send(metric);
+            }
         }
     }
 }
@@ -48,6 +176,8 @@ pub trait MessageTransmitter<Message> {
 
 impl MessageTransmitter<Event> for EventTx {
     fn send(&mut self, message: Event) {
+        self.send_metric(&message);
+
         if self.receiver_dropped {
             return;
         }
@@ -63,6 +193,8 @@ impl MessageTransmitter<Event> for EventTx {
     }
 
     fn send_many(&mut self, messages: Vec<Event>) {
+        messages.iter().for_each(|message| self.send_metric(message));
+
         if self.receiver_dropped {
             return;
         }
@@ -72,3 +204,257 @@ impl MessageTransmitter<Event> for EventTx {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use wednesday_model::{
+        bar::Bar,
+        enums::{AggressorSide, OrderType},
+        identifiers::{Exchange, ExchangeId},
+        instruments::{Instrument, InstrumentKind},
+        orderbook::{Level, OrderBookL1},
+        perpetual::{MarkPrice, OpenInterest},
+        trade::{AggregatedTrade, PublicTrade},
+    };
+
+    use super::*;
+    use crate::model::{
+        decision::Decision,
+        fee::Fees,
+        market_meta::MarketMeta,
+        position::{PositionMeta, PositionSide},
+        signal::SignalStrength,
+    };
+
+    #[test]
+    fn test_event_tx_sends_metrics() {
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let (metric_tx, mut metric_rx) = mpsc::unbounded_channel();
+        let mut event_tx = EventTx::new(event_tx).with_metric_tx(metric_tx);
+
+        let update = PositionUpdate {
+            position_id: "engine_okx_btc_usdt_position".to_string(),
+            update_timestamp: Utc::now(),
+            current_symbol_price: 100.0,
+            current_value_gross: 200.0,
+            unrealised_profit_loss: -5.0,
+        };
+        event_tx.send_many(vec![
+            Event::OrderUpdate,
+            Event::PositionUpdate(update),
+            Event::Balance(Balance::new(Utc::now(), 1000.0, 800.0)),
+        ]);
+
+        assert_eq!(std::iter::from_fn(|| event_rx.try_recv().ok()).count(), 3);
+
+        let position = metric_rx.try_recv().unwrap();
+        assert_eq!(position.name, "engine_position");
+        assert_eq!(position.tags, vec![Tag::new("position_id", "engine_okx_btc_usdt_position")]);
+        assert_eq!(position.fields, vec![Field::new("exposure", 200.0), Field::new("unrealised_pnl", -5.0)]);
+
+        let balance = metric_rx.try_recv().unwrap();
+        assert_eq!(balance.fields, vec![Field::new("equity", 1000.0), Field::new("available", 800.0)]);
+
+        // Events without gauges send no metric
+        assert!(metric_rx.try_recv().is_err());
+    }
+
+    fn time() -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000, 0).unwrap()
+    }
+
+    fn market_event(kind: DataKind) -> Event {
+        Event::Market(MarketEvent {
+            exchange_ts: time(),
+            local_ts: time(),
+            exchange: Exchange::from(ExchangeId::Okx),
+            instrument: instrument(),
+            kind,
+        })
+    }
+
+    fn instrument() -> Instrument {
+        Instrument::from(("btc", "usdt", InstrumentKind::CryptoPerpetual))
+    }
+
+    fn market_meta() -> MarketMeta {
+        MarketMeta { close: 100.0, timestamp: time() }
+    }
+
+    fn fees() -> Fees {
+        Fees { exchange: 0.5, slippage: 0.25 }
+    }
+
+    fn position() -> Position {
+        Position::builder()
+            .position_id("engine_okx_btc_usdt_position".to_string())
+            .exchange(Exchange::from(ExchangeId::Okx))
+            .instrument(instrument())
+            .meta(PositionMeta {
+                enter_timestamp: time(),
+                update_timestamp: time(),
+                exit_balance: Some(Balance::new(time(), 1000.0, 800.0)),
+            })
+            .side(PositionSide::Sell)
+            .quantity(-2.0)
+            .enter_fees(fees())
+            .enter_fees_total(0.75)
+            .enter_avg_price_gross(100.0)
+            .enter_value_gross(200.0)
+            .exit_fees(Fees::default())
+            .exit_fees_total(0.0)
+            .exit_avg_price_gross(0.0)
+            .exit_value_gross(0.0)
+            .current_symbol_price(100.0)
+            .current_value_gross(200.0)
+            .unrealised_profit_loss(-0.75)
+            .realised_profit_loss(0.0)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_event_record_round_trip() {
+        let events = vec![
+            market_event(DataKind::PublicTrade(PublicTrade {
+                id: "1".to_string(),
+                price: 100.0,
+                quantity: 0.5,
+                aggressor_side: AggressorSide::Buy,
+            })),
+            market_event(DataKind::AggregatedTrade(AggregatedTrade {
+                id: "2".to_string(),
+                price: 100.0,
+                quantity: 1.5,
+                first_trade_id: "10".to_string(),
+                last_trade_id: "12".to_string(),
+                aggressor_side: AggressorSide::Sell,
+            })),
+            market_event(DataKind::OrderBookL1(OrderBookL1 {
+                last_update_ts: time(),
+                best_bid: Level::new(99.5, 2.0),
+                best_ask: Level::new(100.5, 1.0),
+            })),
+            market_event(DataKind::Bar(Bar {
+                close_time: time(),
+                open: 99.0,
+                high: 101.0,
+                low: 98.5,
+                close: 100.0,
+                volume: 25.0,
+                trade_count: 42,
+            })),
+            market_event(DataKind::MarkPrice(MarkPrice {
+                mark_price: 100.0,
+                index_price: 99.75,
+                funding_rate: 0.0001,
+                next_funding_time: time(),
+            })),
+            market_event(DataKind::OpenInterest(OpenInterest {
+                contracts: 1500.0,
+                notional: None,
+            })),
+            Event::Signal(Signal {
+                datetime: time(),
+                exchange: Exchange::from(ExchangeId::Okx),
+                instrument: instrument(),
+                signals: HashMap::from([(Decision::Long, SignalStrength(1.0)), (Decision::CloseShort, SignalStrength(0.5))]),
+                market_meta: market_meta(),
+            }),
+            Event::SignalForceExit(SignalForceExit {
+                datetime: time(),
+                exchange: Exchange::from(ExchangeId::Okx),
+                instrument: instrument(),
+            }),
+            Event::OrderNew(OrderEvent {
+                timestamp: time(),
+                exchange: Exchange::from(ExchangeId::Okx),
+                instrument: instrument(),
+                market_meta: market_meta(),
+                decision: Decision::Short,
+                quantity: -2.0,
+                order_type: OrderType::Market,
+            }),
+            Event::OrderUpdate,
+            Event::Fill(FillEvent {
+                timestamp: time(),
+                exchange: Exchange::from(ExchangeId::Okx),
+                instrument: instrument(),
+                market_meta: market_meta(),
+                decision: Decision::Short,
+                quantity: -2.0,
+                fill_value_gross: 200.0,
+                fees: fees(),
+            }),
+            Event::PositionNew(position()),
+            Event::PositionUpdate(PositionUpdate {
+                position_id: "engine_okx_btc_usdt_position".to_string(),
+                update_timestamp: time(),
+                current_symbol_price: 99.0,
+                current_value_gross: 198.0,
+                unrealised_profit_loss: 1.25,
+            }),
+            Event::PositionExit(PositionExit {
+                position_id: "engine_okx_btc_usdt_position".to_string(),
+                exit_time: time(),
+                exit_balance: Balance::new(time(), 1001.0, 1001.0),
+                exit_fees: fees(),
+                exit_fees_total: 0.75,
+                exit_avg_price_gross: 99.0,
+                exit_value_gross: 198.0,
+                realised_profit_loss: 0.5,
+            }),
+            Event::Balance(Balance::new(time(), 1000.0, 800.0)),
+        ];
+
+        for (index, event) in events.into_iter().enumerate() {
+            let json = serde_json::to_string(&EventRecord::new(&event)).unwrap();
+            let actual = serde_json::from_str::<EventRecord>(&json).unwrap();
+            assert_eq!(actual, EventRecord::new(event), "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_event_record_schema() {
+        let balance = EventRecord::new(Event::Balance(Balance::new(time(), 1000.0, 800.0)));
+        assert_eq!(
+            serde_json::to_string(&balance).unwrap(),
+            r#"{"version":1,"type":"balance","data":{"timestamp":"2023-11-14T22:13:20Z","total":1000.0,"available":800.0}}"#
+        );
+
+        struct TestCase {
+            input: &'static str,
+            expected: Result<EventRecord, ()>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: unit variant
+                input: r#"{"version":1,"type":"order_update"}"#,
+                expected: Ok(EventRecord::new(Event::OrderUpdate)),
+            },
+            TestCase {
+                // TC1: newer schema version
+                input: r#"{"version":2,"type":"order_update"}"#,
+                expected: Err(()),
+            },
+            TestCase {
+                // TC2: missing schema version
+                input: r#"{"type":"order_update"}"#,
+                expected: Err(()),
+            },
+            TestCase {
+                // TC3: unknown event type
+                input: r#"{"version":1,"type":"liquidation","data":{}}"#,
+                expected: Err(()),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = serde_json::from_str::<EventRecord>(test.input).map_err(|_| ());
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+}
@@ -1,9 +1,10 @@
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use wednesday_model::{identifiers::Exchange, instruments::Instrument};
 
 use super::{decision::Decision, execution_error::ExecutionError, fee::Fees, market_meta::MarketMeta};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct FillEvent {
     pub timestamp: DateTime<Utc>,
     pub exchange: Exchange,
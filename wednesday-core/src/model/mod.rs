@@ -8,6 +8,7 @@ pub mod execution_error;
 pub mod fee;
 pub mod fill_event;
 pub mod market_meta;
+pub mod optimise_error;
 pub mod order_event;
 pub mod portfolio_error;
 pub mod position;
@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+use super::{engine_error::EngineError, portfolio_error::PortfolioError, repository_error::RepositoryError};
+
+/// All errors generated in the wednesday::optimise module.
+#[derive(Error, Debug)]
+pub enum OptimiseError {
+    #[error("Failed to build struct due to missing attributes: {0}")]
+    BuilderIncomplete(&'static str),
+
+    #[error("Parameter {0} has no values to search")]
+    EmptyParam(String),
+
+    #[error("Invalid parameter {name}: {reason}")]
+    InvalidParam { name: String, reason: String },
+
+    #[error("Invalid walk-forward: {0}")]
+    InvalidWalkForward(String),
+
+    #[error("Failed to build backtest portfolio: {0}")]
+    Portfolio(#[from] PortfolioError),
+
+    #[error("Failed to build backtest trader: {0}")]
+    Engine(#[from] EngineError),
+
+    #[error("Failed to collect backtest results: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Backtest thread panicked")]
+    BacktestPanicked,
+}
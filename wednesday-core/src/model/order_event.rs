@@ -1,9 +1,10 @@
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use wednesday_model::{enums::OrderType, identifiers::Exchange, instruments::Instrument};
 
 use super::{decision::Decision, market_meta::MarketMeta, portfolio_error::PortfolioError};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct OrderEvent {
     pub timestamp: DateTime<Utc>,
     pub exchange: Exchange,
@@ -1,4 +1,4 @@
-use wednesday_model::{identifiers::Exchange, instruments::Instrument};
+use wednesday_model::{identifiers::Exchange, instruments::Instrument, perpetual::MarkPrice};
 
 use crate::model::{
     fee::{FeeAmount, Fees},
@@ -28,6 +28,8 @@ pub struct PositionBuilder {
     pub current_value_gross: Option<f64>,
     pub unrealised_profit_loss: Option<f64>,
     pub realised_profit_loss: Option<f64>,
+    pub funding_profit_loss: Option<f64>,
+    pub last_mark_price: Option<MarkPrice>,
 }
 
 impl PositionBuilder {
@@ -155,6 +157,20 @@ impl PositionBuilder {
         }
     }
 
+    pub fn funding_profit_loss(self, value: f64) -> Self {
+        Self {
+            funding_profit_loss: Some(value),
+            ..self
+        }
+    }
+
+    pub fn last_mark_price(self, value: MarkPrice) -> Self {
+        Self {
+            last_mark_price: Some(value),
+            ..self
+        }
+    }
+
     pub fn build(self) -> Result<Position, PortfolioError> {
         Ok(Position {
             position_id: self.position_id.ok_or(PortfolioError::BuilderIncomplete("position_id"))?,
@@ -189,6 +205,8 @@ impl PositionBuilder {
             realised_profit_loss: self
                 .realised_profit_loss
                 .ok_or(PortfolioError::BuilderIncomplete("realised_profit_loss"))?,
+            funding_profit_loss: self.funding_profit_loss.unwrap_or_default(),
+            last_mark_price: self.last_mark_price,
         })
     }
 }
@@ -47,6 +47,8 @@ impl PositionEnterer for Position {
             current_value_gross: fill.fill_value_gross,
             unrealised_profit_loss,
             realised_profit_loss: 0.0,
+            funding_profit_loss: 0.0,
+            last_mark_price: None,
         })
     }
 }
@@ -22,7 +22,7 @@ pub struct PositionExit {
     /// Unique identifier for a [`Position`], generated from an exchange, symbol, and enter_time.
     pub position_id: String,
 
-    /// [`FillEvent`] timestamp that triggered the exiting of this [`Position`].
+    /// [`FillEvent`] market timestamp that triggered the exiting of this [`Position`].
     pub exit_time: DateTime<Utc>,
 
     /// Portfolio [`Balance`] calculated at the point of exiting a [`Position`].
@@ -81,7 +81,7 @@ impl PositionExiter for Position {
 
         // Metadata
         balance.total += self.realised_profit_loss;
-        self.meta.update_timestamp = fill.timestamp;
+        self.meta.update_timestamp = fill.market_meta.timestamp;
         self.meta.exit_balance = Some(balance);
 
         PositionExit::try_from(self)
@@ -8,7 +8,11 @@ use std::fmt::{self, Display};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use wednesday_model::{identifiers::Exchange, instruments::Instrument};
+use wednesday_model::{
+    identifiers::Exchange,
+    instruments::{Instrument, InstrumentKind},
+    perpetual::MarkPrice,
+};
 
 use self::builder::PositionBuilder;
 
@@ -22,7 +26,7 @@ use super::{
 
 pub type PositionId = String;
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct PositionMeta {
     pub enter_timestamp: DateTime<Utc>,
     pub update_timestamp: DateTime<Utc>,
@@ -51,7 +55,7 @@ impl Display for PositionSide {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct Position {
     pub position_id: PositionId,
     pub meta: PositionMeta,
@@ -94,6 +98,14 @@ pub struct Position {
 
     /// Realised P&L after the [`Position`] has closed.
     pub realised_profit_loss: f64,
+
+    /// Cumulative perpetual funding settled whilst the [`Position`] is open. Positive when
+    /// funding is received, negative when funding is paid.
+    pub funding_profit_loss: f64,
+
+    /// Last [`MarkPrice`] observed whilst the [`Position`] is open, used to settle the next
+    /// perpetual funding payment.
+    pub last_mark_price: Option<MarkPrice>,
 }
 
 pub fn determine_position_id(exgine_id: Uuid, exchange: &Exchange, instrument: &Instrument) -> PositionId {
@@ -130,13 +142,32 @@ impl Position {
         }
     }
 
+    /// Settles the perpetual funding payment that fell due since the last observed [`MarkPrice`],
+    /// and tracks the input [`MarkPrice`] for the next settlement. Returns the settled payment.
+    ///
+    /// Note: at most one funding payment is settled between two consecutive [`MarkPrice`]s.
+    pub fn accrue_funding(&mut self, timestamp: DateTime<Utc>, mark: &MarkPrice) -> f64 {
+        if self.instrument.kind != InstrumentKind::CryptoPerpetual {
+            return 0.0;
+        }
+
+        let payment = match self.last_mark_price {
+            Some(last_mark) if timestamp >= last_mark.next_funding_time => last_mark.funding_payment(self.quantity),
+            _ => 0.0,
+        };
+
+        self.funding_profit_loss += payment;
+        self.last_mark_price = Some(*mark);
+        payment
+    }
+
     /// Calculate the approximate [`Position::unrealised_profit_loss`] of a [`Position`].
     pub fn calculate_unrealised_profit_loss(&self) -> f64 {
         let approx_total_fees = self.enter_fees_total * 2.0;
 
         match self.side {
-            PositionSide::Buy => self.current_value_gross - self.enter_value_gross - approx_total_fees,
-            PositionSide::Sell => self.enter_value_gross - self.current_value_gross - approx_total_fees,
+            PositionSide::Buy => self.current_value_gross - self.enter_value_gross - approx_total_fees + self.funding_profit_loss,
+            PositionSide::Sell => self.enter_value_gross - self.current_value_gross - approx_total_fees + self.funding_profit_loss,
         }
     }
 
@@ -145,8 +176,8 @@ impl Position {
         let total_fees = self.enter_fees_total + self.exit_fees_total;
 
         match self.side {
-            PositionSide::Buy => self.exit_value_gross - self.enter_value_gross - total_fees,
-            PositionSide::Sell => self.enter_value_gross - self.exit_value_gross - total_fees,
+            PositionSide::Buy => self.exit_value_gross - self.enter_value_gross - total_fees + self.funding_profit_loss,
+            PositionSide::Sell => self.enter_value_gross - self.exit_value_gross - total_fees + self.funding_profit_loss,
         }
     }
 
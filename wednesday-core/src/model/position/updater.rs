@@ -38,8 +38,13 @@ impl PositionUpdater for Position {
         // Determine close from MarketEvent
         let close = match &market.kind {
             DataKind::PublicTrade(trade) => trade.price,
+            DataKind::AggregatedTrade(trade) => trade.price,
             DataKind::OrderBookL1(book_l1) => book_l1.volume_weighed_mid_price(),
             DataKind::Bar(bar) => bar.close,
+            DataKind::MarkPrice(mark) => {
+                self.accrue_funding(market.exchange_ts, mark);
+                mark.mark_price
+            },
             _ => return None,
         };
 
@@ -57,3 +62,141 @@ impl PositionUpdater for Position {
         Some(PositionUpdate::from(self))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone};
+    use wednesday_model::{
+        identifiers::{Exchange, ExchangeId},
+        instruments::{Instrument, InstrumentKind},
+        perpetual::MarkPrice,
+    };
+
+    use crate::model::{fee::Fees, position::PositionMeta};
+
+    use super::*;
+    use crate::model::position::PositionSide;
+
+    fn perpetual_position(quantity: f64) -> Position {
+        let enter_timestamp = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        Position::builder()
+            .position_id("position".to_string())
+            .exchange(Exchange::from(ExchangeId::BinanceFuturesUsd))
+            .instrument(Instrument::from(("btc", "usdt", InstrumentKind::CryptoPerpetual)))
+            .meta(PositionMeta {
+                enter_timestamp,
+                update_timestamp: enter_timestamp,
+                exit_balance: None,
+            })
+            .side(if quantity.is_sign_positive() { PositionSide::Buy } else { PositionSide::Sell })
+            .quantity(quantity)
+            .enter_fees(Fees::default())
+            .enter_fees_total(0.0)
+            .enter_avg_price_gross(100.0)
+            .enter_value_gross(100.0 * quantity.abs())
+            .exit_fees(Fees::default())
+            .exit_fees_total(0.0)
+            .exit_avg_price_gross(0.0)
+            .exit_value_gross(0.0)
+            .current_symbol_price(100.0)
+            .current_value_gross(100.0 * quantity.abs())
+            .unrealised_profit_loss(0.0)
+            .realised_profit_loss(0.0)
+            .build()
+            .unwrap()
+    }
+
+    fn mark_price_event(exchange_ts: DateTime<Utc>, funding_rate: f64, next_funding_time: DateTime<Utc>) -> MarketEvent<DataKind> {
+        MarketEvent {
+            exchange_ts,
+            local_ts: exchange_ts,
+            exchange: Exchange::from(ExchangeId::BinanceFuturesUsd),
+            instrument: Instrument::from(("btc", "usdt", InstrumentKind::CryptoPerpetual)),
+            kind: DataKind::MarkPrice(MarkPrice {
+                mark_price: 100.0,
+                index_price: 100.0,
+                funding_rate,
+                next_funding_time,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_position_accrues_funding_from_mark_price() {
+        struct TestCase {
+            quantity: f64,
+            funding_rate: f64,
+            expected_funding_profit_loss: f64,
+        }
+
+        let tests = vec![
+            // TC0: long pays positive funding at each settlement
+            TestCase {
+                quantity: 1.0,
+                funding_rate: 0.001,
+                expected_funding_profit_loss: -0.2,
+            },
+            // TC1: short receives positive funding at each settlement
+            TestCase {
+                quantity: -1.0,
+                funding_rate: 0.001,
+                expected_funding_profit_loss: 0.2,
+            },
+            // TC2: long receives negative funding at each settlement
+            TestCase {
+                quantity: 1.0,
+                funding_rate: -0.001,
+                expected_funding_profit_loss: 0.2,
+            },
+        ];
+
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 1, 0, 0).unwrap();
+        let first_funding = Utc.with_ymd_and_hms(2024, 1, 1, 8, 0, 0).unwrap();
+        let second_funding = first_funding + Duration::hours(8);
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let mut position = perpetual_position(test.quantity);
+
+            let events = [
+                // Observed before the first settlement, nothing is settled
+                mark_price_event(start, test.funding_rate, first_funding),
+                mark_price_event(start + Duration::hours(1), test.funding_rate, first_funding),
+                // First settlement has passed
+                mark_price_event(first_funding + Duration::seconds(1), test.funding_rate, second_funding),
+                // Second settlement has passed, mark price is observed twice afterwards
+                mark_price_event(second_funding + Duration::seconds(1), test.funding_rate, second_funding + Duration::hours(8)),
+                mark_price_event(second_funding + Duration::seconds(2), test.funding_rate, second_funding + Duration::hours(8)),
+            ];
+
+            for event in events.iter() {
+                position.update(event);
+            }
+
+            assert!(
+                (position.funding_profit_loss - test.expected_funding_profit_loss).abs() < 1e-9,
+                "TC{} failed",
+                index
+            );
+            assert!(
+                (position.unrealised_profit_loss - test.expected_funding_profit_loss).abs() < 1e-9,
+                "TC{} failed",
+                index
+            );
+        }
+    }
+
+    #[test]
+    fn test_spot_position_does_not_accrue_funding() {
+        let mut position = perpetual_position(1.0);
+        position.instrument = Instrument::from(("btc", "usdt", InstrumentKind::CryptoSpot));
+
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 1, 0, 0).unwrap();
+        let funding = Utc.with_ymd_and_hms(2024, 1, 1, 8, 0, 0).unwrap();
+
+        position.update(&mark_price_event(start, 0.001, funding));
+        position.update(&mark_price_event(funding + Duration::seconds(1), 0.001, funding + Duration::hours(8)));
+
+        assert_eq!(position.funding_profit_loss, 0.0);
+        assert_eq!(position.last_mark_price, None);
+    }
+}
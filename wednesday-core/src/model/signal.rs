@@ -9,7 +9,7 @@ use wednesday_model::{
 
 use super::{decision::Decision, market_meta::MarketMeta};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct Signal {
     pub datetime: DateTime<Utc>,
     pub exchange: Exchange,
@@ -0,0 +1,101 @@
+use std::marker::PhantomData;
+
+use crate::{model::optimise_error::OptimiseError, strategy::SignalGenerator};
+
+use super::{
+    param::{ParamSet, ParamSpace, Search},
+    BacktestConfig, Candidate, MarketData, Metric, Optimiser,
+};
+
+pub struct OptimiserBuilder<Strategy, CandidateFn>
+where
+    Strategy: SignalGenerator + Clone + Send,
+    CandidateFn: Fn(&ParamSet) -> Result<Candidate<Strategy>, OptimiseError> + Sync,
+{
+    data: Option<MarketData>,
+    config: Option<BacktestConfig>,
+    space: Option<ParamSpace>,
+    search: Option<Search>,
+    metric: Option<Metric>,
+    threads: Option<usize>,
+    candidate: Option<CandidateFn>,
+    _strategy_marker: PhantomData<fn() -> Strategy>,
+}
+
+impl<Strategy, CandidateFn> Default for OptimiserBuilder<Strategy, CandidateFn>
+where
+    Strategy: SignalGenerator + Clone + Send,
+    CandidateFn: Fn(&ParamSet) -> Result<Candidate<Strategy>, OptimiseError> + Sync,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Strategy, CandidateFn> OptimiserBuilder<Strategy, CandidateFn>
+where
+    Strategy: SignalGenerator + Clone + Send,
+    CandidateFn: Fn(&ParamSet) -> Result<Candidate<Strategy>, OptimiseError> + Sync,
+{
+    pub fn new() -> Self {
+        Self {
+            data: None,
+            config: None,
+            space: None,
+            search: None,
+            metric: None,
+            threads: None,
+            candidate: None,
+            _strategy_marker: PhantomData,
+        }
+    }
+
+    pub fn data(self, value: MarketData) -> Self {
+        Self { data: Some(value), ..self }
+    }
+
+    pub fn config(self, value: BacktestConfig) -> Self {
+        Self { config: Some(value), ..self }
+    }
+
+    pub fn space(self, value: ParamSpace) -> Self {
+        Self { space: Some(value), ..self }
+    }
+
+    /// Defaults to a [`Search::Grid`] of the [`ParamSpace`].
+    pub fn search(self, value: Search) -> Self {
+        Self { search: Some(value), ..self }
+    }
+
+    pub fn metric(self, value: Metric) -> Self {
+        Self { metric: Some(value), ..self }
+    }
+
+    /// Number of backtests run in parallel, defaulting to the available parallelism.
+    pub fn threads(self, value: usize) -> Self {
+        Self { threads: Some(value), ..self }
+    }
+
+    /// Function building the [`Candidate`] of each backtest from a [`ParamSet`].
+    pub fn candidate(self, value: CandidateFn) -> Self {
+        Self {
+            candidate: Some(value),
+            ..self
+        }
+    }
+
+    pub fn build(self) -> Result<Optimiser<Strategy, CandidateFn>, OptimiseError> {
+        Ok(Optimiser {
+            data: self.data.ok_or(OptimiseError::BuilderIncomplete("data"))?,
+            config: self.config.ok_or(OptimiseError::BuilderIncomplete("config"))?,
+            space: self.space.ok_or(OptimiseError::BuilderIncomplete("space"))?,
+            search: self.search.unwrap_or(Search::Grid),
+            metric: self.metric.ok_or(OptimiseError::BuilderIncomplete("metric"))?,
+            threads: self
+                .threads
+                .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |threads| threads.get())),
+            candidate: self.candidate.ok_or(OptimiseError::BuilderIncomplete("candidate"))?,
+            _strategy_marker: PhantomData,
+        })
+    }
+}
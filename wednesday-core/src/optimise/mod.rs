@@ -0,0 +1,547 @@
+pub mod builder;
+pub mod param;
+pub mod walk_forward;
+
+use std::{
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+use parking_lot::Mutex;
+use prettytable::{Cell, Row, Table};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+use wednesday_model::{
+    events::{DataKind, MarketEvent},
+    identifiers::Market,
+};
+
+use crate::{
+    data::historical::HistoricalMarketFeed,
+    engine::trader::Trader,
+    execution::simulated::{SimExecConfig, SimulatedExecution},
+    model::{
+        event::{Event, MessageTransmitter},
+        fee::Fees,
+        optimise_error::OptimiseError,
+        signal::Signal,
+    },
+    oms::{allocator::DefaultAllocator, evaluator::DefaultRisk},
+    portfolio::{
+        repository::{in_memory::InMemoryRepository, PositionHandler},
+        MetaPortfolio,
+    },
+    statistic::{
+        metric::ratio::Ratio,
+        summary::{
+            combine,
+            trading::{Config as StatisticConfig, TradingSummary},
+            Initialiser, PositionSummariser, TableBuilder,
+        },
+    },
+    strategy::SignalGenerator,
+};
+
+use self::{
+    builder::OptimiserBuilder,
+    param::{ParamSet, ParamSpace, Search},
+};
+
+/// Historical [`MarketEvent`]s of each [`Market`] replayed by every backtest of an
+/// [`Optimiser`].
+pub type MarketData = Vec<(Market, Vec<MarketEvent<DataKind>>)>;
+
+/// [`TradingSummary`] metric used to rank backtests, where a higher score is always better.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Metric {
+    SharpeRatio,
+    SortinoRatio,
+    CalmarRatio,
+    /// Sum of the returns of every exited position.
+    TotalReturn,
+    /// Mean return of the exited positions.
+    MeanReturn,
+    /// Max drawdown, scored negatively so the smallest drawdown ranks first.
+    MaxDrawdown,
+}
+
+impl Metric {
+    pub fn score(&self, summary: &TradingSummary) -> f64 {
+        match self {
+            Metric::SharpeRatio => summary.tear_sheet.sharpe_ratio.daily(),
+            Metric::SortinoRatio => summary.tear_sheet.sortino_ratio.daily(),
+            Metric::CalmarRatio => summary.tear_sheet.calmar_ratio.daily(),
+            Metric::TotalReturn => summary.pnl_returns.total.sum,
+            Metric::MeanReturn => summary.pnl_returns.total.mean,
+            Metric::MaxDrawdown => -summary.drawdown.max_drawdown.drawdown.drawdown.abs(),
+        }
+    }
+
+    fn title(&self) -> &'static str {
+        match self {
+            Metric::SharpeRatio => "Score (Sharpe Ratio)",
+            Metric::SortinoRatio => "Score (Sortino Ratio)",
+            Metric::CalmarRatio => "Score (Calmar Ratio)",
+            Metric::TotalReturn => "Score (Total Return)",
+            Metric::MeanReturn => "Score (Mean Return)",
+            Metric::MaxDrawdown => "Score (Max Drawdown)",
+        }
+    }
+}
+
+/// Portfolio & fee settings shared by every backtest of an [`Optimiser`].
+#[derive(Copy, Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct BacktestConfig {
+    pub starting_cash: f64,
+    pub fees: Fees,
+    pub trading_days_per_year: usize,
+    pub risk_free_return: f64,
+}
+
+impl BacktestConfig {
+    fn statistic_config(&self) -> StatisticConfig {
+        StatisticConfig {
+            starting_equity: self.starting_cash,
+            trading_days_per_year: self.trading_days_per_year,
+            risk_free_return: self.risk_free_return,
+        }
+    }
+}
+
+/// Strategy & portfolio allocator built from a [`ParamSet`] by the [`Optimiser`] candidate
+/// function once per backtest, where each market trades its own clone of the strategy.
+pub struct Candidate<Strategy> {
+    pub strategy: Strategy,
+    pub allocator: DefaultAllocator,
+}
+
+/// Result of backtesting a [`ParamSet`].
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct Trial {
+    pub params: ParamSet,
+    /// [`TradingSummary`] of the exited positions of every market.
+    pub summary: TradingSummary,
+    /// [`Metric`] score of the summary, used to rank the [`Trial`].
+    pub score: f64,
+}
+
+impl TableBuilder for Trial {
+    fn titles(&self) -> Row {
+        let mut titles = vec![Cell::new("Parameters"), Cell::new("Score")];
+        titles.extend(self.summary.titles().iter().cloned());
+        Row::new(titles)
+    }
+
+    fn row(&self) -> Row {
+        let mut cells = vec![Cell::new(&self.params.to_string()), Cell::new(&format!("{:.3}", self.score))];
+        cells.extend(self.summary.row().iter().cloned());
+        Row::new(cells)
+    }
+}
+
+/// [`Trial`]s of every [`ParamSet`] searched, ranked by [`Metric`] score from best to worst.
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct Ranking {
+    pub metric: Metric,
+    pub trials: Vec<Trial>,
+}
+
+impl Ranking {
+    pub fn new(metric: Metric, mut trials: Vec<Trial>) -> Self {
+        // NaN scores (eg/ a ratio without any exited positions) rank last
+        let rank_score = |trial: &Trial| if trial.score.is_nan() { f64::NEG_INFINITY } else { trial.score };
+        trials.sort_by(|a, b| rank_score(b).total_cmp(&rank_score(a)));
+        Self { metric, trials }
+    }
+
+    pub fn best(&self) -> Option<&Trial> {
+        self.trials.first()
+    }
+
+    /// Table of the ranked [`ParamSet`]s & their statistics.
+    pub fn table(&self) -> Table {
+        let mut table = combine(self.trials.iter().enumerate().map(|(index, trial)| ((index + 1).to_string(), trial.clone())));
+        if let Some(trial) = self.best() {
+            table.set_titles(self.titles(trial));
+        }
+        table
+    }
+
+    fn titles(&self, trial: &Trial) -> Row {
+        let mut titles = trial.titles();
+        titles.insert_cell(0, Cell::new("Rank"));
+        titles.set_cell(Cell::new(self.metric.title()), 2).expect("Trial titles contain a score");
+        titles
+    }
+}
+
+/// Runs many backtests of a strategy in parallel across a [`ParamSpace`], each replaying the
+/// [`MarketData`] with a [`HistoricalMarketFeed`] & [`SimulatedExecution`], and ranks them by a
+/// [`Metric`].
+pub struct Optimiser<Strategy, CandidateFn>
+where
+    Strategy: SignalGenerator + Clone + Send,
+    CandidateFn: Fn(&ParamSet) -> Result<Candidate<Strategy>, OptimiseError> + Sync,
+{
+    pub(crate) data: MarketData,
+    pub(crate) config: BacktestConfig,
+    pub(crate) space: ParamSpace,
+    pub(crate) search: Search,
+    pub(crate) metric: Metric,
+    pub(crate) threads: usize,
+    pub(crate) candidate: CandidateFn,
+    pub(crate) _strategy_marker: PhantomData<fn() -> Strategy>,
+}
+
+impl<Strategy, CandidateFn> Optimiser<Strategy, CandidateFn>
+where
+    Strategy: SignalGenerator + Clone + Send,
+    CandidateFn: Fn(&ParamSet) -> Result<Candidate<Strategy>, OptimiseError> + Sync,
+{
+    pub fn builder() -> OptimiserBuilder<Strategy, CandidateFn> {
+        OptimiserBuilder::new()
+    }
+
+    /// Backtest every [`ParamSet`] of the [`Search`] over all of the [`MarketData`].
+    pub fn optimise(&self) -> Result<Ranking, OptimiseError> {
+        self.rank(&self.data, self.search.param_sets(&self.space)?)
+    }
+
+    /// Backtest the [`ParamSet`]s in parallel over the `data`, & rank the [`Trial`]s.
+    pub(crate) fn rank(&self, data: &MarketData, param_sets: Vec<ParamSet>) -> Result<Ranking, OptimiseError> {
+        let next = AtomicUsize::new(0);
+        let trials: Result<Vec<_>, OptimiseError> = thread::scope(|scope| {
+            let workers = (0..self.threads.min(param_sets.len()).max(1))
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut trials = Vec::new();
+                        loop {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(params) = param_sets.get(index) else {
+                                break Ok::<_, OptimiseError>(trials);
+                            };
+                            trials.push((index, self.backtest(data, params.clone())?));
+                        }
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            workers.into_iter().try_fold(Vec::with_capacity(param_sets.len()), |mut trials, worker| {
+                trials.extend(worker.join().map_err(|_| OptimiseError::BacktestPanicked)??);
+                Ok(trials)
+            })
+        });
+
+        // Restore the search order so equal scores are ranked deterministically
+        let mut trials = trials?;
+        trials.sort_by_key(|(index, _)| *index);
+        Ok(Ranking::new(self.metric, trials.into_iter().map(|(_, trial)| trial).collect()))
+    }
+
+    /// Backtest a single [`ParamSet`] over the `data` on the current thread, replaying the
+    /// [`MarketEvent`]s of every market in time order so the markets trade against the same cash
+    /// as they would live. Positions still open at the end of the `data` are exited at the last
+    /// price of their market, so strategies that never exit a position are still scored.
+    pub fn backtest(&self, data: &MarketData, params: ParamSet) -> Result<Trial, OptimiseError> {
+        let engine_id = Uuid::new_v4();
+        let statistic_config = self.config.statistic_config();
+        let Candidate { strategy, allocator } = (self.candidate)(&params)?;
+        let markets = data.iter().map(|(market, _)| market.clone()).collect::<Vec<_>>();
+
+        let portfolio = Arc::new(Mutex::new(
+            MetaPortfolio::builder()
+                .engine_id(engine_id)
+                .markets(markets.clone())
+                .starting_cash(self.config.starting_cash)
+                .repository(InMemoryRepository::<TradingSummary>::new())
+                .allocation_manager(allocator)
+                .risk_manager(DefaultRisk {})
+                .statistic_config(statistic_config)
+                .build_and_init()?,
+        ));
+
+        if let Some(market) = markets.first() {
+            // Stable sort keeps the order of the events of a market with the same timestamp
+            let mut events = data.iter().flat_map(|(_, events)| events.iter().cloned()).collect::<Vec<_>>();
+            events.sort_by_key(|event| event.exchange_ts);
+
+            // Transmitter is kept alive until the Trader finishes, since dropping it terminates
+            // the Trader. A single Trader replays every market, so its market only identifies it
+            let (_command_tx, command_rx) = mpsc::channel(1);
+            Trader::<_, TradingSummary, _, _, _, _>::builder()
+                .engine_id(engine_id)
+                .market(market.clone())
+                .command_rx(command_rx)
+                .event_tx(DiscardTx)
+                .portfolio(Arc::clone(&portfolio))
+                .data(HistoricalMarketFeed::new(events))
+                .strategy(MarketStrategies(markets.iter().map(|market| (market.clone(), strategy.clone())).collect()))
+                .execution(SimulatedExecution::new(SimExecConfig {
+                    simulated_fees_pct: self.config.fees,
+                }))
+                .exit_on_finish(markets)
+                .build()?
+                .run();
+        }
+
+        let positions = portfolio.lock().get_exited_positions(engine_id)?;
+        let mut summary = TradingSummary::init(statistic_config);
+        summary.generate_summary(&positions);
+
+        Ok(Trial {
+            params,
+            summary,
+            score: self.metric.score(&summary),
+        })
+    }
+}
+
+/// Strategy of each [`Market`], generating the [`Signal`]s of the time ordered [`MarketEvent`]s
+/// of every market replayed by a single backtest [`Trader`].
+struct MarketStrategies<Strategy>(Vec<(Market, Strategy)>);
+
+impl<Strategy> SignalGenerator for MarketStrategies<Strategy>
+where
+    Strategy: SignalGenerator,
+{
+    fn generate_signal(&mut self, market: &MarketEvent<DataKind>) -> Option<Signal> {
+        self.0
+            .iter_mut()
+            .find(|(strategy_market, _)| strategy_market.exchange == market.exchange && strategy_market.instrument == market.instrument)
+            .and_then(|(_, strategy)| strategy.generate_signal(market))
+    }
+}
+
+// Backtest Events are not consumed, so are discarded rather than buffered in a channel.
+#[derive(Debug, Copy, Clone)]
+struct DiscardTx;
+
+impl MessageTransmitter<Event> for DiscardTx {
+    fn send(&mut self, _: Event) {}
+
+    fn send_many(&mut self, _: Vec<Event>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, Utc};
+    use wednesday_model::{bar::Bar, instruments::InstrumentKind};
+
+    use super::*;
+    use crate::strategy::sample::{RsiStrategy, StrategyConfig};
+
+    pub(crate) fn market_data(bars: i64) -> MarketData {
+        let market = Market::new("binance_spot", ("btc", "usdt", InstrumentKind::CryptoSpot));
+        let events = (0..bars)
+            .map(|index| {
+                let close = 100.0 + 10.0 * (index as f64 / 5.0).sin() + index as f64 * 0.05;
+                let close_time = DateTime::from_timestamp(1_700_000_000, 0).unwrap() + Duration::hours(index);
+                MarketEvent {
+                    exchange_ts: close_time,
+                    local_ts: close_time,
+                    exchange: market.exchange.clone(),
+                    instrument: market.instrument.clone(),
+                    kind: DataKind::Bar(Bar {
+                        close_time,
+                        open: close,
+                        high: close + 1.0,
+                        low: close - 1.0,
+                        close,
+                        volume: 10.0,
+                        trade_count: 5,
+                    }),
+                }
+            })
+            .collect();
+        vec![(market, events)]
+    }
+
+    pub(crate) fn rsi_candidate(params: &ParamSet) -> Result<Candidate<RsiStrategy>, OptimiseError> {
+        Ok(Candidate {
+            strategy: RsiStrategy::new(StrategyConfig {
+                rsi_period: params.usize("rsi_period")?,
+                oversold: params.f64("oversold")?,
+                overbought: 100.0 - params.f64("oversold")?,
+            }),
+            allocator: DefaultAllocator {
+                default_order_value: params.f64("order_value")?,
+            },
+        })
+    }
+
+    pub(crate) fn config() -> BacktestConfig {
+        BacktestConfig {
+            starting_cash: 10_000.0,
+            fees: Fees {
+                exchange: 0.001,
+                slippage: 0.0,
+            },
+            trading_days_per_year: 365,
+            risk_free_return: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_optimise() {
+        let space = ParamSpace::new()
+            .param("rsi_period", [5_usize, 9, 14])
+            .param("oversold", [30.0, 40.0])
+            .param("order_value", [100.0, 200.0]);
+
+        let optimise = |threads| {
+            Optimiser::builder()
+                .data(market_data(200))
+                .config(config())
+                .space(space.clone())
+                .metric(Metric::TotalReturn)
+                .threads(threads)
+                .candidate(rsi_candidate)
+                .build()
+                .unwrap()
+                .optimise()
+                .unwrap()
+        };
+
+        let ranking = optimise(4);
+        assert_eq!(ranking.trials.len(), space.grid_len());
+        // RsiStrategy never exits the position it enters, which is exited at the end of the data
+        assert!(ranking.trials.iter().all(|trial| trial.summary.pnl_returns.total.count == 1));
+        assert!(ranking.trials.iter().all(|trial| trial.score.is_finite() && trial.score != 0.0));
+        for (index, trials) in ranking.trials.windows(2).enumerate() {
+            assert!(trials[0].score >= trials[1].score, "trial {} ranked out of order", index);
+            assert_eq!(trials[0].score, Metric::TotalReturn.score(&trials[0].summary));
+        }
+
+        // Parallelism does not change the ranking
+        let sequential = optimise(1);
+        assert_eq!(
+            ranking.trials.iter().map(|trial| (&trial.params, trial.score)).collect::<Vec<_>>(),
+            sequential.trials.iter().map(|trial| (&trial.params, trial.score)).collect::<Vec<_>>()
+        );
+
+        // Table has a title row & a row for each trial
+        assert_eq!(ranking.table().len(), space.grid_len());
+    }
+
+    #[test]
+    fn test_backtest_replays_markets_in_time_order() {
+        /// Records the time of every event it is given.
+        #[derive(Clone)]
+        struct Recorder(Arc<Mutex<Vec<DateTime<Utc>>>>);
+
+        impl SignalGenerator for Recorder {
+            fn generate_signal(&mut self, market: &MarketEvent<DataKind>) -> Option<Signal> {
+                self.0.lock().push(market.exchange_ts);
+                None
+            }
+        }
+
+        // Second market trades half an hour after each bar of the first
+        let mut data = market_data(10);
+        let market = Market::new("binance_spot", ("eth", "usdt", InstrumentKind::CryptoSpot));
+        let events = data[0]
+            .1
+            .iter()
+            .map(|event| MarketEvent {
+                exchange_ts: event.exchange_ts + Duration::minutes(30),
+                instrument: market.instrument.clone(),
+                ..event.clone()
+            })
+            .collect();
+        data.push((market, events));
+
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        let candidates = AtomicUsize::new(0);
+        let optimiser = Optimiser::builder()
+            .data(data.clone())
+            .config(config())
+            .space(ParamSpace::new().param("order_value", [100.0]))
+            .metric(Metric::TotalReturn)
+            .candidate(|_: &ParamSet| {
+                candidates.fetch_add(1, Ordering::Relaxed);
+                Ok(Candidate {
+                    strategy: Recorder(Arc::clone(&recorded)),
+                    allocator: DefaultAllocator::default(),
+                })
+            })
+            .build()
+            .unwrap();
+
+        let params = ParamSet(std::collections::BTreeMap::from([("order_value".to_owned(), 100.0.into())]));
+        optimiser.backtest(&data, params).unwrap();
+
+        // Candidate is built once, & the events of both markets are interleaved in time order
+        assert_eq!(candidates.load(Ordering::Relaxed), 1);
+        let recorded = recorded.lock();
+        assert_eq!(recorded.len(), 20);
+        assert!(recorded.windows(2).all(|times| times[0] < times[1]));
+    }
+
+    #[test]
+    fn test_optimise_errors() {
+        struct TestCase {
+            space: ParamSpace,
+            search: Search,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: parameter missing from the space
+                space: ParamSpace::new().param("rsi_period", [14_usize]).param("oversold", [30.0]),
+                search: Search::Grid,
+            },
+            TestCase {
+                // TC1: parameter without values
+                space: ParamSpace::new()
+                    .param("rsi_period", Vec::<usize>::new())
+                    .param("oversold", [30.0])
+                    .param("order_value", [100.0]),
+                search: Search::Random { samples: 5, seed: 1 },
+            },
+            TestCase {
+                // TC2: parameter of the wrong type
+                space: ParamSpace::new()
+                    .param("rsi_period", [14.5])
+                    .param("oversold", [30.0])
+                    .param("order_value", [100.0]),
+                search: Search::Grid,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let optimiser = Optimiser::builder()
+                .data(market_data(50))
+                .config(config())
+                .space(test.space)
+                .search(test.search)
+                .metric(Metric::SharpeRatio)
+                .candidate(rsi_candidate)
+                .build()
+                .unwrap();
+            assert!(optimiser.optimise().is_err(), "TC{} failed", index);
+        }
+
+        let incomplete = Optimiser::builder().data(market_data(50)).config(config()).candidate(rsi_candidate).build();
+        assert!(matches!(incomplete, Err(OptimiseError::BuilderIncomplete("space"))));
+    }
+
+    #[test]
+    fn test_ranking_orders_nan_scores_last() {
+        let trial = |rsi_period: usize, score: f64| Trial {
+            params: ParamSet(std::collections::BTreeMap::from([("rsi_period".to_owned(), rsi_period.into())])),
+            summary: TradingSummary::init(config().statistic_config()),
+            score,
+        };
+
+        let ranking = Ranking::new(Metric::SharpeRatio, vec![trial(1, f64::NAN), trial(2, -1.0), trial(3, 2.0), trial(4, -1.0)]);
+
+        let ranked = ranking.trials.iter().map(|trial| trial.params.usize("rsi_period").unwrap()).collect::<Vec<_>>();
+        assert_eq!(ranked, vec![3, 2, 4, 1]);
+    }
+}
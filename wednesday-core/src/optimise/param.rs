@@ -0,0 +1,263 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::optimise_error::OptimiseError;
+
+/// Value of a strategy or allocator parameter searched by an [`Optimiser`](super::Optimiser).
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ParamValue {
+    Int(i64),
+    Float(f64),
+}
+
+impl From<i64> for ParamValue {
+    fn from(value: i64) -> Self {
+        ParamValue::Int(value)
+    }
+}
+
+impl From<usize> for ParamValue {
+    fn from(value: usize) -> Self {
+        ParamValue::Int(value as i64)
+    }
+}
+
+impl From<f64> for ParamValue {
+    fn from(value: f64) -> Self {
+        ParamValue::Float(value)
+    }
+}
+
+impl Display for ParamValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParamValue::Int(value) => write!(f, "{value}"),
+            ParamValue::Float(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// Named parameters of a single backtest, eg/ { "rsi_period": 14, "oversold": 30.0 }.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct ParamSet(pub BTreeMap<String, ParamValue>);
+
+impl ParamSet {
+    pub fn get(&self, name: &str) -> Result<ParamValue, OptimiseError> {
+        self.0.get(name).copied().ok_or_else(|| OptimiseError::InvalidParam {
+            name: name.to_owned(),
+            reason: "missing from the parameter set".to_owned(),
+        })
+    }
+
+    /// Integer parameter as a usize, eg/ an indicator period.
+    pub fn usize(&self, name: &str) -> Result<usize, OptimiseError> {
+        match self.get(name)? {
+            ParamValue::Int(value) if value >= 0 => Ok(value as usize),
+            value => Err(OptimiseError::InvalidParam {
+                name: name.to_owned(),
+                reason: format!("expected a non-negative integer, found {value}"),
+            }),
+        }
+    }
+
+    /// Numeric parameter as an f64, where integers are converted.
+    pub fn f64(&self, name: &str) -> Result<f64, OptimiseError> {
+        match self.get(name)? {
+            ParamValue::Int(value) => Ok(value as f64),
+            ParamValue::Float(value) => Ok(value),
+        }
+    }
+}
+
+impl Display for ParamSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, (name, value)) in self.0.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{name}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Candidate values of each named parameter searched by an [`Optimiser`](super::Optimiser).
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct ParamSpace(pub BTreeMap<String, Vec<ParamValue>>);
+
+impl ParamSpace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a parameter & it's candidate values, replacing any previous values of the parameter.
+    pub fn param<Value, Values>(mut self, name: &str, values: Values) -> Self
+    where
+        Value: Into<ParamValue>,
+        Values: IntoIterator<Item = Value>,
+    {
+        self.0.insert(name.to_owned(), values.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Number of [`ParamSet`]s in the full grid of the [`ParamSpace`].
+    pub fn grid_len(&self) -> usize {
+        self.0.values().map(Vec::len).product()
+    }
+
+    /// Every combination of the parameter values, in lexicographic order of the parameter names.
+    pub fn grid(&self) -> Result<Vec<ParamSet>, OptimiseError> {
+        self.validate()?;
+        Ok((0..self.grid_len()).map(|index| self.param_set(index)).collect())
+    }
+
+    /// Up to `samples` distinct combinations of the parameter values, drawn uniformly from the
+    /// grid with a deterministic generator seeded by `seed`.
+    pub fn random(&self, samples: usize, seed: u64) -> Result<Vec<ParamSet>, OptimiseError> {
+        self.validate()?;
+
+        let grid_len = self.grid_len();
+        if samples >= grid_len {
+            return self.grid();
+        }
+
+        // Partial Fisher-Yates shuffle of the grid indices, tracking only the swapped indices
+        let mut rng = SplitMix64(seed);
+        let mut swapped = BTreeMap::new();
+        let mut indices = Vec::with_capacity(samples);
+        for position in 0..samples {
+            let other = position + (rng.next() % (grid_len - position) as u64) as usize;
+            let index = *swapped.get(&other).unwrap_or(&other);
+            swapped.insert(other, *swapped.get(&position).unwrap_or(&position));
+            indices.push(index);
+        }
+
+        Ok(indices.into_iter().map(|index| self.param_set(index)).collect())
+    }
+
+    fn validate(&self) -> Result<(), OptimiseError> {
+        match self.0.iter().find(|(_, values)| values.is_empty()) {
+            Some((name, _)) => Err(OptimiseError::EmptyParam(name.clone())),
+            None => Ok(()),
+        }
+    }
+
+    // Decode the grid index as a mixed radix number, with the last parameter varying fastest.
+    fn param_set(&self, mut index: usize) -> ParamSet {
+        let mut params = BTreeMap::new();
+        for (name, values) in self.0.iter().rev() {
+            params.insert(name.clone(), values[index % values.len()]);
+            index /= values.len();
+        }
+        ParamSet(params)
+    }
+}
+
+/// How the [`ParamSet`]s of a [`ParamSpace`] are searched.
+#[derive(Copy, Clone, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Search {
+    /// Every combination of the parameter values.
+    Grid,
+    /// Up to `samples` distinct combinations, reproducible for the same `seed`.
+    Random { samples: usize, seed: u64 },
+}
+
+impl Search {
+    pub fn param_sets(&self, space: &ParamSpace) -> Result<Vec<ParamSet>, OptimiseError> {
+        match *self {
+            Search::Grid => space.grid(),
+            Search::Random { samples, seed } => space.random(samples, seed),
+        }
+    }
+}
+
+// SplitMix64 pseudo random number generator, sufficient for sampling a parameter grid without an
+// extra dependency.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn param_set(rsi_period: i64, oversold: f64) -> ParamSet {
+        ParamSet(BTreeMap::from([
+            ("oversold".to_owned(), ParamValue::Float(oversold)),
+            ("rsi_period".to_owned(), ParamValue::Int(rsi_period)),
+        ]))
+    }
+
+    #[test]
+    fn test_param_space_grid() {
+        struct TestCase {
+            space: ParamSpace,
+            expected: Result<Vec<ParamSet>, ()>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: every combination, with the last parameter varying fastest
+                space: ParamSpace::new().param("rsi_period", [7_usize, 14]).param("oversold", [30.0, 40.0]),
+                expected: Ok(vec![param_set(7, 30.0), param_set(14, 30.0), param_set(7, 40.0), param_set(14, 40.0)]),
+            },
+            TestCase {
+                // TC1: parameter without values
+                space: ParamSpace::new().param("rsi_period", [7_usize, 14]).param("oversold", Vec::<f64>::new()),
+                expected: Err(()),
+            },
+            TestCase {
+                // TC2: no parameters is a single empty parameter set
+                space: ParamSpace::new(),
+                expected: Ok(vec![ParamSet::default()]),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            assert_eq!(test.space.grid().map_err(|_| ()), test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_param_space_random() {
+        let space = ParamSpace::new()
+            .param("rsi_period", 2_usize..=30)
+            .param("oversold", [20.0, 25.0, 30.0, 35.0, 40.0]);
+
+        let sampled = space.random(20, 42).unwrap();
+        assert_eq!(sampled.len(), 20);
+        assert_eq!(sampled, space.random(20, 42).unwrap());
+        assert_ne!(sampled, space.random(20, 7).unwrap());
+
+        let grid = space.grid().unwrap();
+        for (index, params) in sampled.iter().enumerate() {
+            assert!(grid.contains(params), "sample {} not in grid", index);
+            assert!(!sampled[..index].contains(params), "sample {} duplicated", index);
+        }
+
+        assert_eq!(space.random(1_000, 42).unwrap(), grid);
+    }
+
+    #[test]
+    fn test_param_set_getters() {
+        let params = param_set(14, 30.5);
+
+        assert_eq!(params.usize("rsi_period").unwrap(), 14);
+        assert_eq!(params.f64("rsi_period").unwrap(), 14.0);
+        assert_eq!(params.f64("oversold").unwrap(), 30.5);
+        assert!(params.usize("oversold").is_err());
+        assert!(params.f64("overbought").is_err());
+        assert_eq!(params.to_string(), "oversold=30.5, rsi_period=14");
+    }
+}
@@ -0,0 +1,261 @@
+use chrono::{DateTime, Duration, Utc};
+use prettytable::{Cell, Table};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    model::optimise_error::OptimiseError,
+    statistic::summary::{combine, TableBuilder},
+    strategy::SignalGenerator,
+};
+
+use super::{param::ParamSet, Candidate, MarketData, Metric, Optimiser, Ranking, Trial};
+
+/// Rolling walk-forward of the [`MarketData`] timeline, split into `windows` consecutive windows
+/// of equal duration. The first `in_sample` fraction of each window is optimised, & the best
+/// [`ParamSet`] is evaluated over the remaining out-of-sample period.
+#[derive(Copy, Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct WalkForward {
+    pub windows: usize,
+    /// Fraction of each window optimised, eg/ 0.75.
+    pub in_sample: f64,
+}
+
+/// Half-open period of [`MarketEvent::exchange_ts`](wednesday_model::events::MarketEvent) from
+/// `start` until `end`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct Period {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl Period {
+    /// [`MarketData`] with only the events within the [`Period`].
+    pub fn slice(&self, data: &MarketData) -> MarketData {
+        data.iter()
+            .map(|(market, events)| {
+                let events = events
+                    .iter()
+                    .filter(|event| self.start <= event.exchange_ts && event.exchange_ts < self.end)
+                    .cloned()
+                    .collect();
+                (market.clone(), events)
+            })
+            .collect()
+    }
+}
+
+impl WalkForward {
+    /// In-sample & out-of-sample [`Period`]s of each window of the `data` timeline.
+    pub fn splits(&self, data: &MarketData) -> Result<Vec<(Period, Period)>, OptimiseError> {
+        if self.windows == 0 {
+            return Err(OptimiseError::InvalidWalkForward("at least one window is required".to_owned()));
+        }
+        if !(self.in_sample > 0.0 && self.in_sample < 1.0) {
+            return Err(OptimiseError::InvalidWalkForward(format!(
+                "in-sample fraction {} must be between 0 & 1 exclusive",
+                self.in_sample
+            )));
+        }
+
+        let timestamps = data.iter().flat_map(|(_, events)| events.iter().map(|event| event.exchange_ts));
+        let (Some(start), Some(last)) = (timestamps.clone().min(), timestamps.max()) else {
+            return Err(OptimiseError::InvalidWalkForward("market data has no events".to_owned()));
+        };
+
+        // End is exclusive, so extended past the last event to include it in the final window
+        let end = last + Duration::nanoseconds(1);
+        let window = (end - start) / self.windows as i32;
+        let in_sample = Duration::nanoseconds((window.num_nanoseconds().unwrap_or(i64::MAX) as f64 * self.in_sample) as i64);
+        if in_sample.is_zero() || in_sample == window {
+            return Err(OptimiseError::InvalidWalkForward(format!(
+                "market data from {start} until {last} is too short for {} windows",
+                self.windows
+            )));
+        }
+
+        Ok((0..self.windows)
+            .map(|index| {
+                let window_start = start + window * index as i32;
+                let window_end = if index + 1 == self.windows { end } else { window_start + window };
+                let split = window_start + in_sample;
+                (
+                    Period {
+                        start: window_start,
+                        end: split,
+                    },
+                    Period { start: split, end: window_end },
+                )
+            })
+            .collect())
+    }
+}
+
+/// Walk-forward window, optimised in-sample & evaluated out-of-sample.
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct Fold {
+    pub in_sample_period: Period,
+    pub out_of_sample_period: Period,
+    /// [`Ranking`] of every [`ParamSet`] over the in-sample period.
+    pub in_sample: Ranking,
+    /// Best in-sample [`ParamSet`] backtested over the out-of-sample period.
+    pub out_of_sample: Trial,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct WalkForwardReport {
+    pub metric: Metric,
+    pub folds: Vec<Fold>,
+}
+
+impl WalkForwardReport {
+    /// Table of the [`ParamSet`] selected in-sample for each [`Fold`], & it's out-of-sample
+    /// statistics.
+    pub fn table(&self) -> Table {
+        let mut table = combine(
+            self.folds
+                .iter()
+                .enumerate()
+                .map(|(index, fold)| ((index + 1).to_string(), fold.out_of_sample.clone())),
+        );
+        if let Some(fold) = self.folds.first() {
+            let mut titles = fold.out_of_sample.titles();
+            titles.insert_cell(0, Cell::new("Fold"));
+            titles
+                .set_cell(Cell::new(&format!("Out-of-sample {}", self.metric.title())), 2)
+                .expect("Trial titles contain a score");
+            table.set_titles(titles);
+        }
+        table
+    }
+}
+
+impl<Strategy, CandidateFn> Optimiser<Strategy, CandidateFn>
+where
+    Strategy: SignalGenerator + Clone + Send,
+    CandidateFn: Fn(&ParamSet) -> Result<Candidate<Strategy>, OptimiseError> + Sync,
+{
+    /// Optimise every [`ParamSet`] of the [`Search`](super::param::Search) over the in-sample
+    /// period of each [`WalkForward`] window, & backtest the best over the out-of-sample period.
+    pub fn walk_forward(&self, walk_forward: WalkForward) -> Result<WalkForwardReport, OptimiseError> {
+        let param_sets = self.search.param_sets(&self.space)?;
+
+        let folds = walk_forward
+            .splits(&self.data)?
+            .into_iter()
+            .map(|(in_sample_period, out_of_sample_period)| {
+                let in_sample = self.rank(&in_sample_period.slice(&self.data), param_sets.clone())?;
+                let best = in_sample.best().expect("every search has at least one ParamSet").params.clone();
+                let out_of_sample = self.backtest(&out_of_sample_period.slice(&self.data), best)?;
+
+                Ok(Fold {
+                    in_sample_period,
+                    out_of_sample_period,
+                    in_sample,
+                    out_of_sample,
+                })
+            })
+            .collect::<Result<Vec<_>, OptimiseError>>()?;
+
+        Ok(WalkForwardReport { metric: self.metric, folds })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optimise::{
+        param::ParamSpace,
+        tests::{config, market_data, rsi_candidate},
+    };
+
+    #[test]
+    fn test_walk_forward_splits() {
+        let data = market_data(101);
+        let start = data[0].1[0].exchange_ts;
+        let end = data[0].1[100].exchange_ts + Duration::nanoseconds(1);
+
+        struct TestCase {
+            walk_forward: WalkForward,
+            expected: Result<usize, ()>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: single window
+                walk_forward: WalkForward { windows: 1, in_sample: 0.5 },
+                expected: Ok(1),
+            },
+            TestCase {
+                // TC1: many windows
+                walk_forward: WalkForward { windows: 4, in_sample: 0.75 },
+                expected: Ok(4),
+            },
+            TestCase {
+                // TC2: no windows
+                walk_forward: WalkForward { windows: 0, in_sample: 0.75 },
+                expected: Err(()),
+            },
+            TestCase {
+                // TC3: no out-of-sample period
+                walk_forward: WalkForward { windows: 2, in_sample: 1.0 },
+                expected: Err(()),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let splits = test.walk_forward.splits(&data);
+            assert_eq!(splits.as_ref().map(Vec::len).map_err(|_| ()), test.expected, "TC{} failed", index);
+
+            // Periods are contiguous & cover the whole timeline
+            if let Ok(splits) = splits {
+                assert_eq!(splits.first().unwrap().0.start, start, "TC{} failed", index);
+                assert_eq!(splits.last().unwrap().1.end, end, "TC{} failed", index);
+                for (in_sample, out_of_sample) in &splits {
+                    assert!(in_sample.start < in_sample.end, "TC{} failed", index);
+                    assert_eq!(in_sample.end, out_of_sample.start, "TC{} failed", index);
+                }
+                for windows in splits.windows(2) {
+                    assert_eq!(windows[0].1.end, windows[1].0.start, "TC{} failed", index);
+                }
+
+                let events = splits
+                    .iter()
+                    .flat_map(|(in_sample, out_of_sample)| [in_sample.slice(&data), out_of_sample.slice(&data)])
+                    .map(|data| data[0].1.len())
+                    .sum::<usize>();
+                assert_eq!(events, 101, "TC{} failed", index);
+            }
+        }
+
+        assert!(WalkForward { windows: 2, in_sample: 0.5 }.splits(&vec![]).is_err());
+    }
+
+    #[test]
+    fn test_walk_forward() {
+        let optimiser = Optimiser::builder()
+            .data(market_data(400))
+            .config(config())
+            .space(
+                ParamSpace::new()
+                    .param("rsi_period", [5_usize, 14])
+                    .param("oversold", [30.0, 40.0])
+                    .param("order_value", [100.0]),
+            )
+            .metric(Metric::TotalReturn)
+            .threads(2)
+            .candidate(rsi_candidate)
+            .build()
+            .unwrap();
+
+        let report = optimiser.walk_forward(WalkForward { windows: 3, in_sample: 0.7 }).unwrap();
+
+        assert_eq!(report.folds.len(), 3);
+        for (index, fold) in report.folds.iter().enumerate() {
+            assert_eq!(fold.in_sample.trials.len(), 4, "fold {} failed", index);
+            assert_eq!(&fold.out_of_sample.params, &fold.in_sample.best().unwrap().params, "fold {} failed", index);
+            assert_eq!(fold.out_of_sample.summary.pnl_returns.total.count, 1, "fold {} failed", index);
+            assert!(fold.out_of_sample.score.is_finite(), "fold {} failed", index);
+        }
+        assert_eq!(report.table().len(), 3);
+    }
+}
@@ -0,0 +1,55 @@
+use prettytable::{row, Row, Table};
+use wednesday_model::latency::LatencySnapshot;
+
+use super::{combine, TableBuilder};
+
+impl TableBuilder for LatencySnapshot {
+    fn titles(&self) -> Row {
+        row!["Count", "Min (us)", "Mean (us)", "p50 (us)", "p90 (us)", "p99 (us)", "p99.9 (us)", "Max (us)"]
+    }
+
+    fn row(&self) -> Row {
+        row![
+            self.count,
+            self.min,
+            format!("{:.1}", self.mean),
+            self.p50,
+            self.p90,
+            self.p99,
+            self.p999,
+            self.max,
+        ]
+    }
+}
+
+/// Table of the [`LatencySnapshot`] of each exchange instrument, subscription kind & stage.
+pub fn latency_table(snapshots: Vec<LatencySnapshot>) -> Table {
+    combine(snapshots.into_iter().map(|snapshot| (snapshot.key.to_string(), snapshot)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use wednesday_model::{
+        identifiers::{Exchange, ExchangeId},
+        instruments::{Instrument, InstrumentKind},
+        latency::{LatencyRecorder, LatencyStage},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_latency_table() {
+        let recorder = LatencyRecorder::new();
+        let (exchange, instrument) = (Exchange::from(ExchangeId::Okx), Instrument::from(("btc", "usdt", InstrumentKind::CryptoSpot)));
+        for stage in [LatencyStage::ParseToStrategy, LatencyStage::OrderToAck] {
+            recorder.record(&exchange, &instrument, "PublicTrade", stage, Duration::from_micros(100));
+        }
+
+        let table = latency_table(recorder.snapshots());
+        assert_eq!(table.len(), 2);
+        assert_eq!(table[0][0].get_content(), "okx (btc/usdt, spot) PublicTrade parse_to_strategy");
+        assert_eq!(table[1][1].get_content(), "1");
+    }
+}
@@ -1,5 +1,6 @@
 pub mod data;
 pub mod drawdown;
+pub mod latency;
 pub mod pnl;
 pub mod trading;
 
@@ -14,10 +14,27 @@ use super::SignalGenerator;
 
 pub struct StrategyConfig {
     pub rsi_period: usize,
+    /// RSI below which a Long is entered.
+    pub oversold: f64,
+    /// RSI above which a Short is entered.
+    pub overbought: f64,
 }
 
+impl Default for StrategyConfig {
+    fn default() -> Self {
+        Self {
+            rsi_period: 14,
+            oversold: 40.0,
+            overbought: 60.0,
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct RsiStrategy {
     rsi: RelativeStrengthIndex,
+    oversold: f64,
+    overbought: f64,
 }
 
 impl SignalGenerator for RsiStrategy {
@@ -49,16 +66,20 @@ impl SignalGenerator for RsiStrategy {
 impl RsiStrategy {
     pub fn new(config: StrategyConfig) -> Self {
         let rsi = RelativeStrengthIndex::new(config.rsi_period).unwrap();
-        Self { rsi: rsi }
+        Self {
+            rsi: rsi,
+            oversold: config.oversold,
+            overbought: config.overbought,
+        }
     }
 
     pub fn generate_signals_map(&self, rsi: f64) -> HashMap<Decision, SignalStrength> {
         let mut signals = HashMap::new();
 
-        if rsi < 40.0 {
+        if rsi < self.oversold {
             signals.insert(Decision::Long, self.calculate_signal_strength());
         }
-        if rsi > 60.0 {
+        if rsi > self.overbought {
             signals.insert(Decision::Short, self.calculate_signal_strength());
         }
         signals
@@ -0,0 +1,217 @@
+use std::cmp::Ordering;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use wednesday_model::{
+    enums::BookSide,
+    orderbook::{Level, OrderBookSide},
+};
+
+/// Previous `OrderBookSide` implementation, a `Vec` of levels that is upserted with a linear scan
+/// & sorted before each snapshot. Kept as the baseline for the `OrdMap` implementation.
+#[derive(Clone)]
+struct VecBookSide {
+    side: BookSide,
+    levels: Vec<Level>,
+}
+
+impl VecBookSide {
+    fn new(side: BookSide, levels: Vec<Level>) -> Self {
+        Self { side, levels }
+    }
+
+    fn upsert_single(&mut self, new_level: Level) {
+        match self.levels.iter_mut().enumerate().find(|(_index, level)| level.eq_price(new_level.price)) {
+            Some((index, _)) if new_level.amount == 0.0 => {
+                self.levels.remove(index);
+            },
+            Some((_, level)) => *level = new_level,
+            None if new_level.amount > 0.0 => self.levels.push(new_level),
+            None => {},
+        }
+    }
+
+    fn sort(&mut self) {
+        self.levels.sort_unstable();
+        if let BookSide::Bid = self.side {
+            self.levels.reverse();
+        }
+    }
+
+    fn snapshot(&mut self) -> Self {
+        self.sort();
+        self.clone()
+    }
+}
+
+const DEPTHS: [usize; 3] = [25, 400, 5000];
+
+/// Bid levels from 10_000.0 down in 0.5 ticks.
+fn levels(depth: usize) -> Vec<Level> {
+    (0..depth)
+        .map(|tick| Level::new(10_000.0 - tick as f64 * 0.5, 1.0 + (tick % 7) as f64))
+        .collect()
+}
+
+/// Deterministic stream of updates within the book, a quarter of which remove a level.
+fn updates(depth: usize, count: usize) -> Vec<Level> {
+    let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+    (0..count)
+        .map(|_| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            let tick = (seed % depth as u64) as f64;
+            let amount = match seed % 4 {
+                0 => 0.0,
+                other => other as f64,
+            };
+            Level::new(10_000.0 - tick * 0.5, amount)
+        })
+        .collect()
+}
+
+fn bench_upsert(c: &mut Criterion) {
+    let mut group = c.benchmark_group("upsert_1000");
+
+    for depth in DEPTHS {
+        let updates = updates(depth, 1000);
+
+        group.bench_with_input(BenchmarkId::new("vec", depth), &depth, |b, &depth| {
+            let side = VecBookSide::new(BookSide::Bid, levels(depth));
+            b.iter(|| {
+                let mut side = side.clone();
+                updates.iter().for_each(|level| side.upsert_single(*level));
+                black_box(side)
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("ordmap", depth), &depth, |b, &depth| {
+            let side = OrderBookSide::new(BookSide::Bid, levels(depth));
+            b.iter(|| {
+                let mut side = side.clone();
+                updates.iter().for_each(|level| side.upsert_single(*level));
+                black_box(side)
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_upsert_snapshot(c: &mut Criterion) {
+    let mut group = c.benchmark_group("upsert_then_snapshot");
+
+    for depth in DEPTHS {
+        let updates = updates(depth, 1000);
+
+        group.bench_with_input(BenchmarkId::new("vec", depth), &depth, |b, &depth| {
+            let mut side = VecBookSide::new(BookSide::Bid, levels(depth));
+            let mut updates = updates.iter().cycle();
+            b.iter(|| {
+                side.upsert_single(*updates.next().unwrap());
+                black_box(side.snapshot())
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("ordmap", depth), &depth, |b, &depth| {
+            let mut side = OrderBookSide::new(BookSide::Bid, levels(depth));
+            let mut updates = updates.iter().cycle();
+            b.iter(|| {
+                side.upsert_single(*updates.next().unwrap());
+                black_box(side.clone())
+            })
+        });
+    }
+
+    group.finish();
+}
+
+/// Consumers hold the snapshot of each update until the next one arrives, so every upsert updates
+/// a side that is still shared with a snapshot.
+fn bench_upsert_held_snapshot(c: &mut Criterion) {
+    let mut group = c.benchmark_group("upsert_with_held_snapshot");
+
+    for depth in DEPTHS {
+        let updates = updates(depth, 1000);
+
+        group.bench_with_input(BenchmarkId::new("vec", depth), &depth, |b, &depth| {
+            let mut side = VecBookSide::new(BookSide::Bid, levels(depth));
+            let mut held = side.snapshot();
+            let mut updates = updates.iter().cycle();
+            b.iter(|| {
+                side.upsert_single(*updates.next().unwrap());
+                held = side.snapshot();
+                black_box(&held);
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("ordmap", depth), &depth, |b, &depth| {
+            let mut side = OrderBookSide::new(BookSide::Bid, levels(depth));
+            let mut held = side.clone();
+            let mut updates = updates.iter().cycle();
+            b.iter(|| {
+                side.upsert_single(*updates.next().unwrap());
+                held = side.clone();
+                black_box(&held);
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_best_n(c: &mut Criterion) {
+    let mut group = c.benchmark_group("best_10");
+
+    for depth in DEPTHS {
+        group.bench_with_input(BenchmarkId::new("vec", depth), &depth, |b, &depth| {
+            let mut side = VecBookSide::new(BookSide::Bid, levels(depth));
+            b.iter(|| {
+                side.sort();
+                black_box(side.levels.iter().take(10).map(|level| level.amount).sum::<f64>())
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("ordmap", depth), &depth, |b, &depth| {
+            let side = OrderBookSide::new(BookSide::Bid, levels(depth));
+            b.iter(|| black_box(side.top(10).map(|level| level.amount).sum::<f64>()))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_best(c: &mut Criterion) {
+    let mut group = c.benchmark_group("best");
+
+    for depth in DEPTHS {
+        group.bench_with_input(BenchmarkId::new("vec", depth), &depth, |b, &depth| {
+            let side = VecBookSide::new(BookSide::Bid, levels(depth));
+            b.iter(|| {
+                black_box(
+                    side.levels
+                        .iter()
+                        .max_by(|a, b| a.price.partial_cmp(&b.price).unwrap_or(Ordering::Equal))
+                        .copied(),
+                )
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("ordmap", depth), &depth, |b, &depth| {
+            let side = OrderBookSide::new(BookSide::Bid, levels(depth));
+            b.iter(|| black_box(side.best().copied()))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_upsert,
+    bench_upsert_snapshot,
+    bench_upsert_held_snapshot,
+    bench_best_n,
+    bench_best
+);
+criterion_main!(benches);
@@ -0,0 +1,238 @@
+use serde::{Deserialize, Serialize};
+
+use crate::orderbook::Level;
+
+/// Basis points per unit, eg/ a relative difference of 0.0001 is 1 bps.
+pub const BPS: f64 = 10_000.0;
+
+/// Relative difference of `price` from the `reference` price in basis points.
+pub fn bps(reference: f64, price: f64) -> f64 {
+    (price - reference) / reference * BPS
+}
+
+/// Price that is `bps` basis points away from the `reference` price.
+pub fn price_at_bps(reference: f64, bps: f64) -> f64 {
+    reference * (1.0 + bps / BPS)
+}
+
+/// Bid/ask volume imbalance in the range [-1, 1], where a positive imbalance means more resting
+/// bid volume. Returns [`None`] if both volumes are zero.
+pub fn imbalance(bid_volume: f64, ask_volume: f64) -> Option<f64> {
+    let total = bid_volume + ask_volume;
+    (total > 0.0).then(|| (bid_volume - ask_volume) / total)
+}
+
+/// Microprice of the best prices weighted by the bid/ask volumes, which leans towards the side
+/// with less resting volume (ie/ the side more likely to be taken next).
+///
+/// With the volumes of the best levels this is the [`volume_weighted_mid_price`](super::volume_weighted_mid_price).
+pub fn microprice(best_bid_price: f64, best_ask_price: f64, bid_volume: f64, ask_volume: f64) -> f64 {
+    match imbalance(bid_volume, ask_volume) {
+        Some(imbalance) => {
+            let bid_weight = (1.0 + imbalance) / 2.0;
+            best_ask_price * bid_weight + best_bid_price * (1.0 - bid_weight)
+        },
+        None => super::mid_price(best_bid_price, best_ask_price),
+    }
+}
+
+/// Result of sweeping the levels of one side of a book to execute a given size, see [`sweep`].
+#[derive(Debug, PartialEq, Clone, Copy, Default, Deserialize, Serialize)]
+pub struct Sweep {
+    /// Size requested to execute.
+    pub size: f64,
+    /// Size that the levels could fill, which is less than the requested size if the book is
+    /// not deep enough.
+    pub filled: f64,
+    /// Cost of the filled size, ie/ the sum of price * amount of every level taken.
+    pub notional: f64,
+    /// Price of the first level taken.
+    pub best_price: f64,
+    /// Price of the last level taken.
+    pub worst_price: f64,
+    /// Number of levels taken, including a partially taken last level.
+    pub levels: usize,
+}
+
+impl Sweep {
+    /// Whether the full requested size could be filled.
+    pub fn is_complete(&self) -> bool {
+        self.filled >= self.size
+    }
+
+    /// Volume weighted average price of the fill.
+    pub fn vwap(&self) -> Option<f64> {
+        (self.filled > 0.0).then(|| self.notional / self.filled)
+    }
+
+    /// Slippage of the [`Sweep::vwap`] from the best price in basis points, always positive since
+    /// every level beyond the best price is worse.
+    pub fn slippage_bps(&self) -> Option<f64> {
+        self.vwap().map(|vwap| bps(self.best_price, vwap).abs())
+    }
+
+    /// Signed difference of the [`Sweep::vwap`] from a reference price (eg/ the mid price) in
+    /// basis points, which is positive if the vwap is above the reference (ie/ worse for a buy,
+    /// but better for a sell).
+    pub fn slippage_bps_from(&self, reference: f64) -> Option<f64> {
+        self.vwap().map(|vwap| bps(reference, vwap))
+    }
+}
+
+/// Sweep `levels` sorted from the best price to execute `size`, eg/ the asks for a buy.
+pub fn sweep<'a, Iter>(levels: Iter, size: f64) -> Sweep
+where
+    Iter: IntoIterator<Item = &'a Level>,
+{
+    let mut sweep = Sweep { size, ..Sweep::default() };
+
+    for level in levels {
+        let remaining = size - sweep.filled;
+        if remaining <= 0.0 {
+            break;
+        }
+
+        let amount = level.amount.min(remaining);
+        if sweep.levels == 0 {
+            sweep.best_price = level.price;
+        }
+        sweep.filled += amount;
+        sweep.notional += amount * level.price;
+        sweep.worst_price = level.price;
+        sweep.levels += 1;
+    }
+
+    sweep
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_imbalance() {
+        struct TestCase {
+            input: (f64, f64),
+            expected: Option<f64>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: balanced
+                input: (10.0, 10.0),
+                expected: Some(0.0),
+            },
+            TestCase {
+                // TC1: bid heavy
+                input: (30.0, 10.0),
+                expected: Some(0.5),
+            },
+            TestCase {
+                // TC2: no asks
+                input: (10.0, 0.0),
+                expected: Some(1.0),
+            },
+            TestCase {
+                // TC3: empty
+                input: (0.0, 0.0),
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = imbalance(test.input.0, test.input.1);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_microprice() {
+        // Equals the volume weighted mid price of the best levels
+        let (best_bid, best_ask) = (Level::new(100.0, 30.0), Level::new(102.0, 10.0));
+        assert_eq!(
+            microprice(best_bid.price, best_ask.price, best_bid.amount, best_ask.amount),
+            super::super::volume_weighted_mid_price(best_bid, best_ask)
+        );
+        assert_eq!(microprice(100.0, 102.0, 30.0, 10.0), 101.5);
+
+        // Falls back to the mid price without volume
+        assert_eq!(microprice(100.0, 102.0, 0.0, 0.0), 101.0);
+    }
+
+    #[test]
+    fn test_bps() {
+        assert_eq!(bps(100.0, 101.0), 100.0);
+        assert_eq!(bps(100.0, 99.5), -50.0);
+        assert_eq!(price_at_bps(100.0, 100.0), 101.0);
+        assert_eq!(price_at_bps(100.0, -50.0), 99.5);
+    }
+
+    #[test]
+    fn test_sweep() {
+        struct TestCase {
+            size: f64,
+            expected: Sweep,
+        }
+
+        let levels = vec![Level::new(100.0, 1.0), Level::new(101.0, 2.0), Level::new(102.0, 3.0)];
+
+        let tests = vec![
+            TestCase {
+                // TC0: filled within the best level
+                size: 0.5,
+                expected: Sweep {
+                    size: 0.5,
+                    filled: 0.5,
+                    notional: 50.0,
+                    best_price: 100.0,
+                    worst_price: 100.0,
+                    levels: 1,
+                },
+            },
+            TestCase {
+                // TC1: partially takes the second level
+                size: 2.0,
+                expected: Sweep {
+                    size: 2.0,
+                    filled: 2.0,
+                    notional: 201.0,
+                    best_price: 100.0,
+                    worst_price: 101.0,
+                    levels: 2,
+                },
+            },
+            TestCase {
+                // TC2: book is not deep enough
+                size: 10.0,
+                expected: Sweep {
+                    size: 10.0,
+                    filled: 6.0,
+                    notional: 608.0,
+                    best_price: 100.0,
+                    worst_price: 102.0,
+                    levels: 3,
+                },
+            },
+            TestCase {
+                // TC3: nothing to execute
+                size: 0.0,
+                expected: Sweep::default(),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = sweep(&levels, test.size);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+
+        let actual = sweep(&levels, 2.0);
+        assert!(actual.is_complete());
+        assert_eq!(actual.vwap(), Some(100.5));
+        assert_eq!(actual.slippage_bps(), Some(50.0));
+        assert_eq!(actual.slippage_bps_from(100.0), Some(50.0));
+
+        let actual = sweep(&levels, 10.0);
+        assert!(!actual.is_complete());
+        assert_eq!(sweep(&levels, 0.0).vwap(), None);
+    }
+}
@@ -1,3 +1,5 @@
+pub mod book;
+
 use crate::orderbook::Level;
 
 pub fn mid_price(best_bid_price: f64, best_ask_price: f64) -> f64 {
@@ -0,0 +1,486 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::{self, Debug},
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard},
+    task::{Context, Poll, Waker},
+};
+
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{
+    mpsc::error::{SendError, TryRecvError},
+    Semaphore,
+};
+
+use crate::{events::MarketEvent, identifiers::Exchange, instruments::Instrument};
+
+/// Subscription a message of a channel belongs to, used to conflate messages & count the
+/// dropped messages of each subscription.
+pub type SubscriptionKey = (Exchange, Instrument);
+
+/// Message that can be sent over a [`channel`].
+pub trait ChannelKey {
+    /// [`SubscriptionKey`] of the message, or [`None`] if the message does not belong to a
+    /// single subscription.
+    fn channel_key(&self) -> Option<SubscriptionKey>;
+}
+
+impl<T> ChannelKey for MarketEvent<T> {
+    fn channel_key(&self) -> Option<SubscriptionKey> {
+        Some((self.exchange.clone(), self.instrument.clone()))
+    }
+}
+
+/// What a [`ChannelTx`] does with a message when a bounded [`channel`] is full.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    /// Wait until the receiver makes room, applying backpressure to the sender.
+    #[default]
+    Block,
+    /// Drop the oldest queued message to make room for the new message.
+    DropOldest,
+    /// Drop the new message.
+    DropNewest,
+    /// Replace the queued message of the same subscription with the new message, or drop the
+    /// oldest queued message if there is none.
+    ConflateBySubscription,
+}
+
+/// Configuration of a [`channel`]. The default is an unbounded channel.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub struct ChannelConfig {
+    /// Maximum number of queued messages, or [`None`] for an unbounded channel.
+    pub capacity: Option<usize>,
+    /// [`OverflowPolicy`] once `capacity` messages are queued.
+    pub policy: OverflowPolicy,
+}
+
+impl ChannelConfig {
+    pub fn unbounded() -> Self {
+        Self::default()
+    }
+
+    pub fn bounded(capacity: usize, policy: OverflowPolicy) -> Self {
+        Self {
+            capacity: Some(capacity.max(1)),
+            policy,
+        }
+    }
+}
+
+/// Number of messages a [`channel`] dropped due to its [`OverflowPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DroppedMessages {
+    pub total: u64,
+    pub by_subscription: HashMap<SubscriptionKey, u64>,
+}
+
+/// Shared handle to the [`DroppedMessages`] counters of a [`channel`], that remains usable after
+/// the channel is closed.
+#[derive(Debug, Clone, Default)]
+pub struct DropCounter(Arc<Mutex<DroppedMessages>>);
+
+impl DropCounter {
+    pub fn dropped(&self) -> DroppedMessages {
+        lock(&self.0).clone()
+    }
+
+    fn increment(&self, key: Option<SubscriptionKey>) {
+        let mut dropped = lock(&self.0);
+        dropped.total += 1;
+        if let Some(key) = key {
+            *dropped.by_subscription.entry(key).or_default() += 1;
+        }
+    }
+}
+
+/// Construct a multi-producer, single-consumer channel that queues messages according to the
+/// [`ChannelConfig`]. With an unbounded config it behaves like an unbounded tokio mpsc channel.
+pub fn channel<T>(config: ChannelConfig) -> (ChannelTx<T>, ChannelRx<T>)
+where
+    T: ChannelKey,
+{
+    let shared = Arc::new(Shared {
+        config,
+        key: T::channel_key,
+        permits: match (config.capacity, config.policy) {
+            (Some(capacity), OverflowPolicy::Block) => Some(Semaphore::new(capacity)),
+            _ => None,
+        },
+        state: Mutex::new(State {
+            queue: VecDeque::new(),
+            senders: 1,
+            receiver_open: true,
+            waker: None,
+        }),
+        dropped: DropCounter::default(),
+    });
+
+    (ChannelTx { shared: Arc::clone(&shared) }, ChannelRx { shared })
+}
+
+struct Shared<T> {
+    config: ChannelConfig,
+    key: fn(&T) -> Option<SubscriptionKey>,
+    /// Free slots of a bounded channel with an [`OverflowPolicy::Block`].
+    permits: Option<Semaphore>,
+    state: Mutex<State<T>>,
+    dropped: DropCounter,
+}
+
+struct State<T> {
+    queue: VecDeque<T>,
+    senders: usize,
+    receiver_open: bool,
+    waker: Option<Waker>,
+}
+
+impl<T> State<T> {
+    fn wake(&mut self) {
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Sending half of a [`channel`].
+pub struct ChannelTx<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> ChannelTx<T> {
+    /// Send a message, applying the [`OverflowPolicy`] if the channel is full. Only waits for
+    /// room with an [`OverflowPolicy::Block`]. Returns the message if the receiver is dropped.
+    pub async fn send(&self, message: T) -> Result<(), SendError<T>> {
+        if let Some(permits) = &self.shared.permits {
+            match permits.acquire().await {
+                Ok(permit) => permit.forget(),
+                Err(_) => return Err(SendError(message)),
+            }
+        }
+
+        self.push(message)
+    }
+
+    /// Send a message without waiting, applying the [`OverflowPolicy`] if the channel is full. A
+    /// full channel with an [`OverflowPolicy::Block`] drops the new message.
+    pub fn try_send(&self, message: T) -> Result<(), SendError<T>> {
+        if let Some(permits) = &self.shared.permits {
+            match permits.try_acquire() {
+                Ok(permit) => permit.forget(),
+                Err(tokio::sync::TryAcquireError::Closed) => return Err(SendError(message)),
+                Err(tokio::sync::TryAcquireError::NoPermits) => {
+                    self.shared.dropped.increment((self.shared.key)(&message));
+                    return Ok(());
+                },
+            }
+        }
+
+        self.push(message)
+    }
+
+    fn push(&self, message: T) -> Result<(), SendError<T>> {
+        let mut state = lock(&self.shared.state);
+        if !state.receiver_open {
+            return Err(SendError(message));
+        }
+
+        match self.shared.config.capacity {
+            Some(capacity) if self.shared.permits.is_none() && state.queue.len() >= capacity => match self.shared.config.policy {
+                OverflowPolicy::DropNewest => {
+                    self.shared.dropped.increment((self.shared.key)(&message));
+                    return Ok(());
+                },
+                OverflowPolicy::ConflateBySubscription => {
+                    let key = (self.shared.key)(&message);
+                    let stale = key
+                        .as_ref()
+                        .and_then(|key| state.queue.iter().position(|queued| (self.shared.key)(queued).as_ref() == Some(key)));
+
+                    match stale {
+                        Some(index) => {
+                            state.queue[index] = message;
+                            self.shared.dropped.increment(key);
+                        },
+                        None => self.drop_oldest(&mut state, message),
+                    }
+                },
+                OverflowPolicy::DropOldest | OverflowPolicy::Block => self.drop_oldest(&mut state, message),
+            },
+            _ => state.queue.push_back(message),
+        }
+
+        state.wake();
+        Ok(())
+    }
+
+    fn drop_oldest(&self, state: &mut State<T>, message: T) {
+        if let Some(oldest) = state.queue.pop_front() {
+            self.shared.dropped.increment((self.shared.key)(&oldest));
+        }
+        state.queue.push_back(message);
+    }
+
+    /// Whether the receiver has been dropped.
+    pub fn is_closed(&self) -> bool {
+        !lock(&self.shared.state).receiver_open
+    }
+
+    pub fn config(&self) -> ChannelConfig {
+        self.shared.config
+    }
+
+    pub fn drop_counter(&self) -> DropCounter {
+        self.shared.dropped.clone()
+    }
+}
+
+impl<T> Clone for ChannelTx<T> {
+    fn clone(&self) -> Self {
+        lock(&self.shared.state).senders += 1;
+        Self {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> Drop for ChannelTx<T> {
+    fn drop(&mut self) {
+        let mut state = lock(&self.shared.state);
+        state.senders -= 1;
+        if state.senders == 0 {
+            state.wake();
+        }
+    }
+}
+
+impl<T> Debug for ChannelTx<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChannelTx").field("config", &self.shared.config).finish()
+    }
+}
+
+/// Receiving half of a [`channel`].
+pub struct ChannelRx<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> ChannelRx<T> {
+    /// Receive the next message, or [`None`] once every [`ChannelTx`] is dropped and the queue
+    /// is empty.
+    pub async fn recv(&mut self) -> Option<T> {
+        std::future::poll_fn(|cx| self.poll_recv(cx)).await
+    }
+
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let mut state = lock(&self.shared.state);
+        match self.pop(&mut state) {
+            Some(message) => Ok(message),
+            None if state.senders == 0 => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let mut state = lock(&self.shared.state);
+        match self.pop(&mut state) {
+            Some(message) => Poll::Ready(Some(message)),
+            None if state.senders == 0 => Poll::Ready(None),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            },
+        }
+    }
+
+    fn pop(&self, state: &mut State<T>) -> Option<T> {
+        let message = state.queue.pop_front()?;
+        if let Some(permits) = &self.shared.permits {
+            permits.add_permits(1);
+        }
+        Some(message)
+    }
+
+    /// Number of queued messages.
+    pub fn len(&self) -> usize {
+        lock(&self.shared.state).queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn config(&self) -> ChannelConfig {
+        self.shared.config
+    }
+
+    pub fn drop_counter(&self) -> DropCounter {
+        self.shared.dropped.clone()
+    }
+
+    pub fn dropped(&self) -> DroppedMessages {
+        self.shared.dropped.dropped()
+    }
+}
+
+impl<T> Drop for ChannelRx<T> {
+    fn drop(&mut self) {
+        lock(&self.shared.state).receiver_open = false;
+        if let Some(permits) = &self.shared.permits {
+            permits.close();
+        }
+    }
+}
+
+impl<T> Stream for ChannelRx<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_recv(cx)
+    }
+}
+
+impl<T> Debug for ChannelRx<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChannelRx")
+            .field("config", &self.shared.config)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+
+    use super::*;
+    use crate::{identifiers::ExchangeId, instruments::InstrumentKind};
+
+    fn event(base: &str, id: i64) -> MarketEvent<i64> {
+        let time = DateTime::<Utc>::from_timestamp(id, 0).unwrap();
+        MarketEvent {
+            exchange_ts: time,
+            local_ts: time,
+            exchange: Exchange::from(ExchangeId::BinanceSpot),
+            instrument: Instrument::from((base, "usdt", InstrumentKind::CryptoSpot)),
+            kind: id,
+        }
+    }
+
+    fn key(base: &str) -> SubscriptionKey {
+        (
+            Exchange::from(ExchangeId::BinanceSpot),
+            Instrument::from((base, "usdt", InstrumentKind::CryptoSpot)),
+        )
+    }
+
+    fn drain(rx: &mut ChannelRx<MarketEvent<i64>>) -> Vec<(String, i64)> {
+        std::iter::from_fn(|| rx.try_recv().ok())
+            .map(|event| (event.instrument.base_currency.to_string(), event.kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_overflow_policy() {
+        struct TestCase {
+            config: ChannelConfig,
+            expected: Vec<(&'static str, i64)>,
+            expected_dropped: DroppedMessages,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: unbounded channel queues every message
+                config: ChannelConfig::unbounded(),
+                expected: vec![("btc", 0), ("eth", 1), ("btc", 2), ("btc", 3)],
+                expected_dropped: DroppedMessages::default(),
+            },
+            TestCase {
+                // TC1: drop oldest keeps the latest messages
+                config: ChannelConfig::bounded(2, OverflowPolicy::DropOldest),
+                expected: vec![("btc", 2), ("btc", 3)],
+                expected_dropped: DroppedMessages {
+                    total: 2,
+                    by_subscription: HashMap::from([(key("btc"), 1), (key("eth"), 1)]),
+                },
+            },
+            TestCase {
+                // TC2: drop newest keeps the first messages
+                config: ChannelConfig::bounded(2, OverflowPolicy::DropNewest),
+                expected: vec![("btc", 0), ("eth", 1)],
+                expected_dropped: DroppedMessages {
+                    total: 2,
+                    by_subscription: HashMap::from([(key("btc"), 2)]),
+                },
+            },
+            TestCase {
+                // TC3: conflate replaces the queued message of the same subscription
+                config: ChannelConfig::bounded(2, OverflowPolicy::ConflateBySubscription),
+                expected: vec![("btc", 3), ("eth", 1)],
+                expected_dropped: DroppedMessages {
+                    total: 2,
+                    by_subscription: HashMap::from([(key("btc"), 2)]),
+                },
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let (tx, mut rx) = channel(test.config);
+            for event in [event("btc", 0), event("eth", 1), event("btc", 2), event("btc", 3)] {
+                tx.try_send(event).unwrap();
+            }
+
+            let expected = test.expected.into_iter().map(|(base, id)| (base.to_string(), id)).collect::<Vec<_>>();
+            assert_eq!(drain(&mut rx), expected, "TC{} failed", index);
+            assert_eq!(rx.dropped(), test.expected_dropped, "TC{} failed", index);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_block_applies_backpressure() {
+        let (tx, mut rx) = channel(ChannelConfig::bounded(1, OverflowPolicy::Block));
+
+        tx.send(event("btc", 0)).await.unwrap();
+
+        // Sender waits until the receiver makes room
+        let sender = tokio::spawn(async move {
+            tx.send(event("btc", 1)).await.unwrap();
+            tx.send(event("btc", 2)).await.unwrap();
+        });
+
+        let mut received = Vec::new();
+        while let Some(event) = rx.recv().await {
+            received.push(event.kind);
+        }
+
+        sender.await.unwrap();
+        assert_eq!(received, vec![0, 1, 2]);
+        assert_eq!(rx.dropped(), DroppedMessages::default());
+    }
+
+    #[tokio::test]
+    async fn test_closed() {
+        let (tx, rx) = channel::<MarketEvent<i64>>(ChannelConfig::bounded(1, OverflowPolicy::Block));
+        let counter = rx.drop_counter();
+        tx.send(event("btc", 0)).await.unwrap();
+        drop(rx);
+
+        // Blocked sender is released once the receiver is dropped
+        assert!(tx.is_closed());
+        assert!(tx.send(event("btc", 1)).await.is_err());
+        assert_eq!(counter.dropped().total, 0);
+
+        let (tx, mut rx) = channel::<MarketEvent<i64>>(ChannelConfig::unbounded());
+        tx.send(event("btc", 0)).await.unwrap();
+        drop(tx);
+        assert_eq!(rx.recv().await.map(|event| event.kind), Some(0));
+        assert_eq!(rx.recv().await, None);
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+    }
+}
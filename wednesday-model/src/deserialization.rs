@@ -14,6 +14,20 @@ where
     data.parse::<T>().map_err(serde::de::Error::custom)
 }
 
+/// Deserialize an optional `String` as the desired type, mapping an absent or empty `String` to `None`.
+pub fn de_option_str<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    let data: Option<&str> = serde::de::Deserialize::deserialize(deserializer)?;
+    match data {
+        Some(data) if !data.is_empty() => data.parse::<T>().map(Some).map_err(serde::de::Error::custom),
+        _ => Ok(None),
+    }
+}
+
 /// Deserialize a `u64` milliseconds value as `DateTime<Utc>`.
 pub fn de_u64_epoch_ms_as_datetime_utc<'de, D>(deserializer: D) -> Result<chrono::DateTime<chrono::Utc>, D::Error>
 where
@@ -22,3 +36,11 @@ where
     serde::de::Deserialize::deserialize(deserializer)
         .map(|epoch_ms| datetime_utc_from_epoch_duration(std::time::Duration::from_millis(epoch_ms)))
 }
+
+/// Deserialize a `String` of `u64` milliseconds value as `DateTime<Utc>`.
+pub fn de_str_u64_epoch_ms_as_datetime_utc<'de, D>(deserializer: D) -> Result<chrono::DateTime<chrono::Utc>, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    de_str(deserializer).map(|epoch_ms| datetime_utc_from_epoch_duration(std::time::Duration::from_millis(epoch_ms)))
+}
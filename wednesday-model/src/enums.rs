@@ -32,7 +32,8 @@ pub enum OrderSide {
     None,
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum OrderType {
     Limit,
     Market,
@@ -45,9 +46,9 @@ pub const QUANTITY: i32 = 1;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Deserialize, Serialize)]
 pub enum BookSide {
-    #[serde(alias = "buy", alias = "BUY", alias = "b", alias = "bid")]
+    #[serde(alias = "buy", alias = "BUY", alias = "Buy", alias = "b", alias = "bid")]
     Bid,
-    #[serde(alias = "sell", alias = "SELL", alias = "s", alias = "ask")]
+    #[serde(alias = "sell", alias = "SELL", alias = "Sell", alias = "s", alias = "ask", alias = "offer")]
     Ask,
 }
 
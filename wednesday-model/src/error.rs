@@ -31,14 +31,19 @@ pub enum SocketError {
     #[error("error subscribing to resources over the socket: {0}")]
     Subscribe(String),
 
+    #[error("error signing request: {0}")]
+    Sign(String),
+
     #[error("ExchangeStream terminated with closing frame: {0}")]
     Terminated(String),
 
     #[error("{entity} does not support: {item}")]
     Unsupported { entity: &'static str, item: String },
 
+    /// Boxed, since the [`tungstenite::Error`](tokio_tungstenite::tungstenite::Error) would
+    /// otherwise make every [`SocketError`] & [`DataError`] large.
     #[error("WebSocket error: {0}")]
-    WebSocket(#[from] tokio_tungstenite::tungstenite::Error),
+    WebSocket(Box<tokio_tungstenite::tungstenite::Error>),
 
     #[error("WebSocket Connection error: {0}")]
     WebSocketConnection(String),
@@ -53,6 +58,9 @@ pub enum SocketError {
     #[error("HTTP response (status={0}) error: {1}")]
     HttpResponse(reqwest::StatusCode, String),
 
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
     #[error("consumed unidentifiable message: {0}")]
     Unidentifiable(SubscriptionId),
 
@@ -60,6 +68,12 @@ pub enum SocketError {
     Exchange(String),
 }
 
+impl From<tokio_tungstenite::tungstenite::Error> for SocketError {
+    fn from(error: tokio_tungstenite::tungstenite::Error) -> Self {
+        SocketError::WebSocket(Box::new(error))
+    }
+}
+
 impl From<reqwest::Error> for SocketError {
     fn from(error: Error) -> Self {
         match error {
@@ -88,12 +102,15 @@ pub enum DataError {
         prev_last_update_id {prev_last_update_id}"
     )]
     InvalidSequence { prev_last_update_id: u64, first_update_id: u64 },
+
+    #[error("InvalidChecksum: exchange checksum {expected} does not match the local order book checksum {actual}")]
+    InvalidChecksum { expected: i64, actual: i64 },
 }
 
 impl DataError {
     /// Determine if an error requires a [`MarketStream`](super::MarketStream) to re-initialise.
     pub fn is_terminal(&self) -> bool {
-        matches!(self, DataError::InvalidSequence { .. })
+        matches!(self, DataError::InvalidSequence { .. } | DataError::InvalidChecksum { .. })
     }
 }
 
@@ -125,4 +142,37 @@ mod tests {
         };
         assert!(matches!(data_error, DataError::InvalidSequence { .. }));
     }
+
+    #[test]
+    fn test_data_error_is_terminal() {
+        struct TestCase {
+            input: DataError,
+            expected: bool,
+        }
+
+        let tests = vec![
+            // TC0: InvalidSequence is terminal
+            TestCase {
+                input: DataError::InvalidSequence {
+                    prev_last_update_id: 100,
+                    first_update_id: 200,
+                },
+                expected: true,
+            },
+            // TC1: InvalidChecksum is terminal
+            TestCase {
+                input: DataError::InvalidChecksum { expected: 1, actual: 2 },
+                expected: true,
+            },
+            // TC2: Socket error is not terminal
+            TestCase {
+                input: DataError::Socket(SocketError::Sink),
+                expected: false,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            assert_eq!(test.input.is_terminal(), test.expected, "TC{} failed", index);
+        }
+    }
 }
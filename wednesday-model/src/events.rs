@@ -3,7 +3,14 @@ use std::fmt::Debug;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::{bar::Bar, identifiers::Exchange, instruments::Instrument, orderbook::OrderBookL1, trade::PublicTrade};
+use crate::{
+    bar::Bar,
+    identifiers::Exchange,
+    instruments::Instrument,
+    orderbook::OrderBookL1,
+    perpetual::{MarkPrice, OpenInterest},
+    trade::{AggregatedTrade, PublicTrade},
+};
 
 // use super::orderbook::{OrderBookL1};
 // use super::trade::Trade;
@@ -17,15 +24,36 @@ pub struct MarketEvent<T> {
     pub kind: T,
 }
 
-#[derive(Debug, Clone)]
+/// Kind of market data of a [`MarketEvent`], serialised adjacently tagged with a snake_case
+/// `type`, eg/ `{"type": "public_trade", "data": {..}}`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
 pub enum DataKind {
     PublicTrade(PublicTrade),
+    AggregatedTrade(AggregatedTrade),
     OrderBookL1(OrderBookL1),
     // OrderBook(Orderbook),
     Bar(Bar),
+    MarkPrice(MarkPrice),
+    OpenInterest(OpenInterest),
     // Liquidation(Liquidation)
 }
 
+impl DataKind {
+    /// Name of the kind of data, eg/ to categorise the latency of events, see
+    /// [`LatencyRecorder`](crate::latency::LatencyRecorder).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            DataKind::PublicTrade(_) => "PublicTrade",
+            DataKind::AggregatedTrade(_) => "AggregatedTrade",
+            DataKind::OrderBookL1(_) => "OrderBookL1",
+            DataKind::Bar(_) => "Bar",
+            DataKind::MarkPrice(_) => "MarkPrice",
+            DataKind::OpenInterest(_) => "OpenInterest",
+        }
+    }
+}
+
 impl From<MarketEvent<PublicTrade>> for MarketEvent<DataKind> {
     fn from(event: MarketEvent<PublicTrade>) -> Self {
         Self {
@@ -38,6 +66,18 @@ impl From<MarketEvent<PublicTrade>> for MarketEvent<DataKind> {
     }
 }
 
+impl From<MarketEvent<AggregatedTrade>> for MarketEvent<DataKind> {
+    fn from(event: MarketEvent<AggregatedTrade>) -> Self {
+        Self {
+            exchange_ts: event.exchange_ts,
+            local_ts: event.local_ts,
+            exchange: event.exchange,
+            instrument: event.instrument,
+            kind: DataKind::AggregatedTrade(event.kind),
+        }
+    }
+}
+
 impl From<MarketEvent<OrderBookL1>> for MarketEvent<DataKind> {
     fn from(event: MarketEvent<OrderBookL1>) -> Self {
         Self {
@@ -62,6 +102,30 @@ impl From<MarketEvent<Bar>> for MarketEvent<DataKind> {
     }
 }
 
+impl From<MarketEvent<MarkPrice>> for MarketEvent<DataKind> {
+    fn from(event: MarketEvent<MarkPrice>) -> Self {
+        Self {
+            exchange_ts: event.exchange_ts,
+            local_ts: event.local_ts,
+            exchange: event.exchange,
+            instrument: event.instrument,
+            kind: DataKind::MarkPrice(event.kind),
+        }
+    }
+}
+
+impl From<MarketEvent<OpenInterest>> for MarketEvent<DataKind> {
+    fn from(event: MarketEvent<OpenInterest>) -> Self {
+        Self {
+            exchange_ts: event.exchange_ts,
+            local_ts: event.local_ts,
+            exchange: event.exchange,
+            instrument: event.instrument,
+            kind: DataKind::OpenInterest(event.kind),
+        }
+    }
+}
+
 // / Events that occur when bartering. [`MarketEvent`], [`Signal`], [`OrderEvent`], and
 // / [`FillEvent`] are vital to the [`Trader`](crate::engine::trader::Trader) event loop, dictating
 // / the trading sequence. The [`PositionExit`] Event is a representation of work done by the
@@ -165,6 +165,7 @@ pub enum ExchangeId {
     GateioPerpetualsUsd,
     GateioOptions,
     Kraken,
+    Krx,
     Okx,
 }
 
@@ -197,6 +198,7 @@ impl ExchangeId {
             ExchangeId::GateioPerpetualsBtc => "gateio_perpetuals_btc",
             ExchangeId::GateioOptions => "gateio_options",
             ExchangeId::Kraken => "kraken",
+            ExchangeId::Krx => "krx",
             ExchangeId::Okx => "okx",
         }
     }
@@ -207,6 +209,13 @@ impl ExchangeId {
         use InstrumentKind::*;
 
         match (self, instrument_kind) {
+            // Stock
+            (Krx, Stock) => true,
+            (_, Stock) => false,
+
+            // Krx only lists equities
+            (Krx, _) => false,
+
             // Spot
             (BinanceFuturesUsd | Bitmex | BybitPerpetualsUsd | GateioPerpetualsUsd | GateioPerpetualsBtc, CryptoSpot) => false,
             (_, CryptoSpot) => true,
@@ -217,24 +226,7 @@ impl ExchangeId {
 
             // Future Perpetual Swaps
             (BinanceFuturesUsd | Bitmex | Okx | BybitPerpetualsUsd | GateioPerpetualsUsd | GateioPerpetualsBtc, CryptoPerpetual) => true,
-
-            // Unimplemented
             (_, CryptoPerpetual) => false,
-            (BinanceFuturesUsd, Stock) => todo!(),
-            (BinanceSpot, Stock) => todo!(),
-            (Bitfinex, Stock) => todo!(),
-            (Bitmex, Stock) => todo!(),
-            (BybitSpot, Stock) => todo!(),
-            (BybitPerpetualsUsd, Stock) => todo!(),
-            (Coinbase, Stock) => todo!(),
-            (GateioSpot, Stock) => todo!(),
-            (GateioFuturesUsd, Stock) => todo!(),
-            (GateioFuturesBtc, Stock) => todo!(),
-            (GateioPerpetualsBtc, Stock) => todo!(),
-            (GateioPerpetualsUsd, Stock) => todo!(),
-            (GateioOptions, Stock) => todo!(),
-            (Kraken, Stock) => todo!(),
-            (Okx, Stock) => todo!(),
         }
     }
 }
@@ -325,4 +317,39 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_exchange_id_supports() {
+        struct TestCase {
+            input: (ExchangeId, InstrumentKind),
+            expected: bool,
+        }
+
+        let tests = vec![
+            // TC0: Krx supports stocks
+            TestCase {
+                input: (ExchangeId::Krx, InstrumentKind::Stock),
+                expected: true,
+            },
+            // TC1: Krx does not support crypto
+            TestCase {
+                input: (ExchangeId::Krx, InstrumentKind::CryptoSpot),
+                expected: false,
+            },
+            // TC2: crypto exchanges do not support stocks
+            TestCase {
+                input: (ExchangeId::BinanceSpot, InstrumentKind::Stock),
+                expected: false,
+            },
+            // TC3: crypto exchanges still support crypto
+            TestCase {
+                input: (ExchangeId::Okx, InstrumentKind::CryptoPerpetual),
+                expected: true,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            assert_eq!(test.input.0.supports(test.input.1), test.expected, "TC{} failed", index);
+        }
+    }
 }
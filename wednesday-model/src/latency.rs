@@ -0,0 +1,311 @@
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt::{self, Display},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use hdrhistogram::Histogram;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::{
+    events::MarketEvent,
+    identifiers::Exchange,
+    instruments::Instrument,
+    metric::{Field, Metric, MetricTx, Tag},
+};
+
+/// Highest latency tracked by a histogram, larger latencies are recorded as this value.
+pub const MAX_LATENCY_US: u64 = 60_000_000;
+
+/// Significant figures of the latencies tracked by a histogram.
+pub const LATENCY_SIGNIFICANT_FIGURES: u8 = 2;
+
+/// Stage of the pipeline from the exchange to the order acknowledgement.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LatencyStage {
+    /// Exchange timestamp of an event until its message was received.
+    ExchangeToReceive,
+    /// Message received until it was parsed into a [`MarketEvent`].
+    ReceiveToParse,
+    /// [`MarketEvent`] parsed until it was handed to the strategy.
+    ParseToStrategy,
+    /// Signal generated until the resulting order was sent.
+    SignalToOrder,
+    /// Order sent until it was acknowledged (eg/ filled).
+    OrderToAck,
+}
+
+impl LatencyStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LatencyStage::ExchangeToReceive => "exchange_to_receive",
+            LatencyStage::ReceiveToParse => "receive_to_parse",
+            LatencyStage::ParseToStrategy => "parse_to_strategy",
+            LatencyStage::SignalToOrder => "signal_to_order",
+            LatencyStage::OrderToAck => "order_to_ack",
+        }
+    }
+}
+
+impl Display for LatencyStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Histogram of a [`LatencyStage`] of the subscription `kind` (eg/ "PublicTrades") of an
+/// exchange instrument.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub struct LatencyKey {
+    pub exchange: Exchange,
+    pub instrument: Instrument,
+    pub kind: Cow<'static, str>,
+    pub stage: LatencyStage,
+}
+
+impl Display for LatencyKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {} {}", self.exchange, self.instrument, self.kind, self.stage)
+    }
+}
+
+/// Percentiles of a latency histogram in microseconds, see [`LatencyRecorder::snapshots`].
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LatencySnapshot {
+    pub key: LatencyKey,
+    pub count: u64,
+    pub min: u64,
+    pub mean: f64,
+    pub p50: u64,
+    pub p90: u64,
+    pub p99: u64,
+    pub p999: u64,
+    pub max: u64,
+}
+
+impl LatencySnapshot {
+    fn new(key: LatencyKey, histogram: &Histogram<u64>) -> Self {
+        Self {
+            key,
+            count: histogram.len(),
+            min: histogram.min(),
+            mean: histogram.mean(),
+            p50: histogram.value_at_quantile(0.5),
+            p90: histogram.value_at_quantile(0.9),
+            p99: histogram.value_at_quantile(0.99),
+            p999: histogram.value_at_quantile(0.999),
+            max: histogram.max(),
+        }
+    }
+}
+
+impl From<&LatencySnapshot> for Metric {
+    fn from(snapshot: &LatencySnapshot) -> Self {
+        Metric {
+            name: "latency_us",
+            time: Utc::now().timestamp_millis() as u64,
+            tags: vec![
+                Tag::new("exchange", snapshot.key.exchange.to_string()),
+                Tag::new("instrument", snapshot.key.instrument.to_string()),
+                Tag::new("kind", snapshot.key.kind.clone()),
+                Tag::new("stage", snapshot.key.stage.as_str()),
+            ],
+            fields: vec![
+                Field::new("count", snapshot.count),
+                Field::new("min", snapshot.min),
+                Field::new("mean", snapshot.mean),
+                Field::new("p50", snapshot.p50),
+                Field::new("p90", snapshot.p90),
+                Field::new("p99", snapshot.p99),
+                Field::new("p999", snapshot.p999),
+                Field::new("max", snapshot.max),
+            ],
+        }
+    }
+}
+
+/// Shared HDR histograms of the latency of each [`LatencyStage`] per exchange instrument &
+/// subscription kind, which is cheap to clone into every stage of the pipeline.
+///
+/// Latencies are recorded in microseconds. Latencies between wall clock timestamps of different
+/// hosts (ie/ [`LatencyStage::ExchangeToReceive`]) may be negative due to clock skew, in which
+/// case they are recorded as the lowest trackable latency of 1us.
+#[derive(Debug, Clone, Default)]
+pub struct LatencyRecorder(Arc<Mutex<HashMap<LatencyKey, Histogram<u64>>>>);
+
+impl LatencyRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the `latency` of the [`LatencyStage`] of the subscription `kind` of an exchange
+    /// instrument.
+    pub fn record(&self, exchange: &Exchange, instrument: &Instrument, kind: &'static str, stage: LatencyStage, latency: Duration) {
+        let latency = u64::try_from(latency.as_micros()).unwrap_or(u64::MAX);
+
+        let key = LatencyKey {
+            exchange: exchange.clone(),
+            instrument: instrument.clone(),
+            kind: Cow::Borrowed(kind),
+            stage,
+        };
+
+        let mut histograms = self.0.lock().expect("LatencyRecorder lock poisoned");
+        let histogram = histograms
+            .entry(key)
+            .or_insert_with(|| Histogram::new_with_bounds(1, MAX_LATENCY_US, LATENCY_SIGNIFICANT_FIGURES).expect("latency histogram bounds are valid"));
+        histogram.saturating_record(latency.clamp(1, MAX_LATENCY_US));
+    }
+
+    /// Record the latency between the wall clock timestamps `from` & `to`.
+    pub fn record_between(
+        &self,
+        exchange: &Exchange,
+        instrument: &Instrument,
+        kind: &'static str,
+        stage: LatencyStage,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) {
+        let latency = (to - from).to_std().unwrap_or_default();
+        self.record(exchange, instrument, kind, stage, latency);
+    }
+
+    /// Record the [`LatencyStage::ExchangeToReceive`] & [`LatencyStage::ReceiveToParse`] of a
+    /// [`MarketEvent`] whose message was received at `received_ts`, assuming the `local_ts` of
+    /// the event is the time it was parsed.
+    pub fn record_market_event<T>(&self, event: &MarketEvent<T>, kind: &'static str, received_ts: DateTime<Utc>) {
+        let (exchange, instrument) = (&event.exchange, &event.instrument);
+        self.record_between(exchange, instrument, kind, LatencyStage::ExchangeToReceive, event.exchange_ts, received_ts);
+        self.record_between(exchange, instrument, kind, LatencyStage::ReceiveToParse, received_ts, event.local_ts);
+    }
+
+    /// [`LatencySnapshot`] of every histogram, sorted by [`LatencyKey`].
+    pub fn snapshots(&self) -> Vec<LatencySnapshot> {
+        let histograms = self.0.lock().expect("LatencyRecorder lock poisoned");
+        let mut snapshots = histograms
+            .iter()
+            .map(|(key, histogram)| LatencySnapshot::new(key.clone(), histogram))
+            .collect::<Vec<_>>();
+
+        snapshots.sort_by(|a, b| a.key.cmp(&b.key));
+        snapshots
+    }
+
+    /// [`Metric`] of every [`LatencySnapshot`].
+    pub fn metrics(&self) -> Vec<Metric> {
+        self.snapshots().iter().map(Metric::from).collect()
+    }
+
+    /// Spawn a task that sends the [`Metric`]s of every histogram via the `metric_tx` each
+    /// `interval`. The task ends once the `metric_tx` receiver is dropped.
+    pub fn spawn_snapshots(&self, interval: Duration, metric_tx: MetricTx) {
+        let recorder = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                ticker.tick().await;
+                if recorder.metrics().into_iter().any(|metric| metric_tx.send(metric).is_err()) {
+                    debug!(why = "receiver dropped", "stopping latency snapshots");
+                    return;
+                }
+            }
+        });
+    }
+}
+
+/// Name of a type without its module path, eg/ the name of a subscription kind.
+pub fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{identifiers::ExchangeId, instruments::InstrumentKind};
+
+    use super::*;
+
+    #[test]
+    fn test_latency_recorder() {
+        let recorder = LatencyRecorder::new();
+        let exchange = Exchange::from(ExchangeId::Okx);
+        let instrument = Instrument::from(("btc", "usdt", InstrumentKind::CryptoSpot));
+
+        (1..=100).for_each(|latency| {
+            recorder.record(
+                &exchange,
+                &instrument,
+                "PublicTrades",
+                LatencyStage::ParseToStrategy,
+                Duration::from_millis(latency),
+            );
+        });
+
+        // Negative latency due to clock skew is recorded as the lowest trackable latency
+        let now = Utc::now();
+        recorder.record_between(
+            &exchange,
+            &instrument,
+            "PublicTrades",
+            LatencyStage::ExchangeToReceive,
+            now,
+            now - chrono::Duration::seconds(1),
+        );
+
+        let snapshots = recorder.snapshots();
+        assert_eq!(snapshots.len(), 2);
+
+        let skew = &snapshots[0];
+        assert_eq!(skew.key.stage, LatencyStage::ExchangeToReceive);
+        assert_eq!((skew.count, skew.max), (1, 1));
+
+        let strategy = &snapshots[1];
+        assert_eq!(strategy.key.stage, LatencyStage::ParseToStrategy);
+        assert_eq!(strategy.count, 100);
+        assert!(strategy.min.abs_diff(1_000) <= 10);
+        assert!(strategy.p50.abs_diff(50_000) <= 500);
+        assert!(strategy.p99.abs_diff(99_000) <= 1_000);
+        assert!(strategy.max.abs_diff(100_000) <= 1_000);
+
+        let metric = Metric::from(strategy);
+        assert_eq!(metric.name, "latency_us");
+        assert!(metric.tags.contains(&Tag::new("stage", "parse_to_strategy")));
+    }
+
+    #[test]
+    fn test_record_market_event() {
+        let recorder = LatencyRecorder::new();
+        let exchange_ts = Utc::now();
+        let event = MarketEvent {
+            exchange_ts,
+            local_ts: exchange_ts + chrono::Duration::milliseconds(3),
+            exchange: Exchange::from(ExchangeId::Okx),
+            instrument: Instrument::from(("btc", "usdt", InstrumentKind::CryptoSpot)),
+            kind: (),
+        };
+
+        recorder.record_market_event(&event, "PublicTrades", exchange_ts + chrono::Duration::milliseconds(2));
+
+        let snapshots = recorder.snapshots();
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].key.stage, LatencyStage::ExchangeToReceive);
+        assert!(snapshots[0].max.abs_diff(2_000) <= 20);
+        assert_eq!(snapshots[1].key.stage, LatencyStage::ReceiveToParse);
+        assert!(snapshots[1].max.abs_diff(1_000) <= 10);
+    }
+
+    #[test]
+    fn test_short_type_name() {
+        assert_eq!(short_type_name::<LatencyRecorder>(), "LatencyRecorder");
+        assert_eq!(short_type_name::<Vec<u64>>(), "Vec");
+    }
+}
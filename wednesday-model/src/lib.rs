@@ -2,15 +2,21 @@ pub mod enums;
 pub mod error;
 pub mod order;
 pub mod orderbook;
+pub mod orderbook_l3;
+pub mod perpetual;
 pub mod trade;
 
 pub mod events;
 pub mod identifiers;
 pub mod instruments;
+pub mod latency;
+pub mod metric;
 
+pub mod account;
 pub mod bar;
 pub mod calculator;
+pub mod channel;
 pub mod deserialization;
 pub mod position;
-pub mod account;
+pub mod status;
 pub mod trade_report;
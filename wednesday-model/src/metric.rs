@@ -0,0 +1,336 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{Arc, Mutex},
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+/// Transmitter of [`Metric`]s to an exporter.
+pub type MetricTx = mpsc::UnboundedSender<Metric>;
+
+/// Receiver of [`Metric`]s by an exporter.
+pub type MetricRx = mpsc::UnboundedReceiver<Metric>;
+
+#[derive(Debug, Clone, PartialOrd, PartialEq, Serialize)]
+pub struct Metric {
+    /// Metric name.
+    pub name: &'static str,
+
+    /// Milliseconds since the Unix epoch.
+    pub time: u64,
+
+    /// Key-Value pairs to categorise the Metric.
+    pub tags: Vec<Tag>,
+
+    /// Observed measurements.
+    pub fields: Vec<Field>,
+}
+
+#[derive(Debug, Clone, Serialize, Ord, PartialOrd, Eq, PartialEq)]
+pub struct Tag {
+    pub key: &'static str,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialOrd, PartialEq, Serialize)]
+pub struct Field {
+    pub key: &'static str,
+    pub value: Value,
+}
+
+#[derive(Debug, Clone, PartialOrd, PartialEq, Deserialize, Serialize)]
+pub enum Value {
+    Float(f64),
+    Int(i64),
+    UInt(u64),
+    Bool(bool),
+    String(String),
+}
+
+impl<S> From<(&'static str, S)> for Tag
+where
+    S: Into<String>,
+{
+    fn from((key, value): (&'static str, S)) -> Self {
+        Self::new(key, value)
+    }
+}
+
+impl Tag {
+    pub fn new<S>(key: &'static str, value: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self { key, value: value.into() }
+    }
+}
+
+impl<S> From<(&'static str, S)> for Field
+where
+    S: Into<Value>,
+{
+    fn from((key, value): (&'static str, S)) -> Self {
+        Self::new(key, value)
+    }
+}
+
+impl Field {
+    pub fn new<S>(key: &'static str, value: S) -> Self
+    where
+        S: Into<Value>,
+    {
+        Self { key, value: value.into() }
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<u64> for Value {
+    fn from(value: u64) -> Self {
+        Self::UInt(value)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl Value {
+    /// Numeric value of the [`Value`], where a [`Value::Bool`] is 1 or 0 & a [`Value::String`]
+    /// has none.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Float(value) => Some(*value),
+            Value::Int(value) => Some(*value as f64),
+            Value::UInt(value) => Some(*value as f64),
+            Value::Bool(value) => Some(if *value { 1.0 } else { 0.0 }),
+            Value::String(_) => None,
+        }
+    }
+}
+
+impl Metric {
+    /// Name of the series of a [`Field`] of the [`Metric`], which is the metric name suffixed
+    /// with the field key unless the metric name already ends with it (eg/ "latency_us_p99", but
+    /// "http_request_duration").
+    pub fn series_name(&self, field: &Field) -> String {
+        match self.name.ends_with(field.key) {
+            true => sanitise_name(self.name),
+            false => sanitise_name(&format!("{}_{}", self.name, field.key)),
+        }
+    }
+
+    /// Serialise the [`Metric`] into an InfluxDB line protocol line.
+    ///
+    /// See docs: <https://docs.influxdata.com/influxdb/v2/reference/syntax/line-protocol/>
+    pub fn to_line_protocol(&self) -> String {
+        let mut line = escape_influx(self.name, &[',', ' ']);
+
+        for tag in &self.tags {
+            let _ = write!(
+                line,
+                ",{}={}",
+                escape_influx(tag.key, &[',', '=', ' ']),
+                escape_influx(&tag.value, &[',', '=', ' '])
+            );
+        }
+
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| {
+                let value = match &field.value {
+                    Value::Float(value) => value.to_string(),
+                    Value::Int(value) => format!("{value}i"),
+                    Value::UInt(value) => format!("{value}u"),
+                    Value::Bool(value) => value.to_string(),
+                    Value::String(value) => format!("\"{}\"", escape_influx(value, &['"'])),
+                };
+                format!("{}={}", escape_influx(field.key, &[',', '=', ' ']), value)
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let _ = write!(line, " {} {}", fields, self.time.saturating_mul(1_000_000));
+        line
+    }
+}
+
+/// Latest value of each series of the [`Metric`]s recorded, rendered in the Prometheus text
+/// exposition format. Series whose name ends with "_total" are counters, & all others gauges.
+///
+/// See docs: <https://prometheus.io/docs/instrumenting/exposition_formats/#text-based-format>
+#[derive(Debug, Clone, Default)]
+pub struct MetricRegistry(Arc<Mutex<BTreeMap<String, Series>>>);
+
+/// Latest value of a series per set of labels.
+type Series = BTreeMap<Vec<Tag>, f64>;
+
+impl MetricRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the numeric [`Field`]s of the [`Metric`] as the latest value of their series.
+    pub fn record(&self, metric: &Metric) {
+        let mut labels = metric.tags.clone();
+        labels.sort();
+
+        let mut series = self.0.lock().expect("MetricRegistry lock poisoned");
+        for field in &metric.fields {
+            if let Some(value) = field.value.as_f64() {
+                series.entry(metric.series_name(field)).or_default().insert(labels.clone(), value);
+            }
+        }
+    }
+
+    /// Render the latest value of every series in the Prometheus text exposition format.
+    pub fn prometheus(&self) -> String {
+        let series = self.0.lock().expect("MetricRegistry lock poisoned");
+
+        series.iter().fold(String::new(), |mut text, (name, values)| {
+            let kind = if name.ends_with("_total") { "counter" } else { "gauge" };
+            let _ = writeln!(text, "# TYPE {name} {kind}");
+
+            for (labels, value) in values {
+                let labels = labels
+                    .iter()
+                    .map(|tag| format!("{}=\"{}\"", sanitise_name(tag.key), escape_label(&tag.value)))
+                    .collect::<Vec<_>>()
+                    .join(",");
+
+                let _ = match labels.is_empty() {
+                    true => writeln!(text, "{name} {value}"),
+                    false => writeln!(text, "{name}{{{labels}}} {value}"),
+                };
+            }
+            text
+        })
+    }
+}
+
+/// Replace the characters that are invalid in a Prometheus metric or label name.
+fn sanitise_name(name: &str) -> String {
+    name.chars()
+        .map(|char| match char {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '_' | ':' => char,
+            _ => '_',
+        })
+        .collect()
+}
+
+/// Escape the backslashes, double quotes & line feeds of a Prometheus label value.
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Escape the `special` characters of an InfluxDB line protocol element with a backslash.
+fn escape_influx(value: &str, special: &[char]) -> String {
+    value.chars().fold(String::with_capacity(value.len()), |mut escaped, char| {
+        if special.contains(&char) || char == '\\' {
+            escaped.push('\\');
+        }
+        escaped.push(char);
+        escaped
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metric(name: &'static str, tags: Vec<Tag>, fields: Vec<Field>) -> Metric {
+        Metric {
+            name,
+            time: 1_700_000_000_000,
+            tags,
+            fields,
+        }
+    }
+
+    #[test]
+    fn test_to_line_protocol() {
+        struct TestCase {
+            input: Metric,
+            expected: &'static str,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: every value type
+                input: metric(
+                    "stream",
+                    vec![Tag::new("exchange", "okx")],
+                    vec![
+                        Field::new("rate", 1.5),
+                        Field::new("delta", -2_i64),
+                        Field::new("messages_total", 3_u64),
+                        Field::new("healthy", true),
+                        Field::new("status", "ok".to_string()),
+                    ],
+                ),
+                expected: "stream,exchange=okx rate=1.5,delta=-2i,messages_total=3u,healthy=true,status=\"ok\" 1700000000000000000",
+            },
+            TestCase {
+                // TC1: special characters are escaped
+                input: metric("http request", vec![Tag::new("path", "/a b,c=d")], vec![Field::new("duration", 10_u64)]),
+                expected: "http\\ request,path=/a\\ b\\,c\\=d duration=10u 1700000000000000000",
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            assert_eq!(test.input.to_line_protocol(), test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_metric_registry_prometheus() {
+        let registry = MetricRegistry::new();
+
+        let stream = |messages: u64| {
+            metric(
+                "stream",
+                vec![Tag::new("kind", "PublicTrades"), Tag::new("exchange", "okx")],
+                vec![Field::new("messages_total", messages), Field::new("status", "ok".to_string())],
+            )
+        };
+        registry.record(&stream(1));
+        registry.record(&stream(5));
+        registry.record(&metric(
+            "http_request_duration",
+            vec![Tag::new("path", "/api/\"v3\"")],
+            vec![Field::new("duration", 12_u64)],
+        ));
+        registry.record(&metric("engine_balance", vec![], vec![Field::new("total", 1000.5)]));
+
+        let expected = "\
+# TYPE engine_balance_total counter
+engine_balance_total 1000.5
+# TYPE http_request_duration gauge
+http_request_duration{path=\"/api/\\\"v3\\\"\"} 12
+# TYPE stream_messages_total counter
+stream_messages_total{exchange=\"okx\",kind=\"PublicTrades\"} 5
+";
+        assert_eq!(registry.prometheus(), expected);
+    }
+}
@@ -1,15 +1,19 @@
 use std::cmp::Ordering;
 
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use im::{ordmap, OrdMap};
+use serde::{ser::SerializeStruct, Deserialize, Serialize};
 use tracing::debug;
 
 use crate::{
-    calculator::{mid_price, volume_weighted_mid_price},
+    calculator::{
+        book::{imbalance, microprice, price_at_bps, sweep, Sweep, BPS},
+        mid_price, volume_weighted_mid_price,
+    },
     enums::BookSide,
 };
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
 pub struct OrderBookL1 {
     pub last_update_ts: DateTime<Utc>,
     pub best_bid: Level,
@@ -61,10 +65,10 @@ where
     }
 }
 
+// Price then amount, totally ordered so a NaN level cannot panic
 impl Ord for Level {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other)
-            .unwrap_or_else(|| panic!("{:?}.partial._cmp({:?}) impossible", self, other))
+        self.price.total_cmp(&other.price).then(self.amount.total_cmp(&other.amount))
     }
 }
 
@@ -77,22 +81,36 @@ impl PartialOrd for Level {
 // No need to implement this, since `PartialEq` is already implemented
 impl Eq for Level {}
 
+/// Price level L2 order book, with both sides kept sorted from the best price.
+///
+/// Taking a [`OrderBook::snapshot`] is O(1), since the levels of each side are shared with the
+/// snapshot until the next update (see [`OrderBookSide`]).
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct OrderBook {
     pub last_update_ts: DateTime<Utc>,
+    /// Exchange sequence id of the last update applied to the book, if the exchange provides one.
+    #[serde(default)]
+    pub update_id: Option<u64>,
     pub bids: OrderBookSide,
     pub asks: OrderBookSide,
 }
 
 impl OrderBook {
     pub fn snapshot(&mut self) -> Self {
-        self.bids.sort();
-        self.asks.sort();
         self.clone()
     }
 
+    /// Zero-copy view of the best `depth` levels of each side.
+    pub fn view(&self, depth: usize) -> OrderBookView<'_> {
+        OrderBookView {
+            last_update_ts: self.last_update_ts,
+            bids: self.bids.top(depth),
+            asks: self.asks.top(depth),
+        }
+    }
+
     pub fn mid_price(&self) -> Option<f64> {
-        match (self.bids.levels.first(), self.asks.levels.first()) {
+        match (self.bids.best(), self.asks.best()) {
             (Some(best_bid), Some(best_ask)) => Some(mid_price(best_bid.price, best_ask.price)),
             (Some(best_bid), None) => Some(best_bid.price),
             (None, Some(best_ask)) => Some(best_ask.price),
@@ -101,29 +119,128 @@ impl OrderBook {
     }
 
     pub fn volume_weighed_mid_price(&self) -> Option<f64> {
-        match (self.bids.levels.first(), self.asks.levels.first()) {
+        match (self.bids.best(), self.asks.best()) {
             (Some(best_bid), Some(best_ask)) => Some(volume_weighted_mid_price(*best_bid, *best_ask)),
             (Some(best_bid), None) => Some(best_bid.price),
             (None, Some(best_ask)) => Some(best_ask.price),
             (None, None) => None,
         }
     }
+
+    /// Difference between the best ask & best bid prices.
+    pub fn spread(&self) -> Option<f64> {
+        match (self.bids.best(), self.asks.best()) {
+            (Some(best_bid), Some(best_ask)) => Some(best_ask.price - best_bid.price),
+            _ => None,
+        }
+    }
+
+    /// [`OrderBook::spread`] in basis points of the mid price.
+    pub fn spread_bps(&self) -> Option<f64> {
+        Some(self.spread()? / self.mid_price()? * BPS)
+    }
+
+    /// [`OrderBook::spread`] in number of ticks of the instrument.
+    pub fn spread_ticks(&self, tick_size: f64) -> Option<f64> {
+        Some((self.spread()? / tick_size).round())
+    }
+
+    /// Bid/ask volume imbalance of the best `depth` levels, see [`imbalance`].
+    pub fn imbalance(&self, depth: usize) -> Option<f64> {
+        imbalance(self.bids.volume(depth), self.asks.volume(depth))
+    }
+
+    /// Microprice weighted by the volume of the best `depth` levels, see [`microprice`].
+    pub fn microprice(&self, depth: usize) -> Option<f64> {
+        match (self.bids.best(), self.asks.best()) {
+            (Some(best_bid), Some(best_ask)) => Some(microprice(best_bid.price, best_ask.price, self.bids.volume(depth), self.asks.volume(depth))),
+            _ => None,
+        }
+    }
+
+    /// Cumulative bid & ask volume within `bps` basis points of the mid price.
+    pub fn depth_within_bps(&self, bps: f64) -> Option<(f64, f64)> {
+        let mid_price = self.mid_price()?;
+        Some((self.bids.depth_within_bps(mid_price, bps), self.asks.depth_within_bps(mid_price, bps)))
+    }
+
+    /// [`Sweep`] of the asks to buy `size`.
+    pub fn sweep_buy(&self, size: f64) -> Sweep {
+        self.asks.sweep(size)
+    }
+
+    /// [`Sweep`] of the bids to sell `size`.
+    pub fn sweep_sell(&self, size: f64) -> Sweep {
+        self.bids.sweep(size)
+    }
+
+    /// Expected slippage in basis points of the mid price to execute `size`, where the side is
+    /// that of the aggressor (ie/ a [`BookSide::Bid`] buys from the asks). The slippage is
+    /// positive when the average price is worse than the mid price.
+    ///
+    /// Returns [`None`] if the book is empty, or not deep enough to fill the size.
+    pub fn slippage_bps(&self, side: BookSide, size: f64) -> Option<f64> {
+        let mid_price = self.mid_price()?;
+        let sweep = match side {
+            BookSide::Bid => self.sweep_buy(size),
+            BookSide::Ask => self.sweep_sell(size),
+        };
+
+        if !sweep.is_complete() {
+            return None;
+        }
+
+        let slippage = sweep.slippage_bps_from(mid_price)?;
+        Some(match side {
+            BookSide::Bid => slippage,
+            BookSide::Ask => -slippage,
+        })
+    }
 }
 
 impl Default for OrderBook {
     fn default() -> Self {
         Self {
             last_update_ts: Utc::now(),
+            update_id: None,
             bids: OrderBookSide::new(BookSide::Bid, Vec::<Level>::new()),
             asks: OrderBookSide::new(BookSide::Ask, Vec::<Level>::new()),
         }
     }
 }
 
-#[derive(Debug, PartialEq, Clone, PartialOrd, Ord, Eq, Serialize, Deserialize)]
+/// Borrowed view of the best levels of an [`OrderBook`], see [`OrderBook::view`].
+#[derive(Debug)]
+pub struct OrderBookView<'a> {
+    pub last_update_ts: DateTime<Utc>,
+    pub bids: std::iter::Take<Levels<'a>>,
+    pub asks: std::iter::Take<Levels<'a>>,
+}
+
+/// Integer key of an [`OrderBookSide`] level, that orders exactly like the price.
+///
+/// A fixed tick size cannot represent every instrument (eg/ KRW equities & sub-satoshi tokens),
+/// so the IEEE 754 bits of the price are mapped onto an integer with the same total order.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PriceKey(i64);
+
+impl From<f64> for PriceKey {
+    fn from(price: f64) -> Self {
+        // Adding 0.0 normalises -0.0 to 0.0
+        let bits = (price + 0.0).to_bits() as i64;
+        Self(bits ^ (((bits >> 63) as u64) >> 1) as i64)
+    }
+}
+
+/// One side of an [`OrderBook`], with levels kept sorted by price in a persistent [`OrdMap`] so
+/// that every upsert is O(log n).
+///
+/// Cloning a side is O(1) and shares the tree with the clone, so an update of a side that is still
+/// shared with a snapshot only copies the O(log n) nodes on the path to the updated level.
+#[derive(Debug, PartialEq, Clone, PartialOrd, Ord, Eq)]
 pub struct OrderBookSide {
     side: BookSide,
-    pub levels: Vec<Level>,
+    levels: OrdMap<PriceKey, Level>,
 }
 
 impl OrderBookSide {
@@ -134,10 +251,88 @@ impl OrderBookSide {
     {
         Self {
             side,
-            levels: levels.into_iter().map(L::into).collect(),
+            levels: levels.into_iter().map(L::into).map(|level| (PriceKey::from(level.price), level)).collect(),
+        }
+    }
+
+    pub fn side(&self) -> BookSide {
+        self.side
+    }
+
+    pub fn len(&self) -> usize {
+        self.levels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels.is_empty()
+    }
+
+    /// Best [`Level`], the highest bid or the lowest ask.
+    pub fn best(&self) -> Option<&Level> {
+        match self.side {
+            BookSide::Bid => self.levels.values().next_back(),
+            BookSide::Ask => self.levels.values().next(),
+        }
+    }
+
+    /// Zero-copy iterator over the levels, from the best price.
+    pub fn iter(&self) -> Levels<'_> {
+        Levels {
+            side: self.side,
+            levels: self.levels.values(),
         }
     }
 
+    /// Zero-copy iterator over the best `depth` levels.
+    pub fn top(&self, depth: usize) -> std::iter::Take<Levels<'_>> {
+        self.iter().take(depth)
+    }
+
+    /// Remove every level beyond the best `depth` levels.
+    pub fn truncate(&mut self, depth: usize) {
+        if self.levels.len() > depth {
+            self.levels = match self.side {
+                BookSide::Bid => self.levels.skip(self.levels.len() - depth),
+                BookSide::Ask => self.levels.take(depth),
+            };
+        }
+    }
+
+    /// Levels sorted from the best price, copied into a [`Vec`].
+    pub fn levels(&self) -> Vec<Level> {
+        self.iter().copied().collect()
+    }
+
+    /// Cumulative volume of the best `depth` levels.
+    pub fn volume(&self, depth: usize) -> f64 {
+        self.top(depth).map(|level| level.amount).sum()
+    }
+
+    /// Cumulative volume of the levels at `price` or better.
+    pub fn depth_within(&self, price: f64) -> f64 {
+        self.iter()
+            .take_while(|level| match self.side {
+                BookSide::Bid => level.price >= price,
+                BookSide::Ask => level.price <= price,
+            })
+            .map(|level| level.amount)
+            .sum()
+    }
+
+    /// Cumulative volume of the levels within `bps` basis points of the `reference` price (eg/
+    /// the mid price).
+    pub fn depth_within_bps(&self, reference: f64, bps: f64) -> f64 {
+        match self.side {
+            BookSide::Bid => self.depth_within(price_at_bps(reference, -bps)),
+            BookSide::Ask => self.depth_within(price_at_bps(reference, bps)),
+        }
+    }
+
+    /// [`Sweep`] the levels from the best price to execute `size`.
+    pub fn sweep(&self, size: f64) -> Sweep {
+        sweep(self.iter(), size)
+    }
+
     pub fn upsert<Iter, L>(&mut self, levels: Iter)
     where
         Iter: IntoIterator<Item = L>,
@@ -151,24 +346,22 @@ impl OrderBookSide {
         L: Into<Level>,
     {
         let new_level = new_level.into();
+        let key = PriceKey::from(new_level.price);
 
-        match self
-            .levels
-            .iter_mut()
-            .enumerate()
-            .find(|(_index, level)| level.eq_price(new_level.price))
-        {
-            Some((index, _)) if new_level.amount == 0.0 => {
-                self.levels.remove(index);
+        match self.levels.entry(key) {
+            ordmap::Entry::Occupied(level) if new_level.amount == 0.0 => {
+                level.remove();
             },
 
-            Some((_, level)) => {
-                *level = new_level;
+            ordmap::Entry::Occupied(mut level) => {
+                level.insert(new_level);
             },
 
-            None if new_level.amount > 0.0 => self.levels.push(new_level),
+            ordmap::Entry::Vacant(level) if new_level.amount > 0.0 => {
+                level.insert(new_level);
+            },
 
-            _ => {
+            ordmap::Entry::Vacant(_) => {
                 // {"message":"Level to remove not found","new_level":"Level { price: 61067.39, amount: 0.0 }
                 // 만약 수정하려고 하는 가격 범위가 현재 미드 프라이스 기준으로 너무 멀리 100틱 이상 떨어져 있으면 무시.
                 debug!(
@@ -179,16 +372,88 @@ impl OrderBookSide {
             },
         };
     }
+}
+
+/// Serialised as the levels sorted from the best price, eg/
+/// `{"side": "Bid", "levels": [{"price": 100.0, "amount": 1.0}, ...]}`.
+impl Serialize for OrderBookSide {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut side = serializer.serialize_struct("OrderBookSide", 2)?;
+        side.serialize_field("side", &self.side)?;
+        side.serialize_field("levels", &SerializeLevels(self))?;
+        side.end()
+    }
+}
 
-    pub fn sort(&mut self) {
-        self.levels.sort_unstable();
+struct SerializeLevels<'a>(&'a OrderBookSide);
+
+impl Serialize for SerializeLevels<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(self.0.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for OrderBookSide {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Side {
+            side: BookSide,
+            levels: Vec<Level>,
+        }
+
+        let Side { side, levels } = Side::deserialize(deserializer)?;
+        Ok(Self::new(side, levels))
+    }
+}
+
+/// Iterator over the [`Level`]s of an [`OrderBookSide`] from the best price, see
+/// [`OrderBookSide::iter`].
+pub struct Levels<'a> {
+    side: BookSide,
+    levels: ordmap::Values<'a, PriceKey, Level>,
+}
+
+impl<'a> Iterator for Levels<'a> {
+    type Item = &'a Level;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.side {
+            BookSide::Bid => self.levels.next_back(),
+            BookSide::Ask => self.levels.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.levels.size_hint()
+    }
+}
 
-        if let BookSide::Bid = self.side {
-            self.levels.reverse();
+impl DoubleEndedIterator for Levels<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.side {
+            BookSide::Bid => self.levels.next(),
+            BookSide::Ask => self.levels.next_back(),
         }
     }
 }
 
+impl ExactSizeIterator for Levels<'_> {}
+
+impl std::fmt::Debug for Levels<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Levels").field("side", &self.side).field("remaining", &self.len()).finish()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -296,14 +561,9 @@ mod tests {
                     // TC0: no levels so 0.0 mid-price
                     input: OrderBook {
                         last_update_ts: Default::default(),
-                        bids: OrderBookSide {
-                            side: BookSide::Bid,
-                            levels: vec![],
-                        },
-                        asks: OrderBookSide {
-                            side: BookSide::Ask,
-                            levels: vec![],
-                        },
+                        update_id: None,
+                        bids: OrderBookSide::new(BookSide::Bid, Vec::<Level>::new()),
+                        asks: OrderBookSide::new(BookSide::Ask, Vec::<Level>::new()),
                     },
                     expected: None,
                 },
@@ -311,14 +571,9 @@ mod tests {
                     // TC1: no asks in the book so take best bid price
                     input: OrderBook {
                         last_update_ts: Default::default(),
-                        bids: OrderBookSide {
-                            side: BookSide::Bid,
-                            levels: vec![Level::new(100.0, 100.0), Level::new(50.0, 100.0)],
-                        },
-                        asks: OrderBookSide {
-                            side: BookSide::Ask,
-                            levels: vec![],
-                        },
+                        update_id: None,
+                        bids: OrderBookSide::new(BookSide::Bid, vec![Level::new(100.0, 100.0), Level::new(50.0, 100.0)]),
+                        asks: OrderBookSide::new(BookSide::Ask, Vec::<Level>::new()),
                     },
                     expected: Some(100.0),
                 },
@@ -326,14 +581,9 @@ mod tests {
                     // TC2: no bids in the book so take ask price
                     input: OrderBook {
                         last_update_ts: Default::default(),
-                        bids: OrderBookSide {
-                            side: BookSide::Bid,
-                            levels: vec![],
-                        },
-                        asks: OrderBookSide {
-                            side: BookSide::Ask,
-                            levels: vec![Level::new(50.0, 100.0), Level::new(100.0, 100.0)],
-                        },
+                        update_id: None,
+                        bids: OrderBookSide::new(BookSide::Bid, Vec::<Level>::new()),
+                        asks: OrderBookSide::new(BookSide::Ask, vec![Level::new(50.0, 100.0), Level::new(100.0, 100.0)]),
                     },
                     expected: Some(50.0),
                 },
@@ -341,14 +591,9 @@ mod tests {
                     // TC3: best bid and ask amount is the same, so regular mid-price
                     input: OrderBook {
                         last_update_ts: Default::default(),
-                        bids: OrderBookSide {
-                            side: BookSide::Bid,
-                            levels: vec![Level::new(100.0, 100.0), Level::new(50.0, 100.0)],
-                        },
-                        asks: OrderBookSide {
-                            side: BookSide::Ask,
-                            levels: vec![Level::new(200.0, 100.0), Level::new(300.0, 100.0)],
-                        },
+                        update_id: None,
+                        bids: OrderBookSide::new(BookSide::Bid, vec![Level::new(100.0, 100.0), Level::new(50.0, 100.0)]),
+                        asks: OrderBookSide::new(BookSide::Ask, vec![Level::new(200.0, 100.0), Level::new(300.0, 100.0)]),
                     },
                     expected: Some(150.0),
                 },
@@ -371,14 +616,9 @@ mod tests {
                     // TC0: no levels so 0.0 mid-price
                     input: OrderBook {
                         last_update_ts: Default::default(),
-                        bids: OrderBookSide {
-                            side: BookSide::Bid,
-                            levels: vec![],
-                        },
-                        asks: OrderBookSide {
-                            side: BookSide::Ask,
-                            levels: vec![],
-                        },
+                        update_id: None,
+                        bids: OrderBookSide::new(BookSide::Bid, Vec::<Level>::new()),
+                        asks: OrderBookSide::new(BookSide::Ask, Vec::<Level>::new()),
                     },
                     expected: None,
                 },
@@ -386,14 +626,9 @@ mod tests {
                     // TC1: no asks in the book so take best bid price
                     input: OrderBook {
                         last_update_ts: Default::default(),
-                        bids: OrderBookSide {
-                            side: BookSide::Bid,
-                            levels: vec![Level::new(100.0, 100.0), Level::new(50.0, 100.0)],
-                        },
-                        asks: OrderBookSide {
-                            side: BookSide::Ask,
-                            levels: vec![],
-                        },
+                        update_id: None,
+                        bids: OrderBookSide::new(BookSide::Bid, vec![Level::new(100.0, 100.0), Level::new(50.0, 100.0)]),
+                        asks: OrderBookSide::new(BookSide::Ask, Vec::<Level>::new()),
                     },
                     expected: Some(100.0),
                 },
@@ -401,14 +636,9 @@ mod tests {
                     // TC2: no bids in the book so take ask price
                     input: OrderBook {
                         last_update_ts: Default::default(),
-                        bids: OrderBookSide {
-                            side: BookSide::Bid,
-                            levels: vec![],
-                        },
-                        asks: OrderBookSide {
-                            side: BookSide::Ask,
-                            levels: vec![Level::new(50.0, 100.0), Level::new(100.0, 100.0)],
-                        },
+                        update_id: None,
+                        bids: OrderBookSide::new(BookSide::Bid, Vec::<Level>::new()),
+                        asks: OrderBookSide::new(BookSide::Ask, vec![Level::new(50.0, 100.0), Level::new(100.0, 100.0)]),
                     },
                     expected: Some(50.0),
                 },
@@ -416,14 +646,9 @@ mod tests {
                     // TC3: best bid and ask amount is the same, so regular mid-price
                     input: OrderBook {
                         last_update_ts: Default::default(),
-                        bids: OrderBookSide {
-                            side: BookSide::Bid,
-                            levels: vec![Level::new(100.0, 100.0), Level::new(50.0, 100.0)],
-                        },
-                        asks: OrderBookSide {
-                            side: BookSide::Ask,
-                            levels: vec![Level::new(200.0, 100.0), Level::new(300.0, 100.0)],
-                        },
+                        update_id: None,
+                        bids: OrderBookSide::new(BookSide::Bid, vec![Level::new(100.0, 100.0), Level::new(50.0, 100.0)]),
+                        asks: OrderBookSide::new(BookSide::Ask, vec![Level::new(200.0, 100.0), Level::new(300.0, 100.0)]),
                     },
                     expected: Some(150.0),
                 },
@@ -431,14 +656,9 @@ mod tests {
                     // TC4: valid volume weighted mid-price
                     input: OrderBook {
                         last_update_ts: Default::default(),
-                        bids: OrderBookSide {
-                            side: BookSide::Bid,
-                            levels: vec![Level::new(100.0, 3000.0), Level::new(50.0, 100.0)],
-                        },
-                        asks: OrderBookSide {
-                            side: BookSide::Ask,
-                            levels: vec![Level::new(200.0, 1000.0), Level::new(300.0, 100.0)],
-                        },
+                        update_id: None,
+                        bids: OrderBookSide::new(BookSide::Bid, vec![Level::new(100.0, 3000.0), Level::new(50.0, 100.0)]),
+                        asks: OrderBookSide::new(BookSide::Ask, vec![Level::new(200.0, 1000.0), Level::new(300.0, 100.0)]),
                     },
                     expected: Some(175.0),
                 },
@@ -448,6 +668,81 @@ mod tests {
                 assert_eq!(test.input.volume_weighed_mid_price(), test.expected, "TC{index} failed")
             }
         }
+
+        fn book() -> OrderBook {
+            OrderBook {
+                last_update_ts: Default::default(),
+                update_id: None,
+                bids: OrderBookSide::new(BookSide::Bid, vec![Level::new(99.0, 3.0), Level::new(98.0, 5.0), Level::new(90.0, 10.0)]),
+                asks: OrderBookSide::new(BookSide::Ask, vec![Level::new(101.0, 1.0), Level::new(102.0, 2.0), Level::new(110.0, 10.0)]),
+            }
+        }
+
+        #[test]
+        fn test_analytics() {
+            let book = book();
+
+            assert_eq!(book.spread(), Some(2.0));
+            assert_eq!(book.spread_bps(), Some(200.0));
+            assert_eq!(book.spread_ticks(0.5), Some(4.0));
+
+            assert_eq!(book.imbalance(1), Some(0.5));
+            assert_eq!(book.imbalance(2), Some((8.0 - 3.0) / 11.0));
+            assert_eq!(book.microprice(1), book.volume_weighed_mid_price());
+            assert_eq!(book.microprice(1), Some(100.5));
+
+            // Within 2% of the mid price of 100.0
+            assert_eq!(book.depth_within_bps(200.0), Some((8.0, 3.0)));
+            assert_eq!(book.depth_within_bps(0.0), Some((0.0, 0.0)));
+
+            assert_eq!(OrderBook::default().spread(), None);
+            assert_eq!(OrderBook::default().imbalance(5), None);
+        }
+
+        #[test]
+        fn test_slippage_bps() {
+            struct TestCase {
+                side: BookSide,
+                size: f64,
+                expected: Option<f64>,
+            }
+
+            let tests = vec![
+                TestCase {
+                    // TC0: buy within the best ask
+                    side: BookSide::Bid,
+                    size: 1.0,
+                    expected: Some(100.0),
+                },
+                TestCase {
+                    // TC1: buy sweeping two levels, vwap 101.5
+                    side: BookSide::Bid,
+                    size: 2.0,
+                    expected: Some(150.0),
+                },
+                TestCase {
+                    // TC2: sell sweeping two levels, vwap 98.75
+                    side: BookSide::Ask,
+                    size: 4.0,
+                    expected: Some(125.0),
+                },
+                TestCase {
+                    // TC3: book is not deep enough
+                    side: BookSide::Ask,
+                    size: 100.0,
+                    expected: None,
+                },
+            ];
+
+            let book = book();
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = book.slippage_bps(test.side, test.size);
+                assert_eq!(actual, test.expected, "TC{} failed", index);
+            }
+
+            assert_eq!(book.sweep_buy(2.0).vwap(), Some(101.5));
+            assert_eq!(book.sweep_sell(4.0).worst_price, 98.0);
+        }
     }
 
     mod order_book_side {
@@ -498,10 +793,10 @@ mod tests {
         }
 
         #[test]
-        fn test_sort_bids() {
+        fn test_levels_bids() {
             struct TestCase {
                 input: OrderBookSide,
-                expected: OrderBookSide,
+                expected: Vec<Level>,
             }
 
             let tests = vec![
@@ -509,84 +804,38 @@ mod tests {
                     // TC0: sorted correctly from reverse sorted
                     input: OrderBookSide::new(
                         BookSide::Bid,
-                        vec![
-                            Level::new(80, 1),
-                            Level::new(90, 1),
-                            Level::new(100, 1),
-                            Level::new(110, 1),
-                            Level::new(120, 1),
-                        ],
-                    ),
-                    expected: OrderBookSide::new(
-                        BookSide::Bid,
-                        vec![
-                            Level::new(120, 1),
-                            Level::new(110, 1),
-                            Level::new(100, 1),
-                            Level::new(90, 1),
-                            Level::new(80, 1),
-                        ],
+                        vec![Level::new(80, 1), Level::new(90, 1), Level::new(100, 1), Level::new(110, 1), Level::new(120, 1)],
                     ),
+                    expected: vec![Level::new(120, 1), Level::new(110, 1), Level::new(100, 1), Level::new(90, 1), Level::new(80, 1)],
                 },
                 TestCase {
                     // TC1: sorted correctly from partially sorted
                     input: OrderBookSide::new(
                         BookSide::Bid,
-                        vec![
-                            Level::new(120, 1),
-                            Level::new(90, 1),
-                            Level::new(80, 1),
-                            Level::new(110, 1),
-                            Level::new(100, 1),
-                        ],
-                    ),
-                    expected: OrderBookSide::new(
-                        BookSide::Bid,
-                        vec![
-                            Level::new(120, 1),
-                            Level::new(110, 1),
-                            Level::new(100, 1),
-                            Level::new(90, 1),
-                            Level::new(80, 1),
-                        ],
+                        vec![Level::new(120, 1), Level::new(90, 1), Level::new(80, 1), Level::new(110, 1), Level::new(100, 1)],
                     ),
+                    expected: vec![Level::new(120, 1), Level::new(110, 1), Level::new(100, 1), Level::new(90, 1), Level::new(80, 1)],
                 },
                 TestCase {
-                    // TC1: sorted correctly from already sorted
+                    // TC2: sorted correctly from already sorted
                     input: OrderBookSide::new(
                         BookSide::Bid,
-                        vec![
-                            Level::new(120, 1),
-                            Level::new(110, 1),
-                            Level::new(100, 1),
-                            Level::new(90, 1),
-                            Level::new(80, 1),
-                        ],
-                    ),
-                    expected: OrderBookSide::new(
-                        BookSide::Bid,
-                        vec![
-                            Level::new(120, 1),
-                            Level::new(110, 1),
-                            Level::new(100, 1),
-                            Level::new(90, 1),
-                            Level::new(80, 1),
-                        ],
+                        vec![Level::new(120, 1), Level::new(110, 1), Level::new(100, 1), Level::new(90, 1), Level::new(80, 1)],
                     ),
+                    expected: vec![Level::new(120, 1), Level::new(110, 1), Level::new(100, 1), Level::new(90, 1), Level::new(80, 1)],
                 },
             ];
 
-            for (index, mut test) in tests.into_iter().enumerate() {
-                test.input.sort();
-                assert_eq!(test.input, test.expected, "TC{} failed", index);
+            for (index, test) in tests.into_iter().enumerate() {
+                assert_eq!(test.input.levels(), test.expected, "TC{} failed", index);
             }
         }
 
         #[test]
-        fn test_sort_asks() {
+        fn test_levels_asks() {
             struct TestCase {
                 input: OrderBookSide,
-                expected: OrderBookSide,
+                expected: Vec<Level>,
             }
 
             let tests = vec![
@@ -594,78 +843,100 @@ mod tests {
                     // TC0: sorted correctly from already sorted
                     input: OrderBookSide::new(
                         BookSide::Ask,
-                        vec![
-                            Level::new(80, 1),
-                            Level::new(90, 1),
-                            Level::new(100, 1),
-                            Level::new(110, 1),
-                            Level::new(120, 1),
-                        ],
-                    ),
-                    expected: OrderBookSide::new(
-                        BookSide::Ask,
-                        vec![
-                            Level::new(80, 1),
-                            Level::new(90, 1),
-                            Level::new(100, 1),
-                            Level::new(110, 1),
-                            Level::new(120, 1),
-                        ],
+                        vec![Level::new(80, 1), Level::new(90, 1), Level::new(100, 1), Level::new(110, 1), Level::new(120, 1)],
                     ),
+                    expected: vec![Level::new(80, 1), Level::new(90, 1), Level::new(100, 1), Level::new(110, 1), Level::new(120, 1)],
                 },
                 TestCase {
                     // TC1: sorted correctly from partially sorted
                     input: OrderBookSide::new(
                         BookSide::Ask,
-                        vec![
-                            Level::new(120, 1),
-                            Level::new(90, 1),
-                            Level::new(80, 1),
-                            Level::new(110, 1),
-                            Level::new(100, 1),
-                        ],
-                    ),
-                    expected: OrderBookSide::new(
-                        BookSide::Ask,
-                        vec![
-                            Level::new(80, 1),
-                            Level::new(90, 1),
-                            Level::new(100, 1),
-                            Level::new(110, 1),
-                            Level::new(120, 1),
-                        ],
+                        vec![Level::new(120, 1), Level::new(90, 1), Level::new(80, 1), Level::new(110, 1), Level::new(100, 1)],
                     ),
+                    expected: vec![Level::new(80, 1), Level::new(90, 1), Level::new(100, 1), Level::new(110, 1), Level::new(120, 1)],
                 },
                 TestCase {
-                    // TC1: sorted correctly from reverse sorted
+                    // TC2: sorted correctly from reverse sorted
                     input: OrderBookSide::new(
                         BookSide::Ask,
-                        vec![
-                            Level::new(120, 1),
-                            Level::new(110, 1),
-                            Level::new(100, 1),
-                            Level::new(90, 1),
-                            Level::new(80, 1),
-                        ],
-                    ),
-                    expected: OrderBookSide::new(
-                        BookSide::Ask,
-                        vec![
-                            Level::new(80, 1),
-                            Level::new(90, 1),
-                            Level::new(100, 1),
-                            Level::new(110, 1),
-                            Level::new(120, 1),
-                        ],
+                        vec![Level::new(120, 1), Level::new(110, 1), Level::new(100, 1), Level::new(90, 1), Level::new(80, 1)],
                     ),
+                    expected: vec![Level::new(80, 1), Level::new(90, 1), Level::new(100, 1), Level::new(110, 1), Level::new(120, 1)],
                 },
             ];
 
-            for (index, mut test) in tests.into_iter().enumerate() {
-                test.input.sort();
-                assert_eq!(test.input, test.expected, "TC{} failed", index);
+            for (index, test) in tests.into_iter().enumerate() {
+                assert_eq!(test.input.levels(), test.expected, "TC{} failed", index);
             }
         }
+
+        #[test]
+        fn test_price_key_ord() {
+            let prices = [-1.5, -0.0, 0.0, 1e-12, 0.00000001, 1.0, 61067.39, 1e12];
+
+            for (index, pair) in prices.windows(2).enumerate() {
+                let (lower, higher) = (PriceKey::from(pair[0]), PriceKey::from(pair[1]));
+                match pair[0] == pair[1] {
+                    true => assert_eq!(lower, higher, "TC{} failed", index),
+                    false => assert!(lower < higher, "TC{} failed", index),
+                }
+            }
+        }
+
+        #[test]
+        fn test_top() {
+            let mut bids = OrderBookSide::new(BookSide::Bid, vec![Level::new(80, 1), Level::new(100, 1), Level::new(90, 1)]);
+            let mut asks = OrderBookSide::new(BookSide::Ask, vec![Level::new(120, 1), Level::new(110, 1), Level::new(130, 1)]);
+            bids.upsert_single(Level::new(95, 2));
+            asks.upsert_single(Level::new(110, 0));
+
+            assert_eq!(bids.best(), Some(&Level::new(100, 1)));
+            assert_eq!(bids.top(2).copied().collect::<Vec<_>>(), vec![Level::new(100, 1), Level::new(95, 2)]);
+            assert_eq!(asks.best(), Some(&Level::new(120, 1)));
+            assert_eq!(asks.top(5).copied().collect::<Vec<_>>(), vec![Level::new(120, 1), Level::new(130, 1)]);
+            assert_eq!(asks.iter().next_back(), Some(&Level::new(130, 1)));
+        }
+
+        #[test]
+        fn test_truncate() {
+            let mut bids = OrderBookSide::new(BookSide::Bid, vec![Level::new(80, 1), Level::new(100, 1), Level::new(90, 1)]);
+            let mut asks = OrderBookSide::new(BookSide::Ask, vec![Level::new(120, 1), Level::new(110, 1), Level::new(130, 1)]);
+            bids.truncate(2);
+            asks.truncate(2);
+
+            assert_eq!(bids.levels(), vec![Level::new(100, 1), Level::new(90, 1)]);
+            assert_eq!(asks.levels(), vec![Level::new(110, 1), Level::new(120, 1)]);
+
+            // Side within the depth is unchanged
+            asks.truncate(5);
+            assert_eq!(asks.len(), 2);
+        }
+
+        #[test]
+        fn test_snapshot_copy_on_write() {
+            let mut book = OrderBook {
+                last_update_ts: Default::default(),
+                update_id: None,
+                bids: OrderBookSide::new(BookSide::Bid, vec![Level::new(100, 1)]),
+                asks: OrderBookSide::new(BookSide::Ask, vec![Level::new(110, 1)]),
+            };
+
+            let snapshot = book.snapshot();
+            book.bids.upsert_single(Level::new(105, 1));
+
+            assert_eq!(snapshot.bids.levels(), vec![Level::new(100, 1)]);
+            assert_eq!(book.bids.levels(), vec![Level::new(105, 1), Level::new(100, 1)]);
+            assert_eq!(book.view(1).bids.copied().collect::<Vec<_>>(), vec![Level::new(105, 1)]);
+        }
+
+        #[test]
+        fn test_serde() {
+            let side = OrderBookSide::new(BookSide::Bid, vec![Level::new(90, 1), Level::new(100, 2)]);
+
+            let json = serde_json::to_string(&side).unwrap();
+            assert_eq!(json, r#"{"side":"Bid","levels":[{"price":100.0,"amount":2.0},{"price":90.0,"amount":1.0}]}"#);
+            assert_eq!(serde_json::from_str::<OrderBookSide>(&json).unwrap(), side);
+        }
     }
 
     mod level {
@@ -734,6 +1005,12 @@ mod tests {
                     input_two: Level::new(100, 100),
                     expected: Some(Ordering::Less),
                 },
+                TestCase {
+                    // TC9: Input One has a NaN price -> totally ordered above every price
+                    input_one: Level::new(f64::NAN, 50.0),
+                    input_two: Level::new(100.0, 100.0),
+                    expected: Some(Ordering::Greater),
+                },
             ];
 
             for (index, test) in tests.into_iter().enumerate() {
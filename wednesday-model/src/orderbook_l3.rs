@@ -0,0 +1,308 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::{
+    enums::BookSide,
+    events::MarketEvent,
+    orderbook::{Level, OrderBook, OrderBookSide},
+};
+
+/// Individual resting order of a market-by-order [`OrderBookL3`].
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct L3Order {
+    pub id: String,
+    pub side: BookSide,
+    pub price: f64,
+    /// Remaining amount of the order.
+    pub amount: f64,
+    /// Time the order entered the book, or last lost its queue priority.
+    pub time: DateTime<Utc>,
+}
+
+impl L3Order {
+    pub fn eq_price(&self, price: f64) -> bool {
+        (price - self.price).abs() < f64::EPSILON
+    }
+}
+
+/// Market-by-order event that is applied to an [`OrderBookL3`], see [`OrderBookL3::apply`].
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum OrderBookL3Event {
+    /// New order rests on the book.
+    Add(L3Order),
+    /// Resting order changed price and/or remaining amount. Reducing the amount keeps the queue
+    /// priority of the order, while any other change loses it.
+    Modify { id: String, price: f64, amount: f64, time: DateTime<Utc> },
+    /// Resting order was cancelled.
+    Delete { id: String, time: DateTime<Utc> },
+    /// Resting order was filled by an aggressor, the amount is the executed quantity.
+    Execute { id: String, amount: f64, time: DateTime<Utc> },
+}
+
+impl OrderBookL3Event {
+    pub fn time(&self) -> DateTime<Utc> {
+        match self {
+            OrderBookL3Event::Add(order) => order.time,
+            OrderBookL3Event::Modify { time, .. } | OrderBookL3Event::Delete { time, .. } | OrderBookL3Event::Execute { time, .. } => *time,
+        }
+    }
+}
+
+/// Market-by-order (L3) order book of every individual resting order, keyed by order id.
+///
+/// Use [`OrderBookL3::l2`] (or [`OrderBook::from`]) to aggregate it into a price level
+/// [`OrderBook`].
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct OrderBookL3 {
+    pub last_update_ts: DateTime<Utc>,
+    pub orders: HashMap<String, L3Order>,
+}
+
+impl Default for OrderBookL3 {
+    fn default() -> Self {
+        Self {
+            last_update_ts: Utc::now(),
+            orders: HashMap::new(),
+        }
+    }
+}
+
+impl OrderBookL3 {
+    pub fn new<Iter>(last_update_ts: DateTime<Utc>, orders: Iter) -> Self
+    where
+        Iter: IntoIterator<Item = L3Order>,
+    {
+        Self {
+            last_update_ts,
+            orders: orders.into_iter().map(|order| (order.id.clone(), order)).collect(),
+        }
+    }
+
+    /// Apply an [`OrderBookL3Event`] to the book. Events of unknown orders are ignored, since an
+    /// exchange may report orders that were never part of a depth limited book.
+    pub fn apply(&mut self, event: OrderBookL3Event) {
+        self.last_update_ts = event.time();
+
+        match event {
+            OrderBookL3Event::Add(order) => {
+                self.orders.insert(order.id.clone(), order);
+            },
+            OrderBookL3Event::Modify { id, price, amount, time } => match self.orders.get_mut(&id) {
+                Some(_) if amount <= 0.0 => {
+                    self.orders.remove(&id);
+                },
+                Some(order) => {
+                    if !order.eq_price(price) || amount > order.amount {
+                        order.time = time;
+                    }
+                    order.price = price;
+                    order.amount = amount;
+                },
+                None => debug!(%id, "Order to modify not found"),
+            },
+            OrderBookL3Event::Delete { id, .. } => {
+                if self.orders.remove(&id).is_none() {
+                    debug!(%id, "Order to delete not found");
+                }
+            },
+            OrderBookL3Event::Execute { id, amount, .. } => match self.orders.get_mut(&id) {
+                Some(order) if order.amount - amount <= f64::EPSILON => {
+                    self.orders.remove(&id);
+                },
+                Some(order) => order.amount -= amount,
+                None => debug!(%id, "Order to execute not found"),
+            },
+        }
+    }
+
+    /// Orders of one [`BookSide`], best price first & then by time priority.
+    pub fn orders(&self, side: BookSide) -> Vec<&L3Order> {
+        let mut orders = self.orders.values().filter(|order| order.side == side).collect::<Vec<_>>();
+
+        orders.sort_by(|a, b| {
+            let by_price = match side {
+                BookSide::Bid => b.price.partial_cmp(&a.price),
+                BookSide::Ask => a.price.partial_cmp(&b.price),
+            };
+
+            by_price
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| a.time.cmp(&b.time))
+                .then_with(|| a.id.cmp(&b.id))
+        });
+
+        orders
+    }
+
+    /// Aggregate the orders of one [`BookSide`] into price [`Level`]s, best price first.
+    pub fn levels(&self, side: BookSide) -> Vec<Level> {
+        self.orders(side).into_iter().fold(Vec::new(), |mut levels: Vec<Level>, order| {
+            match levels.last_mut() {
+                Some(level) if level.eq_price(order.price) => level.amount += order.amount,
+                _ => levels.push(Level::new(order.price, order.amount)),
+            }
+            levels
+        })
+    }
+
+    /// Aggregate the book into a price level L2 [`OrderBook`].
+    pub fn l2(&self) -> OrderBook {
+        OrderBook {
+            last_update_ts: self.last_update_ts,
+            update_id: None,
+            bids: OrderBookSide::new(BookSide::Bid, self.levels(BookSide::Bid)),
+            asks: OrderBookSide::new(BookSide::Ask, self.levels(BookSide::Ask)),
+        }
+    }
+}
+
+impl From<&OrderBookL3> for OrderBook {
+    fn from(book: &OrderBookL3) -> Self {
+        book.l2()
+    }
+}
+
+impl From<MarketEvent<OrderBookL3>> for MarketEvent<OrderBook> {
+    fn from(event: MarketEvent<OrderBookL3>) -> Self {
+        Self {
+            exchange_ts: event.exchange_ts,
+            local_ts: event.local_ts,
+            exchange: event.exchange,
+            instrument: event.instrument,
+            kind: event.kind.l2(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(id: &str, side: BookSide, price: f64, amount: f64, time: i64) -> L3Order {
+        L3Order {
+            id: id.to_string(),
+            side,
+            price,
+            amount,
+            time: DateTime::from_timestamp(time, 0).unwrap(),
+        }
+    }
+
+    fn time(time: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(time, 0).unwrap()
+    }
+
+    #[test]
+    fn test_apply() {
+        struct TestCase {
+            input: OrderBookL3Event,
+            expected: Vec<L3Order>,
+        }
+
+        let tests = vec![
+            // TC0: add bid
+            TestCase {
+                input: OrderBookL3Event::Add(order("1", BookSide::Bid, 100.0, 1.0, 0)),
+                expected: vec![order("1", BookSide::Bid, 100.0, 1.0, 0)],
+            },
+            // TC1: add bid at the same price
+            TestCase {
+                input: OrderBookL3Event::Add(order("2", BookSide::Bid, 100.0, 2.0, 1)),
+                expected: vec![order("1", BookSide::Bid, 100.0, 1.0, 0), order("2", BookSide::Bid, 100.0, 2.0, 1)],
+            },
+            // TC2: reducing the amount keeps the queue priority
+            TestCase {
+                input: OrderBookL3Event::Modify {
+                    id: "1".to_string(),
+                    price: 100.0,
+                    amount: 0.5,
+                    time: time(2),
+                },
+                expected: vec![order("1", BookSide::Bid, 100.0, 0.5, 0), order("2", BookSide::Bid, 100.0, 2.0, 1)],
+            },
+            // TC3: increasing the amount loses the queue priority
+            TestCase {
+                input: OrderBookL3Event::Modify {
+                    id: "1".to_string(),
+                    price: 100.0,
+                    amount: 3.0,
+                    time: time(3),
+                },
+                expected: vec![order("2", BookSide::Bid, 100.0, 2.0, 1), order("1", BookSide::Bid, 100.0, 3.0, 3)],
+            },
+            // TC4: partial execution reduces the amount
+            TestCase {
+                input: OrderBookL3Event::Execute {
+                    id: "2".to_string(),
+                    amount: 1.5,
+                    time: time(4),
+                },
+                expected: vec![order("2", BookSide::Bid, 100.0, 0.5, 1), order("1", BookSide::Bid, 100.0, 3.0, 3)],
+            },
+            // TC5: full execution removes the order
+            TestCase {
+                input: OrderBookL3Event::Execute {
+                    id: "2".to_string(),
+                    amount: 0.5,
+                    time: time(5),
+                },
+                expected: vec![order("1", BookSide::Bid, 100.0, 3.0, 3)],
+            },
+            // TC6: delete of an unknown order is ignored
+            TestCase {
+                input: OrderBookL3Event::Delete {
+                    id: "3".to_string(),
+                    time: time(6),
+                },
+                expected: vec![order("1", BookSide::Bid, 100.0, 3.0, 3)],
+            },
+            // TC7: delete removes the order
+            TestCase {
+                input: OrderBookL3Event::Delete {
+                    id: "1".to_string(),
+                    time: time(7),
+                },
+                expected: vec![],
+            },
+        ];
+
+        let mut book = OrderBookL3::default();
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let event_time = test.input.time();
+            book.apply(test.input);
+
+            let actual = book.orders(BookSide::Bid).into_iter().cloned().collect::<Vec<_>>();
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+            assert_eq!(book.last_update_ts, event_time, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_l2() {
+        let book = OrderBookL3::new(
+            time(10),
+            vec![
+                order("1", BookSide::Bid, 99.0, 1.0, 0),
+                order("2", BookSide::Bid, 100.0, 2.0, 1),
+                order("3", BookSide::Bid, 100.0, 3.0, 2),
+                order("4", BookSide::Ask, 102.0, 1.0, 3),
+                order("5", BookSide::Ask, 101.0, 4.0, 4),
+                order("6", BookSide::Ask, 101.0, 1.0, 5),
+            ],
+        );
+
+        assert_eq!(
+            OrderBook::from(&book),
+            OrderBook {
+                last_update_ts: time(10),
+                update_id: None,
+                bids: OrderBookSide::new(BookSide::Bid, vec![Level::new(100.0, 5.0), Level::new(99.0, 1.0)]),
+                asks: OrderBookSide::new(BookSide::Ask, vec![Level::new(101.0, 5.0), Level::new(102.0, 1.0)]),
+            }
+        );
+    }
+}
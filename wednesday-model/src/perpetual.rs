@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Normalised perpetual [`MarkPrice`] model, including the index price & the funding rate that
+/// will be settled at the `next_funding_time`.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct MarkPrice {
+    pub mark_price: f64,
+    pub index_price: f64,
+    pub funding_rate: f64,
+    pub next_funding_time: DateTime<Utc>,
+}
+
+impl MarkPrice {
+    /// Calculate the funding payment settled for a signed position `quantity` (negative for
+    /// shorts) at this [`MarkPrice`]. Positive when the position receives funding, negative when
+    /// the position pays funding.
+    pub fn funding_payment(&self, quantity: f64) -> f64 {
+        -quantity * self.mark_price * self.funding_rate
+    }
+}
+
+/// Normalised perpetual [`OpenInterest`] model.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct OpenInterest {
+    /// Number of open contracts, denominated in the base currency.
+    pub contracts: f64,
+    /// Notional value of the open contracts, denominated in the quote currency (if provided).
+    pub notional: Option<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_funding_payment() {
+        struct TestCase {
+            mark: MarkPrice,
+            quantity: f64,
+            expected: f64,
+        }
+
+        let mark = |funding_rate: f64| MarkPrice {
+            mark_price: 100.0,
+            index_price: 100.0,
+            funding_rate,
+            next_funding_time: DateTime::<Utc>::MIN_UTC,
+        };
+
+        let tests = vec![
+            // TC0: long pays positive funding
+            TestCase {
+                mark: mark(0.0001),
+                quantity: 10.0,
+                expected: -0.1,
+            },
+            // TC1: short receives positive funding
+            TestCase {
+                mark: mark(0.0001),
+                quantity: -10.0,
+                expected: 0.1,
+            },
+            // TC2: long receives negative funding
+            TestCase {
+                mark: mark(-0.0001),
+                quantity: 10.0,
+                expected: 0.1,
+            },
+            // TC3: short pays negative funding
+            TestCase {
+                mark: mark(-0.0001),
+                quantity: -10.0,
+                expected: -0.1,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = test.mark.funding_payment(test.quantity);
+            assert!((actual - test.expected).abs() < 1e-12, "TC{} failed", index);
+        }
+    }
+}
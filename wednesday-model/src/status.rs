@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use crate::identifiers::ExchangeId;
+
+/// Transmitter of the [`StatusEvent`]s of market streams.
+pub type StatusTx = mpsc::UnboundedSender<StatusEvent>;
+
+/// Receiver of the [`StatusEvent`]s of market streams.
+pub type StatusRx = mpsc::UnboundedReceiver<StatusEvent>;
+
+/// Connection status of a market stream, see [`StatusEvent`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamStatus {
+    /// Stream connected for the first time.
+    Connected,
+    /// Stream failed to connect or was disconnected, & re-connects after the backoff.
+    Reconnecting { attempt: u32, backoff: Duration },
+    /// Stream failed to re-connect too many times in a row, & pauses re-connecting for the
+    /// cooldown before a single trial attempt.
+    CircuitOpen { attempt: u32, cooldown: Duration },
+    /// Stream re-connected after a disconnect, with any local state (eg/ order books)
+    /// re-initialised from a fresh snapshot.
+    Resynced,
+    /// Stream exhausted its re-connection attempts & has ended.
+    GaveUp { attempt: u32 },
+}
+
+impl StreamStatus {
+    /// Whether the stream is connected & delivering events.
+    pub fn is_healthy(&self) -> bool {
+        matches!(self, StreamStatus::Connected | StreamStatus::Resynced)
+    }
+}
+
+/// Change of the [`StreamStatus`] of the connection `leg` of an exchange stream.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct StatusEvent {
+    pub time: DateTime<Utc>,
+    pub exchange: ExchangeId,
+    pub leg: usize,
+    pub status: StreamStatus,
+}
+
+impl StatusEvent {
+    pub fn new(exchange: ExchangeId, leg: usize, status: StreamStatus) -> Self {
+        Self {
+            time: Utc::now(),
+            exchange,
+            leg,
+            status,
+        }
+    }
+}
+
+/// Unbounded channel of [`StatusEvent`]s, which are rare enough to never require backpressure.
+#[derive(Debug)]
+pub struct StatusChannel {
+    pub tx: StatusTx,
+    pub rx: StatusRx,
+}
+
+impl Default for StatusChannel {
+    fn default() -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        Self { tx, rx }
+    }
+}
@@ -1,9 +1,23 @@
+use serde::{Deserialize, Serialize};
+
 use crate::enums::AggressorSide;
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
 pub struct PublicTrade {
     pub id: String,
     pub price: f64,
     pub quantity: f64,
     pub aggressor_side: AggressorSide,
 }
+
+/// Normalised aggregated public trade, filled at a single price by one taker order, covering
+/// the exchange trade ids `first_trade_id..=last_trade_id`.
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+pub struct AggregatedTrade {
+    pub id: String,
+    pub price: f64,
+    pub quantity: f64,
+    pub first_trade_id: String,
+    pub last_trade_id: String,
+    pub aggressor_side: AggressorSide,
+}